@@ -259,18 +259,17 @@ class MemoryService:
         """
         context = InjectedContext()
 
-        # 1. Build system prompt from persona
+        # 1. Build system prompt from persona, focused on the session's working files
         persona = self._persona_cache.get(agent_id)
         project = self._project_cache.get(project_id) if project_id else None
+        session = self.get_or_create_session(agent_id, project_id)
 
         if persona:
-            context.system_prompt = persona.build_system_prompt(project)
+            context.system_prompt = persona.build_system_prompt(project, working_files=session.working_files)
         elif project:
-            context.system_prompt = f"## Contexte Projet\n{project.to_prompt()}"
-
-        # 2. Get session and history
-        session = self.get_or_create_session(agent_id, project_id)
+            context.system_prompt = f"## Contexte Projet\n{project.to_prompt(session.working_files)}"
 
+        # 2. Session history
         # Check if resume is needed
         if session.needs_resume:
             context.resume_info = session.generate_resume_summary()
@@ -468,10 +467,11 @@ async def build_agent_context(
     context = InjectedContext()
 
     # Build system prompt
+    working_files = session.working_files if session else None
     if persona:
-        context.system_prompt = persona.build_system_prompt(project)
+        context.system_prompt = persona.build_system_prompt(project, working_files=working_files)
     elif project:
-        context.system_prompt = f"## Contexte Projet\n{project.to_prompt()}"
+        context.system_prompt = f"## Contexte Projet\n{project.to_prompt(working_files)}"
 
     # Add session info
     if session:
@@ -57,6 +57,7 @@ class AgentPersona:
         self,
         project_context: Optional["ProjectContext"] = None,
         additional_context: str = "",
+        working_files: Optional[List[str]] = None,
     ) -> str:
         """
         Build the complete system prompt with persona and context.
@@ -64,6 +65,7 @@ class AgentPersona:
         Args:
             project_context: Project-specific context to include
             additional_context: Any additional context to append
+            working_files: Files the agent is working on (selects the sub-project)
 
         Returns:
             Complete system prompt for the LLM
@@ -81,7 +83,7 @@ class AgentPersona:
 
         # Project context
         if project_context:
-            sections.append(f"## Contexte Projet\n{project_context.to_prompt()}")
+            sections.append(f"## Contexte Projet\n{project_context.to_prompt(working_files)}")
 
         # Specializations
         if self.specializations:
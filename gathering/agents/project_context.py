@@ -3,19 +3,189 @@ Project Context - Persistent project information.
 Stores conventions, tools, structure, and important notes about a project.
 """
 
+import json
+import os
+import re
+import tomllib
+from collections import Counter
 from dataclasses import dataclass, field
 from typing import Any, Dict, List, Optional
 from datetime import datetime
 from pathlib import Path
 
 
+# Python packages that tell an agent how a project is built, by tool category
+PYTHON_PACKAGE_TOOLS = {
+    "pytest": "testing",
+    "sqlalchemy": "orm",
+    "pydantic": "validation",
+    "fastapi": "web_framework",
+    "flask": "web_framework",
+    "django": "web_framework",
+    "anthropic": "llm_provider",
+    "openai": "llm_provider",
+    "psycopg": "database_driver",
+    "pycopg": "database",
+}
+
+
+# Crates that tell an agent how a Rust project is built, by tool category
+RUST_CRATE_TOOLS = {
+    "tokio": "async_runtime",
+    "async-std": "async_runtime",
+    "serde": "serialization",
+    "axum": "web_framework",
+    "actix-web": "web_framework",
+    "rocket": "web_framework",
+    "warp": "web_framework",
+    "sqlx": "database",
+    "diesel": "orm",
+    "sea-orm": "orm",
+    "reqwest": "http_client",
+    "tonic": "grpc",
+    "clap": "cli",
+    "thiserror": "errors",
+    "anyhow": "errors",
+    "tracing": "logging",
+    "log": "logging",
+    "proptest": "property_testing",
+    "criterion": "benchmarks",
+    "insta": "snapshot_testing",
+    "mockall": "mocking",
+}
+
+# Crate features worth surfacing next to the crate name (e.g. the sqlx database)
+RUST_CRATE_FEATURES = {
+    "sqlx": {"postgres", "mysql", "sqlite", "mssql"},
+    "diesel": {"postgres", "mysql", "sqlite"},
+    "sea-orm": {"sqlx-postgres", "sqlx-mysql", "sqlx-sqlite"},
+}
+
+# npm packages that tell an agent how a JavaScript/TypeScript project is built
+JS_PACKAGE_TOOLS = {
+    "react": "ui_framework",
+    "vue": "ui_framework",
+    "svelte": "ui_framework",
+    "@angular/core": "ui_framework",
+    "next": "web_framework",
+    "nuxt": "web_framework",
+    "express": "web_framework",
+    "fastify": "web_framework",
+    "vite": "bundler",
+    "webpack": "bundler",
+    "vitest": "testing",
+    "jest": "testing",
+    "@playwright/test": "e2e_testing",
+    "eslint": "linter",
+    "prettier": "formatter",
+}
+
+# Lockfile -> package manager, in precedence order
+NODE_LOCKFILES = {
+    "pnpm-lock.yaml": "pnpm",
+    "yarn.lock": "yarn",
+    "bun.lockb": "bun",
+    "package-lock.json": "npm",
+}
+
+# package.json scripts surfaced as commands
+NODE_SCRIPTS = ["dev", "build", "start", "test", "lint", "typecheck", "format"]
+
+# Manifests marking the root of a sub-project, in detection order
+SUBPROJECT_MANIFESTS = {
+    "Cargo.toml": "rust",
+    "pyproject.toml": "python",
+    "package.json": "javascript",
+    "go.mod": "go",
+}
+
+# Directories holding dependencies, build output or environments, never scanned
+SUBPROJECT_SKIP_DIRS = {"node_modules", "target", "vendor", "venv", "env", "dist", "build", "__pycache__"}
+SUBPROJECT_MAX_DEPTH = 4
+
+
+@dataclass
+class SubProject:
+    """
+    One buildable root (Cargo.toml, pyproject.toml, package.json, go.mod) of a repository.
+
+    `path` is relative to the repository root ("." for the root itself); key files
+    are relative to the sub-project and commands run from its directory.
+    """
+
+    path: str = "."
+    language: str = ""
+    name: str = ""
+    toolchain: Optional[str] = None  # e.g. "node >=20", "go 1.22", "1.78.0 (edition 2021)"
+    tools: Dict[str, str] = field(default_factory=dict)
+    key_files: Dict[str, str] = field(default_factory=dict)
+    commands: Dict[str, str] = field(default_factory=dict)
+
+    @property
+    def depth(self) -> int:
+        """Nesting depth below the repository root."""
+        return 0 if self.path == "." else self.path.count("/") + 1
+
+    def contains(self, relative_path: str) -> bool:
+        """Whether a repository-relative path lies inside this sub-project."""
+        return self.path == "." or relative_path == self.path or relative_path.startswith(f"{self.path}/")
+
+    def to_prompt(self) -> List[str]:
+        """Prompt lines describing this sub-project."""
+        lines = [f"\nSous-projet actif: {self.path} ({self.language})"]
+        if self.name:
+            lines.append(f"  - nom: {self.name}")
+        if self.toolchain:
+            lines.append(f"  - toolchain: {self.toolchain}")
+        if self.tools:
+            lines.append("  - outils:")
+            for tool, lib in self.tools.items():
+                lines.append(f"    - {tool}: {lib}")
+        if self.key_files:
+            lines.append("  - fichiers importants:")
+            for name, path in self.key_files.items():
+                lines.append(f"    - {name}: {path}")
+        if self.commands:
+            where = "racine" if self.path == "." else f"{self.path}/"
+            lines.append(f"  - commandes (depuis {where}):")
+            for name, cmd in self.commands.items():
+                lines.append(f"    - {name}: {cmd}")
+        return lines
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to dictionary for storage."""
+        return {
+            "path": self.path,
+            "language": self.language,
+            "name": self.name,
+            "toolchain": self.toolchain,
+            "tools": self.tools,
+            "key_files": self.key_files,
+            "commands": self.commands,
+        }
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "SubProject":
+        """Create from dictionary."""
+        return cls(
+            path=data.get("path", "."),
+            language=data.get("language", ""),
+            name=data.get("name", ""),
+            toolchain=data.get("toolchain"),
+            tools=data.get("tools", {}),
+            key_files=data.get("key_files", {}),
+            commands=data.get("commands", {}),
+        )
+
+
 @dataclass
 class ProjectContext:
     """
     Persistent context for a project.
 
     Stores everything an agent needs to know about a project:
-    - Environment (venv, python version)
+    - Environment (venv, python version, Rust toolchain, edition and MSRV)
+    - Sub-projects of a polyglot repository, each with its own toolchain
     - Tools and libraries used
     - Coding conventions
     - Important files
@@ -32,6 +202,17 @@ class ProjectContext:
     venv_path: Optional[str] = None
     python_version: str = "3.11"
 
+    # Rust environment
+    rust_toolchain: Optional[str] = None  # rust-toolchain(.toml) channel, e.g. "1.78.0"
+    rust_edition: Optional[str] = None
+    rust_version: Optional[str] = None  # MSRV from `rust-version`
+    workspace_members: List[str] = field(default_factory=list)
+    features: Dict[str, List[str]] = field(default_factory=dict)
+    # e.g., {"default": ["postgres"], "postgres": ["sqlx/postgres"]}
+
+    # Sub-projects (one per Cargo.toml / pyproject.toml / package.json / go.mod root)
+    subprojects: List[SubProject] = field(default_factory=list)
+
     # Tools and libraries
     tools: Dict[str, str] = field(default_factory=dict)
     # e.g., {"database": "picopg", "testing": "pytest", "orm": "sqlalchemy"}
@@ -59,10 +240,14 @@ class ProjectContext:
     created_at: Optional[datetime] = None
     updated_at: Optional[datetime] = None
 
-    def to_prompt(self) -> str:
+    def to_prompt(self, working_files: Optional[List[str]] = None) -> str:
         """
         Generate context for injection into prompts.
 
+        Args:
+            working_files: Files the agent is working on; the sub-project
+                containing them is described in full
+
         Returns:
             Formatted string for LLM context
         """
@@ -76,6 +261,20 @@ class ProjectContext:
 
         lines.append(f"\nChemin: {self.path}")
 
+        # Sub-projects: list them all, detail the one being worked on
+        if self.subprojects:
+            active = self.subproject_for(working_files or [])
+            lines.append("\nSous-projets:")
+            for sub in self.subprojects:
+                label = f"  - {sub.path}: {sub.language}"
+                if sub.name:
+                    label += f" ({sub.name})"
+                if sub is active:
+                    label += " [actif]"
+                lines.append(label)
+            if active:
+                lines.extend(active.to_prompt())
+
         # Python environment
         if self.venv_path:
             lines.append("\nEnvironnement Python:")
@@ -86,6 +285,25 @@ class ProjectContext:
                 "avant les commandes Python"
             )
 
+        # Rust environment
+        if self.rust_edition or self.rust_toolchain or self.workspace_members:
+            lines.append("\nEnvironnement Rust:")
+            if self.rust_toolchain:
+                lines.append(f"  - toolchain: {self.rust_toolchain} (rust-toolchain)")
+            if self.rust_edition:
+                lines.append(f"  - édition: {self.rust_edition}")
+            if self.rust_version:
+                lines.append(f"  - MSRV: {self.rust_version}")
+                lines.append(
+                    f"  - IMPORTANT: Ne pas utiliser d'API stabilisées après Rust {self.rust_version}"
+                )
+            if self.workspace_members:
+                lines.append(f"  - workspace: {', '.join(self.workspace_members)}")
+            if self.features:
+                lines.append("  - features:")
+                for feature, enables in self.features.items():
+                    lines.append(f"    - {feature}: {', '.join(enables) if enables else '-'}")
+
         # Tools
         if self.tools:
             lines.append("\nOutils du projet:")
@@ -126,6 +344,37 @@ class ProjectContext:
 
         return "\n".join(lines)
 
+    def subproject_for(self, files: List[str]) -> Optional[SubProject]:
+        """
+        Find the sub-project containing the given files.
+
+        Each file counts for its innermost sub-project; the sub-project holding
+        most of the files wins.
+
+        Args:
+            files: Absolute paths or paths relative to the project root
+
+        Returns:
+            The matching SubProject, or None if no file lies in one
+        """
+        counts: Counter = Counter()
+        for file in files:
+            path = os.path.normpath(file)
+            if os.path.isabs(path):
+                if not self.path:
+                    continue
+                path = os.path.relpath(path, os.path.normpath(self.path))
+                if path.startswith(".."):
+                    continue
+            relative = Path(path).as_posix()
+            matches = [i for i, sub in enumerate(self.subprojects) if sub.contains(relative)]
+            if matches:
+                counts[max(matches, key=lambda i: self.subprojects[i].depth)] += 1
+        if not counts:
+            return None
+        best = max(counts, key=lambda i: (counts[i], self.subprojects[i].depth))
+        return self.subprojects[best]
+
     def add_note(self, note: str) -> None:
         """Add an important note."""
         if note not in self.notes:
@@ -156,6 +405,12 @@ class ProjectContext:
             "description": self.description,
             "venv_path": self.venv_path,
             "python_version": self.python_version,
+            "rust_toolchain": self.rust_toolchain,
+            "rust_edition": self.rust_edition,
+            "rust_version": self.rust_version,
+            "workspace_members": self.workspace_members,
+            "features": self.features,
+            "subprojects": [sub.to_dict() for sub in self.subprojects],
             "tools": self.tools,
             "conventions": self.conventions,
             "key_files": self.key_files,
@@ -167,6 +422,24 @@ class ProjectContext:
             "updated_at": self.updated_at.isoformat() if self.updated_at else None,
         }
 
+    def rust_config(self) -> Dict[str, Any]:
+        """Rust environment as stored in the `rust` DB column and project.yaml mapping."""
+        return {
+            "toolchain": self.rust_toolchain,
+            "edition": self.rust_edition,
+            "msrv": self.rust_version,
+            "workspace_members": self.workspace_members,
+            "features": self.features,
+        }
+
+    def set_rust_config(self, config: Dict[str, Any]) -> None:
+        """Restore the Rust environment from rust_config() output."""
+        self.rust_toolchain = config.get("toolchain")
+        self.rust_edition = config.get("edition")
+        self.rust_version = config.get("msrv")
+        self.workspace_members = config.get("workspace_members") or []
+        self.features = config.get("features") or {}
+
     @classmethod
     def from_dict(cls, data: Dict[str, Any]) -> "ProjectContext":
         """Create from dictionary."""
@@ -177,6 +450,12 @@ class ProjectContext:
             description=data.get("description", ""),
             venv_path=data.get("venv_path"),
             python_version=data.get("python_version", "3.11"),
+            rust_toolchain=data.get("rust_toolchain"),
+            rust_edition=data.get("rust_edition"),
+            rust_version=data.get("rust_version"),
+            workspace_members=data.get("workspace_members", []),
+            features=data.get("features", {}),
+            subprojects=[SubProject.from_dict(sub) for sub in data.get("subprojects", [])],
             tools=data.get("tools", {}),
             conventions=data.get("conventions", {}),
             key_files=data.get("key_files", {}),
@@ -241,6 +520,14 @@ class ProjectContext:
         # Detect tools from requirements/pyproject
         context._detect_tools(project_path)
 
+        # Detect Rust crate or workspace
+        is_rust = (project_path / "Cargo.toml").exists()
+        if is_rust:
+            context._detect_rust(project_path, detect_name=name == project_path.name)
+
+        # Detect sub-projects of a monorepo
+        context._detect_subprojects(project_path)
+
         # Detect git
         context._detect_git(project_path)
 
@@ -248,7 +535,12 @@ class ProjectContext:
         if context.venv_path:
             context.commands["activate"] = f"source {context.venv_path}/bin/activate"
 
-        if (project_path / "pytest.ini").exists() or (project_path / "tests").exists():
+        # A Rust crate's tests/ holds integration tests, not pytest files
+        tests_dir = project_path / "tests"
+        has_pytest = (project_path / "pytest.ini").exists() or (
+            tests_dir.exists() and (not is_rust or any(tests_dir.rglob("*.py")))
+        )
+        if has_pytest:
             context.commands["test"] = "pytest tests/ -v"
             context.tools["testing"] = "pytest"
 
@@ -266,20 +558,7 @@ class ProjectContext:
             content = req_file.read_text()
 
             # Common tools detection
-            tool_patterns = {
-                "pytest": "testing",
-                "sqlalchemy": "orm",
-                "pydantic": "validation",
-                "fastapi": "web_framework",
-                "flask": "web_framework",
-                "django": "web_framework",
-                "anthropic": "llm_provider",
-                "openai": "llm_provider",
-                "psycopg": "database_driver",
-                "pycopg": "database",
-            }
-
-            for pattern, category in tool_patterns.items():
+            for pattern, category in PYTHON_PACKAGE_TOOLS.items():
                 if pattern in content.lower():
                     self.tools[category] = pattern
 
@@ -287,7 +566,6 @@ class ProjectContext:
         pyproject = project_path / "pyproject.toml"
         if pyproject.exists():
             try:
-                import tomllib
                 with open(pyproject, "rb") as f:
                     data = tomllib.load(f)
                     # Get project name
@@ -296,6 +574,198 @@ class ProjectContext:
             except Exception:
                 pass
 
+    def _detect_rust(self, project_path: Path, detect_name: bool = True) -> None:
+        """Detect Rust settings from Cargo.toml, workspace members and rust-toolchain."""
+
+        root = _load_toml(project_path / "Cargo.toml")
+        package = root.get("package", {})
+        workspace = root.get("workspace", {})
+        inherited = workspace.get("package", {})
+
+        def field_value(pkg: Dict[str, Any], key: str) -> Optional[str]:
+            # `edition.workspace = true` takes the value from [workspace.package]
+            value = pkg.get(key)
+            if isinstance(value, dict) and value.get("workspace"):
+                value = inherited.get(key)
+            return str(value) if value is not None else None
+
+        self.key_files["cargo"] = "Cargo.toml"
+        if (project_path / "Cargo.lock").exists():
+            self.key_files["cargo_lock"] = "Cargo.lock"
+
+        if package:
+            if detect_name and package.get("name"):
+                self.name = package["name"]
+            description = field_value(package, "description")
+            if description and not self.description:
+                self.description = description
+
+        # Workspace members (globs expanded, excludes dropped)
+        manifests = [(project_path, root)] if package else []
+        excluded = {(project_path / e).resolve() for e in workspace.get("exclude", [])}
+        for pattern in workspace.get("members", []):
+            for member in sorted(project_path.glob(pattern)):
+                if member.resolve() in excluded or member.resolve() == project_path:
+                    continue
+                member_manifest = _load_toml(member / "Cargo.toml")
+                if not member_manifest.get("package"):
+                    continue
+                relative = member.relative_to(project_path).as_posix()
+                self.workspace_members.append(relative)
+                self.key_files[f"crate:{member_manifest['package'].get('name', member.name)}"] = relative
+                manifests.append((member, member_manifest))
+
+        # Edition and MSRV: the root package, else shared workspace values, else the first member
+        for _, manifest in manifests or [(project_path, {"package": inherited})]:
+            pkg = manifest.get("package", {})
+            self.rust_edition = self.rust_edition or field_value(pkg, "edition")
+            self.rust_version = self.rust_version or field_value(pkg, "rust-version")
+        if manifests and not self.rust_edition:
+            # Cargo's default when a package does not set one
+            self.rust_edition = "2015"
+
+        # Features (members' features are prefixed with the crate name)
+        for member, manifest in manifests:
+            prefix = "" if member == project_path else f"{manifest['package'].get('name', member.name)}/"
+            for feature, enables in manifest.get("features", {}).items():
+                self.features[f"{prefix}{feature}"] = list(enables)
+
+        # Key dependencies -> tools
+        crates: Dict[str, set] = {}
+        tables = [workspace.get("dependencies", {})]
+        for _, manifest in manifests:
+            for section in ("dependencies", "dev-dependencies", "build-dependencies"):
+                tables.append(manifest.get(section, {}))
+            for target in manifest.get("target", {}).values():
+                for section in ("dependencies", "dev-dependencies", "build-dependencies"):
+                    tables.append(target.get(section, {}))
+        for table in tables:
+            for key, spec in table.items():
+                name = spec.get("package", key) if isinstance(spec, dict) else key
+                enabled = crates.setdefault(name, set())
+                if isinstance(spec, dict):
+                    enabled.update(spec.get("features", []))
+
+        for crate, category in RUST_CRATE_TOOLS.items():
+            if crate not in crates:
+                continue
+            label = crate
+            backends = sorted(crates[crate] & RUST_CRATE_FEATURES.get(crate, set()))
+            if backends:
+                label = f"{crate} ({', '.join(backends)})"
+            current = self.tools.get(category)
+            self.tools[category] = f"{current}, {label}" if current else label
+
+        nextest = (project_path / ".config" / "nextest.toml").exists()
+        self.tools["testing"] = "cargo-nextest" if nextest else "cargo test"
+
+        # Toolchain pin
+        for toolchain_file in ("rust-toolchain.toml", "rust-toolchain"):
+            path = project_path / toolchain_file
+            if not path.exists():
+                continue
+            self.key_files["toolchain"] = toolchain_file
+            text = path.read_text().strip()
+            try:
+                self.rust_toolchain = tomllib.loads(text).get("toolchain", {}).get("channel")
+            except tomllib.TOMLDecodeError:
+                # Legacy rust-toolchain: just the channel name
+                self.rust_toolchain = text.splitlines()[0].strip() if text else None
+            break
+
+        # Crate roots and tool configuration
+        for key, relative in (
+            ("lib", "src/lib.rs"),
+            ("main", "src/main.rs"),
+            ("build_script", "build.rs"),
+            ("rustfmt", "rustfmt.toml"),
+            ("rustfmt", ".rustfmt.toml"),
+            ("clippy", "clippy.toml"),
+            ("clippy", ".clippy.toml"),
+        ):
+            if (project_path / relative).exists():
+                self.key_files.setdefault(key, relative)
+
+        # Conventions: lint levels, unsafe policy and rustfmt settings
+        lints = root.get("lints", {})
+        if not lints or lints.get("workspace"):
+            lints = workspace.get("lints", {})
+        unsafe = lints.get("rust", {}).get("unsafe_code")
+        if isinstance(unsafe, dict):
+            unsafe = unsafe.get("level")
+        for crate_root in ("src/lib.rs", "src/main.rs"):
+            path = project_path / crate_root
+            if not unsafe and path.exists() and "#![forbid(unsafe_code)]" in path.read_text(errors="ignore"):
+                unsafe = "forbid"
+        if unsafe:
+            self.conventions["unsafe_code"] = unsafe
+
+        clippy = lints.get("clippy", {})
+        if clippy:
+            self.conventions["clippy"] = ", ".join(
+                f"{lint}={level.get('level') if isinstance(level, dict) else level}"
+                for lint, level in clippy.items()
+            )
+
+        rustfmt_file = self.key_files.get("rustfmt")
+        if rustfmt_file:
+            try:
+                settings = tomllib.loads((project_path / rustfmt_file).read_text())
+                if settings:
+                    self.conventions["rustfmt"] = ", ".join(f"{k}={v}" for k, v in settings.items())
+            except tomllib.TOMLDecodeError:
+                pass
+
+        # Commands
+        scope = " --workspace" if self.workspace_members else ""
+        self.commands["build"] = f"cargo build{scope}"
+        self.commands["test"] = f"cargo nextest run{scope}" if nextest else f"cargo test{scope}"
+        self.commands["clippy"] = f"cargo clippy{scope} --all-targets -- -D warnings"
+        self.commands["fmt"] = "cargo fmt --all"
+        self.commands["fmt_check"] = "cargo fmt --all -- --check"
+        if any(not feature.endswith("default") for feature in self.features):
+            self.commands["test_all_features"] = f"cargo test{scope} --all-features"
+
+    def _detect_subprojects(self, project_path: Path) -> None:
+        """Detect one sub-project per manifest root (Cargo, Python, Node, Go)."""
+        detectors = {
+            "rust": _detect_rust_subproject,
+            "python": _detect_python_subproject,
+            "javascript": _detect_node_subproject,
+            "go": _detect_go_subproject,
+        }
+        found: List[SubProject] = []
+        workspace_members: set = set()
+
+        for current, dirnames, filenames in os.walk(project_path):
+            directory = Path(current)
+            relative = directory.relative_to(project_path).as_posix()
+            depth = 0 if relative == "." else relative.count("/") + 1
+            dirnames[:] = sorted(
+                d for d in dirnames
+                if not d.startswith(".") and d not in SUBPROJECT_SKIP_DIRS and depth < SUBPROJECT_MAX_DEPTH
+            )
+
+            for manifest, language in SUBPROJECT_MANIFESTS.items():
+                if manifest not in filenames:
+                    continue
+                # Crates of a Cargo workspace belong to the workspace root
+                if language == "rust" and directory in workspace_members:
+                    continue
+                try:
+                    sub = detectors[language](directory, relative)
+                except (OSError, ValueError):
+                    continue
+                if language == "rust":
+                    workspace_members.update(
+                        directory / member for key, member in sub.key_files.items() if key.startswith("crate:")
+                    )
+                found.append(sub)
+
+        # A single root at the top level is already described by the flat fields
+        if len(found) > 1 or any(sub.path != "." for sub in found):
+            self.subprojects = found
+
     def _detect_git(self, project_path: Path) -> None:
         """Detect git information."""
         git_dir = project_path / ".git"
@@ -333,6 +803,159 @@ class ProjectContext:
             pass
 
 
+def _load_toml(path: Path) -> Dict[str, Any]:
+    """Parsed TOML file, or an empty dict when it is missing or invalid."""
+    try:
+        with open(path, "rb") as f:
+            return tomllib.load(f)
+    except (OSError, tomllib.TOMLDecodeError):
+        return {}
+
+
+def _detect_rust_subproject(directory: Path, relative: str) -> SubProject:
+    """Describe a Cargo crate or workspace, reusing the root Rust detection."""
+    crate = ProjectContext(name=directory.name, path=str(directory))
+    crate._detect_rust(directory)
+
+    toolchain = crate.rust_toolchain or "stable"
+    details = [f"edition {crate.rust_edition}"] if crate.rust_edition else []
+    if crate.rust_version:
+        details.append(f"MSRV {crate.rust_version}")
+    if details:
+        toolchain += f" ({', '.join(details)})"
+
+    return SubProject(
+        path=relative,
+        language="rust",
+        name=crate.name,
+        toolchain=toolchain,
+        tools=crate.tools,
+        key_files=crate.key_files,
+        commands=crate.commands,
+    )
+
+
+def _detect_python_subproject(directory: Path, relative: str) -> SubProject:
+    """Describe a Python package from pyproject.toml."""
+    data = _load_toml(directory / "pyproject.toml")
+    project = data.get("project", {})
+    sub = SubProject(
+        path=relative,
+        language="python",
+        name=project.get("name") or directory.name,
+        key_files={"pyproject": "pyproject.toml"},
+    )
+    if project.get("requires-python"):
+        sub.toolchain = f"python {project['requires-python']}"
+
+    for venv_name in ["venv", ".venv", "env", ".env"]:
+        if (directory / venv_name / "bin" / "python").exists():
+            sub.key_files["venv"] = venv_name
+            sub.commands["activate"] = f"source {venv_name}/bin/activate"
+            break
+
+    dependencies = list(project.get("dependencies", []))
+    for extra in project.get("optional-dependencies", {}).values():
+        dependencies.extend(extra)
+    requirements = directory / "requirements.txt"
+    if requirements.exists():
+        sub.key_files["requirements"] = "requirements.txt"
+        dependencies.append(requirements.read_text())
+    content = " ".join(dependencies).lower()
+    for pattern, category in PYTHON_PACKAGE_TOOLS.items():
+        if pattern in content:
+            sub.tools[category] = pattern
+
+    if (directory / "tests").is_dir() or (directory / "pytest.ini").exists():
+        sub.tools["testing"] = "pytest"
+        sub.commands["test"] = "pytest tests/ -v"
+    if "ruff" in data.get("tool", {}):
+        sub.tools["linter"] = "ruff"
+        sub.commands["lint"] = "ruff check ."
+    return sub
+
+
+def _detect_node_subproject(directory: Path, relative: str) -> SubProject:
+    """Describe a JavaScript/TypeScript package from package.json."""
+    package = json.loads((directory / "package.json").read_text())
+    if not isinstance(package, dict):
+        raise ValueError("package.json is not an object")
+    dependencies = {**package.get("dependencies", {}), **package.get("devDependencies", {})}
+
+    typescript = (directory / "tsconfig.json").exists() or "typescript" in dependencies
+    sub = SubProject(
+        path=relative,
+        language="typescript" if typescript else "javascript",
+        name=package.get("name") or directory.name,
+        key_files={"package": "package.json"},
+    )
+    if (directory / "tsconfig.json").exists():
+        sub.key_files["tsconfig"] = "tsconfig.json"
+
+    node = package.get("engines", {}).get("node")
+    for version_file in (".nvmrc", ".node-version"):
+        if not node and (directory / version_file).exists():
+            node = (directory / version_file).read_text().strip() or None
+            sub.key_files["node_version"] = version_file
+    if node:
+        sub.toolchain = f"node {node}"
+
+    # "packageManager": "pnpm@9.1.0" wins over lockfiles
+    manager = package.get("packageManager", "").split("@")[0] or None
+    for lockfile, lock_manager in NODE_LOCKFILES.items():
+        if (directory / lockfile).exists():
+            sub.key_files["lockfile"] = lockfile
+            manager = manager or lock_manager
+            break
+    manager = manager or "npm"
+    sub.tools["package_manager"] = manager
+
+    for dependency, category in JS_PACKAGE_TOOLS.items():
+        if dependency in dependencies:
+            current = sub.tools.get(category)
+            sub.tools[category] = f"{current}, {dependency}" if current else dependency
+
+    sub.commands["install"] = f"{manager} install"
+    scripts = package.get("scripts", {})
+    for script in NODE_SCRIPTS:
+        if script in scripts:
+            sub.commands[script] = f"{manager} run {script}"
+    return sub
+
+
+def _detect_go_subproject(directory: Path, relative: str) -> SubProject:
+    """Describe a Go module from go.mod."""
+    content = (directory / "go.mod").read_text()
+    module = re.search(r"^module\s+(\S+)", content, re.MULTILINE)
+    go_version = re.search(r"^go\s+(\S+)", content, re.MULTILINE)
+    # `toolchain go1.22.3` pins a newer toolchain than the language version
+    pinned = re.search(r"^toolchain\s+go(\S+)", content, re.MULTILINE)
+
+    sub = SubProject(
+        path=relative,
+        language="go",
+        name=module.group(1) if module else directory.name,
+        key_files={"go_mod": "go.mod"},
+        tools={"testing": "go test"},
+        commands={
+            "build": "go build ./...",
+            "test": "go test ./...",
+            "vet": "go vet ./...",
+        },
+    )
+    if pinned or go_version:
+        sub.toolchain = f"go {(pinned or go_version).group(1)}"
+    if (directory / "go.sum").exists():
+        sub.key_files["go_sum"] = "go.sum"
+    for config in (".golangci.yml", ".golangci.yaml"):
+        if (directory / config).exists():
+            sub.key_files["golangci"] = config
+            sub.tools["linter"] = "golangci-lint"
+            sub.commands["lint"] = "golangci-lint run"
+            break
+    return sub
+
+
 def load_project_from_db(project_id: Optional[int] = None, project_name: Optional[str] = None) -> Optional[ProjectContext]:
     """
     Load project context from database.
@@ -374,6 +997,13 @@ def load_project_from_db(project_id: Optional[int] = None, project_name: Optiona
             else:
                 venv_path = venv
 
+        subprojects = row.get("subprojects") or []
+        if isinstance(subprojects, str):
+            subprojects = json.loads(subprojects)
+        rust = row.get("rust") or {}
+        if isinstance(rust, str):
+            rust = json.loads(rust)
+
         context = ProjectContext(
             id=row.get("id"),
             name=row.get("name", ""),
@@ -386,11 +1016,13 @@ def load_project_from_db(project_id: Optional[int] = None, project_name: Optiona
             key_files=row.get("key_files") or {},
             commands=row.get("commands") or {},
             notes=row.get("notes") or [],
+            subprojects=[SubProject.from_dict(sub) for sub in subprojects],
             git_branch=row.get("branch"),
             git_remote=row.get("repository_url"),
             created_at=row.get("created_at"),
             updated_at=row.get("updated_at"),
         )
+        context.set_rust_config(rust)
 
         return context
 
@@ -433,6 +1065,16 @@ def load_project_from_yaml(project_path: str) -> Optional[ProjectContext]:
             if venv:
                 context.venv_path = str(Path(project_path) / venv)
 
+        # Rust environment
+        rust_config = data.get("rust", {})
+        if isinstance(rust_config, dict):
+            context.set_rust_config(rust_config)
+
+        # Sub-projects of a monorepo (same keys as SubProject.to_dict)
+        context.subprojects = [
+            SubProject.from_dict(sub) for sub in data.get("subprojects", []) if isinstance(sub, dict)
+        ]
+
         # Simple mappings
         context.tools = data.get("tools", {})
         context.conventions = data.get("conventions", {})
@@ -367,14 +367,19 @@ class AgentWrapper:
                 include_memories=include_memories,
             )
 
-            # Add project context if set
+            # Add project context if set, focused on the files being worked on
             if self._project:
+                working_files = self.session.working_files
                 if context.system_prompt:
                     # Append project context to existing prompt
-                    context.system_prompt += f"\n\n## Contexte Projet\n{self._project.to_prompt()}"
+                    context.system_prompt += (
+                        f"\n\n## Contexte Projet\n{self._project.to_prompt(working_files)}"
+                    )
                 else:
                     # Build complete prompt with project
-                    context.system_prompt = self.persona.build_system_prompt(self._project)
+                    context.system_prompt = self.persona.build_system_prompt(
+                        self._project, working_files=working_files
+                    )
 
             # Add resume info if needed
             if context.resume_info:
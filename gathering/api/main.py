@@ -107,6 +107,19 @@ async def lifespan(app: FastAPI):
     except Exception as e:
         print(f"Warning: Could not start scheduler: {e}")
 
+    # Start LSP pool maintenance (idle eviction, health checks)
+    try:
+        from gathering.lsp.manager import LSPManager
+        LSPManager.configure_pool(
+            max_servers=settings.lsp_max_servers,
+            idle_timeout=settings.lsp_idle_timeout,
+            health_interval=settings.lsp_health_interval,
+            analysis_delay=settings.lsp_analysis_delay,
+        )
+        LSPManager.start_maintenance()
+    except Exception as e:
+        print(f"Warning: Could not start LSP pool maintenance: {e}")
+
     yield
 
     # Shutdown
@@ -145,7 +158,15 @@ async def lifespan(app: FastAPI):
     except Exception as e:
         print(f"Warning: Error during background executor shutdown: {e}")
 
-    # 6. Close async database pool LAST
+    # 6. Shut down language servers (rust-analyzer and other child processes)
+    try:
+        from gathering.lsp.manager import LSPManager
+        await LSPManager.shutdown_all()
+        print("LSP servers shut down")
+    except Exception as e:
+        print(f"Warning: Error during LSP server shutdown: {e}")
+
+    # 7. Close async database pool LAST
     #    In-flight requests may still need DB access during drain period
     try:
         from gathering.api.async_db import AsyncDatabaseService
@@ -9,10 +9,11 @@ from starlette.requests import Request
 
 from gathering.api.rate_limit import limiter, TIER_READ, TIER_WRITE
 from pydantic import BaseModel
-from typing import Optional
+from typing import List, Optional
 import logging
 
 from gathering.lsp.manager import LSPManager
+from gathering.lsp.plugin_system import LSPPluginRegistry
 
 logger = logging.getLogger(__name__)
 
@@ -28,6 +29,7 @@ class InitializeRequest(BaseModel):
     """Request to initialize an LSP server."""
     language: str
     workspace_path: str
+    options: Optional[dict] = None  # Server-specific settings
 
 
 class CompletionRequest(BaseModel):
@@ -35,7 +37,7 @@ class CompletionRequest(BaseModel):
     file_path: str
     line: int  # 1-indexed
     character: int  # 0-indexed
-    content: Optional[str] = None  # If None, reads from disk
+    content: Optional[str] = None  # If None, the open document or the file on disk
 
 
 class DiagnosticsRequest(BaseModel):
@@ -60,11 +62,104 @@ class DefinitionRequest(BaseModel):
     content: Optional[str] = None
 
 
+class CodeActionRequest(BaseModel):
+    """Request for code actions (quick fixes) on a range."""
+    file_path: str
+    line: int  # 1-indexed
+    character: int  # 0-indexed
+    end_line: Optional[int] = None  # Defaults to line
+    end_character: Optional[int] = None  # Defaults to character
+    diagnostics: Optional[List[dict]] = None  # If None, the server computes them
+    content: Optional[str] = None
+
+
+class FormattingRequest(BaseModel):
+    """Request to format a document, or a range of lines."""
+    file_path: str
+    line: Optional[int] = None  # 1-indexed; with end_line, formats a range
+    end_line: Optional[int] = None
+    content: Optional[str] = None
+
+
+class ReferencesRequest(BaseModel):
+    """Request for references to a symbol."""
+    file_path: str
+    line: int  # 1-indexed
+    character: int  # 0-indexed
+    include_declaration: bool = True
+    content: Optional[str] = None
+
+
+class RenameRequest(BaseModel):
+    """Request to rename a symbol."""
+    file_path: str
+    line: int  # 1-indexed
+    character: int  # 0-indexed
+    new_name: str
+    content: Optional[str] = None
+
+
+class DocumentSymbolsRequest(BaseModel):
+    """Request for a document outline."""
+    file_path: str
+    content: Optional[str] = None
+
+
+class ContentChange(BaseModel):
+    """A didChange content change: incremental with a range, else the full text."""
+    range: Optional[dict] = None  # 0-indexed LSP Range
+    text: str
+
+
+class DidOpenRequest(BaseModel):
+    """Open a document for editing."""
+    file_path: str
+    content: Optional[str] = None  # If None, reads from disk
+    version: Optional[int] = None
+
+
+class DidChangeRequest(BaseModel):
+    """Apply edits to an open document."""
+    file_path: str
+    content_changes: List[ContentChange]
+    version: Optional[int] = None  # Must increase; defaults to the next version
+
+
+class DidCloseRequest(BaseModel):
+    """Close an open document."""
+    file_path: str
+
+
+def _content(server, file_path: str, content: Optional[str]) -> Optional[str]:
+    """Request content, else the text of the open document (None reads from disk)."""
+    if content is not None:
+        return content
+    documents = getattr(server, "documents", None)
+    return documents.text(file_path) if documents is not None else None
+
+
 # ============================================================================
 # LSP Endpoints
 # ============================================================================
 
 
+@router.get("/plugins")
+@limiter.limit(TIER_READ)
+async def list_lsp_plugins(request: Request):
+    """
+    List the registered language server plugins.
+
+    Returns:
+        Plugin metadata per language, with the configuration schema and
+        any declared dependencies that are not installed
+    """
+    plugins = LSPPluginRegistry.plugin_info()
+    return {
+        "plugins": plugins,
+        "count": len(plugins)
+    }
+
+
 @router.post("/{project_id}/initialize")
 @limiter.limit(TIER_WRITE)
 async def initialize_lsp(
@@ -86,13 +181,15 @@ async def initialize_lsp(
         capabilities = await LSPManager.initialize_server(
             project_id=project_id,
             language=lsp_request.language,
-            workspace_path=lsp_request.workspace_path
+            workspace_path=lsp_request.workspace_path,
+            options=lsp_request.options
         )
 
         return {
             "status": "initialized",
             "language": lsp_request.language,
-            "capabilities": capabilities
+            "capabilities": capabilities,
+            "missing_dependencies": LSPPluginRegistry.missing_dependencies(lsp_request.language)
         }
 
     except ValueError as e:
@@ -102,6 +199,112 @@ async def initialize_lsp(
         raise HTTPException(status_code=500, detail=f"Failed to initialize LSP: {str(e)}")
 
 
+@router.post("/{project_id}/did-open")
+@limiter.limit(TIER_WRITE)
+async def did_open(
+    request: Request,
+    project_id: int,
+    lsp_request: DidOpenRequest,
+    language: str = Query(default="python")
+):
+    """
+    Open a document. Later requests for it may omit `content`; the
+    server keeps its text in sync from did-change edits.
+
+    Args:
+        project_id: Project identifier
+        request: Document and initial content
+        language: Programming language
+
+    Returns:
+        Document path and version
+    """
+    try:
+        server = LSPManager.get_server(project_id, language)
+        document = await server.did_open(
+            file_path=lsp_request.file_path,
+            content=lsp_request.content,
+            version=lsp_request.version
+        )
+        return {"file_path": document.file_path, "version": document.version}
+
+    except FileNotFoundError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except Exception as e:
+        logger.error(f"didOpen error: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@router.post("/{project_id}/did-change")
+@limiter.limit(TIER_WRITE)
+async def did_change(
+    request: Request,
+    project_id: int,
+    lsp_request: DidChangeRequest,
+    language: str = Query(default="python")
+):
+    """
+    Apply full or incremental content changes to an open document.
+
+    Changes apply in order, each range referring to the text left by the
+    previous change. Language servers supporting incremental sync
+    receive the changes as-is.
+
+    Args:
+        project_id: Project identifier
+        request: Content changes and new version
+        language: Programming language
+
+    Returns:
+        Document path and version
+    """
+    try:
+        server = LSPManager.get_server(project_id, language)
+        document = await server.did_change(
+            file_path=lsp_request.file_path,
+            changes=[change.model_dump(exclude_none=True) for change in lsp_request.content_changes],
+            version=lsp_request.version
+        )
+        return {"file_path": document.file_path, "version": document.version}
+
+    except KeyError as e:
+        raise HTTPException(status_code=404, detail=str(e.args[0]))
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except Exception as e:
+        logger.error(f"didChange error: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@router.post("/{project_id}/did-close")
+@limiter.limit(TIER_WRITE)
+async def did_close(
+    request: Request,
+    project_id: int,
+    lsp_request: DidCloseRequest,
+    language: str = Query(default="python")
+):
+    """
+    Close a document; requests for it read the file from disk again.
+
+    Args:
+        project_id: Project identifier
+        request: Document to close
+        language: Programming language
+
+    Returns:
+        Whether the document was open
+    """
+    try:
+        server = LSPManager.get_server(project_id, language)
+        closed = await server.did_close(lsp_request.file_path)
+        return {"file_path": lsp_request.file_path, "closed": closed}
+
+    except Exception as e:
+        logger.error(f"didClose error: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
+
+
 @router.post("/{project_id}/completions")
 @limiter.limit(TIER_WRITE)
 async def get_completions(
@@ -128,7 +331,7 @@ async def get_completions(
             file_path=lsp_request.file_path,
             line=lsp_request.line,
             character=lsp_request.character,
-            content=lsp_request.content
+            content=_content(server, lsp_request.file_path, lsp_request.content)
         )
 
         return {
@@ -165,7 +368,7 @@ async def get_diagnostics(
 
         diagnostics = await server.get_diagnostics(
             file_path=lsp_request.file_path,
-            content=lsp_request.content
+            content=_content(server, lsp_request.file_path, lsp_request.content)
         )
 
         return {
@@ -204,7 +407,7 @@ async def get_hover(
             file_path=lsp_request.file_path,
             line=lsp_request.line,
             character=lsp_request.character,
-            content=lsp_request.content
+            content=_content(server, lsp_request.file_path, lsp_request.content)
         )
 
         return hover if hover else {"contents": None}
@@ -240,7 +443,7 @@ async def get_definition(
             file_path=lsp_request.file_path,
             line=lsp_request.line,
             character=lsp_request.character,
-            content=lsp_request.content
+            content=_content(server, lsp_request.file_path, lsp_request.content)
         )
 
         return definition if definition else {"uri": None}
@@ -250,6 +453,260 @@ async def get_definition(
         raise HTTPException(status_code=500, detail=str(e))
 
 
+@router.post("/{project_id}/references")
+@limiter.limit(TIER_WRITE)
+async def get_references(
+    request: Request,
+    project_id: int,
+    lsp_request: ReferencesRequest,
+    language: str = Query(default="python")
+):
+    """
+    Find references to the symbol at a position.
+
+    Args:
+        project_id: Project identifier
+        request: References request parameters
+        language: Programming language
+
+    Returns:
+        List of locations
+    """
+    try:
+        server = LSPManager.get_server(project_id, language)
+
+        references = await server.get_references(
+            file_path=lsp_request.file_path,
+            line=lsp_request.line,
+            character=lsp_request.character,
+            include_declaration=lsp_request.include_declaration,
+            content=_content(server, lsp_request.file_path, lsp_request.content)
+        )
+
+        return {
+            "references": references,
+            "count": len(references)
+        }
+
+    except Exception as e:
+        logger.error(f"References error: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@router.post("/{project_id}/rename")
+@limiter.limit(TIER_WRITE)
+async def rename_symbol(
+    request: Request,
+    project_id: int,
+    lsp_request: RenameRequest,
+    language: str = Query(default="python")
+):
+    """
+    Compute a rename of the symbol at a position.
+
+    Nothing is written: preview and apply the returned edit with the
+    workspace API (`/workspace/{project_id}/edit/preview` and `/edit/apply`).
+
+    Args:
+        project_id: Project identifier
+        request: Rename request parameters
+        language: Programming language
+
+    Returns:
+        WorkspaceEdit with the text edits per file
+    """
+    try:
+        server = LSPManager.get_server(project_id, language)
+
+        edit = await server.rename(
+            file_path=lsp_request.file_path,
+            line=lsp_request.line,
+            character=lsp_request.character,
+            new_name=lsp_request.new_name,
+            content=_content(server, lsp_request.file_path, lsp_request.content)
+        )
+
+        if edit is None:
+            raise HTTPException(status_code=404, detail="No renameable symbol at this position")
+
+        changes = edit.get("changes") or {
+            change["textDocument"]["uri"]: change.get("edits", [])
+            for change in edit.get("documentChanges") or []
+            if "textDocument" in change
+        }
+        return {
+            "edit": edit,
+            "files": len(changes),
+            "edits": sum(len(edits) for edits in changes.values())
+        }
+
+    except HTTPException:
+        raise
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except Exception as e:
+        logger.error(f"Rename error: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@router.post("/{project_id}/code-actions")
+@limiter.limit(TIER_WRITE)
+async def get_code_actions(
+    request: Request,
+    project_id: int,
+    lsp_request: CodeActionRequest,
+    language: str = Query(default="python")
+):
+    """
+    Get code actions (quick fixes, refactorings) for a range.
+
+    Args:
+        project_id: Project identifier
+        request: Code action request parameters
+        language: Programming language
+
+    Returns:
+        List of code actions, each with a workspace edit
+    """
+    try:
+        server = LSPManager.get_server(project_id, language)
+
+        actions = await server.get_code_actions(
+            file_path=lsp_request.file_path,
+            line=lsp_request.line,
+            character=lsp_request.character,
+            end_line=lsp_request.end_line,
+            end_character=lsp_request.end_character,
+            diagnostics=lsp_request.diagnostics,
+            content=_content(server, lsp_request.file_path, lsp_request.content)
+        )
+
+        return {
+            "actions": actions,
+            "count": len(actions)
+        }
+
+    except Exception as e:
+        logger.error(f"Code action error: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@router.post("/{project_id}/formatting")
+@limiter.limit(TIER_WRITE)
+async def get_formatting(
+    request: Request,
+    project_id: int,
+    lsp_request: FormattingRequest,
+    language: str = Query(default="python")
+):
+    """
+    Format a document, or only the given lines.
+
+    Args:
+        project_id: Project identifier
+        request: Formatting request parameters
+        language: Programming language
+
+    Returns:
+        Text edits to apply (empty when already formatted)
+    """
+    try:
+        server = LSPManager.get_server(project_id, language)
+
+        if lsp_request.line is not None:
+            edits = await server.get_range_formatting(
+                file_path=lsp_request.file_path,
+                line=lsp_request.line,
+                end_line=lsp_request.end_line or lsp_request.line,
+                content=_content(server, lsp_request.file_path, lsp_request.content)
+            )
+        else:
+            edits = await server.get_formatting(
+                file_path=lsp_request.file_path,
+                content=_content(server, lsp_request.file_path, lsp_request.content)
+            )
+
+        return {
+            "edits": edits,
+            "count": len(edits)
+        }
+
+    except Exception as e:
+        logger.error(f"Formatting error: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@router.post("/{project_id}/document-symbols")
+@limiter.limit(TIER_WRITE)
+async def get_document_symbols(
+    request: Request,
+    project_id: int,
+    lsp_request: DocumentSymbolsRequest,
+    language: str = Query(default="python")
+):
+    """
+    Get the symbol outline of a file.
+
+    Args:
+        project_id: Project identifier
+        request: Document symbols request parameters
+        language: Programming language
+
+    Returns:
+        Symbol tree (classes, functions, members, ...)
+    """
+    try:
+        server = LSPManager.get_server(project_id, language)
+
+        symbols = await server.get_document_symbols(
+            file_path=lsp_request.file_path,
+            content=_content(server, lsp_request.file_path, lsp_request.content)
+        )
+
+        return {
+            "symbols": symbols,
+            "count": len(symbols)
+        }
+
+    except Exception as e:
+        logger.error(f"Document symbols error: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
+
+
+@router.get("/{project_id}/workspace-symbols")
+@limiter.limit(TIER_READ)
+async def get_workspace_symbols(
+    request: Request,
+    project_id: int,
+    query: str = Query(default=""),
+    language: str = Query(default="python")
+):
+    """
+    Search symbols across the project's workspace.
+
+    Args:
+        project_id: Project identifier
+        query: Fuzzy symbol name query (empty matches everything)
+        language: Programming language
+
+    Returns:
+        Matching symbols with their locations
+    """
+    try:
+        server = LSPManager.get_server(project_id, language)
+
+        symbols = await server.workspace_symbols(query)
+
+        return {
+            "symbols": symbols,
+            "count": len(symbols)
+        }
+
+    except Exception as e:
+        logger.error(f"Workspace symbols error: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
+
+
 @router.delete("/{project_id}/shutdown")
 @limiter.limit(TIER_WRITE)
 async def shutdown_lsp(
@@ -296,16 +753,19 @@ async def get_lsp_status(
         language: Programming language
 
     Returns:
-        Server status information
+        Server status information, with the state of the project's
+        servers in the pool (health, idle time, restarts)
     """
     try:
         key = f"{project_id}:{language}"
-        active = key in LSPManager._servers
+        server = LSPManager.server_status(key)
 
         return {
-            "active": active,
+            "active": server is not None,
             "project_id": project_id,
-            "language": language
+            "language": language,
+            "server": server,
+            "pool": LSPManager.status(project_id)
         }
 
     except Exception as e:
@@ -16,7 +16,7 @@ from gathering.api.rate_limit import limiter, TIER_READ, TIER_WRITE
 from pydantic import BaseModel, Field
 
 from gathering.api.dependencies import get_database_service, DatabaseService
-from gathering.agents.project_context import ProjectContext
+from gathering.agents.project_context import ProjectContext, SubProject
 from gathering.utils.sql import safe_update_builder
 
 
@@ -71,6 +71,8 @@ class ProjectResponse(BaseModel):
     key_files: Dict[str, str] = {}
     commands: Dict[str, str] = {}
     notes: List[str] = []
+    subprojects: List[Dict[str, Any]] = []
+    rust: Dict[str, Any] = {}
     circle_count: int = 0
     created_at: datetime
     updated_at: Optional[datetime] = None
@@ -170,6 +172,8 @@ def _project_from_row(row: Dict[str, Any]) -> ProjectResponse:
         key_files=_parse_json_field(row.get("key_files"), {}),
         commands=_parse_json_field(row.get("commands"), {}),
         notes=_parse_array_field(row.get("notes")),
+        subprojects=_parse_json_field(row.get("subprojects"), []),
+        rust=_parse_json_field(row.get("rust"), {}),
         circle_count=row.get("circle_count", 0),
         created_at=row.get("created_at") or datetime.now(timezone.utc),
         updated_at=row.get("updated_at"),
@@ -260,18 +264,24 @@ async def create_project(
         languages.append("python")
     if "web_framework" in context.tools:
         frameworks.append(context.tools["web_framework"])
+    for sub in context.subprojects:
+        if sub.language not in languages:
+            languages.append(sub.language)
+        framework = sub.tools.get("web_framework")
+        if framework and framework not in frameworks:
+            frameworks.append(framework)
 
     now = datetime.now(timezone.utc)
     result = db.execute("""
         INSERT INTO project.projects (
             name, display_name, description, local_path, branch,
             venv_path, python_version, tools, conventions, key_files,
-            commands, notes, languages, frameworks, status,
+            commands, notes, subprojects, rust, languages, frameworks, status,
             created_at, updated_at
         ) VALUES (
             %(name)s, %(display_name)s, %(description)s, %(local_path)s, %(branch)s,
             %(venv_path)s, %(python_version)s, %(tools)s, %(conventions)s, %(key_files)s,
-            %(commands)s, %(notes)s, %(languages)s, %(frameworks)s, %(status)s,
+            %(commands)s, %(notes)s, %(subprojects)s, %(rust)s, %(languages)s, %(frameworks)s, %(status)s,
             %(created_at)s, %(updated_at)s
         )
         RETURNING id
@@ -288,6 +298,8 @@ async def create_project(
         "key_files": json.dumps(context.key_files),
         "commands": json.dumps(context.commands),
         "notes": context.notes,
+        "subprojects": json.dumps([sub.to_dict() for sub in context.subprojects]),
+        "rust": json.dumps(context.rust_config()),
         "languages": languages,
         "frameworks": frameworks,
         "status": "active",
@@ -681,6 +693,8 @@ async def refresh_project(
             conventions = %(conventions)s,
             key_files = %(key_files)s,
             commands = %(commands)s,
+            subprojects = %(subprojects)s,
+            rust = %(rust)s,
             branch = %(branch)s,
             updated_at = %(updated_at)s
         WHERE id = %(id)s
@@ -692,6 +706,8 @@ async def refresh_project(
         "conventions": json.dumps(context.conventions),
         "key_files": json.dumps(context.key_files),
         "commands": json.dumps(context.commands),
+        "subprojects": json.dumps([sub.to_dict() for sub in context.subprojects]),
+        "rust": json.dumps(context.rust_config()),
         "branch": context.git_branch or "main",
         "updated_at": now,
     })
@@ -733,9 +749,13 @@ async def get_project_context(
         key_files=_parse_json_field(row.get("key_files"), {}),
         commands=_parse_json_field(row.get("commands"), {}),
         notes=_parse_array_field(row.get("notes")),
+        subprojects=[
+            SubProject.from_dict(sub) for sub in _parse_json_field(row.get("subprojects"), [])
+        ],
         git_branch=row.get("branch"),
         git_remote=row.get("repository_url"),
     )
+    context.set_rust_config(_parse_json_field(row.get("rust"), {}))
 
     return {
         "project_id": project_id,
@@ -74,6 +74,14 @@ async def websocket_dashboard(websocket: WebSocket, client_id: Optional[str] = N
             "timestamp": "2025-01-15T10:30:00Z"
         }
 
+    LSP diagnostics are only pushed to clients subscribed to the project
+    (or one of its files):
+        ws.send(JSON.stringify({type: 'subscribe', project_id: 1, file_path: 'src/lib.rs'}));
+        // -> {"type": "subscribed", ..., "diagnostics": [...last published...]}
+        // -> {"type": "lsp.diagnostics", "data": {"project_id": 1, "file_path": "src/lib.rs",
+        //                                         "language": "rust", "diagnostics": [...], "count": 2}}
+        ws.send(JSON.stringify({type: 'unsubscribe', project_id: 1}));
+
     Args:
         websocket: WebSocket connection
         client_id: Optional client identifier for tracking
@@ -109,6 +117,17 @@ async def websocket_dashboard(websocket: WebSocket, client_id: Optional[str] = N
                         websocket,
                     )
 
+                elif data.get("type") == "subscribe":
+                    await _handle_subscribe(manager, websocket, data)
+
+                elif data.get("type") == "unsubscribe":
+                    removed = manager.unsubscribe(
+                        websocket,
+                        project_id=data.get("project_id"),
+                        file_path=data.get("file_path"),
+                    )
+                    await manager.send_personal({"type": "unsubscribed", "removed": removed}, websocket)
+
                 # Add more client message handling here as needed
 
             except WebSocketDisconnect:
@@ -122,6 +141,32 @@ async def websocket_dashboard(websocket: WebSocket, client_id: Optional[str] = N
         await manager.disconnect(websocket)
 
 
+async def _handle_subscribe(manager, websocket: WebSocket, data: dict) -> None:
+    """Subscribe a dashboard client to a project (or file) and send its current diagnostics."""
+    from gathering.lsp.manager import LSPManager
+
+    project_id = data.get("project_id")
+    if not isinstance(project_id, int):
+        await manager.send_personal(
+            {"type": "error", "message": "subscribe requires an integer project_id"},
+            websocket,
+        )
+        return
+
+    file_path = data.get("file_path")
+    manager.subscribe(websocket, project_id, file_path)
+
+    await manager.send_personal(
+        {
+            "type": "subscribed",
+            "project_id": project_id,
+            "file_path": file_path,
+            "diagnostics": LSPManager.published_diagnostics(project_id, file_path),
+        },
+        websocket,
+    )
+
+
 @router.get("/ws/stats")
 async def websocket_stats():
     """
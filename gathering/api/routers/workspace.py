@@ -26,6 +26,7 @@ from gathering.workspace import (
     WorkspaceType,
 )
 from gathering.workspace.activity_tracker import ActivityType, activity_tracker
+from gathering.lsp.manager import LSPManager
 from gathering.cache import (
     get_cached_file_tree,
     cache_file_tree,
@@ -166,6 +167,13 @@ class WriteFileRequest(BaseModel):
     create_backup: bool = True
 
 
+class WorkspaceEditRequest(BaseModel):
+    """Request to preview or apply an LSP WorkspaceEdit (e.g. a rename)."""
+
+    edit: Dict[str, Any]
+    create_backup: bool = True
+
+
 class ActivityRequest(BaseModel):
     """Request to track an activity."""
 
@@ -348,6 +356,7 @@ async def write_file(
             write_request.content,
             create_backup=write_request.create_backup,
         )
+        await LSPManager.notify_file_changed(project_id, path)
         return result
     except ValueError as e:
         raise HTTPException(status_code=400, detail=str(e))
@@ -371,6 +380,7 @@ async def delete_file(
 
     try:
         result = FileManager.delete_file(project_path, path)
+        await LSPManager.notify_file_changed(project_id, path, deleted=True)
         return result
     except FileNotFoundError:
         raise HTTPException(status_code=404, detail=f"File not found: {path}")
@@ -384,6 +394,58 @@ async def delete_file(
         raise HTTPException(status_code=500, detail="Internal server error")
 
 
+@router.post("/{project_id}/edit/preview")
+@limiter.limit(TIER_WRITE)
+async def preview_workspace_edit(
+    request: Request,
+    project_id: int,
+    edit_request: WorkspaceEditRequest,
+):
+    """Preview a multi-file WorkspaceEdit as unified diffs (nothing is written)."""
+    project_path = get_project_path(project_id)
+
+    try:
+        return FileManager.preview_workspace_edit(project_path, edit_request.edit)
+    except FileNotFoundError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except Exception as e:
+        logger.exception("Unexpected error in preview_workspace_edit")
+        raise HTTPException(status_code=500, detail="Internal server error")
+
+
+@router.post("/{project_id}/edit/apply")
+@limiter.limit(TIER_WRITE)
+async def apply_workspace_edit(
+    request: Request,
+    project_id: int,
+    edit_request: WorkspaceEditRequest,
+):
+    """Apply a multi-file WorkspaceEdit and notify the LSP servers."""
+    project_path = get_project_path(project_id)
+
+    try:
+        result = FileManager.apply_workspace_edit(
+            project_path,
+            edit_request.edit,
+            create_backup=edit_request.create_backup,
+        )
+        for written in result["files"]:
+            await LSPManager.notify_file_changed(project_id, written["path"])
+        return result
+    except FileNotFoundError as e:
+        raise HTTPException(status_code=404, detail=str(e))
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except (OSError, IOError, PermissionError) as e:
+        logger.error(f"File operation error in apply_workspace_edit: {e}")
+        raise HTTPException(status_code=500, detail="Internal server error")
+    except Exception as e:
+        logger.exception("Unexpected error in apply_workspace_edit")
+        raise HTTPException(status_code=500, detail="Internal server error")
+
+
 # ============================================================================
 # Git Endpoints
 # ============================================================================
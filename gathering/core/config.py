@@ -110,6 +110,20 @@ class Settings(BaseSettings):
     file_storage_base_path: Path = Field(default=Path("/tmp/gathering"))
     file_storage_max_size_mb: int = Field(default=100, ge=1, le=10000)
 
+    # Rust tooling (local mirrors, no network access)
+    cargo_registry_index: Optional[Path] = Field(
+        default=None, description="Local crates.io index mirror used for Cargo.toml checks"
+    )
+    cargo_advisory_db: Optional[Path] = Field(
+        default=None, description="Local RustSec advisory-db checkout used by cargo audit"
+    )
+
+    # LSP server pool
+    lsp_max_servers: int = Field(default=16, ge=1, le=256)
+    lsp_idle_timeout: int = Field(default=1800, ge=60, description="Seconds before an idle LSP server is evicted")
+    lsp_health_interval: int = Field(default=60, ge=5, description="Seconds between LSP pool health checks")
+    lsp_analysis_delay: float = Field(default=0.5, ge=0, description="Seconds to debounce re-analysis after a file edit")
+
     # Database
     database_url: Optional[str] = None
     redis_url: Optional[str] = None
@@ -0,0 +1,22 @@
+-- Migration 008: Sub-projects of polyglot repositories and Rust environment
+--
+-- Stores one entry per detected root (Cargo.toml, pyproject.toml, package.json,
+-- go.mod) with its own language, toolchain, tools, key files and commands,
+-- as produced by ProjectContext.to_dict()["subprojects"].
+--
+-- Also stores the detected Rust environment (toolchain, edition, MSRV,
+-- workspace members, features) as produced by ProjectContext.rust_config().
+--
+-- Idempotent: Uses IF NOT EXISTS patterns.
+
+ALTER TABLE project.projects
+    ADD COLUMN IF NOT EXISTS subprojects JSONB DEFAULT '[]';
+
+ALTER TABLE project.projects
+    ADD COLUMN IF NOT EXISTS rust JSONB DEFAULT '{}';
+
+COMMENT ON COLUMN project.projects.subprojects IS
+    'Sub-projects: [{path, language, name, toolchain, tools, key_files, commands}]';
+
+COMMENT ON COLUMN project.projects.rust IS
+    'Rust environment: {toolchain, edition, msrv, workspace_members, features}';
@@ -35,6 +35,7 @@ class EventType(str, Enum):
     - circle.*: Circle coordination
     - conversation.*: Inter-agent communication
     - task.*: Task management
+    - lsp.*: Language server updates
     """
 
     # Agent events
@@ -69,6 +70,9 @@ class EventType(str, Enum):
     CONVERSATION_MESSAGE = "conversation.message"
     CONVERSATION_TURN_COMPLETE = "conversation.turn.complete"
 
+    # LSP events
+    LSP_DIAGNOSTICS = "lsp.diagnostics"  # Diagnostics published for a file
+
     # System events
     SYSTEM_ERROR = "system.error"
     SYSTEM_WARNING = "system.warning"
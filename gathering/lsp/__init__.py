@@ -14,5 +14,6 @@ from gathering.lsp.plugin_system import LSPPluginRegistry
 import gathering.lsp.plugins.python_pylsp  # noqa: F401
 import gathering.lsp.plugins.javascript_lsp  # noqa: F401
 import gathering.lsp.plugins.rust_lsp  # noqa: F401
+import gathering.lsp.plugins.external_servers  # noqa: F401
 
 __all__ = ["LSPManager", "PythonLSPServer", "LSPPluginRegistry"]
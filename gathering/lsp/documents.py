@@ -0,0 +1,108 @@
+"""
+Open Document Tracking.
+
+Keeps the editor's view of open documents: their text and version,
+updated by didOpen/didChange notifications with full or incremental
+contentChanges. Requests for an open document then use this text
+instead of re-sending the content or reading the file from disk.
+"""
+
+from dataclasses import dataclass
+from pathlib import PurePosixPath
+from typing import Dict, List, Optional
+
+from gathering.lsp.text_edits import apply_content_changes
+
+
+@dataclass
+class TextDocument:
+    """An open document (path relative to the workspace root)."""
+    file_path: str
+    text: str
+    version: int
+
+
+class DocumentStore:
+    """
+    Open documents of one workspace, keyed by normalized path.
+
+    Example:
+        store = DocumentStore()
+        store.open("src/lib.rs", "fn main() {}\\n")
+        store.change("src/lib.rs", [{
+            "range": {"start": {"line": 0, "character": 3}, "end": {"line": 0, "character": 7}},
+            "text": "run"
+        }])
+        store.text("src/lib.rs")  # "fn run() {}\\n"
+    """
+
+    def __init__(self):
+        self._documents: Dict[str, TextDocument] = {}
+
+    @staticmethod
+    def _key(file_path: str) -> str:
+        """Normalize a path so "./src/lib.rs" and "src/lib.rs" match."""
+        return str(PurePosixPath(file_path.replace("\\", "/")))
+
+    def open(self, file_path: str, text: str, version: Optional[int] = None) -> TextDocument:
+        """Open (or reopen) a document."""
+        document = TextDocument(file_path=self._key(file_path), text=text, version=version or 1)
+        self._documents[document.file_path] = document
+        return document
+
+    def change(
+        self,
+        file_path: str,
+        changes: List[Dict],
+        version: Optional[int] = None
+    ) -> TextDocument:
+        """
+        Apply contentChanges to an open document.
+
+        Args:
+            file_path: Document path
+            changes: Full ({"text"}) or incremental ({"range", "text"}) changes
+            version: New document version (default: current version + 1)
+
+        Raises:
+            KeyError: If the document is not open
+            ValueError: If the version is not newer than the current one,
+                or a change range is invalid
+        """
+        document = self._documents.get(self._key(file_path))
+        if document is None:
+            raise KeyError(f"Document not open: {file_path}")
+
+        if version is None:
+            version = document.version + 1
+        elif version <= document.version:
+            raise ValueError(
+                f"Stale version {version} for {file_path} (current version is {document.version})"
+            )
+
+        document.text = apply_content_changes(document.text, changes)
+        document.version = version
+        return document
+
+    def close(self, file_path: str) -> bool:
+        """Close a document; returns whether it was open."""
+        return self._documents.pop(self._key(file_path), None) is not None
+
+    def get(self, file_path: str) -> Optional[TextDocument]:
+        """An open document, or None."""
+        return self._documents.get(self._key(file_path))
+
+    def text(self, file_path: str) -> Optional[str]:
+        """Text of an open document, or None."""
+        document = self.get(file_path)
+        return document.text if document else None
+
+    def documents(self) -> List[TextDocument]:
+        """Open documents, by path."""
+        return [self._documents[key] for key in sorted(self._documents)]
+
+    def __contains__(self, file_path: str) -> bool:
+        return self._key(file_path) in self._documents
+
+    def __len__(self) -> int:
+        return len(self._documents)
@@ -0,0 +1,429 @@
+"""
+External Language Server Adapter.
+
+Wraps any standard stdio language server (gopls, clangd,
+typescript-language-server, rust-analyzer, ...) as an LSP plugin from
+configuration alone. The configuration is described by
+EXTERNAL_SERVER_SCHEMA; a plugin's `PluginMetadata.config_schema`
+supplies its values as property defaults, and per-project options
+passed to /initialize override them, except the settings that decide
+which process is launched (SERVER_ONLY_SETTINGS).
+"""
+
+import copy
+import fnmatch
+import logging
+import shutil
+from pathlib import Path
+from typing import Optional, List, Dict, Any
+
+from gathering.lsp.documents import TextDocument
+from gathering.lsp.manager import BaseLSPServer
+from gathering.lsp.stdio_client import StdioLSPClient
+
+logger = logging.getLogger(__name__)
+
+# JSON Schema of an external server configuration
+EXTERNAL_SERVER_SCHEMA: Dict[str, Any] = {
+    "type": "object",
+    "required": ["command"],
+    "properties": {
+        "command": {
+            "type": "string",
+            "description": "Language server executable"
+        },
+        "args": {
+            "type": "array",
+            "items": {"type": "string"},
+            "description": "Command-line arguments, e.g. [\"--stdio\"]",
+            "default": []
+        },
+        "initialization_options": {
+            "type": "object",
+            "description": "Sent as initializationOptions",
+            "default": {}
+        },
+        "settings": {
+            "type": "object",
+            "description": "Answers to workspace/configuration, by section",
+            "default": {}
+        },
+        "file_globs": {
+            "type": "array",
+            "items": {"type": "string"},
+            "description": "Files handled by the server, e.g. [\"*.go\"]",
+            "default": ["*"]
+        },
+        "language_id": {
+            "type": "string",
+            "description": "languageId of opened documents (default: the plugin language)"
+        },
+        "language_ids": {
+            "type": "object",
+            "description": "languageId per file glob, e.g. {\"*.tsx\": \"typescriptreact\"}",
+            "default": {}
+        },
+        "request_timeout": {
+            "type": "number",
+            "description": "Seconds to wait for a response",
+            "default": 30.0
+        },
+        "install_hint": {
+            "type": "string",
+            "description": "How to install the server"
+        }
+    }
+}
+
+# Settings that choose the launched executable: plugin metadata only, never /initialize options
+SERVER_ONLY_SETTINGS = frozenset({"command", "args", "install_hint"})
+
+_JSON_TYPES = {
+    "string": str,
+    "array": list,
+    "object": dict,
+    "number": (int, float),
+    "boolean": bool
+}
+
+
+def server_schema(**defaults: Any) -> Dict[str, Any]:
+    """
+    EXTERNAL_SERVER_SCHEMA with the given values as property defaults.
+
+    Example:
+        server_schema(command="gopls", args=["serve"], file_globs=["*.go"])
+    """
+    schema = copy.deepcopy(EXTERNAL_SERVER_SCHEMA)
+    unknown = set(defaults) - set(schema["properties"])
+    if unknown:
+        raise ValueError(f"Unknown external server settings: {', '.join(sorted(unknown))}")
+
+    for key, value in defaults.items():
+        schema["properties"][key]["default"] = value
+    return schema
+
+
+def schema_defaults(schema: Dict[str, Any]) -> Dict[str, Any]:
+    """Property defaults of a JSON Schema object."""
+    return {
+        key: copy.deepcopy(prop["default"])
+        for key, prop in schema.get("properties", {}).items()
+        if "default" in prop
+    }
+
+
+def validate_config(config: Dict[str, Any], schema: Dict[str, Any]) -> Dict[str, Any]:
+    """
+    Check a server configuration against its schema (required keys,
+    known keys and top-level types).
+
+    Raises:
+        ValueError: If the configuration does not match
+    """
+    properties = schema.get("properties", {})
+    for key, value in config.items():
+        if key not in properties:
+            raise ValueError(f"Unknown setting: {key}")
+        expected = _JSON_TYPES.get(properties[key].get("type"))
+        if expected and not isinstance(value, expected):
+            raise ValueError(f"Setting {key} must be of type {properties[key]['type']}")
+
+    for key in schema.get("required", []):
+        if not config.get(key):
+            raise ValueError(f"Missing required setting: {key}")
+
+    return config
+
+
+class ExternalLSPServer(BaseLSPServer):
+    """
+    LSP server backed by an external language server process.
+
+    Subclasses are created by LSPPluginRegistry.register_external from
+    plugin metadata; `config_schema` holds their configuration.
+    """
+
+    # Plugin language and configuration schema (set per registered server)
+    language: str = "plaintext"
+    config_schema: Dict[str, Any] = EXTERNAL_SERVER_SCHEMA
+
+    def __init__(self, workspace_path: str):
+        super().__init__(workspace_path)
+        self.config = schema_defaults(self.config_schema)
+        self.client: Optional[StdioLSPClient] = None
+
+    def configure(self, options: dict):
+        """
+        Override configuration values (validated against the schema).
+
+        Raises:
+            ValueError: For unknown or mistyped settings, and for
+                SERVER_ONLY_SETTINGS
+        """
+        forbidden = sorted(SERVER_ONLY_SETTINGS & set(options))
+        if forbidden:
+            raise ValueError(f"Setting cannot be overridden per project: {', '.join(forbidden)}")
+        validate_config({**self.config, **options}, self.config_schema)
+        super().configure(options)
+        self.config.update(options)
+
+    @property
+    def command(self) -> List[str]:
+        """Server command line."""
+        return [self.config.get("command", ""), *self.config.get("args", [])]
+
+    def missing_dependencies(self) -> List[str]:
+        """Executables required by the configuration that are not installed."""
+        executable = self.config.get("command")
+        if executable and shutil.which(executable):
+            return []
+        return [executable or "<command>"]
+
+    async def initialize(self, workspace_path: str) -> dict:
+        """Start the language server and report its capabilities."""
+        self.workspace_path = Path(workspace_path)
+        validate_config(self.config, self.config_schema)
+
+        if self.client is not None:
+            await self.client.shutdown()
+            self.client = None
+
+        missing = self.missing_dependencies()
+        if missing:
+            hint = self.config.get("install_hint")
+            raise RuntimeError(
+                f"{self.language} language server not found: {', '.join(missing)}"
+                + (f". Install with: {hint}" if hint else "")
+            )
+
+        client = StdioLSPClient(
+            str(self.workspace_path),
+            command=self.command,
+            request_timeout=self.config.get("request_timeout", 30.0),
+            name=self.config["command"],
+            language_id=self.config.get("language_id") or self.language,
+            language_ids=self.config.get("language_ids"),
+            initialization_options=self.config.get("initialization_options"),
+            settings=self.config.get("settings")
+        )
+        client.on_diagnostics = self.publish_diagnostics
+        await client.start()
+        self.client = client
+        self.initialized = True
+
+        return {
+            "capabilities": client.server_capabilities,
+            "backend": self.config["command"]
+        }
+
+    async def shutdown(self):
+        """Stop the language server process."""
+        if self.client:
+            await self.client.shutdown()
+            self.client = None
+        self.initialized = False
+
+    async def health_check(self) -> bool:
+        """Healthy while the language server process is running."""
+        return self.client is not None and self.client.is_alive()
+
+    def handles(self, file_path: str) -> bool:
+        """Whether a file matches the configured globs."""
+        name = Path(file_path).name
+        return any(
+            fnmatch.fnmatch(name, pattern) or fnmatch.fnmatch(file_path, pattern)
+            for pattern in self.config.get("file_globs") or ["*"]
+        )
+
+    async def _ensure_initialized(self):
+        """Lazily initialize when a request arrives before /initialize."""
+        if not self.initialized:
+            await self.initialize(str(self.workspace_path))
+
+    def _read_content(self, file_path: str, content: Optional[str]) -> Optional[str]:
+        """Return the given content, or read the file from the workspace."""
+        if content is not None:
+            return content
+
+        full_path = self.workspace_path / file_path
+        if full_path.is_file():
+            return full_path.read_text()
+        return None
+
+    async def _document(self, file_path: str, content: Optional[str]) -> Optional[str]:
+        """Content of a handled document, initializing the server first."""
+        if not self.handles(file_path):
+            return None
+        await self._ensure_initialized()
+        return self._read_content(file_path, content)
+
+    async def file_changed(self, file_path: str, deleted: bool = False):
+        """Resync an open document after it changed on disk."""
+        if self.client is None or deleted or not self.handles(file_path):
+            return
+        if self.client.is_open(file_path):
+            content = self._read_content(file_path, None)
+            if content is not None:
+                await self.client.did_change(file_path, content)
+
+    async def did_open(
+        self,
+        file_path: str,
+        content: Optional[str] = None,
+        version: Optional[int] = None
+    ) -> TextDocument:
+        """Open a document and sync it to the language server."""
+        document = await super().did_open(file_path, content, version)
+        if self.handles(file_path):
+            await self._ensure_initialized()
+            await self.client.did_change(document.file_path, document.text)
+        return document
+
+    async def did_change(
+        self,
+        file_path: str,
+        changes: List[Dict],
+        version: Optional[int] = None
+    ) -> TextDocument:
+        """Apply contentChanges, forwarding them incrementally when supported."""
+        previous = self.documents.text(file_path)
+        document = await super().did_change(file_path, changes, version)
+        if self.client is not None and self.handles(file_path):
+            await self.client.apply_changes(document.file_path, changes, previous, document.text)
+        return document
+
+    async def did_close(self, file_path: str) -> bool:
+        """Close a document with the language server too."""
+        closed = await super().did_close(file_path)
+        if self.client is not None:
+            await self.client.did_close(file_path)
+        return closed
+
+    async def get_completions(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Get completions from the language server."""
+        content = await self._document(file_path, content)
+        if content is None:
+            return []
+        return await self.client.completion(file_path, line, character, content)
+
+    async def get_diagnostics(
+        self,
+        file_path: str,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Get the diagnostics published for a document."""
+        content = await self._document(file_path, content)
+        if content is None:
+            return []
+        return await self.client.diagnostics(file_path, content)
+
+    async def get_hover(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        content: Optional[str] = None
+    ) -> Optional[Dict]:
+        """Get hover information."""
+        content = await self._document(file_path, content)
+        if content is None:
+            return None
+        return await self.client.hover(file_path, line, character, content)
+
+    async def get_definition(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        content: Optional[str] = None
+    ) -> Optional[Dict]:
+        """Get definition location."""
+        content = await self._document(file_path, content)
+        if content is None:
+            return None
+        return await self.client.definition(file_path, line, character, content)
+
+    async def get_code_actions(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        end_line: Optional[int] = None,
+        end_character: Optional[int] = None,
+        diagnostics: Optional[List[Dict]] = None,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Get code actions for a range."""
+        content = await self._document(file_path, content)
+        if content is None:
+            return []
+
+        end_line = line if end_line is None else end_line
+        end_character = character if end_character is None else end_character
+        range_ = {
+            "start": {"line": line - 1, "character": character},
+            "end": {"line": end_line - 1, "character": end_character}
+        }
+        return await self.client.code_actions(file_path, range_, diagnostics or [], content)
+
+    async def get_formatting(
+        self,
+        file_path: str,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Format a document with the language server."""
+        content = await self._document(file_path, content)
+        if content is None:
+            return []
+        return await self.client.formatting(file_path, content)
+
+    async def get_document_symbols(
+        self,
+        file_path: str,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Get the document outline."""
+        content = await self._document(file_path, content)
+        if content is None:
+            return []
+        return await self.client.document_symbols(file_path, content)
+
+    async def workspace_symbols(self, query: str) -> List[Dict]:
+        """Search symbols across the workspace."""
+        await self._ensure_initialized()
+        return await self.client.workspace_symbols(query)
+
+    async def get_references(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        include_declaration: bool = True,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Find references to the symbol at a position."""
+        content = await self._document(file_path, content)
+        if content is None:
+            return []
+        return await self.client.references(file_path, line, character, content, include_declaration)
+
+    async def rename(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        new_name: str,
+        content: Optional[str] = None
+    ) -> Optional[Dict]:
+        """Rename the symbol at a position."""
+        content = await self._document(file_path, content)
+        if content is None:
+            return None
+        return await self.client.rename(file_path, line, character, new_name, content)
@@ -0,0 +1,289 @@
+"""
+JavaScript/TypeScript Document Symbols.
+
+Builds the outline of a JavaScript or TypeScript file (functions, classes
+and their members, top-level variables, and TypeScript interfaces, enums,
+type aliases and namespaces). Comments, strings, template literals and
+regex literals are masked first, so braces inside them do not confuse
+the block structure.
+"""
+
+import bisect
+import re
+from typing import Optional, List, Dict
+
+from gathering.lsp.symbols import document_symbol, lsp_range, nest
+
+IDENT = r"[A-Za-z_$][\w$]*"
+
+# Declarations recognised anywhere except directly inside a class body
+FUNCTION = re.compile(rf"(?<![.\w$])(?:async\s+)?function\b\s*\*?\s*({IDENT})\s*(?:<[^>(]*>\s*)?\(")
+CLASS = re.compile(rf"(?<![.\w$])class\s+({IDENT})")
+INTERFACE = re.compile(rf"(?<![.\w$])interface\s+({IDENT})")
+ENUM = re.compile(rf"(?<![.\w$])(?:const\s+)?enum\s+({IDENT})\s*\{{")
+NAMESPACE = re.compile(rf"(?<![.\w$])(?:namespace|module)\s+({IDENT}(?:\.{IDENT})*)\s*\{{")
+TYPE_ALIAS = re.compile(rf"(?<![.\w$])type\s+({IDENT})\s*(?:<[^=]*>)?\s*=")
+
+# Top-level `const`/`let`/`var` bindings
+VARIABLE = re.compile(rf"(?<![.\w$])(const|let|var)\s+({IDENT})\s*(?::[^=;\n]+)?=\s*")
+FUNCTION_VALUE = re.compile(
+    rf"(?:async\s+)?(?:function\b|(?:\([^()]*\)|{IDENT})\s*(?::[^=;\n]+)?=>)"
+)
+
+# Members at the top level of a class body
+MEMBER = re.compile(
+    rf"(?:(?:public|private|protected|readonly|static|async|abstract|override|declare|get|set)\s+)*"
+    rf"\*?\s*(#?{IDENT})\s*(\(|<|[?!]?\s*[:=;\n])"
+)
+
+# Tokens after which a `/` starts a regex literal rather than a division
+REGEX_PRECEDERS = set("(,=:[!&|?{};+-*%<>~^") | {""}
+REGEX_KEYWORDS = {"return", "typeof", "instanceof", "in", "of", "new", "delete", "void", "throw", "case", "yield", "await"}
+
+
+def mask(source: str) -> str:
+    """
+    Replace comments and string, template and regex literals with spaces.
+
+    The result has the same length and line breaks as the source, so
+    offsets map back to the original text.
+    """
+    out = list(source)
+    n = len(source)
+    i = 0
+    last = ""  # last significant character or word, for regex detection
+
+    def blank(start: int, end: int):
+        for k in range(start, min(end, n)):
+            if out[k] != "\n":
+                out[k] = " "
+
+    while i < n:
+        char = source[i]
+        if source.startswith("//", i):
+            end = source.find("\n", i)
+            end = n if end == -1 else end
+            blank(i, end)
+            i = end
+        elif source.startswith("/*", i):
+            end = source.find("*/", i + 2)
+            end = n if end == -1 else end + 2
+            blank(i, end)
+            i = end
+        elif char in "'\"`":
+            j = i + 1
+            while j < n and source[j] != char:
+                if source[j] == "\\":
+                    j += 1
+                elif source[j] == "\n" and char != "`":
+                    break
+                j += 1
+            blank(i + 1, j)
+            i = j + 1
+            last = char
+        elif char == "/" and (last in REGEX_PRECEDERS or last in REGEX_KEYWORDS):
+            j = i + 1
+            in_class = False
+            while j < n and source[j] != "\n":
+                if source[j] == "\\":
+                    j += 1
+                elif source[j] == "[":
+                    in_class = True
+                elif source[j] == "]":
+                    in_class = False
+                elif source[j] == "/" and not in_class:
+                    break
+                j += 1
+            blank(i + 1, j)
+            i = j + 1
+            last = "/"
+        elif char.isspace():
+            i += 1
+        else:
+            match = re.match(r"[\w$]+", source[i:i + 64])
+            if match:
+                last = match.group(0)
+                i += len(last)
+            else:
+                last = char
+                i += 1
+
+    return "".join(out)
+
+
+class _Outline:
+    """Declaration scanner over masked source."""
+
+    def __init__(self, source: str):
+        self.source = source
+        self.code = mask(source)
+        self.line_starts = [0] + [m.end() for m in re.finditer("\n", source)]
+
+        # Matching braces and, per offset, the innermost enclosing '{'
+        self.closing: Dict[int, int] = {}
+        self.enclosing: List[int] = [-1] * (len(self.code) + 1)
+        stack: List[int] = []
+        for offset, char in enumerate(self.code):
+            if char == "}" and stack:
+                self.closing[stack.pop()] = offset
+            self.enclosing[offset] = stack[-1] if stack else -1
+            if char == "{":
+                stack.append(offset)
+        self.enclosing[len(self.code)] = stack[-1] if stack else -1
+
+        self.class_bodies: Dict[int, str] = {}  # '{' offset -> class name
+        self.symbols: List[Dict] = []
+
+    def position(self, offset: int) -> tuple:
+        line = bisect.bisect_right(self.line_starts, offset) - 1
+        return line, offset - self.line_starts[line]
+
+    def range(self, start: int, end: int) -> Dict:
+        return lsp_range(*self.position(start), *self.position(end))
+
+    def add(self, name: str, kind: str, start: int, end: int, name_start: int, detail: str = ""):
+        self.symbols.append(document_symbol(
+            name, kind, self.range(start, end),
+            self.range(name_start, name_start + len(name)), detail
+        ))
+
+    def _matching_paren(self, offset: int) -> int:
+        """Offset of the ')' closing the '(' at offset (or the end of source)."""
+        depth = 0
+        for j in range(offset, len(self.code)):
+            if self.code[j] == "(":
+                depth += 1
+            elif self.code[j] == ")":
+                depth -= 1
+                if depth == 0:
+                    return j
+        return len(self.code) - 1
+
+    def _body_end(self, offset: int) -> Optional[int]:
+        """End (after '}') of the block opening at or after offset, before any ';'."""
+        match = re.compile(r"[{;]").search(self.code, offset)
+        if match and match.group(0) == "{" and match.start() in self.closing:
+            return self.closing[match.start()] + 1
+        return None
+
+    def _statement_end(self, offset: int) -> int:
+        """End of a statement starting at offset, skipping over nested blocks."""
+        depth = 0
+        for j in range(offset, len(self.code)):
+            char = self.code[j]
+            if char in "({[":
+                depth += 1
+            elif char in ")}]":
+                if depth == 0:
+                    return j
+                depth -= 1
+            elif depth == 0 and (char == ";" or (char == "\n" and not self.code[offset:j].rstrip().endswith(("=", ",", "=>")))):
+                return j + (char == ";")
+        return len(self.code)
+
+    def _in_class_body(self, offset: int) -> bool:
+        return self.enclosing[offset] in self.class_bodies
+
+    def scan(self) -> List[Dict]:
+        code = self.code
+
+        for match in CLASS.finditer(code):
+            if self._in_class_body(match.start()):
+                continue
+            brace = code.find("{", match.end())
+            if brace == -1 or brace not in self.closing:
+                continue
+            heritage = " ".join(code[match.end():brace].split())
+            self.class_bodies[brace] = match.group(1)
+            self.add(match.group(1), "class", match.start(), self.closing[brace] + 1, match.start(1), heritage)
+
+        for brace in self.class_bodies:
+            self._members(brace)
+
+        for match in FUNCTION.finditer(code):
+            if self._in_class_body(match.start()):
+                continue
+            params_end = self._matching_paren(match.end() - 1)
+            end = self._body_end(params_end + 1) or params_end + 1
+            params = " ".join(self.source[match.end() - 1:params_end + 1].split())
+            self.add(match.group(1), "function", match.start(), end, match.start(1), params)
+
+        for pattern, kind in ((INTERFACE, "interface"), (ENUM, "enum"), (NAMESPACE, "namespace")):
+            for match in pattern.finditer(code):
+                if self._in_class_body(match.start()):
+                    continue
+                end = self._body_end(match.start(1) + len(match.group(1)))
+                if end is None:
+                    continue
+                self.add(match.group(1), kind, match.start(), end, match.start(1))
+                if kind == "enum":
+                    self._enum_members(code.find("{", match.end() - 1), end - 1)
+
+        for match in TYPE_ALIAS.finditer(code):
+            if self.enclosing[match.start()] == -1:
+                self.add(match.group(1), "type_parameter", match.start(),
+                         self._statement_end(match.end()), match.start(1))
+
+        for match in VARIABLE.finditer(code):
+            if self.enclosing[match.start()] != -1:
+                continue
+            keyword, name = match.group(1), match.group(2)
+            value = FUNCTION_VALUE.match(code, match.end())
+            if value:
+                # `function (...) {` and `(...) => {` have a block body; `x => x + 1` does not
+                has_block = value.group(0).endswith("function") or code[value.end():].lstrip().startswith("{")
+                end = (self._body_end(value.end()) if has_block else None) or self._statement_end(match.end())
+                self.add(name, "function", match.start(), end, match.start(2))
+            else:
+                kind = "constant" if keyword == "const" else "variable"
+                self.add(name, kind, match.start(), self._statement_end(match.end()), match.start(2))
+
+        return nest(self.symbols)
+
+    def _members(self, brace: int):
+        """Methods, accessors and properties at the top level of a class body."""
+        code = self.code
+        end = self.closing[brace]
+        offset = brace + 1
+        while offset < end:
+            # Skip to the start of the next member
+            while offset < end and (code[offset].isspace() or code[offset] in ";,"):
+                offset += 1
+            if offset >= end:
+                break
+            if code[offset] == "@":  # decorator
+                decorator = re.compile(rf"@{IDENT}(?:\.{IDENT})*").match(code, offset)
+                offset = decorator.end() if decorator else offset + 1
+                if offset < end and code[offset] == "(":
+                    offset = self._matching_paren(offset) + 1
+                continue
+
+            match = MEMBER.match(code, offset)
+            if not match or self.enclosing[offset] != brace:
+                offset = self._statement_end(offset) + 1
+                continue
+
+            name = match.group(1)
+            if match.group(2) in ("(", "<"):
+                params = code.find("(", match.start(2))
+                params_end = self._matching_paren(params)
+                member_end = self._body_end(params_end + 1) or self._statement_end(params_end + 1)
+                kind = "constructor" if name == "constructor" else "method"
+                detail = " ".join(self.source[params:params_end + 1].split())
+                self.add(name, kind, offset, member_end, match.start(1), detail)
+            else:
+                member_end = self._statement_end(match.start(2))
+                self.add(name, "property", offset, member_end, match.start(1))
+            offset = max(member_end, offset + 1)
+
+    def _enum_members(self, brace: int, end: int):
+        """Members of an enum body (from '{' at brace to '}' at end)."""
+        for match in re.compile(rf"({IDENT})\s*(?:=[^,}}]*)?(?=[,}}])").finditer(self.code, brace + 1, end + 1):
+            if self.enclosing[match.start(1)] == brace:
+                self.add(match.group(1), "enum_member", match.start(1),
+                         match.start(1) + len(match.group(1)), match.start(1))
+
+
+def outline(source: str) -> List[Dict]:
+    """DocumentSymbol tree for JavaScript or TypeScript source."""
+    return _Outline(source).scan()
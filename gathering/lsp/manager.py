@@ -1,22 +1,99 @@
 """
 LSP Manager - Manages language server instances.
+
+Servers live in a bounded pool keyed by project and language. A
+maintenance task evicts servers that have been idle too long and probes
+the others, restarting crashed language server processes with
+exponential backoff.
+
+Servers publish diagnostics asynchronously (e.g. when `cargo check`
+finishes); changed results are sent on the event bus as
+`lsp.diagnostics` events. Workspace file changes trigger a debounced
+re-analysis of the file.
 """
 
-from typing import Dict, Optional
+import asyncio
+import time
+from dataclasses import dataclass
+from typing import Awaitable, Callable, Dict, List, Optional
 import logging
 from pathlib import Path
 
+from gathering.lsp.documents import DocumentStore, TextDocument
+
 logger = logging.getLogger(__name__)
 
 
+@dataclass
+class ServerState:
+    """Pool bookkeeping for one server (monotonic timestamps)."""
+    created_at: float
+    last_used: float
+    healthy: bool = True
+    restarts: int = 0
+    failures: int = 0  # consecutive failed restarts
+    next_restart: float = 0.0
+    last_error: Optional[str] = None
+
+
 class LSPManager:
     """
     Manages Language Server Protocol servers for different languages.
 
     Maintains a pool of LSP server instances per project and language.
+    When the pool is full the least recently used server is shut down
+    to make room for a new one.
     """
 
-    _servers: Dict[str, Dict[str, 'BaseLSPServer']] = {}
+    _servers: Dict[str, 'BaseLSPServer'] = {}
+    _states: Dict[str, ServerState] = {}
+    _maintenance_task: Optional[asyncio.Task] = None
+    _retiring: set = set()  # shutdown tasks of evicted servers
+    _analysis_tasks: Dict[str, asyncio.Task] = {}  # "project_id:file_path" -> debounced analysis
+    _published: Dict[str, Dict[str, List[Dict]]] = {}  # server key -> file_path -> diagnostics
+
+    # Pool settings (see configure_pool)
+    max_servers: int = 16
+    idle_timeout: float = 1800.0
+    health_interval: float = 60.0
+    restart_backoff: float = 2.0
+    max_restart_backoff: float = 300.0
+    max_restart_attempts: int = 5
+    analysis_delay: float = 0.5  # debounce before re-analyzing a changed file
+
+    @classmethod
+    def configure_pool(
+        cls,
+        max_servers: Optional[int] = None,
+        idle_timeout: Optional[float] = None,
+        health_interval: Optional[float] = None,
+        analysis_delay: Optional[float] = None
+    ):
+        """
+        Adjust the pool limits.
+
+        Args:
+            max_servers: Maximum number of live servers
+            idle_timeout: Seconds without requests before a server is evicted
+            health_interval: Seconds between maintenance passes
+            analysis_delay: Seconds to wait for further edits before re-analyzing a file
+        """
+        if max_servers is not None:
+            cls.max_servers = max_servers
+        if idle_timeout is not None:
+            cls.idle_timeout = idle_timeout
+        if health_interval is not None:
+            cls.health_interval = health_interval
+        if analysis_delay is not None:
+            cls.analysis_delay = analysis_delay
+
+    @classmethod
+    def _state(cls, key: str) -> ServerState:
+        """Bookkeeping for a pooled server, created on first access."""
+        if key not in cls._states:
+            now = time.monotonic()
+            cls._states[key] = ServerState(created_at=now, last_used=now)
+        return cls._states[key]
 
     @classmethod
     def get_server(
@@ -60,16 +137,53 @@ class LSPManager:
                         f"Available plugins: {LSPPluginRegistry.list_plugins()}"
                     )
 
+            while len(cls._servers) >= cls.max_servers:
+                cls._evict_least_recently_used()
+
+            async def publish(file_path: str, diagnostics: List[Dict]):
+                await cls.publish_diagnostics(project_id, language, file_path, diagnostics)
+
+            server.diagnostics_callback = publish
             cls._servers[key] = server
+            cls._states.pop(key, None)
 
+        cls._state(key).last_used = time.monotonic()
         return cls._servers[key]
 
+    @classmethod
+    def _evict_least_recently_used(cls):
+        """Remove the least recently used server and shut it down in the background."""
+        key = min(cls._servers, key=lambda k: cls._state(k).last_used)
+        server = cls._servers.pop(key)
+        cls._published.pop(key, None)
+        cls._states.pop(key, None)
+        logger.info(f"LSP pool full ({cls.max_servers}), evicting {key}")
+
+        try:
+            loop = asyncio.get_running_loop()
+        except RuntimeError:
+            return  # no event loop, so no server process can be running
+
+        task = loop.create_task(cls._shutdown(key, server))
+        cls._retiring.add(task)
+        task.add_done_callback(cls._retiring.discard)
+
+    @staticmethod
+    async def _shutdown(key: str, server: 'BaseLSPServer'):
+        """Shut a server down, logging rather than raising errors."""
+        try:
+            await server.shutdown()
+            logger.info(f"Shutdown LSP server for {key}")
+        except Exception as e:
+            logger.error(f"Error shutting down LSP server {key}: {e}")
+
     @classmethod
     async def initialize_server(
         cls,
         project_id: int,
         language: str,
-        workspace_path: str
+        workspace_path: str,
+        options: Optional[dict] = None
     ) -> dict:
         """
         Initialize an LSP server.
@@ -78,11 +192,14 @@ class LSPManager:
             project_id: Project identifier
             language: Programming language
             workspace_path: Path to workspace root
+            options: Server-specific settings
 
         Returns:
             Server capabilities
         """
         server = cls.get_server(project_id, language, workspace_path)
+        if options:
+            server.configure(options)
         capabilities = await server.initialize(workspace_path)
 
         logger.info(f"Initialized LSP server for {language} in project {project_id}")
@@ -93,18 +210,311 @@ class LSPManager:
         """Shutdown an LSP server."""
         key = f"{project_id}:{language}"
 
-        if key in cls._servers:
-            server = cls._servers[key]
-            await server.shutdown()
-            del cls._servers[key]
-            logger.info(f"Shutdown LSP server for {key}")
+        server = cls._servers.pop(key, None)
+        cls._states.pop(key, None)
+        cls._published.pop(key, None)
+        if server is not None:
+            await cls._shutdown(key, server)
+
+    @classmethod
+    async def notify_file_changed(cls, project_id: int, file_path: str, deleted: bool = False):
+        """
+        Notify a project's servers that a workspace file changed on disk.
+
+        Args:
+            project_id: Project identifier
+            file_path: Path relative to the workspace root
+            deleted: Whether the file was removed
+        """
+        prefix = f"{project_id}:"
+        for key, server in list(cls._servers.items()):
+            if not key.startswith(prefix):
+                continue
+            try:
+                await server.file_changed(file_path, deleted=deleted)
+            except Exception as e:
+                logger.error(f"LSP server {key} failed to process change to {file_path}: {e}")
+
+            if deleted and file_path in cls._published.get(key, {}):
+                project, language = key.split(":", 1)
+                await cls.publish_diagnostics(int(project), language, file_path, [])
+                cls._published[key].pop(file_path, None)
+
+        if not deleted:
+            cls.schedule_analysis(project_id, file_path)
+
+    @classmethod
+    def schedule_analysis(cls, project_id: int, file_path: str, delay: Optional[float] = None):
+        """
+        Re-analyze a file once no further changes arrive for `delay` seconds.
+
+        Each initialized server of the project that handles the file
+        computes fresh diagnostics and publishes them. A pending analysis
+        of the same file is cancelled and rescheduled.
+
+        Args:
+            project_id: Project identifier
+            file_path: Path relative to the workspace root
+            delay: Debounce delay (default: `analysis_delay`)
+        """
+        key = f"{project_id}:{file_path}"
+        pending = cls._analysis_tasks.get(key)
+        if pending is not None and not pending.done():
+            pending.cancel()
+
+        delay = cls.analysis_delay if delay is None else delay
+        task = asyncio.get_running_loop().create_task(cls._analyze_later(project_id, file_path, delay))
+        cls._analysis_tasks[key] = task
+
+        def forget(done: asyncio.Task):
+            if cls._analysis_tasks.get(key) is done:
+                del cls._analysis_tasks[key]
+
+        task.add_done_callback(forget)
+
+    @classmethod
+    async def _analyze_later(cls, project_id: int, file_path: str, delay: float):
+        """Wait out the debounce delay, then analyze a file with the project's servers."""
+        await asyncio.sleep(delay)
+
+        prefix = f"{project_id}:"
+        for key, server in list(cls._servers.items()):
+            if not key.startswith(prefix) or not server.initialized:
+                continue
+            try:
+                if server.handles(file_path):
+                    await server.analyze(file_path)
+            except Exception as e:
+                logger.error(f"LSP server {key} failed to analyze {file_path}: {e}")
+
+    @classmethod
+    async def publish_diagnostics(
+        cls,
+        project_id: int,
+        language: str,
+        file_path: str,
+        diagnostics: List[Dict]
+    ) -> bool:
+        """
+        Publish a file's diagnostics as an `lsp.diagnostics` event.
+
+        Results identical to the last ones published for the file by the
+        same server are not sent again.
+
+        Returns:
+            True if an event was published
+        """
+        published = cls._published.setdefault(f"{project_id}:{language}", {})
+        if published.get(file_path) == diagnostics:
+            return False
+        published[file_path] = list(diagnostics)
+
+        from gathering.events import event_bus, Event, EventType
+
+        await event_bus.publish(Event(
+            type=EventType.LSP_DIAGNOSTICS,
+            data={
+                "file_path": file_path,
+                "language": language,
+                "diagnostics": diagnostics,
+                "count": len(diagnostics),
+            },
+            project_id=project_id,
+        ))
+        return True
+
+    @classmethod
+    def published_diagnostics(cls, project_id: int, file_path: Optional[str] = None) -> List[Dict]:
+        """
+        Last diagnostics published for a project, optionally for one file.
+
+        Returns:
+            List of {"file_path", "language", "diagnostics"} entries
+        """
+        results = []
+        for key, files in sorted(cls._published.items()):
+            project, language = key.split(":", 1)
+            if int(project) != project_id:
+                continue
+            for path, diagnostics in sorted(files.items()):
+                if file_path is None or path == file_path:
+                    results.append({"file_path": path, "language": language, "diagnostics": diagnostics})
+        return results
+
+    @classmethod
+    async def evict_idle(cls) -> List[str]:
+        """
+        Shut down servers that received no request within the idle timeout.
+
+        Returns:
+            Keys of the evicted servers
+        """
+        now = time.monotonic()
+        idle = [
+            key for key in list(cls._servers)
+            if now - cls._state(key).last_used > cls.idle_timeout
+        ]
+        for key in idle:
+            logger.info(f"Evicting idle LSP server {key}")
+            project_id, language = key.split(":", 1)
+            await cls.shutdown_server(int(project_id), language)
+        return idle
+
+    @classmethod
+    async def check_health(cls) -> Dict[str, bool]:
+        """
+        Probe initialized servers and restart unhealthy ones.
+
+        A server whose restart fails is retried with exponential backoff
+        and given up on after `max_restart_attempts` consecutive failures
+        (it keeps serving whatever fallback it has).
+
+        Returns:
+            Health per server key after any restarts
+        """
+        results = {}
+        for key, server in list(cls._servers.items()):
+            if not server.initialized:
+                continue  # nothing started yet
+
+            state = cls._state(key)
+            try:
+                healthy = await server.health_check()
+            except Exception as e:
+                logger.error(f"LSP health probe failed for {key}: {e}")
+                healthy = False
+
+            if healthy:
+                state.healthy = True
+                state.failures = 0
+            else:
+                state.healthy = False
+                if state.failures < cls.max_restart_attempts and time.monotonic() >= state.next_restart:
+                    await cls._restart(key, server, state)
+
+            results[key] = state.healthy
+        return results
+
+    @classmethod
+    async def _restart(cls, key: str, server: 'BaseLSPServer', state: ServerState):
+        """Restart a server, scheduling the next attempt with backoff on failure."""
+        logger.warning(f"Restarting unhealthy LSP server {key}")
+        state.restarts += 1
+        try:
+            await server.restart()
+            state.healthy = True
+            state.failures = 0
+            state.last_error = None
+            logger.info(f"Restarted LSP server {key}")
+        except Exception as e:
+            state.failures += 1
+            state.last_error = str(e)
+            delay = min(cls.restart_backoff * 2 ** (state.failures - 1), cls.max_restart_backoff)
+            state.next_restart = time.monotonic() + delay
+            if state.failures >= cls.max_restart_attempts:
+                logger.error(f"Giving up on LSP server {key} after {state.failures} failed restarts: {e}")
+            else:
+                logger.error(f"Failed to restart LSP server {key} (retrying in {delay:.0f}s): {e}")
+
+    @classmethod
+    async def maintain(cls):
+        """Run one maintenance pass: idle eviction, then health probes."""
+        await cls.evict_idle()
+        await cls.check_health()
+
+        # Forget bookkeeping for servers removed from the pool directly
+        for key in set(cls._states) - set(cls._servers):
+            del cls._states[key]
+
+    @classmethod
+    async def _maintenance_loop(cls):
+        """Run maintenance passes every `health_interval` seconds."""
+        while True:
+            await asyncio.sleep(cls.health_interval)
+            try:
+                await cls.maintain()
+            except Exception as e:
+                logger.error(f"LSP pool maintenance error: {e}")
+
+    @classmethod
+    def start_maintenance(cls):
+        """Start the background maintenance task (idempotent)."""
+        if cls._maintenance_task is None or cls._maintenance_task.done():
+            cls._maintenance_task = asyncio.get_running_loop().create_task(cls._maintenance_loop())
+
+    @classmethod
+    async def stop_maintenance(cls):
+        """Stop the background maintenance task."""
+        task, cls._maintenance_task = cls._maintenance_task, None
+        if task is not None and not task.done():
+            task.cancel()
+            try:
+                await task
+            except asyncio.CancelledError:
+                pass
+
+    @classmethod
+    def server_status(cls, key: str) -> Optional[dict]:
+        """Pool status of one server, or None if it is not running."""
+        server = cls._servers.get(key)
+        if server is None:
+            return None
+
+        state = cls._state(key)
+        now = time.monotonic()
+        project_id, language = key.split(":", 1)
+        return {
+            "project_id": int(project_id),
+            "language": language,
+            "server": type(server).__name__,
+            "initialized": server.initialized,
+            "open_documents": len(getattr(server, "documents", ())),
+            "healthy": state.healthy,
+            "uptime_seconds": round(now - state.created_at, 1),
+            "idle_seconds": round(now - state.last_used, 1),
+            "restarts": state.restarts,
+            "failed_restarts": state.failures,
+            "last_error": state.last_error,
+        }
+
+    @classmethod
+    def status(cls, project_id: Optional[int] = None) -> dict:
+        """
+        Pool status, optionally restricted to one project's servers.
+
+        Returns:
+            Pool limits and per-server status
+        """
+        keys = [
+            key for key in cls._servers
+            if project_id is None or key.startswith(f"{project_id}:")
+        ]
+        return {
+            "size": len(cls._servers),
+            "max_servers": cls.max_servers,
+            "idle_timeout": cls.idle_timeout,
+            "health_interval": cls.health_interval,
+            "maintenance_running": cls._maintenance_task is not None and not cls._maintenance_task.done(),
+            "servers": [cls.server_status(key) for key in sorted(keys)],
+        }
 
     @classmethod
-    def shutdown_all(cls):
-        """Shutdown all LSP servers."""
+    async def shutdown_all(cls):
+        """Shutdown all LSP servers and stop pool maintenance."""
+        await cls.stop_maintenance()
+
+        pending = list(cls._analysis_tasks.values())
+        for task in pending:
+            task.cancel()
+        if pending:
+            await asyncio.gather(*pending, return_exceptions=True)
+
         for key in list(cls._servers.keys()):
-            project_id, language = key.split(":")
-            cls.shutdown_server(int(project_id), language)
+            project_id, language = key.split(":", 1)
+            await cls.shutdown_server(int(project_id), language)
+
+        if cls._retiring:
+            await asyncio.gather(*cls._retiring, return_exceptions=True)
 
 
 class BaseLSPServer:
@@ -113,6 +523,15 @@ class BaseLSPServer:
     def __init__(self, workspace_path: str):
         self.workspace_path = Path(workspace_path)
         self.initialized = False
+        self.options: dict = {}
+        # Documents opened by the editor (didOpen/didChange)
+        self.documents = DocumentStore()
+        # Receives (file_path, diagnostics) pushes; set by LSPManager
+        self.diagnostics_callback: Optional[Callable[[str, List[Dict]], Awaitable[None]]] = None
+
+    def configure(self, options: dict):
+        """Apply server-specific settings before initialization."""
+        self.options.update(options)
 
     async def initialize(self, workspace_path: str) -> dict:
         """Initialize the LSP server."""
@@ -122,6 +541,89 @@ class BaseLSPServer:
         """Shutdown the LSP server."""
         self.initialized = False
 
+    async def health_check(self) -> bool:
+        """
+        Probe whether the server can still answer requests.
+
+        Servers backed by a language server process report False once
+        it has exited; in-process servers are always healthy.
+        """
+        return True
+
+    async def restart(self) -> dict:
+        """
+        Restart the server in place, keeping its configuration.
+
+        Returns:
+            Server capabilities
+
+        Raises:
+            Exception: If the server could not be brought back
+        """
+        await self.shutdown()
+        return await self.initialize(str(self.workspace_path))
+
+    async def file_changed(self, file_path: str, deleted: bool = False):
+        """Handle a workspace file being written or deleted (no-op by default)."""
+        return None
+
+    async def did_open(
+        self,
+        file_path: str,
+        content: Optional[str] = None,
+        version: Optional[int] = None
+    ) -> TextDocument:
+        """
+        Open a document for editing; requests without content then use its text.
+
+        Args:
+            file_path: Path relative to the workspace root
+            content: Editor content (default: the file on disk)
+            version: Editor document version (default: 1)
+
+        Raises:
+            FileNotFoundError: If no content is given and the file does not exist
+        """
+        if content is None:
+            full_path = self.workspace_path / file_path
+            if not full_path.is_file():
+                raise FileNotFoundError(f"File not found: {file_path}")
+            content = full_path.read_text()
+        return self.documents.open(file_path, content, version)
+
+    async def did_change(
+        self,
+        file_path: str,
+        changes: List[Dict],
+        version: Optional[int] = None
+    ) -> TextDocument:
+        """
+        Apply full or incremental contentChanges to an open document.
+
+        Raises:
+            KeyError: If the document is not open
+            ValueError: For a stale version or an invalid range
+        """
+        return self.documents.change(file_path, changes, version)
+
+    async def did_close(self, file_path: str) -> bool:
+        """Close a document; returns whether it was open."""
+        return self.documents.close(file_path)
+
+    def handles(self, file_path: str) -> bool:
+        """Whether this server analyzes a file (all files by default)."""
+        return True
+
+    async def publish_diagnostics(self, file_path: str, diagnostics: List[Dict]):
+        """Push diagnostics for a file to subscribers, if anyone is listening."""
+        if self.diagnostics_callback is not None:
+            await self.diagnostics_callback(file_path, diagnostics)
+
+    async def analyze(self, file_path: str):
+        """Compute a file's diagnostics from disk and publish them."""
+        diagnostics = await self.get_diagnostics(file_path)
+        await self.publish_diagnostics(file_path, diagnostics)
+
     async def get_completions(
         self,
         file_path: str,
@@ -157,3 +659,106 @@ class BaseLSPServer:
     ) -> Optional[dict]:
         """Get definition location."""
         raise NotImplementedError
+
+    async def get_code_actions(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        end_line: Optional[int] = None,
+        end_character: Optional[int] = None,
+        diagnostics: Optional[list] = None,
+        content: Optional[str] = None
+    ) -> list:
+        """
+        Get code actions (quick fixes, refactorings) for a range.
+
+        Positions use the same convention as completions (1-indexed lines).
+        Each action carries a WorkspaceEdit; servers without code actions
+        return an empty list.
+        """
+        return []
+
+    async def get_formatting(
+        self,
+        file_path: str,
+        content: Optional[str] = None
+    ) -> list:
+        """
+        Format a document (textDocument/formatting).
+
+        Returns:
+            Minimal TextEdits; servers without a formatter return an empty list
+        """
+        return []
+
+    async def get_range_formatting(
+        self,
+        file_path: str,
+        line: int,
+        end_line: int,
+        content: Optional[str] = None
+    ) -> list:
+        """Format the 1-indexed lines [line, end_line] (textDocument/rangeFormatting)."""
+        return []
+
+    async def get_document_symbols(
+        self,
+        file_path: str,
+        content: Optional[str] = None
+    ) -> list:
+        """
+        Get the outline of a document (textDocument/documentSymbol).
+
+        Returns:
+            DocumentSymbol tree (name, kind, range, selectionRange, children);
+            servers without symbol support return an empty list
+        """
+        return []
+
+    async def workspace_symbols(self, query: str) -> list:
+        """
+        Search symbols across the workspace (workspace/symbol).
+
+        Returns:
+            SymbolInformation list (name, kind, location, containerName),
+            best matches first
+        """
+        return []
+
+    async def get_references(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        include_declaration: bool = True,
+        content: Optional[str] = None
+    ) -> list:
+        """
+        Find references to the symbol at a position (textDocument/references).
+
+        Returns:
+            Locations (uri, range); servers without reference search
+            return an empty list
+        """
+        return []
+
+    async def rename(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        new_name: str,
+        content: Optional[str] = None
+    ) -> Optional[dict]:
+        """
+        Rename the symbol at a position (textDocument/rename).
+
+        Returns:
+            WorkspaceEdit (`{"changes": {uri: [TextEdit]}}`), or None when
+            nothing can be renamed there
+
+        Raises:
+            ValueError: If the new name is invalid or conflicts
+        """
+        return None
@@ -4,11 +4,15 @@ LSP Plugin System - Extensible architecture for custom LSP servers.
 Allows users to add their own language servers without modifying core code.
 """
 
-from typing import Dict, Type, Optional, List
+from typing import Dict, Type, Optional, List, Any
 from pathlib import Path
 import logging
 import importlib
+import importlib.metadata
 import inspect
+import json
+import re
+import shutil
 
 from gathering.lsp.manager import BaseLSPServer
 
@@ -54,6 +58,52 @@ class LSPPluginRegistry:
 
         return decorator
 
+    @classmethod
+    def register_external(cls, metadata: 'PluginMetadata') -> Type[BaseLSPServer]:
+        """
+        Register an external stdio language server from metadata alone.
+
+        The server configuration (command, args, init options, file
+        globs, ...) is read from the defaults of `metadata.config_schema`
+        (see gathering.lsp.external_server.server_schema).
+
+        Usage:
+            LSPPluginRegistry.register_external(PluginMetadata(
+                name="gopls",
+                version="1.0.0",
+                author="Gathering Team",
+                description="Go language server",
+                language="go",
+                config_schema=server_schema(command="gopls", file_globs=["*.go"])
+            ))
+
+        Raises:
+            ValueError: If the configuration does not match the schema
+        """
+        from gathering.lsp.external_server import (
+            ExternalLSPServer, schema_defaults, server_schema, validate_config
+        )
+
+        # Complete a partial schema with the standard external server settings
+        schema = server_schema()
+        for key, prop in (metadata.config_schema or {}).get("properties", {}).items():
+            schema["properties"].setdefault(key, {}).update(prop)
+        metadata.config_schema = schema
+
+        config = validate_config(schema_defaults(schema), schema)
+        if not metadata.dependencies:
+            metadata.dependencies = [config["command"]]
+
+        class_name = "".join(part.capitalize() for part in re.split(r"[^A-Za-z0-9]+", metadata.language))
+        server_class = type(f"{class_name}ExternalLSPServer", (ExternalLSPServer,), {
+            "__doc__": metadata.description or f"{metadata.name} (external language server).",
+            "__lsp_language__": metadata.language,
+            "__lsp_metadata__": metadata,
+            "language": metadata.language,
+            "config_schema": schema,
+        })
+        return cls.register(metadata.language)(server_class)
+
     @classmethod
     def get_plugin(cls, language: str) -> Optional[Type[BaseLSPServer]]:
         """Get the plugin class for a language."""
@@ -64,13 +114,32 @@ class LSPPluginRegistry:
         """List all registered language plugins."""
         return list(cls._plugins.keys())
 
+    @classmethod
+    def plugin_info(cls) -> List[Dict[str, Any]]:
+        """Registered plugins with their metadata and missing dependencies."""
+        plugins = []
+        for language, server_class in sorted(cls._plugins.items()):
+            metadata = getattr(server_class, "__lsp_metadata__", None)
+            info = metadata.to_dict() if metadata else {"language": language, "name": server_class.__name__}
+            info["language"] = language
+            info["missing_dependencies"] = metadata.missing_dependencies() if metadata else []
+            plugins.append(info)
+        return plugins
+
+    @classmethod
+    def missing_dependencies(cls, language: str) -> List[str]:
+        """Declared dependencies of a language's plugin that are not installed."""
+        metadata = getattr(cls._plugins.get(language), "__lsp_metadata__", None)
+        return metadata.missing_dependencies() if metadata else []
+
     @classmethod
     def discover_plugins(cls, plugin_dir: Optional[str] = None):
         """
         Auto-discover plugins from a directory.
 
         Looks for Python files in the plugins directory and loads classes
-        that inherit from BaseLSPServer.
+        that inherit from BaseLSPServer. JSON files are read as plugin
+        metadata for external language servers (see register_external).
 
         Args:
             plugin_dir: Directory to search for plugins
@@ -111,6 +180,14 @@ class LSPPluginRegistry:
             except Exception as e:
                 logger.error(f"Failed to load plugin from {plugin_file}: {e}")
 
+        for definition_file in plugin_dir.glob("*.json"):
+            try:
+                metadata = PluginMetadata.from_dict(json.loads(definition_file.read_text()))
+                cls.register_external(metadata)
+                logger.info(f"Loaded external server definition: {metadata.name} for {metadata.language}")
+            except Exception as e:
+                logger.error(f"Failed to load external server from {definition_file}: {e}")
+
 
 class PluginMetadata:
     """
@@ -137,6 +214,36 @@ class PluginMetadata:
         self.dependencies = dependencies or []
         self.config_schema = config_schema or {}
 
+    @classmethod
+    def from_dict(cls, data: Dict) -> 'PluginMetadata':
+        """Create metadata from a dictionary (the inverse of to_dict)."""
+        return cls(
+            name=data["name"],
+            version=data.get("version", "1.0.0"),
+            author=data.get("author", "Unknown"),
+            description=data.get("description", ""),
+            language=data["language"],
+            dependencies=data.get("dependencies"),
+            config_schema=data.get("config_schema")
+        )
+
+    def missing_dependencies(self) -> List[str]:
+        """
+        Dependencies that are neither an executable on PATH nor an
+        installed Python distribution (version specifiers and extras
+        are ignored).
+        """
+        missing = []
+        for dependency in self.dependencies:
+            name = re.split(r"[\[<>=!~;\s]", dependency, maxsplit=1)[0]
+            if shutil.which(name):
+                continue
+            try:
+                importlib.metadata.distribution(name)
+            except importlib.metadata.PackageNotFoundError:
+                missing.append(dependency)
+        return missing
+
     def to_dict(self) -> Dict:
         """Convert metadata to dictionary."""
         return {
@@ -255,4 +362,24 @@ class MyLangLSPServer(BaseLSPServer):
 ```
 
 The plugin will be auto-discovered on startup!
+
+A standard stdio language server needs no Python class at all: save its
+metadata as gathering/lsp/plugins/your_language.json, with the server
+configuration as defaults of the config schema (see
+gathering.lsp.external_server.EXTERNAL_SERVER_SCHEMA):
+
+```json
+{
+    "name": "Zig Language Server",
+    "language": "zig",
+    "description": "zls over stdio",
+    "config_schema": {
+        "type": "object",
+        "properties": {
+            "command": {"type": "string", "default": "zls"},
+            "file_globs": {"type": "array", "default": ["*.zig"]}
+        }
+    }
+}
+```
 """
@@ -0,0 +1,45 @@
+"""
+External Language Servers.
+
+Languages served by a standard stdio language server, registered from
+configuration alone (no Python subclass per language).
+"""
+
+from gathering.lsp.external_server import server_schema
+from gathering.lsp.plugin_system import LSPPluginRegistry, PluginMetadata
+
+EXTERNAL_SERVERS = [
+    PluginMetadata(
+        name="Go (gopls)",
+        version="1.0.0",
+        author="Gathering Team",
+        description="Go language server (gopls) over stdio",
+        language="go",
+        dependencies=["gopls"],
+        config_schema=server_schema(
+            command="gopls",
+            args=["serve"],
+            file_globs=["*.go", "go.mod", "go.work"],
+            language_ids={"go.mod": "go.mod", "go.work": "go.work"},
+            install_hint="go install golang.org/x/tools/gopls@latest"
+        )
+    ),
+    PluginMetadata(
+        name="C/C++ (clangd)",
+        version="1.0.0",
+        author="Gathering Team",
+        description="C and C++ language server (clangd) over stdio",
+        language="cpp",
+        dependencies=["clangd"],
+        config_schema=server_schema(
+            command="clangd",
+            args=["--background-index"],
+            file_globs=["*.c", "*.h", "*.cc", "*.cpp", "*.cxx", "*.hpp", "*.hh"],
+            language_ids={"*.c": "c", "*.h": "c"},
+            install_hint="apt install clangd"
+        )
+    ),
+]
+
+for metadata in EXTERNAL_SERVERS:
+    LSPPluginRegistry.register_external(metadata)
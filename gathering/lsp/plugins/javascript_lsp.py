@@ -10,6 +10,8 @@ from pathlib import Path
 
 from gathering.lsp.plugin_system import lsp_plugin
 from gathering.lsp.manager import BaseLSPServer
+from gathering.lsp.javascript_symbols import outline
+from gathering.lsp.symbols import workspace_search
 
 
 @lsp_plugin(
@@ -28,8 +30,13 @@ class JavaScriptLSPServer(BaseLSPServer):
     - JavaScript keywords
     - Common DOM APIs
     - Node.js APIs
+
+    Also provides a document outline and workspace symbol search.
     """
 
+    # File extensions searched for workspace symbols
+    source_suffixes = [".js", ".jsx", ".mjs", ".cjs"]
+
     def __init__(self, workspace_path: str):
         super().__init__(workspace_path)
         self.js_keywords = [
@@ -76,10 +83,16 @@ class JavaScriptLSPServer(BaseLSPServer):
                 },
                 "hoverProvider": False,
                 "definitionProvider": False,
+                "documentSymbolProvider": True,
+                "workspaceSymbolProvider": True,
                 "diagnosticProvider": True
             }
         }
 
+    def handles(self, file_path: str) -> bool:
+        """JavaScript (and, for TypeScript, TypeScript) sources."""
+        return file_path.endswith(tuple(self.source_suffixes))
+
     async def get_completions(
         self,
         file_path: str,
@@ -196,6 +209,25 @@ class JavaScriptLSPServer(BaseLSPServer):
         """JavaScript go-to-definition not implemented."""
         return None
 
+    async def get_document_symbols(
+        self,
+        file_path: str,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Get the outline of a file (functions, classes, members, variables)."""
+        if content is None:
+            full_path = self.workspace_path / file_path
+            if full_path.exists():
+                content = full_path.read_text()
+            else:
+                return []
+
+        return outline(content)
+
+    async def workspace_symbols(self, query: str) -> List[Dict]:
+        """Search symbols across the workspace's source files."""
+        return workspace_search(self.workspace_path, self.source_suffixes, outline, query)
+
 
 # Also register TypeScript with the same server for now
 @lsp_plugin(
@@ -212,6 +244,8 @@ class TypeScriptLSPServer(JavaScriptLSPServer):
     def __init__(self, workspace_path: str):
         super().__init__(workspace_path)
 
+        self.source_suffixes = self.source_suffixes + [".ts", ".tsx", ".mts", ".cts"]
+
         # Add TypeScript-specific keywords
         self.js_keywords.extend([
             "interface", "type", "enum", "namespace", "declare",
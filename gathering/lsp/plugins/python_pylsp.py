@@ -16,6 +16,8 @@ from pathlib import Path
 
 from gathering.lsp.plugin_system import lsp_plugin
 from gathering.lsp.manager import BaseLSPServer
+from gathering.lsp.python_symbols import outline
+from gathering.lsp.symbols import workspace_search
 
 try:
     from gathering.lsp.pylsp_wrapper import PylspWrapper, PYLSP_AVAILABLE
@@ -45,6 +47,7 @@ class PythonPylspServer(BaseLSPServer):
     - Go-to-definition
     - Hover documentation
     - Signature help
+    - Document outline and workspace symbols (AST-based, without pylsp too)
     """
 
     def __init__(self, workspace_path: str):
@@ -60,6 +63,8 @@ class PythonPylspServer(BaseLSPServer):
             return {
                 "capabilities": {
                     "completionProvider": {"triggerCharacters": ["."]},
+                    "documentSymbolProvider": True,
+                    "workspaceSymbolProvider": True,
                     "diagnosticProvider": False
                 }
             }
@@ -97,10 +102,16 @@ class PythonPylspServer(BaseLSPServer):
             return {
                 "capabilities": {
                     "completionProvider": {"triggerCharacters": ["."]},
+                    "documentSymbolProvider": True,
+                    "workspaceSymbolProvider": True,
                     "diagnosticProvider": False
                 }
             }
 
+    def handles(self, file_path: str) -> bool:
+        """Python sources and stubs."""
+        return file_path.endswith((".py", ".pyi"))
+
     async def get_completions(
         self,
         file_path: str,
@@ -194,6 +205,24 @@ class PythonPylspServer(BaseLSPServer):
             logger.error(f"Definition error: {e}")
             return None
 
+    async def get_document_symbols(
+        self,
+        file_path: str,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Get the outline of a module (classes, functions, methods, assignments)."""
+        if content is None:
+            full_path = self.workspace_path / file_path
+            if not full_path.exists():
+                return []
+            content = full_path.read_text()
+
+        return outline(content)
+
+    async def workspace_symbols(self, query: str) -> List[Dict]:
+        """Search symbols across the workspace's Python files."""
+        return workspace_search(self.workspace_path, [".py"], outline, query)
+
     async def shutdown(self):
         """Shutdown pylsp server."""
         self.wrapper = None
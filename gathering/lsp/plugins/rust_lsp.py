@@ -1,39 +1,103 @@
 """
 Rust Language Server Plugin.
 
-Provides LSP capabilities for Rust using keyword-based autocomplete
-and basic syntax checking.
+Provides LSP capabilities for Rust. Cargo workspaces are served by
+rust-analyzer when the binary is installed; otherwise the plugin falls
+back to keyword-based autocomplete, with diagnostics from
+`cargo check --message-format=json` or basic syntax checking.
+
+`Cargo.toml` files get section/key/crate completions and manifest
+validation, using a local crates.io index mirror when configured.
 """
 
 from typing import Optional, List, Dict
+import logging
 import re
 from pathlib import Path
 
 from gathering.lsp.plugin_system import lsp_plugin
+from gathering.lsp.documents import TextDocument
 from gathering.lsp.manager import BaseLSPServer
+from gathering.lsp.rust_analyzer_client import RustAnalyzerClient
+from gathering.lsp.rust_cargo_check import CargoDiagnostics
+from gathering.lsp.rust_code_actions import RustCodeActions, suggestion_actions
+from gathering.lsp.rust_format import RustFormatter, RustfmtError
+from gathering.lsp.rust_lexer import RustLexer, Token, TokenKind
+from gathering.lsp.rust_manifest import BUILTIN_CRATES, CargoManifest, CrateRegistry
+from gathering.lsp.rust_references import RustReferences
+from gathering.lsp.rust_symbols import MEMBER_KINDS, ITEM_KINDS, RustSymbol, RustSymbolIndex, outline
+
+logger = logging.getLogger(__name__)
+
+# Indexed symbol kind -> LSP CompletionItemKind
+SYMBOL_COMPLETION_KINDS = {
+    "function": 3,   # Function
+    "method": 2,     # Method
+    "field": 5,      # Field
+    "variant": 20,   # EnumMember
+    "struct": 22,    # Struct
+    "enum": 13,      # Enum
+    "trait": 8,      # Interface
+    "const": 21,     # Constant
+    "static": 21,    # Constant
+    "module": 9,     # Module
+    "type": 7,       # Class
+    "macro": 3,      # Function
+}
+
+# Methods callable with `.` take a self receiver
+SELF_PARAM = re.compile(r"\(\s*&?\s*(?:'\w+\s+)?(?:mut\s+)?self\b")
 
 
 @lsp_plugin(
     language="rust",
     name="Rust LSP",
-    version="1.0.0",
+    version="1.1.0",
     author="Gathering Team",
-    description="Rust language server with autocomplete and diagnostics",
-    dependencies=[]  # Can be upgraded to use rust-analyzer later
+    description="Rust language server backed by rust-analyzer with a keyword fallback",
+    dependencies=["rust-analyzer"]
 )
 class RustLSPServer(BaseLSPServer):
     """
     Rust Language Server.
 
-    Provides:
+    Provides (with rust-analyzer, for Cargo workspaces):
+    - Type-aware autocomplete
+    - Hover documentation
+    - Go-to-definition
+    - Document outline and workspace symbol search
+    - Find references and rename across the workspace
+    - Compiler diagnostics
+
+    Fallback (without rust-analyzer):
+    - Symbol completion from the crate's own items and members
     - Keyword autocomplete
     - Standard library completion
     - Basic syntax checking
     - Common patterns
+
+    Cargo.toml:
+    - Section, key, crate, version and feature completion
+    - Dependency, feature and missing-crate diagnostics
     """
 
+    # Command used to launch rust-analyzer (overridable for tests/custom installs)
+    analyzer_command: List[str] = ["rust-analyzer"]
+
+    # Diagnostics backend: "auto", "analyzer", "cargo" or "heuristic"
+    diagnostics_mode: str = "auto"
+
+    # Cargo subcommand for the "cargo" diagnostics backend: "check" or "clippy"
+    cargo_command: str = "check"
+
     def __init__(self, workspace_path: str):
         super().__init__(workspace_path)
+        self.client: Optional[RustAnalyzerClient] = None
+        self.analyzer_lost = False  # rust-analyzer crashed and has not come back
+        self.cargo: Optional[CargoDiagnostics] = None
+        self.index: Optional[RustSymbolIndex] = None
+        self.registry: Optional[CrateRegistry] = None
+        self.formatter = RustFormatter(workspace_path)
 
         # Rust keywords
         self.rust_keywords = [
@@ -80,23 +144,157 @@ class RustLSPServer(BaseLSPServer):
             "vec!", "dbg!", "todo!", "unimplemented!"
         ]
 
+        # Attributes
+        self.rust_attributes = [
+            "derive", "cfg", "cfg_attr", "test", "allow", "warn", "deny",
+            "inline", "must_use", "deprecated", "doc", "repr", "non_exhaustive"
+        ]
+        self.derivable_traits = [
+            "Debug", "Clone", "Copy", "PartialEq", "Eq", "Hash",
+            "PartialOrd", "Ord", "Default"
+        ]
+
+    def configure(self, options: dict):
+        """Apply settings such as diagnostics_mode and cargo_command."""
+        super().configure(options)
+        self.diagnostics_mode = options.get("diagnostics_mode", self.diagnostics_mode)
+        self.cargo_command = options.get("cargo_command", self.cargo_command)
+        self.cargo = None
+        self.registry = None
+
     async def initialize(self, workspace_path: str) -> dict:
-        """Initialize Rust LSP server."""
+        """Initialize Rust LSP server, starting rust-analyzer when possible."""
         self.workspace_path = Path(workspace_path)
+        self.formatter = RustFormatter(workspace_path)
         self.initialized = True
 
+        await self._start_analyzer()
+        has_analyzer = self.client is not None
+
         return {
             "capabilities": {
                 "completionProvider": {
                     "resolveProvider": False,
                     "triggerCharacters": [":", ".", "<"]
                 },
-                "hoverProvider": False,
-                "definitionProvider": False,
+                "hoverProvider": True,
+                "definitionProvider": True,
+                "documentSymbolProvider": True,
+                "workspaceSymbolProvider": True,
+                "referencesProvider": True,
+                "renameProvider": True,
+                "codeActionProvider": {"codeActionKinds": ["quickfix", "refactor.rewrite", "source.fixAll"]},
+                "documentFormattingProvider": self.formatter.is_available(),
+                "documentRangeFormattingProvider": self.formatter.is_available(),
                 "diagnosticProvider": True
-            }
+            },
+            "backend": "rust-analyzer" if has_analyzer else "keyword",
+            "diagnostics_backend": self._diagnostics_backend()
         }
 
+    def _is_cargo_workspace(self) -> bool:
+        """Check for a Cargo.toml at the workspace root or one level below."""
+        if (self.workspace_path / "Cargo.toml").exists():
+            return True
+        return any(self.workspace_path.glob("*/Cargo.toml"))
+
+    async def _start_analyzer(self):
+        """Start rust-analyzer for Cargo workspaces, if installed."""
+        if self.client is not None:
+            return
+
+        if not self._is_cargo_workspace():
+            logger.info(f"No Cargo.toml in {self.workspace_path}, using keyword fallback")
+            return
+
+        if not RustAnalyzerClient.is_available(self.analyzer_command):
+            logger.warning(
+                "rust-analyzer not found, using keyword fallback. "
+                "Install with: rustup component add rust-analyzer"
+            )
+            return
+
+        client = RustAnalyzerClient(str(self.workspace_path), command=self.analyzer_command)
+        client.on_diagnostics = self.publish_diagnostics
+        try:
+            await client.start()
+            self.client = client
+        except Exception as e:
+            logger.warning(f"rust-analyzer unavailable, using keyword fallback: {e}")
+
+    def _diagnostics_backend(self) -> str:
+        """Resolve the diagnostics backend for the configured mode."""
+        if self.diagnostics_mode != "auto":
+            return self.diagnostics_mode
+
+        if self.client:
+            return "analyzer"
+        if self._is_cargo_workspace() and CargoDiagnostics.is_available():
+            return "cargo"
+        return "heuristic"
+
+    async def health_check(self) -> bool:
+        """Healthy unless a started rust-analyzer has exited (the keyword fallback still answers)."""
+        if self.client is not None and not self.client.is_alive():
+            logger.warning("rust-analyzer exited unexpectedly")
+            self.analyzer_lost = True
+        return not self.analyzer_lost
+
+    async def restart(self) -> dict:
+        """Restart rust-analyzer, failing if it crashed and cannot be started again."""
+        capabilities = await super().restart()
+        if self.analyzer_lost and self.client is None:
+            raise RuntimeError("rust-analyzer could not be restarted")
+        self.analyzer_lost = False
+        return capabilities
+
+    async def _ensure_initialized(self):
+        """Lazily initialize when a request arrives before /initialize."""
+        if not self.initialized:
+            await self.initialize(str(self.workspace_path))
+
+    def _read_content(self, file_path: str, content: Optional[str]) -> Optional[str]:
+        """Return the given content, or read the file from the workspace."""
+        if content is not None:
+            return content
+
+        full_path = self.workspace_path / file_path
+        if full_path.exists():
+            return full_path.read_text()
+        return None
+
+    async def did_open(
+        self,
+        file_path: str,
+        content: Optional[str] = None,
+        version: Optional[int] = None
+    ) -> TextDocument:
+        """Open a document, syncing it to rust-analyzer when running."""
+        document = await super().did_open(file_path, content, version)
+        if self.client is not None and file_path.endswith(".rs"):
+            await self.client.did_change(document.file_path, document.text)
+        return document
+
+    async def did_change(
+        self,
+        file_path: str,
+        changes: List[Dict],
+        version: Optional[int] = None
+    ) -> TextDocument:
+        """Apply contentChanges, forwarding them incrementally to rust-analyzer."""
+        previous = self.documents.text(file_path)
+        document = await super().did_change(file_path, changes, version)
+        if self.client is not None and file_path.endswith(".rs"):
+            await self.client.apply_changes(document.file_path, changes, previous, document.text)
+        return document
+
+    async def did_close(self, file_path: str) -> bool:
+        """Close a document with rust-analyzer too."""
+        closed = await super().did_close(file_path)
+        if self.client is not None:
+            await self.client.did_close(file_path)
+        return closed
+
     async def get_completions(
         self,
         file_path: str,
@@ -105,19 +303,50 @@ class RustLSPServer(BaseLSPServer):
         content: Optional[str] = None
     ) -> List[Dict]:
         """Get Rust completions."""
+        await self._ensure_initialized()
+
+        content = self._read_content(file_path, content)
         if content is None:
-            full_path = self.workspace_path / file_path
-            if full_path.exists():
-                content = full_path.read_text()
-            else:
-                return []
+            return []
+
+        if self._is_manifest(file_path):
+            return CargoManifest(content).completions(line - 1, character, self._get_registry())
 
+        if self.client:
+            try:
+                completions = await self.client.completion(file_path, line, character, content)
+                if completions:
+                    return completions
+            except Exception as e:
+                logger.error(f"rust-analyzer completion error: {e}")
+
+        return self._fallback_completions(file_path, content, line, character)
+
+    def _fallback_completions(self, file_path: str, content: str, line: int, character: int) -> List[Dict]:
+        """Symbol, keyword, type, std and macro completions (fallback)."""
         lines = content.split('\n')
         if line <= 0 or line > len(lines):
             return []
 
         current_line = lines[line - 1][:character]
 
+        # Never complete inside strings, chars or comments
+        lexer = RustLexer(content)
+        context = lexer.context_at(lexer.offset(line - 1, character))
+        if context == TokenKind.ATTRIBUTE:
+            return self._attribute_completions(current_line)
+        if context is not None:
+            return []
+
+        # Index the buffer so completions see unsaved items
+        index = self._get_index()
+        index.update_file(file_path, content)
+
+        # Check for member completions on user types (self.x, value.x, Type::x, module::x)
+        members = self._member_completions(file_path, content, line, current_line)
+        if members is not None:
+            return members
+
         # Check for std library completions
         for prefix, items in self.std_items.items():
             if current_line.endswith(prefix):
@@ -152,8 +381,12 @@ class RustLSPServer(BaseLSPServer):
         if match:
             partial = match.group(1)
 
-            # Combine keywords, types, and macros
-            all_completions = []
+            # Combine user items, keywords, types, and macros
+            all_completions = [
+                self._symbol_item(symbol)
+                for symbol in index.items(partial)
+                if symbol.module == index.module_path(file_path) or symbol.is_pub
+            ]
 
             # Keywords
             all_completions.extend([
@@ -195,46 +428,296 @@ class RustLSPServer(BaseLSPServer):
 
         return []
 
+    def _get_index(self) -> RustSymbolIndex:
+        """Get the workspace symbol index, building it on first use."""
+        if self.index is None:
+            self.index = RustSymbolIndex(str(self.workspace_path))
+        self.index.ensure_built()
+        return self.index
+
+    def _member_completions(
+        self,
+        file_path: str,
+        content: str,
+        line: int,
+        current_line: str
+    ) -> Optional[List[Dict]]:
+        """
+        Completions after `.` or `::` on a user-defined type or module.
+
+        Returns None when the receiver is not a known user type, so that
+        the std library and keyword completers can run instead.
+        """
+        index = self._get_index()
+
+        # value.member / self.member
+        match = re.search(r'\b([A-Za-z_]\w*)\s*\.\s*(\w*)$', current_line)
+        if match:
+            receiver, partial = match.groups()
+            if receiver == "self":
+                type_name = self._enclosing_impl(file_path, line - 1)
+            else:
+                before = "\n".join(content.split("\n")[:line - 1] + [current_line])
+                type_name = self._infer_type(before, receiver, file_path, line - 1)
+            if not type_name:
+                return None
+            return [
+                self._symbol_item(member)
+                for member in index.members(type_name)
+                if member.name.startswith(partial)
+                and (member.kind == "field" or (member.kind == "method" and SELF_PARAM.search(member.signature)))
+            ]
+
+        # Type::member / module::item
+        match = re.search(r'\b([A-Za-z_]\w*)::(\w*)$', current_line)
+        if match:
+            owner, partial = match.groups()
+            if owner == "Self":
+                owner = self._enclosing_impl(file_path, line - 1) or owner
+
+            if index.lookup(owner, {"struct", "enum", "trait", "type"}):
+                return [
+                    self._symbol_item(member)
+                    for member in index.members(owner)
+                    if member.name.startswith(partial) and member.kind != "field"
+                ]
+
+            modules = {
+                module for module in index.modules()
+                if module.rsplit("::", 1)[-1] == owner
+            }
+            if modules:
+                return [
+                    self._symbol_item(item)
+                    for item in index.items(partial)
+                    if item.module in modules
+                ]
+
+        return None
+
+    def _enclosing_impl(self, file_path: str, line: int) -> Optional[str]:
+        """Type name of the impl (or trait) block containing a 0-based line."""
+        for symbol in self._get_index().file_symbols(file_path):
+            if symbol.kind in ("impl", "trait") and symbol.start_line <= line <= symbol.end_line:
+                return symbol.name
+        return None
+
+    def _infer_type(self, before: str, variable: str, file_path: str, line: int) -> Optional[str]:
+        """
+        Best-effort type of a local variable from its declaration.
+
+        Recognizes `let x: Type`, `let x = Type::new(..)`, `let x = Type { .. }`
+        and function parameters `x: &Type`.
+        """
+        var = re.escape(variable)
+        patterns = [
+            rf'\blet\s+(?:mut\s+)?{var}\s*:\s*&?\s*(?:mut\s+)?([A-Z]\w*)',
+            rf'\blet\s+(?:mut\s+)?{var}\s*=\s*&?\s*(?:mut\s+)?(?:\w+::)*([A-Z]\w*)\s*(?:::|\{{|\()',
+            rf'[(,]\s*(?:mut\s+)?{var}\s*:\s*&?\s*(?:\'\w+\s+)?(?:mut\s+)?([A-Z]\w*)',
+        ]
+
+        best = None
+        for pattern in patterns:
+            for match in re.finditer(pattern, before):
+                if best is None or match.start() > best.start():
+                    best = match
+
+        if best is None:
+            return None
+
+        type_name = best.group(1)
+        if type_name == "Self":
+            return self._enclosing_impl(file_path, line)
+        return type_name
+
+    def _symbol_item(self, symbol: RustSymbol) -> Dict:
+        """Convert an indexed symbol to a completion item."""
+        label = f"{symbol.name}!" if symbol.kind == "macro" else symbol.name
+        return {
+            "label": label,
+            "kind": SYMBOL_COMPLETION_KINDS.get(symbol.kind, 1),
+            "insertText": label,
+            "detail": symbol.signature,
+            "documentation": symbol.doc or None
+        }
+
+    async def file_changed(self, file_path: str, deleted: bool = False):
+        """Keep the symbol index in sync with workspace file changes."""
+        if self.index is None or not self.index.built or not file_path.endswith(".rs"):
+            return
+
+        if deleted:
+            self.index.remove_file(file_path)
+        else:
+            self.index.update_file(file_path)
+
+    def _attribute_completions(self, current_line: str) -> List[Dict]:
+        """Attribute name (or derivable trait) completions inside #[...]."""
+        match = re.search(r'(\w*)$', current_line)
+        partial = match.group(1) if match else ""
+
+        if re.search(r'derive\([^)]*$', current_line):
+            return [
+                {
+                    "label": trait,
+                    "kind": 8,  # Interface (trait)
+                    "insertText": trait,
+                    "detail": "derive"
+                }
+                for trait in self.derivable_traits
+                if trait.startswith(partial)
+            ]
+
+        return [
+            {
+                "label": attr,
+                "kind": 14,  # Keyword
+                "insertText": attr,
+                "detail": "attribute"
+            }
+            for attr in self.rust_attributes
+            if attr.startswith(partial)
+        ]
+
     async def get_diagnostics(
         self,
         file_path: str,
         content: Optional[str] = None
     ) -> List[Dict]:
         """Get Rust diagnostics."""
+        await self._ensure_initialized()
+
+        content = self._read_content(file_path, content)
         if content is None:
-            full_path = self.workspace_path / file_path
-            if full_path.exists():
-                content = full_path.read_text()
-            else:
-                return []
+            return []
+
+        if self._is_manifest(file_path):
+            return CargoManifest(content).diagnostics(self._get_registry(), self._used_crates(file_path))
+
+        backend = self._diagnostics_backend()
+
+        if backend == "analyzer" and self.client:
+            try:
+                return await self.client.diagnostics(file_path, content)
+            except Exception as e:
+                logger.error(f"rust-analyzer diagnostics error: {e}")
+
+        if backend == "cargo":
+            if self.cargo is None:
+                self.cargo = CargoDiagnostics(
+                    str(self.workspace_path),
+                    command=self.cargo_command,
+                    on_results=self._publish_cargo_results
+                )
+            try:
+                diagnostics = await self.cargo.get_diagnostics(file_path, content)
+                if diagnostics is not None:
+                    return diagnostics
+            except Exception as e:
+                logger.error(f"cargo {self.cargo_command} diagnostics error: {e}")
+
+        return self._heuristic_diagnostics(content)
 
+    async def _publish_cargo_results(self, by_file: Dict[str, List[Dict]]):
+        """Publish the diagnostics of every file covered by a finished cargo run."""
+        root = self.workspace_path.absolute()
+        for path, diagnostics in by_file.items():
+            try:
+                file_path = Path(path).relative_to(root).as_posix()
+            except ValueError:
+                continue
+            await self.publish_diagnostics(file_path, diagnostics)
+
+    def handles(self, file_path: str) -> bool:
+        """Rust sources and Cargo manifests."""
+        return file_path.endswith(".rs") or self._is_manifest(file_path)
+
+    @staticmethod
+    def _is_manifest(file_path: str) -> bool:
+        return Path(file_path).name == "Cargo.toml"
+
+    def _get_registry(self) -> Optional[CrateRegistry]:
+        """Local crates.io index mirror from the `registry_index` option or settings."""
+        if self.registry is None:
+            index_path = self.options.get("registry_index")
+            if index_path is None:
+                from gathering.core.config import get_settings
+                index_path = get_settings().cargo_registry_index
+            if index_path and Path(index_path).is_dir():
+                self.registry = CrateRegistry(str(index_path))
+        return self.registry
+
+    def _used_crates(self, manifest_path: str) -> Dict[str, tuple]:
+        """
+        External crates named by `use` statements in a package's sources.
+
+        Paths that resolve to the crate's own modules or items, or to names
+        imported by another `use`, are not external crates.
+
+        Returns:
+            Mapping of crate name to the (file, 0-based line) of its first use
+        """
+        index = self._get_index()
+        root = self.workspace_path.absolute()
+        package_dirs = {
+            manifest.parent.absolute().relative_to(root)
+            for manifest in root.glob("**/Cargo.toml")
+            if "target" not in manifest.parts
+        }
+        package_dir = (root / manifest_path).parent.relative_to(root)
+
+        def owner(path: Path) -> Optional[Path]:
+            """Nearest package directory containing a file."""
+            return next((d for d in path.parents if d in package_dirs), None)
+
+        used: Dict[str, tuple] = {}
+        for file_path in sorted(index.files()):
+            if owner(Path(file_path)) != package_dir:
+                continue
+
+            imports = index.imports(file_path)
+            for imp in imports:
+                head = imp.path.split("::", 1)[0]
+                if head in BUILTIN_CRATES or head in used or not head:
+                    continue
+                if any(other.alias == head and other is not imp for other in imports):
+                    continue
+                if index.resolve([head], file_path, imp.module) is not None:
+                    continue
+                used[head] = (file_path, imp.line)
+
+        return used
+
+    def _heuristic_diagnostics(self, content: str) -> List[Dict]:
+        """Token-based lint checks (fallback); never fire in strings or comments."""
         diagnostics = []
-        lines = content.split('\n')
+        tokens = [t for t in RustLexer(content).tokenize() if not t.is_comment]
 
-        for i, line in enumerate(lines):
-            # Check for missing semicolons (basic heuristic)
-            stripped = line.strip()
-            if stripped and not stripped.endswith((';', '{', '}', ',')):
-                # Check if it's a statement that should end with semicolon
-                if any(stripped.startswith(kw) for kw in ['let', 'return', 'use']):
-                    if not stripped.endswith('!'):  # Macros don't need semicolons
-                        diagnostics.append({
-                            "range": {
-                                "start": {"line": i, "character": 0},
-                                "end": {"line": i, "character": len(line)}
-                            },
-                            "severity": 3,  # Information
-                            "message": "Consider adding a semicolon ';'",
-                            "source": "rust-linter"
-                        })
+        for i, token in enumerate(tokens):
+            # Check for let/use statements missing their semicolon
+            if token.kind == TokenKind.KEYWORD and token.text in ("let", "use"):
+                last = self._unterminated_statement(tokens, i)
+                if last is not None:
+                    diagnostics.append({
+                        "range": {
+                            "start": {"line": last.line, "character": last.column},
+                            "end": {"line": last.line, "character": last.column + len(last.text)}
+                        },
+                        "severity": 3,  # Information
+                        "message": "Consider adding a semicolon ';'",
+                        "source": "rust-linter"
+                    })
 
             # Check for unwrap() usage (warning)
-            if '.unwrap()' in line:
-                col = line.find('.unwrap()')
+            if (
+                token.kind == TokenKind.PUNCT and token.text == "."
+                and [t.text for t in tokens[i + 1:i + 4]] == ["unwrap", "(", ")"]
+            ):
+                close = tokens[i + 3]
                 diagnostics.append({
                     "range": {
-                        "start": {"line": i, "character": col},
-                        "end": {"line": i, "character": col + 9}
+                        "start": {"line": token.line, "character": token.column},
+                        "end": {"line": close.line, "character": close.column + 1}
                     },
                     "severity": 2,  # Warning
                     "message": "Using unwrap() can cause panics. Consider using expect() or match instead",
@@ -242,11 +725,14 @@ class RustLSPServer(BaseLSPServer):
                 })
 
             # Check for println! in release code
-            if 'println!' in line and not line.strip().startswith('//'):
+            if (
+                token.kind == TokenKind.IDENT and token.text == "println"
+                and i + 1 < len(tokens) and tokens[i + 1].text == "!"
+            ):
                 diagnostics.append({
                     "range": {
-                        "start": {"line": i, "character": line.find('println!')},
-                        "end": {"line": i, "character": line.find('println!') + 8}
+                        "start": {"line": token.line, "character": token.column},
+                        "end": {"line": token.line, "character": token.column + 8}
                     },
                     "severity": 3,  # Information
                     "message": "Consider using proper logging instead of println! in production",
@@ -255,6 +741,38 @@ class RustLSPServer(BaseLSPServer):
 
         return diagnostics
 
+    @staticmethod
+    def _unterminated_statement(tokens: List[Token], index: int) -> Optional[Token]:
+        """
+        Check whether the let/use statement at tokens[index] lacks a ';'.
+
+        Scans across lines, tracking bracket depth, so multi-line statements
+        (closures, struct literals, let-else) are handled.
+
+        Returns:
+            The statement's last token if it is unterminated, else None
+        """
+        previous = tokens[index - 1] if index > 0 else None
+        if tokens[index].text == "let" and previous is not None:
+            # `if let`, `while let` and let-chains are expressions
+            if previous.text not in (";", "{", "}") and previous.kind != TokenKind.ATTRIBUTE:
+                return None
+
+        depth = 0
+        last = tokens[index]
+        for token in tokens[index + 1:]:
+            if token.text in ("(", "[", "{"):
+                depth += 1
+            elif token.text in (")", "]", "}"):
+                depth -= 1
+                if depth < 0:
+                    return last
+            elif token.text == ";" and depth == 0:
+                return None
+            last = token
+
+        return last
+
     async def get_hover(
         self,
         file_path: str,
@@ -262,8 +780,34 @@ class RustLSPServer(BaseLSPServer):
         character: int,
         content: Optional[str] = None
     ) -> Optional[Dict]:
-        """Rust hover not implemented (would need rust-analyzer)."""
-        return None
+        """Get hover information (rust-analyzer, else the symbol index)."""
+        await self._ensure_initialized()
+
+        content = self._read_content(file_path, content)
+        if content is None:
+            return None
+
+        if self.client:
+            try:
+                hover = await self.client.hover(file_path, line, character, content)
+                if hover:
+                    return hover
+            except Exception as e:
+                logger.error(f"rust-analyzer hover error: {e}")
+
+        symbol = self._symbol_at(file_path, content, line, character)
+        if symbol is None:
+            return None
+
+        location = symbol.module
+        if symbol.container and symbol.kind != "impl":
+            location = f"{location}::{symbol.container}"
+
+        value = f"```rust\n{location}\n```\n\n```rust\n{symbol.signature}\n```"
+        if symbol.doc:
+            value += f"\n\n---\n\n{symbol.doc}"
+
+        return {"contents": {"kind": "markdown", "value": value}}
 
     async def get_definition(
         self,
@@ -272,5 +816,336 @@ class RustLSPServer(BaseLSPServer):
         character: int,
         content: Optional[str] = None
     ) -> Optional[Dict]:
-        """Rust go-to-definition not implemented (would need rust-analyzer)."""
+        """Get definition location (rust-analyzer, else the symbol index)."""
+        await self._ensure_initialized()
+
+        content = self._read_content(file_path, content)
+        if content is None:
+            return None
+
+        if self.client:
+            try:
+                definition = await self.client.definition(file_path, line, character, content)
+                if definition:
+                    return definition
+            except Exception as e:
+                logger.error(f"rust-analyzer definition error: {e}")
+
+        symbol = self._symbol_at(file_path, content, line, character)
+        if symbol is None:
+            return None
+
+        return {
+            "uri": (self.workspace_path / symbol.file).absolute().as_uri(),
+            "range": {
+                "start": {"line": symbol.line, "character": symbol.column},
+                "end": {"line": symbol.line, "character": symbol.column + len(symbol.name)}
+            }
+        }
+
+    def _symbol_at(self, file_path: str, content: str, line: int, character: int) -> Optional[RustSymbol]:
+        """
+        Resolve the identifier under the cursor to an indexed symbol.
+
+        Handles paths (`shapes::Circle::new`, `crate::`, `super::`, `Self::`),
+        names brought in by `use`, `mod foo;` declarations and `.member`
+        accesses on locals whose type can be inferred.
+        """
+        lexer = RustLexer(content)
+        tokens = lexer.tokenize()
+        offset = lexer.offset(line - 1, character)
+
+        path_words = ("self", "Self", "super", "crate")
+        index = None
+        for i, token in enumerate(tokens):
+            if token.start <= offset <= token.end and (
+                token.kind == TokenKind.IDENT or token.text in path_words
+            ):
+                index = i
+                if offset < token.end:
+                    break
+        if index is None:
+            return None
+
+        self._get_index().update_file(file_path, content)
+        return self._resolve_token(file_path, content, tokens, index)
+
+    def _resolve_token(self, file_path: str, content: str, tokens: List[Token], index: int) -> Optional[RustSymbol]:
+        """Resolve the identifier token at `index` (the file must already be indexed)."""
+        symbols = self._get_index()
+        token = tokens[index]
+        line0 = token.line
+        path_words = ("self", "Self", "super", "crate")
+
+        # The name in a declaration refers to the declared symbol (`mod foo;` resolves to foo's file)
+        for symbol in symbols.file_symbols(file_path):
+            if (symbol.line, symbol.column, symbol.name) == (line0, token.column, token.text) and symbol.kind != "impl" \
+                    and not (symbol.kind == "module" and symbols.module_files(symbol.path)):
+                return symbol
+
+        # Collect the path leading up to the cursor: a::b::name
+        segments = [tokens[index].text]
+        j = index
+        while j >= 2 and tokens[j - 1].text == "::" and (
+            tokens[j - 2].kind == TokenKind.IDENT or tokens[j - 2].text in path_words
+        ):
+            segments.insert(0, tokens[j - 2].text)
+            j -= 2
+
+        name = segments[-1]
+
+        # receiver.member
+        if j >= 1 and tokens[j - 1].text == "." and len(segments) == 1:
+            type_name = None
+            if j >= 2 and tokens[j - 2].kind == TokenKind.IDENT:
+                type_name = self._infer_type(content[:tokens[j - 2].start + len(tokens[j - 2].text)],
+                                             tokens[j - 2].text, file_path, line0)
+            elif j >= 2 and tokens[j - 2].text == "self":
+                type_name = self._enclosing_impl(file_path, line0)
+
+            if type_name:
+                for member in symbols.members(type_name):
+                    if member.name == name:
+                        return member
+
+            candidates = symbols.lookup(name, MEMBER_KINDS)
+            return candidates[0] if len(candidates) == 1 else None
+
+        # Field in a struct literal or pattern: Point { x: 1, y }
+        if len(segments) == 1 and j >= 1 and tokens[j - 1].text in ("{", ",") \
+                and index + 1 < len(tokens) and tokens[index + 1].text in (":", ",", "}"):
+            type_name = self._struct_literal_type(file_path, tokens, index)
+            if type_name:
+                # `use geo::Point as P;` then `P { x }`
+                owner = symbols.resolve([type_name], file_path, symbols.module_at(file_path, line0))
+                type_name = owner.name if owner is not None else type_name
+            for member in symbols.members(type_name) if type_name else []:
+                if member.kind == "field" and member.name == name:
+                    return member
+
+        if segments[0] == "Self":
+            segments[0] = self._enclosing_impl(file_path, line0) or "Self"
+
+        symbol = symbols.resolve(segments, file_path, symbols.module_at(file_path, line0))
+        if symbol is not None:
+            return symbol
+
+        # Last resort for bare names: a unique item anywhere in the crate
+        if len(segments) == 1 and name not in path_words:
+            candidates = [s for s in symbols.lookup(name, ITEM_KINDS) if s.container is None]
+            if len(candidates) == 1:
+                return candidates[0]
+
         return None
+
+    async def get_document_symbols(
+        self,
+        file_path: str,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Get the outline of a file (rust-analyzer, else the symbol parser)."""
+        await self._ensure_initialized()
+
+        content = self._read_content(file_path, content)
+        if content is None or not file_path.endswith(".rs"):
+            return []
+
+        if self.client:
+            try:
+                symbols = await self.client.document_symbols(file_path, content)
+                if symbols:
+                    return symbols
+            except Exception as e:
+                logger.error(f"rust-analyzer document symbol error: {e}")
+
+        return outline(content, file_path)
+
+    async def workspace_symbols(self, query: str) -> List[Dict]:
+        """Search the crate's items and members (rust-analyzer, else the symbol index)."""
+        await self._ensure_initialized()
+
+        if self.client:
+            try:
+                symbols = await self.client.workspace_symbols(query)
+                if symbols:
+                    return symbols
+            except Exception as e:
+                logger.error(f"rust-analyzer workspace symbol error: {e}")
+
+        return self._get_index().workspace_symbols(query)
+
+    def _struct_literal_type(self, file_path: str, tokens: List[Token], index: int) -> Optional[str]:
+        """Type named before the `{` enclosing a token, e.g. `Point` in `Point { x, .. }`."""
+        depth = 0
+        for j in range(index - 1, 0, -1):
+            text = tokens[j].text
+            if text in (")", "]", "}"):
+                depth += 1
+            elif text in ("(", "["):
+                if depth == 0:
+                    return None
+                depth -= 1
+            elif text == "{":
+                if depth > 0:
+                    depth -= 1
+                    continue
+                owner = tokens[j - 1]
+                if owner.text == "Self":
+                    return self._enclosing_impl(file_path, owner.line)
+                return owner.text if owner.kind == TokenKind.IDENT else None
+        return None
+
+    async def get_references(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        include_declaration: bool = True,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Find references across the crate (rust-analyzer, else the symbol index)."""
+        await self._ensure_initialized()
+
+        content = self._read_content(file_path, content)
+        if content is None or not file_path.endswith(".rs"):
+            return []
+
+        if self.client:
+            try:
+                references = await self.client.references(file_path, line, character, content, include_declaration)
+                if references:
+                    return references
+            except Exception as e:
+                logger.error(f"rust-analyzer references error: {e}")
+
+        symbol = self._symbol_at(file_path, content, line, character)
+        if symbol is None:
+            return []
+
+        finder = RustReferences(self._get_index(), self._resolve_token)
+        documents = {finder.index.relative(file_path): content}
+        return [
+            reference.location(finder.uri)
+            for reference in finder.references(symbol, documents, include_declaration)
+        ]
+
+    async def rename(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        new_name: str,
+        content: Optional[str] = None
+    ) -> Optional[Dict]:
+        """
+        Rename the symbol at a position across the crate.
+
+        Raises:
+            ValueError: If the new name is invalid or conflicts with an existing item
+        """
+        await self._ensure_initialized()
+
+        content = self._read_content(file_path, content)
+        if content is None or not file_path.endswith(".rs"):
+            return None
+
+        if self.client:
+            try:
+                edit = await self.client.rename(file_path, line, character, new_name, content)
+                if edit:
+                    return edit
+            except Exception as e:
+                logger.error(f"rust-analyzer rename error: {e}")
+
+        symbol = self._symbol_at(file_path, content, line, character)
+        if symbol is None:
+            return None
+
+        finder = RustReferences(self._get_index(), self._resolve_token)
+        documents = {finder.index.relative(file_path): content}
+        return finder.rename(symbol, new_name, documents)
+
+    async def get_code_actions(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        end_line: Optional[int] = None,
+        end_character: Optional[int] = None,
+        diagnostics: Optional[List[Dict]] = None,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """
+        Get quick fixes and refactorings for a range.
+
+        Uses rust-analyzer's assists when available, plus machine-applicable
+        compiler/clippy suggestions from cargo diagnostics.
+        """
+        await self._ensure_initialized()
+
+        content = self._read_content(file_path, content)
+        if content is None or self._is_manifest(file_path):
+            return []
+
+        end_line = line if end_line is None else end_line
+        end_character = character if end_character is None else end_character
+        if diagnostics is None:
+            diagnostics = await self.get_diagnostics(file_path, content)
+
+        if self.client:
+            range_ = {
+                "start": {"line": line - 1, "character": character},
+                "end": {"line": end_line - 1, "character": end_character}
+            }
+            try:
+                actions = await self.client.code_actions(file_path, range_, diagnostics, content)
+                if actions:
+                    return actions + suggestion_actions(diagnostics, line - 1, end_line - 1)
+            except Exception as e:
+                logger.error(f"rust-analyzer code action error: {e}")
+
+        return RustCodeActions(self._get_index()).actions(
+            file_path, content, (line - 1, character), (end_line - 1, end_character), diagnostics
+        )
+
+    async def get_formatting(
+        self,
+        file_path: str,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Format a file with rustfmt, returning minimal text edits."""
+        return await self._format(file_path, content)
+
+    async def get_range_formatting(
+        self,
+        file_path: str,
+        line: int,
+        end_line: int,
+        content: Optional[str] = None
+    ) -> List[Dict]:
+        """Format only the rustfmt changes touching lines [line, end_line]."""
+        return await self._format(file_path, content, line - 1, end_line - 1)
+
+    async def _format(
+        self,
+        file_path: str,
+        content: Optional[str],
+        start_line: Optional[int] = None,
+        end_line: Optional[int] = None
+    ) -> List[Dict]:
+        content = self._read_content(file_path, content)
+        if content is None or not file_path.endswith(".rs"):
+            return []
+
+        try:
+            return await self.formatter.format_edits(file_path, content, start_line, end_line)
+        except RustfmtError as e:
+            logger.warning(f"rustfmt could not format {file_path}: {e}")
+            return []
+
+    async def shutdown(self):
+        """Shutdown rust-analyzer if it is running."""
+        if self.client:
+            await self.client.shutdown()
+            self.client = None
+        self.initialized = False
@@ -0,0 +1,117 @@
+"""
+Python Document Symbols.
+
+Builds the outline of a Python module (classes, functions, methods and
+module/class-level assignments) from its AST. This does not depend on
+pylsp, so outlines and workspace symbol search work without it.
+"""
+
+import ast
+from typing import List, Dict
+
+from gathering.lsp.symbols import document_symbol, lsp_range, nest
+
+
+def _name_range(lines: List[str], node: ast.AST, keyword: str, name: str) -> Dict:
+    """Range of the name after `def`/`class` on the node's first line."""
+    line = node.lineno - 1
+    text = lines[line] if line < len(lines) else ""
+    column = text.find(f"{keyword} {name}", node.col_offset)
+    column = column + len(keyword) + 1 if column != -1 else node.col_offset
+    return lsp_range(line, column, line, column + len(name))
+
+
+def _node_range(node: ast.AST) -> Dict:
+    """Full range of a node, including its decorators."""
+    start_line, start_column = node.lineno, node.col_offset
+    for decorator in getattr(node, "decorator_list", []):
+        if decorator.lineno < start_line:
+            start_line, start_column = decorator.lineno, decorator.col_offset - 1  # the '@'
+    return lsp_range(start_line - 1, start_column, node.end_lineno - 1, node.end_col_offset)
+
+
+def _signature(node: ast.AST) -> str:
+    """Parameter list and return annotation of a function, e.g. `(self, x: int) -> str`."""
+    signature = f"({ast.unparse(node.args)})"
+    if node.returns is not None:
+        signature += f" -> {ast.unparse(node.returns)}"
+    return signature
+
+
+def _targets(node: ast.AST) -> List[ast.Name]:
+    """Names bound by an assignment statement."""
+    if isinstance(node, ast.Assign):
+        targets = node.targets
+    elif isinstance(node, ast.AnnAssign):
+        targets = [node.target]
+    else:
+        return []
+
+    names = []
+    for target in targets:
+        if isinstance(target, ast.Name):
+            names.append(target)
+        elif isinstance(target, ast.Tuple):
+            names.extend(element for element in target.elts if isinstance(element, ast.Name))
+    return names
+
+
+def _collect(body: List[ast.stmt], lines: List[str], scope: str, symbols: List[Dict]):
+    """Append symbols declared in a module, class or function body."""
+    for node in body:
+        if isinstance(node, (ast.FunctionDef, ast.AsyncFunctionDef)):
+            if scope == "class":
+                kind = "constructor" if node.name == "__init__" else "method"
+            else:
+                kind = "function"
+            symbols.append(document_symbol(
+                node.name, kind, _node_range(node),
+                _name_range(lines, node, "def", node.name), _signature(node)
+            ))
+            _collect(node.body, lines, "function", symbols)
+
+        elif isinstance(node, ast.ClassDef):
+            bases = ", ".join(ast.unparse(base) for base in node.bases)
+            symbols.append(document_symbol(
+                node.name, "class", _node_range(node),
+                _name_range(lines, node, "class", node.name), f"({bases})" if bases else ""
+            ))
+            _collect(node.body, lines, "class", symbols)
+
+        elif isinstance(node, (ast.If, ast.Try)) and scope == "module":
+            # Definitions guarded by `if TYPE_CHECKING:`, `try: import ...` etc.
+            _collect(node.body, lines, scope, symbols)
+            _collect(node.orelse, lines, scope, symbols)
+
+        elif scope != "function":
+            targets = _targets(node)
+            for target in targets:
+                if scope == "class":
+                    kind = "field"
+                elif target.id.isupper():
+                    kind = "constant"
+                else:
+                    kind = "variable"
+                name_range = lsp_range(
+                    target.lineno - 1, target.col_offset, target.end_lineno - 1, target.end_col_offset
+                )
+                # `a, b = ...` binds several names; give each only its own range
+                range_ = _node_range(node) if len(targets) == 1 else name_range
+                symbols.append(document_symbol(target.id, kind, range_, name_range))
+
+
+def outline(source: str) -> List[Dict]:
+    """
+    DocumentSymbol tree for Python source.
+
+    Local variables inside functions are not reported; nested functions
+    and classes are. Returns an empty list for source that does not parse.
+    """
+    try:
+        tree = ast.parse(source)
+    except (SyntaxError, ValueError):
+        return []
+
+    symbols: List[Dict] = []
+    _collect(tree.body, source.splitlines(), "module", symbols)
+    return nest(symbols)
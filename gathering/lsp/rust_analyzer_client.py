@@ -0,0 +1,46 @@
+"""
+Rust Analyzer Client.
+
+Full-featured Rust language server using rust-analyzer over stdio.
+
+This provides:
+- Type-aware autocomplete
+- Hover documentation with signatures
+- Go-to-definition across the Cargo workspace
+- Diagnostics pushed via textDocument/publishDiagnostics
+"""
+
+from typing import Optional, List
+
+from gathering.lsp.stdio_client import StdioLSPClient
+
+
+class RustAnalyzerClient(StdioLSPClient):
+    """
+    Client for rust-analyzer via stdio communication.
+
+    A StdioLSPClient with rust-analyzer's command, languageId and
+    initialization options (check on save, build scripts, proc macros).
+    """
+
+    name = "rust-analyzer"
+    default_command = ["rust-analyzer"]
+    language_id = "rust"
+    install_hint = "rustup component add rust-analyzer"
+
+    def __init__(
+        self,
+        workspace_path: str,
+        command: Optional[List[str]] = None,
+        request_timeout: float = 30.0
+    ):
+        super().__init__(
+            workspace_path,
+            command=command,
+            request_timeout=request_timeout,
+            initialization_options={
+                "checkOnSave": True,
+                "cargo": {"buildScripts": {"enable": True}},
+                "procMacro": {"enable": True}
+            }
+        )
@@ -0,0 +1,439 @@
+"""
+Cargo Check Diagnostics.
+
+Runs `cargo check` (or `cargo clippy`) with JSON output for a Cargo
+workspace and maps each compiler message to an LSP diagnostic in the
+same dict shape returned by the LSP API.
+
+Results are cached per file content hash, so repeated requests for an
+unchanged file do not re-run cargo.
+"""
+
+import asyncio
+import hashlib
+import json
+import logging
+import os
+import shutil
+from typing import Awaitable, Callable, Optional, List, Dict, Any, Tuple
+from pathlib import Path
+
+logger = logging.getLogger(__name__)
+
+
+# Compiler message level -> LSP DiagnosticSeverity
+SEVERITY_MAP = {
+    "error": 1,
+    "error: internal compiler error": 1,
+    "warning": 2,
+    "note": 3,
+    "failure-note": 3,
+    "help": 4,
+}
+
+RUSTC_ERROR_URL = "https://doc.rust-lang.org/error_codes/{code}.html"
+CLIPPY_LINT_URL = "https://rust-lang.github.io/rust-clippy/master/index.html#{lint}"
+
+# Directories never scanned when fingerprinting the workspace
+IGNORED_DIRS = {"target", ".git", "node_modules"}
+
+
+class CargoDiagnostics:
+    """
+    Compiler diagnostics from `cargo check --message-format=json`.
+
+    Example:
+        cargo = CargoDiagnostics("/path/to/crate", command="clippy")
+        diagnostics = await cargo.get_diagnostics("src/lib.rs")
+    """
+
+    def __init__(
+        self,
+        workspace_path: str,
+        command: str = "check",
+        timeout: float = 120.0,
+        on_results: Optional[Callable[[Dict[str, List[Dict]]], Awaitable[None]]] = None
+    ):
+        """
+        Args:
+            workspace_path: Workspace root
+            command: Cargo subcommand, "check" or "clippy"
+            timeout: Seconds before a cargo run is killed
+            on_results: Called after each run with the diagnostics per
+                absolute file path, including an empty list for files
+                whose diagnostics were cleared since the previous run
+        """
+        if command not in ("check", "clippy"):
+            raise ValueError(f"Unsupported cargo command: {command}")
+
+        self.workspace_path = Path(workspace_path)
+        self.command = command
+        self.timeout = timeout
+
+        # absolute file path -> (content hash, workspace stamp, diagnostics)
+        self._cache: Dict[str, Tuple[str, float, List[Dict]]] = {}
+        self._lock: Optional[asyncio.Lock] = None
+        self.on_results = on_results
+        self._reported: set = set()  # files with diagnostics in the last run
+        self._workspace_roots: Dict[Path, Path] = {}  # manifest -> Cargo workspace root
+
+    @staticmethod
+    def is_available() -> bool:
+        """Check whether cargo can be found."""
+        return shutil.which("cargo") is not None
+
+    def find_manifest(self, file_path: str) -> Optional[Path]:
+        """Find the nearest Cargo.toml above a file, within the workspace."""
+        path = self._resolve(file_path)
+        root = self.workspace_path.absolute()
+
+        for directory in [path.parent, *path.parent.parents]:
+            manifest = directory / "Cargo.toml"
+            if manifest.exists():
+                return manifest
+            if directory == root:
+                break
+
+        return None
+
+    async def get_diagnostics(
+        self,
+        file_path: str,
+        content: Optional[str] = None
+    ) -> Optional[List[Dict]]:
+        """
+        Get compiler diagnostics for a file.
+
+        Args:
+            file_path: Path relative to the workspace (or absolute)
+            content: Editor content; cargo only sees the saved file
+
+        Returns:
+            List of diagnostics, or None if cargo cannot analyze this
+            content (no manifest, or unsaved changes without a cached result)
+        """
+        path = self._resolve(file_path)
+        manifest = self.find_manifest(file_path)
+        if manifest is None:
+            return None
+
+        disk_content = path.read_text() if path.exists() else None
+        if content is None:
+            content = disk_content
+        if content is None:
+            return None
+
+        content_hash = self._hash(content)
+        stamp = self._workspace_stamp(self.workspace_path)
+
+        cached = self._cache.get(str(path))
+        if cached and cached[0] == content_hash and cached[1] == stamp:
+            return list(cached[2])
+
+        if content != disk_content:
+            # cargo reads from disk; results would not match the buffer
+            return None
+
+        if self._lock is None:
+            self._lock = asyncio.Lock()
+
+        async with self._lock:
+            by_file = await self.run(manifest)
+
+            # Every .rs file in the workspace gets a (possibly empty) entry
+            self._cache[str(path)] = (content_hash, stamp, by_file.get(str(path), []))
+            for other_path, diagnostics in by_file.items():
+                if other_path == str(path):
+                    continue
+                other = Path(other_path)
+                if other.exists():
+                    self._cache[other_path] = (self._hash(other.read_text()), stamp, diagnostics)
+
+            if self.on_results is not None:
+                cleared = {other_path: [] for other_path in self._reported - set(by_file)}
+                await self.on_results({**cleared, str(path): [], **by_file})
+            self._reported = set(by_file)
+
+        return list(self._cache[str(path)][2])
+
+    async def run(self, manifest: Path) -> Dict[str, List[Dict]]:
+        """
+        Run cargo for a manifest and collect diagnostics per file.
+
+        Returns:
+            Mapping of absolute file path to its diagnostics
+        """
+        args = [
+            "cargo", self.command,
+            "--message-format=json",
+            "--workspace",
+            "--all-targets",
+            "--manifest-path", str(manifest),
+        ]
+
+        process = await asyncio.create_subprocess_exec(
+            *args,
+            stdout=asyncio.subprocess.PIPE,
+            stderr=asyncio.subprocess.PIPE,
+            cwd=str(manifest.parent)
+        )
+
+        try:
+            stdout, stderr = await asyncio.wait_for(process.communicate(), self.timeout)
+        except asyncio.TimeoutError:
+            process.kill()
+            await process.wait()
+            logger.warning(f"cargo {self.command} timed out after {self.timeout}s")
+            return {}
+
+        if not stdout and process.returncode:
+            logger.error(f"cargo {self.command} failed: {stderr.decode(errors='replace')[:500]}")
+
+        workspace_root = await self.locate_workspace(manifest)
+        return self.parse_output(stdout.decode("utf-8", errors="replace"), manifest.parent, workspace_root)
+
+    async def locate_workspace(self, manifest: Path) -> Path:
+        """
+        Cargo workspace root of a manifest.
+
+        It may lie above the editor workspace, e.g. when a member crate
+        is opened on its own.
+        """
+        if manifest in self._workspace_roots:
+            return self._workspace_roots[manifest]
+
+        process = await asyncio.create_subprocess_exec(
+            "cargo", "locate-project", "--workspace",
+            "--message-format", "plain",
+            "--manifest-path", str(manifest),
+            stdout=asyncio.subprocess.PIPE,
+            stderr=asyncio.subprocess.PIPE,
+            cwd=str(manifest.parent)
+        )
+        try:
+            stdout, _ = await asyncio.wait_for(process.communicate(), self.timeout)
+        except asyncio.TimeoutError:
+            process.kill()
+            await process.wait()
+            stdout = b""
+
+        located = stdout.decode("utf-8", errors="replace").strip()
+        root = Path(located).parent if process.returncode == 0 and located else manifest.parent
+        self._workspace_roots[manifest] = root
+        return root
+
+    def parse_output(
+        self,
+        output: str,
+        manifest_dir: Path,
+        workspace_root: Optional[Path] = None
+    ) -> Dict[str, List[Dict]]:
+        """Parse cargo's JSON message stream into diagnostics per file."""
+        by_file: Dict[str, List[Dict]] = {}
+        seen = set()
+
+        for line in output.splitlines():
+            line = line.strip()
+            if not line.startswith("{"):
+                continue
+
+            try:
+                record = json.loads(line)
+            except json.JSONDecodeError:
+                continue
+
+            if record.get("reason") != "compiler-message":
+                continue
+
+            package_dir = Path(record.get("manifest_path", manifest_dir / "Cargo.toml")).parent
+
+            message = record.get("message", {})
+            for file_path, diagnostic in self.to_diagnostics(message, package_dir, workspace_root):
+                # The same message is reported once per target (lib, bin, test)
+                key = (file_path, json.dumps(diagnostic, sort_keys=True))
+                if key in seen:
+                    continue
+                seen.add(key)
+                by_file.setdefault(file_path, []).append(diagnostic)
+
+        return by_file
+
+    def to_diagnostics(
+        self,
+        message: Dict[str, Any],
+        package_dir: Path,
+        workspace_root: Optional[Path] = None
+    ) -> List[Tuple[str, Dict]]:
+        """Map one compiler message to (file path, LSP diagnostic) pairs."""
+        level = message.get("level", "")
+        spans = message.get("spans", [])
+        if level not in SEVERITY_MAP or level == "failure-note" or not spans:
+            return []
+
+        code = (message.get("code") or {}).get("code")
+        is_clippy = bool(code and code.startswith("clippy::"))
+
+        # Notes (without help links or lint-level chatter) are appended to the message
+        notes = []
+        suggestions = []
+        for child in message.get("children", []):
+            child_spans = child.get("spans", [])
+            child_message = child.get("message", "")
+
+            for span in child_spans:
+                if span.get("suggested_replacement") is None:
+                    continue
+                suggestions.append({
+                    "message": child_message,
+                    "file": str(self._span_path(span, package_dir, workspace_root)),
+                    "range": self._span_range(span),
+                    "replacement": span["suggested_replacement"],
+                    "applicability": span.get("suggestion_applicability") or "Unspecified"
+                })
+
+            if child_message.startswith("for further information visit"):
+                continue
+            if child_message.startswith("`#[") and "on by default" in child_message:
+                continue
+            notes.append(f"{child.get('level', 'note')}: {child_message}")
+
+        primary_spans = [
+            self._user_span(span) for span in spans if span.get("is_primary")
+        ] or [self._user_span(spans[0])]
+        secondary_spans = [span for span in spans if not span.get("is_primary")]
+
+        results = []
+        for span in primary_spans:
+            file_path = self._span_path(span, package_dir, workspace_root)
+
+            lines = [message.get("message", "")]
+            if span.get("label"):
+                lines.append(span["label"])
+            lines.extend(notes)
+
+            diagnostic: Dict[str, Any] = {
+                "range": self._span_range(span),
+                "severity": SEVERITY_MAP[level],
+                "message": "\n".join(lines),
+                "source": "clippy" if is_clippy else "rustc"
+            }
+
+            if code:
+                diagnostic["code"] = code
+                if is_clippy:
+                    href = CLIPPY_LINT_URL.format(lint=code.split("::", 1)[1])
+                    diagnostic["codeDescription"] = {"href": href}
+                elif code.startswith("E"):
+                    diagnostic["codeDescription"] = {"href": RUSTC_ERROR_URL.format(code=code)}
+
+            related = [
+                {
+                    "location": {
+                        "uri": self._span_path(other, package_dir, workspace_root).as_uri(),
+                        "range": self._span_range(other)
+                    },
+                    "message": other.get("label") or ""
+                }
+                for other in secondary_spans
+            ]
+            if related:
+                diagnostic["relatedInformation"] = related
+
+            if suggestions:
+                diagnostic["data"] = {"suggestions": suggestions}
+
+            results.append((str(file_path), diagnostic))
+
+        return results
+
+    def _user_span(self, span: Dict[str, Any]) -> Dict[str, Any]:
+        """Follow macro expansions back to the span in user code."""
+        while span.get("expansion") and self._is_external(span.get("file_name", "")):
+            span = span["expansion"]["span"]
+        return span
+
+    def _is_external(self, file_name: str) -> bool:
+        """Whether a span file lies outside the workspace (std, registry crates)."""
+        if file_name.startswith("<"):
+            return True
+        path = Path(file_name)
+        if not path.is_absolute():
+            return False
+        try:
+            path.relative_to(self.workspace_path.absolute())
+            return False
+        except ValueError:
+            return True
+
+    def _span_path(
+        self,
+        span: Dict[str, Any],
+        package_dir: Path,
+        workspace_root: Optional[Path] = None
+    ) -> Path:
+        """
+        Resolve a span's file name.
+
+        rustc reports paths relative to the Cargo workspace root, which may
+        be the package directory or one of its parents (possibly above the
+        editor workspace). Without a located root, the nearest existing
+        match wins.
+        """
+        file_name = Path(span.get("file_name", ""))
+        if file_name.is_absolute():
+            return file_name
+
+        if workspace_root is not None:
+            return (workspace_root / file_name).absolute()
+
+        for directory in [package_dir, *package_dir.parents]:
+            candidate = directory / file_name
+            if candidate.exists():
+                return candidate.absolute()
+
+        return (package_dir / file_name).absolute()
+
+    @staticmethod
+    def _span_range(span: Dict[str, Any]) -> Dict[str, Dict[str, int]]:
+        """Convert a 1-based compiler span to a 0-based LSP range."""
+        return {
+            "start": {
+                "line": max(span.get("line_start", 1) - 1, 0),
+                "character": max(span.get("column_start", 1) - 1, 0)
+            },
+            "end": {
+                "line": max(span.get("line_end", 1) - 1, 0),
+                "character": max(span.get("column_end", 1) - 1, 0)
+            }
+        }
+
+    def _resolve(self, file_path: str) -> Path:
+        """Resolve a workspace-relative path to an absolute path."""
+        path = Path(file_path)
+        if not path.is_absolute():
+            path = self.workspace_path / path
+        return path.absolute()
+
+    @staticmethod
+    def _hash(content: str) -> str:
+        """Hash file content for the cache key."""
+        return hashlib.sha256(content.encode("utf-8")).hexdigest()
+
+    @staticmethod
+    def _workspace_stamp(root: Path) -> float:
+        """
+        Latest modification time of any Rust source or manifest.
+
+        Edits to other files can change a file's diagnostics, so cached
+        entries are only valid for the same workspace stamp.
+        """
+        latest = 0.0
+        for directory, dirnames, filenames in os.walk(root):
+            dirnames[:] = [d for d in dirnames if d not in IGNORED_DIRS]
+            for name in filenames:
+                if name.endswith(".rs") or name in ("Cargo.toml", "Cargo.lock"):
+                    try:
+                        latest = max(latest, os.path.getmtime(os.path.join(directory, name)))
+                    except OSError:
+                        continue
+        return latest
@@ -0,0 +1,474 @@
+"""
+Rust Code Actions.
+
+Quick fixes and small refactorings for the fallback Rust LSP server,
+returned as LSP CodeAction dicts whose `edit` is a WorkspaceEdit
+(`{"changes": {uri: [TextEdit, ...]}}`).
+
+Actions:
+- Replace `.unwrap()` with `.expect("...")`, or with `?` inside functions
+  returning Result/Option
+- Import an unresolved name from the crate or the standard library
+- Derive Debug and/or Clone on a struct or enum
+- Apply machine-applicable compiler/clippy suggestions carried by cargo
+  diagnostics (`data.suggestions`)
+"""
+
+import re
+from pathlib import Path
+from typing import Optional, List, Dict, Tuple
+
+from gathering.lsp.rust_lexer import RustLexer, Token, TokenKind
+from gathering.lsp.rust_symbols import RustSymbolIndex
+
+# Names in scope without an import (std prelude and primitive types)
+PRELUDE = {
+    "Option", "Some", "None", "Result", "Ok", "Err", "Vec", "String", "Box",
+    "ToString", "ToOwned", "Clone", "Copy", "Send", "Sync", "Sized", "Unpin",
+    "Drop", "Fn", "FnMut", "FnOnce", "From", "Into", "TryFrom", "TryInto",
+    "Iterator", "IntoIterator", "DoubleEndedIterator", "ExactSizeIterator",
+    "Extend", "Default", "Debug", "PartialEq", "Eq", "PartialOrd", "Ord",
+    "Hash", "AsRef", "AsMut", "Self", "FromIterator",
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64",
+    "u128", "usize", "f32", "f64", "bool", "char", "str",
+}
+
+# Common standard library items and where to import them from
+STD_IMPORTS = {
+    "HashMap": "std::collections::HashMap",
+    "HashSet": "std::collections::HashSet",
+    "BTreeMap": "std::collections::BTreeMap",
+    "BTreeSet": "std::collections::BTreeSet",
+    "VecDeque": "std::collections::VecDeque",
+    "BinaryHeap": "std::collections::BinaryHeap",
+    "Rc": "std::rc::Rc",
+    "Arc": "std::sync::Arc",
+    "Mutex": "std::sync::Mutex",
+    "RwLock": "std::sync::RwLock",
+    "Cell": "std::cell::Cell",
+    "RefCell": "std::cell::RefCell",
+    "File": "std::fs::File",
+    "Path": "std::path::Path",
+    "PathBuf": "std::path::PathBuf",
+    "Duration": "std::time::Duration",
+    "Instant": "std::time::Instant",
+    "Ordering": "std::cmp::Ordering",
+    "Cow": "std::borrow::Cow",
+    "PhantomData": "std::marker::PhantomData",
+    "Read": "std::io::Read",
+    "Write": "std::io::Write",
+    "BufRead": "std::io::BufRead",
+    "BufReader": "std::io::BufReader",
+    "BufWriter": "std::io::BufWriter",
+    "FromStr": "std::str::FromStr",
+    "Display": "std::fmt::Display",
+    "fmt": "std::fmt",
+    "io": "std::io",
+    "fs": "std::fs",
+    "mem": "std::mem",
+    "thread": "std::thread",
+    "env": "std::env",
+}
+
+DERIVE_TRAITS = ("Debug", "Clone")
+
+# Compiler messages naming an unresolved item
+UNRESOLVED_PATTERNS = [
+    re.compile(r"cannot find (?:type|value|trait|struct|function|macro) `(\w+)`"),
+    re.compile(r"use of undeclared (?:type|crate or module) `(\w+)`"),
+]
+
+FALLIBLE_RETURN = re.compile(r"->\s*(?:[\w]+::)*(Result|Option)\b")
+
+
+def text_edit(start: Tuple[int, int], end: Tuple[int, int], new_text: str) -> Dict:
+    """Build an LSP TextEdit from 0-based (line, character) positions."""
+    return {
+        "range": {
+            "start": {"line": start[0], "character": start[1]},
+            "end": {"line": end[0], "character": end[1]}
+        },
+        "newText": new_text
+    }
+
+
+def code_action(
+    title: str,
+    kind: str,
+    changes: Dict[str, List[Dict]],
+    diagnostics: Optional[List[Dict]] = None,
+    preferred: bool = False
+) -> Dict:
+    """Build an LSP CodeAction carrying a WorkspaceEdit."""
+    action = {"title": title, "kind": kind, "edit": {"changes": changes}}
+    if diagnostics:
+        action["diagnostics"] = diagnostics
+    if preferred:
+        action["isPreferred"] = True
+    return action
+
+
+def _overlaps(diagnostic: Dict, start_line: int, end_line: int) -> bool:
+    diag_range = diagnostic.get("range", {})
+    first = diag_range.get("start", {}).get("line", -1)
+    last = diag_range.get("end", {}).get("line", first)
+    return first <= end_line and last >= start_line
+
+
+def suggestion_actions(diagnostics: List[Dict], start_line: int, end_line: int) -> List[Dict]:
+    """
+    Code actions for machine-applicable suggestions attached to cargo diagnostics.
+
+    One quick fix per diagnostic in the range, plus a `source.fixAll`
+    action applying every machine-applicable suggestion at once.
+    """
+    actions = []
+    all_edits: Dict[str, List[Dict]] = {}
+    fixable = 0
+
+    for diagnostic in diagnostics:
+        suggestions = [
+            s for s in (diagnostic.get("data") or {}).get("suggestions", [])
+            if s.get("applicability") == "MachineApplicable"
+        ]
+        if not suggestions:
+            continue
+
+        changes: Dict[str, List[Dict]] = {}
+        for suggestion in suggestions:
+            uri = Path(suggestion["file"]).absolute().as_uri()
+            edit = {"range": suggestion["range"], "newText": suggestion["replacement"]}
+            changes.setdefault(uri, []).append(edit)
+            all_edits.setdefault(uri, []).append(edit)
+        fixable += 1
+
+        if not _overlaps(diagnostic, start_line, end_line):
+            continue
+
+        first = suggestions[0]
+        title = first["message"][:1].upper() + first["message"][1:]
+        if first["replacement"] and "\n" not in first["replacement"] and len(first["replacement"]) <= 60:
+            title = f"{title}: `{first['replacement']}`"
+
+        source = diagnostic.get("source", "rustc")
+        actions.append(code_action(
+            f"{title} ({source})", "quickfix", _without_overlaps(changes), [diagnostic], preferred=True
+        ))
+
+    if fixable > 1:
+        actions.append(code_action(
+            "Apply all machine-applicable suggestions", "source.fixAll", _without_overlaps(all_edits)
+        ))
+
+    return actions
+
+
+def _without_overlaps(changes: Dict[str, List[Dict]]) -> Dict[str, List[Dict]]:
+    """Sort each file's edits and drop duplicates or edits overlapping an earlier one."""
+    cleaned = {}
+    for uri, edits in changes.items():
+        def key(edit):
+            start = edit["range"]["start"]
+            return start["line"], start["character"]
+
+        kept: List[Dict] = []
+        for edit in sorted(edits, key=key):
+            if kept:
+                previous_end = kept[-1]["range"]["end"]
+                if key(edit) < (previous_end["line"], previous_end["character"]) or edit == kept[-1]:
+                    continue
+            kept.append(edit)
+        cleaned[uri] = kept
+    return cleaned
+
+
+class RustCodeActions:
+    """
+    Computes code actions for a range of a Rust file.
+
+    Example:
+        provider = RustCodeActions(index)
+        actions = provider.actions("src/main.rs", content, (9, 0), (9, 20), diagnostics)
+    """
+
+    def __init__(self, index: RustSymbolIndex):
+        self.index = index
+
+    def actions(
+        self,
+        file_path: str,
+        content: str,
+        start: Tuple[int, int],
+        end: Tuple[int, int],
+        diagnostics: Optional[List[Dict]] = None
+    ) -> List[Dict]:
+        """
+        Code actions for a 0-based range.
+
+        Args:
+            file_path: Path relative to the workspace
+            content: Current file content
+            start: Range start (line, character)
+            end: Range end (line, character)
+            diagnostics: Diagnostics reported for the file
+        """
+        diagnostics = diagnostics or []
+        self.index.update_file(file_path, content)
+
+        lexer = RustLexer(content)
+        tokens = [t for t in lexer.tokenize() if not t.is_comment]
+        uri = (self.index.workspace_path / file_path).absolute().as_uri()
+
+        actions = []
+        actions.extend(self._unwrap_actions(file_path, uri, tokens, start[0], end[0], diagnostics))
+        actions.extend(self._import_actions(file_path, uri, content, tokens, start[0], end[0], diagnostics))
+        actions.extend(self._derive_actions(file_path, uri, lexer, start[0], end[0]))
+        actions.extend(suggestion_actions(diagnostics, start[0], end[0]))
+        return actions
+
+    # ------------------------------------------------------------------
+    # .unwrap()
+    # ------------------------------------------------------------------
+
+    def _unwrap_actions(
+        self,
+        file_path: str,
+        uri: str,
+        tokens: List[Token],
+        start_line: int,
+        end_line: int,
+        diagnostics: List[Dict]
+    ) -> List[Dict]:
+        actions = []
+
+        for i, token in enumerate(tokens):
+            if token.text != "unwrap" or not start_line <= token.line <= end_line:
+                continue
+            if i < 1 or tokens[i - 1].text != "." or i + 2 >= len(tokens) \
+                    or tokens[i + 1].text != "(" or tokens[i + 2].text != ")":
+                continue
+
+            dot, close = tokens[i - 1], tokens[i + 2]
+            related = [
+                d for d in diagnostics
+                if "unwrap" in d.get("message", "") and _overlaps(d, token.line, token.line)
+            ]
+
+            receiver = self._receiver_name(tokens, i - 1)
+            message = f"{receiver} failed" if receiver else "unexpected failure"
+            actions.append(code_action(
+                "Replace `.unwrap()` with `.expect(\"...\")`",
+                "quickfix",
+                {uri: [text_edit((token.line, token.column), (close.line, close.column + 1),
+                                 f'expect("{message}")')]},
+                related
+            ))
+
+            function = self._enclosing_function(file_path, token.line)
+            if function and FALLIBLE_RETURN.search(function.signature):
+                actions.append(code_action(
+                    "Replace `.unwrap()` with `?`",
+                    "quickfix",
+                    {uri: [text_edit((dot.line, dot.column), (close.line, close.column + 1), "?")]},
+                    related,
+                    preferred=True
+                ))
+
+        return actions
+
+    @staticmethod
+    def _receiver_name(tokens: List[Token], dot: int) -> Optional[str]:
+        """Last identifier of the expression before `.`, e.g. `parse` in `parse(x).unwrap()`."""
+        j = dot - 1
+        if j >= 0 and tokens[j].text == ")":
+            depth = 0
+            while j >= 0:
+                if tokens[j].text == ")":
+                    depth += 1
+                elif tokens[j].text == "(":
+                    depth -= 1
+                    if depth == 0:
+                        break
+                j -= 1
+            j -= 1
+            if j >= 0 and tokens[j].text == "!":
+                j -= 1
+        if j >= 0 and tokens[j].kind == TokenKind.IDENT:
+            return tokens[j].text
+        return None
+
+    def _enclosing_function(self, file_path: str, line: int):
+        """Innermost function or method containing a 0-based line."""
+        candidates = [
+            s for s in self.index.file_symbols(file_path)
+            if s.kind in ("function", "method") and s.start_line <= line <= s.end_line
+        ]
+        return max(candidates, key=lambda s: s.start_line, default=None)
+
+    # ------------------------------------------------------------------
+    # Missing `use`
+    # ------------------------------------------------------------------
+
+    def _import_actions(
+        self,
+        file_path: str,
+        uri: str,
+        content: str,
+        tokens: List[Token],
+        start_line: int,
+        end_line: int,
+        diagnostics: List[Dict]
+    ) -> List[Dict]:
+        names: Dict[str, List[Dict]] = {}
+
+        for diagnostic in diagnostics:
+            if not _overlaps(diagnostic, start_line, end_line):
+                continue
+            for pattern in UNRESOLVED_PATTERNS:
+                match = pattern.search(diagnostic.get("message", ""))
+                if match:
+                    names.setdefault(match.group(1), []).append(diagnostic)
+
+        for i, token in enumerate(tokens):
+            if token.kind != TokenKind.IDENT or not start_line <= token.line <= end_line:
+                continue
+            previous = tokens[i - 1].text if i > 0 else ""
+            following = tokens[i + 1].text if i + 1 < len(tokens) else ""
+            if previous in (".", "::", "use") or following == "!":
+                continue
+            if token.text[0].isupper() or following == "::":
+                names.setdefault(token.text, [])
+
+        module = self.index.module_at(file_path, start_line)
+        imported = {imp.alias for imp in self.index.imports(file_path)}
+
+        actions = []
+        for name, related in names.items():
+            if name in PRELUDE or name in imported:
+                continue
+            if self.index.resolve([name], file_path, module) is not None:
+                continue
+
+            for path in self._import_candidates(name, module):
+                position, prefix, suffix = self._import_position(tokens, content)
+                actions.append(code_action(
+                    f"Import `{path}`",
+                    "quickfix",
+                    {uri: [text_edit(position, position, f"{prefix}use {path};\n{suffix}")]},
+                    related
+                ))
+
+        return actions
+
+    def _import_candidates(self, name: str, module: str) -> List[str]:
+        """Paths that could be imported for a name: crate items first, then std."""
+        paths = sorted({
+            symbol.path for symbol in self.index.lookup(name)
+            if symbol.container is None and symbol.kind != "impl" and symbol.module != module
+        })
+        if name in STD_IMPORTS:
+            paths.append(STD_IMPORTS[name])
+        return paths
+
+    @staticmethod
+    def _import_position(tokens: List[Token], content: str) -> Tuple[Tuple[int, int], str, str]:
+        """
+        Where to insert a new `use`: after the last top-level `use`, else
+        after inner attributes and `//!` docs at the top of the file.
+
+        Returns:
+            (position, text before the use, text after it)
+        """
+        depth = 0
+        last_use_end = None
+        for i, token in enumerate(tokens):
+            if token.text == "{":
+                depth += 1
+            elif token.text == "}":
+                depth -= 1
+            elif depth == 0 and token.text == "use":
+                j = i
+                while j < len(tokens) and tokens[j].text != ";":
+                    j += 1
+                if j < len(tokens):
+                    last_use_end = tokens[j].line
+
+        if last_use_end is not None:
+            return (last_use_end + 1, 0), "", ""
+
+        # Skip `//!` docs and `#![...]` attributes (comments were filtered from tokens)
+        lexer_tokens = RustLexer(content).tokenize()
+        line = 0
+        for token in lexer_tokens:
+            if (token.kind == TokenKind.DOC_COMMENT and token.text.startswith(("//!", "/*!"))) \
+                    or (token.kind == TokenKind.ATTRIBUTE and token.text.startswith("#!")) \
+                    or token.kind == TokenKind.LINE_COMMENT:
+                line = token.line + token.text.count("\n") + 1
+                continue
+            break
+
+        if line:
+            return (line, 0), "\n", ""
+        return (0, 0), "", "\n"
+
+    # ------------------------------------------------------------------
+    # #[derive(Debug, Clone)]
+    # ------------------------------------------------------------------
+
+    def _derive_actions(
+        self,
+        file_path: str,
+        uri: str,
+        lexer: RustLexer,
+        start_line: int,
+        end_line: int
+    ) -> List[Dict]:
+        tokens = lexer.tokenize()
+        lines = lexer.source.split("\n")
+
+        for symbol in self.index.file_symbols(file_path):
+            if symbol.kind not in ("struct", "enum"):
+                continue
+
+            # The item's first token, and the attributes/docs directly above it
+            first = next((i for i, t in enumerate(tokens) if t.line == symbol.start_line and not t.is_comment), None)
+            if first is None:
+                continue
+            attributes = []
+            j = first - 1
+            while j >= 0 and (tokens[j].kind == TokenKind.ATTRIBUTE or tokens[j].is_comment):
+                if tokens[j].kind == TokenKind.ATTRIBUTE:
+                    attributes.append(tokens[j])
+                j -= 1
+            top_line = tokens[j + 1].line if j + 1 < len(tokens) else symbol.start_line
+
+            if not (top_line <= start_line <= symbol.end_line or top_line <= end_line <= symbol.end_line):
+                continue
+
+            derive = next((a for a in attributes if re.match(r"#\s*\[\s*derive\s*\(", a.text)), None)
+            existing = set()
+            if derive:
+                inner = derive.text[derive.text.index("(") + 1:derive.text.rindex(")")]
+                existing = {part.strip().rsplit("::", 1)[-1] for part in inner.split(",") if part.strip()}
+
+            missing = [t for t in DERIVE_TRAITS if t not in existing]
+            options = [[t] for t in missing]
+            if len(missing) > 1:
+                options.append(missing)
+
+            actions = []
+            for traits in options:
+                if derive:
+                    close = lexer.position(derive.start + derive.text.rindex(")"))
+                    separator = ", " if existing else ""
+                    edit = text_edit(close, close, separator + ", ".join(traits))
+                else:
+                    indent = re.match(r"\s*", lines[symbol.start_line]).group(0)
+                    edit = text_edit((symbol.start_line, 0), (symbol.start_line, 0),
+                                     f"{indent}#[derive({', '.join(traits)})]\n")
+                actions.append(code_action(
+                    f"Derive `{'`, `'.join(traits)}` for `{symbol.name}`", "refactor.rewrite", {uri: [edit]}
+                ))
+            return actions
+
+        return []
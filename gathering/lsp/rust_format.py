@@ -0,0 +1,143 @@
+"""
+Rust Formatting.
+
+Formats Rust source by piping it through `rustfmt --emit stdout`. The
+nearest `rustfmt.toml` (or `.rustfmt.toml`) and the package edition from
+`Cargo.toml` are passed explicitly, since rustfmt cannot discover them
+when reading from stdin.
+"""
+
+import asyncio
+import logging
+import shutil
+import tomllib
+from pathlib import Path
+from typing import Optional, List, Dict
+
+from gathering.lsp.text_edits import diff_edits, edits_in_range
+
+logger = logging.getLogger(__name__)
+
+CONFIG_FILES = ("rustfmt.toml", ".rustfmt.toml")
+DEFAULT_EDITION = "2021"
+
+
+class RustfmtError(Exception):
+    """Raised when rustfmt cannot format the source (e.g. a syntax error)."""
+    pass
+
+
+class RustFormatter:
+    """
+    rustfmt wrapper producing minimal LSP text edits.
+
+    Example:
+        formatter = RustFormatter("/path/to/crate")
+        edits = await formatter.format_edits("src/main.rs", content)
+    """
+
+    def __init__(self, workspace_path: str, command: str = "rustfmt", timeout: float = 30.0):
+        self.workspace_path = Path(workspace_path)
+        self.command = command
+        self.timeout = timeout
+
+    def is_available(self) -> bool:
+        """Check whether rustfmt can be found."""
+        return shutil.which(self.command) is not None
+
+    def _ancestors(self, file_path: str) -> List[Path]:
+        """Directories from the file's own up to the workspace root."""
+        path = Path(file_path)
+        if not path.is_absolute():
+            path = self.workspace_path / path
+        root = self.workspace_path.absolute()
+
+        directories = []
+        for directory in [path.absolute().parent, *path.absolute().parent.parents]:
+            directories.append(directory)
+            if directory == root:
+                break
+        return directories
+
+    def find_config(self, file_path: str) -> Optional[Path]:
+        """Nearest rustfmt.toml or .rustfmt.toml above a file."""
+        for directory in self._ancestors(file_path):
+            for name in CONFIG_FILES:
+                if (directory / name).is_file():
+                    return directory / name
+        return None
+
+    def edition(self, file_path: str) -> str:
+        """Edition of the package containing a file (rustfmt defaults to 2015 otherwise)."""
+        for directory in self._ancestors(file_path):
+            manifest = directory / "Cargo.toml"
+            if not manifest.is_file():
+                continue
+            try:
+                data = tomllib.loads(manifest.read_text())
+            except (OSError, tomllib.TOMLDecodeError):
+                return DEFAULT_EDITION
+            edition = (data.get("package") or {}).get("edition", DEFAULT_EDITION)
+            if isinstance(edition, dict):  # edition.workspace = true
+                root = (data.get("workspace") or {}).get("package") or {}
+                edition = root.get("edition", DEFAULT_EDITION)
+            return str(edition)
+        return DEFAULT_EDITION
+
+    def command_args(self, file_path: str) -> List[str]:
+        """rustfmt command line for formatting a file's content from stdin."""
+        args = [self.command, "--emit", "stdout", "--edition", self.edition(file_path)]
+        config = self.find_config(file_path)
+        if config is not None:
+            args += ["--config-path", str(config)]
+        return args
+
+    async def format(self, file_path: str, content: str) -> str:
+        """
+        Format Rust source.
+
+        Raises:
+            RustfmtError: If rustfmt is missing, times out or rejects the source
+        """
+        if not self.is_available():
+            raise RustfmtError("rustfmt not found. Install with: rustup component add rustfmt")
+
+        process = await asyncio.create_subprocess_exec(
+            *self.command_args(file_path),
+            stdin=asyncio.subprocess.PIPE,
+            stdout=asyncio.subprocess.PIPE,
+            stderr=asyncio.subprocess.PIPE,
+            cwd=str(self.workspace_path)
+        )
+
+        try:
+            stdout, stderr = await asyncio.wait_for(
+                process.communicate(content.encode("utf-8")), self.timeout
+            )
+        except asyncio.TimeoutError:
+            process.kill()
+            await process.wait()
+            raise RustfmtError(f"rustfmt timed out after {self.timeout}s")
+
+        if process.returncode != 0:
+            raise RustfmtError(stderr.decode("utf-8", errors="replace").strip() or "rustfmt failed")
+
+        return stdout.decode("utf-8")
+
+    async def format_edits(
+        self,
+        file_path: str,
+        content: str,
+        start_line: Optional[int] = None,
+        end_line: Optional[int] = None
+    ) -> List[Dict]:
+        """
+        Minimal text edits formatting a document, or only the edits
+        touching 0-based lines [start_line, end_line] for range formatting.
+        """
+        formatted = await self.format(file_path, content)
+        edits = diff_edits(content, formatted)
+
+        if start_line is not None:
+            edits = edits_in_range(edits, start_line, end_line if end_line is not None else start_line)
+        return edits
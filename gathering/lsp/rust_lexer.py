@@ -0,0 +1,376 @@
+"""
+Rust Lexer.
+
+A small, dependency-free tokenizer for Rust source used by the fallback
+Rust LSP server. It does not build a syntax tree; it only needs to know
+whether a position is code, a literal, a comment or an attribute so that
+completions and diagnostics never fire inside strings or comments.
+
+Handles:
+- Nested block comments and doc comments (///, //!, /** */, /*! */)
+- Raw strings (r"...", r#"..."#, br#"..."#) and byte/C strings
+- Char literals vs lifetimes ('a' vs 'a)
+- Raw identifiers (r#match)
+- Attributes (#[...], #![...])
+"""
+
+import bisect
+from dataclasses import dataclass
+from enum import Enum
+from typing import List, Optional
+
+
+class TokenKind(str, Enum):
+    """Kinds of Rust tokens."""
+    IDENT = "ident"
+    KEYWORD = "keyword"
+    LIFETIME = "lifetime"
+    CHAR = "char"
+    STRING = "string"
+    RAW_STRING = "raw_string"
+    NUMBER = "number"
+    LINE_COMMENT = "line_comment"
+    BLOCK_COMMENT = "block_comment"
+    DOC_COMMENT = "doc_comment"
+    ATTRIBUTE = "attribute"
+    PUNCT = "punct"
+
+
+RUST_KEYWORDS = {
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn",
+    "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in",
+    "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+}
+
+# Longest first, so that e.g. "..=" wins over ".."
+MULTI_CHAR_PUNCT = [
+    "..=", "...", "<<=", ">>=",
+    "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "..",
+    "+=", "-=", "*=", "/=", "%=", "^=", "&=", "|=", "<<", ">>",
+]
+
+COMMENT_KINDS = {TokenKind.LINE_COMMENT, TokenKind.BLOCK_COMMENT, TokenKind.DOC_COMMENT}
+LITERAL_KINDS = {TokenKind.STRING, TokenKind.RAW_STRING, TokenKind.CHAR}
+
+
+@dataclass
+class Token:
+    """A lexed token with its source span (0-based line/column)."""
+    kind: TokenKind
+    text: str
+    start: int
+    end: int
+    line: int
+    column: int
+    terminated: bool = True
+
+    @property
+    def is_comment(self) -> bool:
+        return self.kind in COMMENT_KINDS
+
+    @property
+    def is_literal(self) -> bool:
+        return self.kind in LITERAL_KINDS
+
+    def contains(self, offset: int) -> bool:
+        """
+        Whether a cursor offset lies inside this token.
+
+        A cursor right after a line comment or an unterminated literal is
+        still inside it; a cursor right after a closing quote is not.
+        """
+        if self.start < offset < self.end:
+            return True
+        if offset == self.end and (self.kind == TokenKind.LINE_COMMENT or not self.terminated):
+            return True
+        return False
+
+
+def _is_ident_start(ch: str) -> bool:
+    return ch == "_" or ch.isalpha()
+
+
+def _is_ident_continue(ch: str) -> bool:
+    return ch == "_" or ch.isalnum()
+
+
+class RustLexer:
+    """
+    Tokenizer for Rust source.
+
+    Example:
+        lexer = RustLexer(source)
+        for token in lexer.tokenize():
+            if token.kind == TokenKind.IDENT:
+                ...
+        if lexer.context_at(offset) in (TokenKind.STRING, TokenKind.LINE_COMMENT):
+            ...
+    """
+
+    def __init__(self, source: str):
+        self.source = source
+        self._line_starts = [0]
+        for i, ch in enumerate(source):
+            if ch == "\n":
+                self._line_starts.append(i + 1)
+        self._tokens: Optional[List[Token]] = None
+
+    def tokenize(self) -> List[Token]:
+        """Tokenize the source (whitespace is skipped)."""
+        if self._tokens is not None:
+            return self._tokens
+
+        src = self.source
+        n = len(src)
+        tokens: List[Token] = []
+        i = 0
+
+        while i < n:
+            ch = src[i]
+
+            if ch.isspace():
+                i += 1
+                continue
+
+            start = i
+            kind = TokenKind.PUNCT
+            terminated = True
+
+            if src.startswith("//", i):
+                end = src.find("\n", i)
+                i = n if end == -1 else end
+                text = src[start:i]
+                is_doc = (text.startswith("///") and not text.startswith("////")) or text.startswith("//!")
+                kind = TokenKind.DOC_COMMENT if is_doc else TokenKind.LINE_COMMENT
+
+            elif src.startswith("/*", i):
+                i, terminated = self._block_comment(i)
+                text = src[start:i]
+                is_doc = (
+                    (text.startswith("/**") and not text.startswith("/***") and text != "/**/")
+                    or text.startswith("/*!")
+                )
+                kind = TokenKind.DOC_COMMENT if is_doc else TokenKind.BLOCK_COMMENT
+
+            elif self._raw_string_start(i) is not None:
+                i, terminated = self._raw_string(self._raw_string_start(i))
+                kind = TokenKind.RAW_STRING
+
+            elif ch == '"' or (ch in "bc" and src.startswith('"', i + 1)):
+                i, terminated = self._quoted(i + (0 if ch == '"' else 1), '"')
+                kind = TokenKind.STRING
+
+            elif ch == "b" and src.startswith("'", i + 1):
+                i, terminated = self._quoted(i + 1, "'")
+                kind = TokenKind.CHAR
+
+            elif ch == "'":
+                i, kind, terminated = self._quote_or_lifetime(i)
+
+            elif ch == "r" and src.startswith("#", i + 1) and i + 2 < n and _is_ident_start(src[i + 2]):
+                # Raw identifier: r#match
+                i += 2
+                while i < n and _is_ident_continue(src[i]):
+                    i += 1
+                kind = TokenKind.IDENT
+
+            elif _is_ident_start(ch):
+                while i < n and _is_ident_continue(src[i]):
+                    i += 1
+                kind = TokenKind.KEYWORD if src[start:i] in RUST_KEYWORDS else TokenKind.IDENT
+
+            elif ch.isdigit():
+                i = self._number(i)
+                kind = TokenKind.NUMBER
+
+            elif ch == "#" and self._attribute_open(i) is not None:
+                i, terminated = self._attribute(self._attribute_open(i))
+                kind = TokenKind.ATTRIBUTE
+
+            else:
+                for punct in MULTI_CHAR_PUNCT:
+                    if src.startswith(punct, i):
+                        i += len(punct)
+                        break
+                else:
+                    i += 1
+
+            line, column = self.position(start)
+            tokens.append(Token(kind, src[start:i], start, i, line, column, terminated))
+
+        self._tokens = tokens
+        return tokens
+
+    def position(self, offset: int) -> tuple:
+        """Convert an offset to a 0-based (line, column) pair."""
+        line = bisect.bisect_right(self._line_starts, offset) - 1
+        return line, offset - self._line_starts[line]
+
+    def offset(self, line: int, column: int) -> int:
+        """Convert a 0-based (line, column) pair to an offset."""
+        if line >= len(self._line_starts):
+            return len(self.source)
+        return min(self._line_starts[line] + column, len(self.source))
+
+    def token_at(self, offset: int) -> Optional[Token]:
+        """Find the token containing a cursor offset, if any."""
+        tokens = self.tokenize()
+        starts = [token.start for token in tokens]
+        index = bisect.bisect_left(starts, offset) - 1
+        if 0 <= index < len(tokens) and tokens[index].contains(offset):
+            return tokens[index]
+        return None
+
+    def context_at(self, offset: int) -> Optional[TokenKind]:
+        """
+        Kind of the string, char, comment or attribute enclosing a cursor.
+
+        Returns None when the cursor is in plain code.
+        """
+        token = self.token_at(offset)
+        if token and (token.is_comment or token.is_literal or token.kind == TokenKind.ATTRIBUTE):
+            return token.kind
+        return None
+
+    # ------------------------------------------------------------------
+    # Scanners: each takes a start offset and returns the end offset
+    # ------------------------------------------------------------------
+
+    def _block_comment(self, i: int) -> tuple:
+        """Scan a (possibly nested) block comment."""
+        src = self.source
+        depth = 0
+        while i < len(src):
+            if src.startswith("/*", i):
+                depth += 1
+                i += 2
+            elif src.startswith("*/", i):
+                depth -= 1
+                i += 2
+                if depth == 0:
+                    return i, True
+            else:
+                i += 1
+        return len(src), False
+
+    def _raw_string_start(self, i: int) -> Optional[int]:
+        """Return the offset of the 'r' if a raw string starts here."""
+        src = self.source
+        j = i + 1 if src[i] in "bc" else i
+        if not src.startswith("r", j):
+            return None
+        if j > 0 and _is_ident_continue(src[j - 1]) and j == i:
+            # 'r' in the middle of an identifier, e.g. `for"`
+            return None
+        k = j + 1
+        while k < len(src) and src[k] == "#":
+            k += 1
+        if k < len(src) and src[k] == '"':
+            return j
+        return None
+
+    def _raw_string(self, r_offset: int) -> tuple:
+        """Scan a raw string starting at its 'r'."""
+        src = self.source
+        k = r_offset + 1
+        hashes = 0
+        while src[k] == "#":
+            hashes += 1
+            k += 1
+        closing = '"' + "#" * hashes
+        end = src.find(closing, k + 1)
+        if end == -1:
+            return len(src), False
+        return end + len(closing), True
+
+    def _quoted(self, i: int, quote: str) -> tuple:
+        """Scan a quoted literal with backslash escapes, starting at the quote."""
+        src = self.source
+        i += 1
+        while i < len(src):
+            ch = src[i]
+            if ch == "\\":
+                i += 2
+                continue
+            if ch == quote:
+                return i + 1, True
+            if quote == "'" and ch == "\n":
+                break
+            i += 1
+        return min(i, len(src)), False
+
+    def _quote_or_lifetime(self, i: int) -> tuple:
+        """Disambiguate a char literal from a lifetime or label."""
+        src = self.source
+        n = len(src)
+
+        if i + 1 < n and src[i + 1] == "\\":
+            end, terminated = self._quoted(i, "'")
+            return end, TokenKind.CHAR, terminated
+
+        # 'x' where x is any single character
+        if i + 2 < n and src[i + 2] == "'" and src[i + 1] != "\n":
+            return i + 3, TokenKind.CHAR, True
+
+        if i + 1 < n and _is_ident_start(src[i + 1]):
+            j = i + 1
+            while j < n and _is_ident_continue(src[j]):
+                j += 1
+            return j, TokenKind.LIFETIME, True
+
+        end, terminated = self._quoted(i, "'")
+        return end, TokenKind.CHAR, terminated
+
+    def _number(self, i: int) -> int:
+        """Scan a numeric literal, leaving range operators alone."""
+        src = self.source
+        n = len(src)
+        while i < n:
+            ch = src[i]
+            if _is_ident_continue(ch):
+                i += 1
+            elif ch == "." and i + 1 < n and src[i + 1].isdigit():
+                i += 1
+            else:
+                break
+        return i
+
+    def _attribute_open(self, i: int) -> Optional[int]:
+        """Return the offset of '[' if an attribute starts at '#'."""
+        src = self.source
+        j = i + 1
+        if src.startswith("!", j):
+            j += 1
+        while j < len(src) and src[j] in " \t":
+            j += 1
+        if src.startswith("[", j):
+            return j
+        return None
+
+    def _attribute(self, i: int) -> tuple:
+        """Scan an attribute body to its matching bracket, skipping literals."""
+        src = self.source
+        depth = 0
+        while i < len(src):
+            ch = src[i]
+            if ch == '"':
+                i, _ = self._quoted(i, '"')
+                continue
+            if self._raw_string_start(i) is not None and ch in "rbc":
+                i, _ = self._raw_string(self._raw_string_start(i))
+                continue
+            if ch == "[":
+                depth += 1
+            elif ch == "]":
+                depth -= 1
+                if depth == 0:
+                    return i + 1, True
+            i += 1
+        return len(src), False
+
+
+def tokenize(source: str) -> List[Token]:
+    """Tokenize Rust source."""
+    return RustLexer(source).tokenize()
@@ -0,0 +1,800 @@
+"""
+Cargo Manifest Support.
+
+Completions and diagnostics for `Cargo.toml` files, used by the Rust LSP
+plugin. Crate names, versions and features come from a local mirror of
+the crates.io index (git or sparse layout), so no network is needed.
+
+Handles:
+- Section names ([dependencies], [features], [workspace], [profile.*], ...)
+- Keys for package, dependency, workspace, profile and target tables
+- Dependency table validation (sources, version requirements, features)
+- Feature references (`dep:x`, `x/feat`, `x?/feat`)
+- Crates imported with `use` but missing from [dependencies]
+"""
+
+import json
+import logging
+import os
+import re
+import tomllib
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Optional, List, Dict, Any, Iterable, Tuple
+
+logger = logging.getLogger(__name__)
+
+
+TABLE_SECTIONS = [
+    "package", "dependencies", "dev-dependencies", "build-dependencies",
+    "features", "workspace", "workspace.package", "workspace.dependencies",
+    "lib", "profile.dev", "profile.release", "profile.test", "profile.bench",
+    "target.'cfg(unix)'.dependencies", "patch.crates-io", "lints.rust", "lints.clippy",
+    "badges",
+]
+ARRAY_SECTIONS = ["bin", "example", "test", "bench"]
+
+KNOWN_TABLES = {
+    "package", "project", "lib", "bin", "example", "test", "bench",
+    "dependencies", "dev-dependencies", "build-dependencies", "target",
+    "features", "workspace", "profile", "patch", "replace", "badges",
+    "lints", "cargo-features",
+}
+
+DEPENDENCY_SECTIONS = {"dependencies", "dev-dependencies", "build-dependencies"}
+
+PACKAGE_KEYS = [
+    "name", "version", "edition", "rust-version", "authors", "description",
+    "documentation", "readme", "homepage", "repository", "license",
+    "license-file", "keywords", "categories", "workspace", "build", "links",
+    "exclude", "include", "publish", "metadata", "default-run", "autobins",
+    "autoexamples", "autotests", "autobenches", "resolver",
+]
+DEPENDENCY_KEYS = [
+    "version", "features", "optional", "default-features", "path", "git",
+    "branch", "tag", "rev", "package", "registry", "workspace",
+]
+WORKSPACE_KEYS = [
+    "members", "exclude", "default-members", "resolver", "package",
+    "dependencies", "lints", "metadata",
+]
+PROFILE_KEYS = [
+    "opt-level", "debug", "split-debuginfo", "strip", "debug-assertions",
+    "overflow-checks", "lto", "panic", "incremental", "codegen-units", "rpath",
+]
+TARGET_KEYS = [
+    "name", "path", "test", "doctest", "bench", "doc", "proc-macro",
+    "harness", "edition", "crate-type", "required-features",
+]
+
+EDITIONS = ["2015", "2018", "2021", "2024"]
+
+# Path roots in `use` statements that never name a dependency
+BUILTIN_CRATES = {"crate", "self", "super", "Self", "std", "core", "alloc", "proc_macro", "test"}
+
+# Completion item kinds (LSP CompletionItemKind)
+KIND_MODULE = 9
+KIND_PROPERTY = 10
+KIND_VALUE = 12
+KIND_ENUM_MEMBER = 20
+
+HEADER_PATTERN = re.compile(r'^\s*(\[\[?)\s*([^\]]*?)\s*\]')
+KEY_PATTERN = re.compile(r'^\s*((?:"[^"]*"|[\w\-]+)(?:\s*\.\s*(?:"[^"]*"|[\w\-]+))*)\s*=')
+TOML_ERROR_POSITION = re.compile(r'\(at line (\d+), column (\d+)\)')
+
+
+# ----------------------------------------------------------------------
+# Version requirements
+# ----------------------------------------------------------------------
+
+def parse_version(text: str) -> Optional[Tuple[int, int, int, str]]:
+    """Parse a semver version into (major, minor, patch, pre-release)."""
+    match = re.fullmatch(r'(\d+)\.(\d+)\.(\d+)(?:-([0-9A-Za-z.\-]+))?(?:\+[0-9A-Za-z.\-]+)?', text.strip())
+    if not match:
+        return None
+    return int(match.group(1)), int(match.group(2)), int(match.group(3)), match.group(4) or ""
+
+
+class VersionReq:
+    """
+    A Cargo version requirement such as `1.2`, `^0.4`, `~1.2.3`, `>=1, <2` or `1.*`.
+
+    Raises:
+        ValueError: If the requirement is not valid syntax
+    """
+
+    def __init__(self, text: str):
+        self.text = text
+        self.bounds: List[Tuple[Optional[tuple], Optional[tuple]]] = []
+
+        parts = [part.strip() for part in text.split(",")]
+        if not text.strip() or any(not part for part in parts):
+            raise ValueError(f"invalid version requirement `{text}`")
+
+        for part in parts:
+            self.bounds.append(self._comparator(part))
+
+    def matches(self, version: str) -> bool:
+        """Whether a version satisfies every comparator (pre-releases never match)."""
+        parsed = parse_version(version)
+        if parsed is None or parsed[3]:
+            return False
+        value = parsed[:3]
+        for low, high in self.bounds:
+            if low is not None and value < low:
+                return False
+            if high is not None and value >= high:
+                return False
+        return True
+
+    def _comparator(self, text: str) -> Tuple[Optional[tuple], Optional[tuple]]:
+        match = re.fullmatch(
+            r'(\^|~|=|>=|<=|>|<)?\s*(\*|\d+)(?:\.(\*|\d+))?(?:\.(\*|\d+))?(?:-[0-9A-Za-z.\-]+)?', text
+        )
+        if not match:
+            raise ValueError(f"invalid version requirement `{self.text}`")
+
+        op = match.group(1) or "^"
+        numbers = []
+        for group in match.group(2, 3, 4):
+            if group is None or group == "*":
+                break
+            numbers.append(int(group))
+
+        if len(numbers) < 3 and any(g == "*" for g in match.group(2, 3, 4)):
+            op = "="  # wildcards: 1.* == =1
+
+        if not numbers:
+            return None, None
+
+        full = tuple(numbers + [0] * (3 - len(numbers)))
+        major, minor, patch = full
+
+        def bump(n: int) -> tuple:
+            """Smallest version above every version sharing the first n parts."""
+            if n == 1:
+                return major + 1, 0, 0
+            if n == 2:
+                return major, minor + 1, 0
+            return major, minor, patch + 1
+
+        if op == "^":
+            if major > 0 or len(numbers) == 1:
+                return full, bump(1)
+            if minor > 0 or len(numbers) == 2:
+                return full, bump(2)
+            return full, bump(3)
+        if op == "~":
+            return full, bump(1 if len(numbers) == 1 else 2)
+        if op == "=":
+            return full, bump(len(numbers))
+        if op == ">=":
+            return full, None
+        if op == ">":
+            return bump(len(numbers)), None
+        if op == "<":
+            return None, full
+        return None, bump(len(numbers))  # <=
+
+
+# ----------------------------------------------------------------------
+# Registry index
+# ----------------------------------------------------------------------
+
+class CrateRegistry:
+    """
+    Read-only view of a local crates.io index mirror.
+
+    Each crate has a file of JSON lines (one per published version) at
+    `1/a`, `2/ab`, `3/a/abc` or `ab/cd/abcd...`, as in the upstream index.
+
+    Example:
+        registry = CrateRegistry("/var/lib/crates.io-index")
+        registry.latest("serde")  # {"vers": "1.0.210", "features": {...}, ...}
+    """
+
+    def __init__(self, index_path: str):
+        self.index_path = Path(index_path)
+        self._versions: Dict[str, List[Dict[str, Any]]] = {}
+        self._names: Optional[List[str]] = None
+
+    @staticmethod
+    def crate_path(name: str) -> str:
+        """Relative path of a crate's file in the index."""
+        name = name.lower()
+        if len(name) <= 2:
+            return f"{len(name)}/{name}"
+        if len(name) == 3:
+            return f"3/{name[0]}/{name}"
+        return f"{name[:2]}/{name[2:4]}/{name}"
+
+    def versions(self, name: str) -> List[Dict[str, Any]]:
+        """All published versions of a crate, oldest first."""
+        key = name.lower()
+        if key not in self._versions:
+            entries = []
+            path = self.index_path / self.crate_path(key)
+            try:
+                for line in path.read_text().splitlines():
+                    if line.strip():
+                        entries.append(json.loads(line))
+            except FileNotFoundError:
+                pass
+            except (OSError, json.JSONDecodeError) as e:
+                logger.warning(f"Could not read index entry for {name}: {e}")
+            self._versions[key] = entries
+        return self._versions[key]
+
+    def exists(self, name: str) -> bool:
+        """Whether the crate is published in the index."""
+        return bool(self.versions(name))
+
+    def matching(self, name: str, requirement: Optional[str] = None) -> List[Dict[str, Any]]:
+        """Non-yanked versions matching a requirement, newest first."""
+        req = None
+        if requirement:
+            try:
+                req = VersionReq(requirement)
+            except ValueError:
+                return []
+
+        found = []
+        for entry in self.versions(name):
+            if entry.get("yanked"):
+                continue
+            version = parse_version(entry.get("vers", ""))
+            if version is None:
+                continue
+            if req is not None and not req.matches(entry["vers"]):
+                continue
+            if req is None and version[3]:
+                continue
+            found.append((version[:3], entry))
+
+        return [entry for _, entry in sorted(found, key=lambda item: item[0], reverse=True)]
+
+    def latest(self, name: str, requirement: Optional[str] = None) -> Optional[Dict[str, Any]]:
+        """Newest non-yanked version matching a requirement."""
+        matching = self.matching(name, requirement)
+        return matching[0] if matching else None
+
+    def features(self, name: str, requirement: Optional[str] = None) -> List[str]:
+        """Features of the newest matching version, including implicit optional-dependency features."""
+        entry = self.latest(name, requirement)
+        if entry is None:
+            return []
+
+        features = dict(entry.get("features") or {})
+        features.update(entry.get("features2") or {})
+
+        explicit = {
+            value[4:] for values in features.values() for value in values
+            if value.startswith("dep:")
+        }
+        names = list(features)
+        for dep in entry.get("deps", []):
+            if dep.get("optional") and dep.get("name") not in explicit and dep.get("name") not in features:
+                names.append(dep["name"])
+        return sorted(names)
+
+    def search(self, prefix: str, limit: int = 50) -> List[str]:
+        """Crate names starting with a prefix."""
+        if self._names is None:
+            names = []
+            for directory, dirnames, filenames in os.walk(self.index_path):
+                dirnames[:] = [d for d in dirnames if not d.startswith(".")]
+                if directory == str(self.index_path):
+                    continue
+                names.extend(filenames)
+            self._names = sorted(names)
+
+        prefix = prefix.lower().replace("_", "-")
+        matches = [n for n in self._names if n.replace("_", "-").startswith(prefix)]
+        return sorted(matches, key=len)[:limit]
+
+
+# ----------------------------------------------------------------------
+# Manifest
+# ----------------------------------------------------------------------
+
+@dataclass
+class ManifestKey:
+    """A `key = value` line in the manifest (0-based position)."""
+    section: str
+    key: str
+    line: int
+    column: int
+
+
+class CargoManifest:
+    """
+    A parsed Cargo.toml with source positions for its sections and keys.
+
+    Example:
+        manifest = CargoManifest(content)
+        diagnostics = manifest.diagnostics(registry, used_crates={"serde": ("src/lib.rs", 0)})
+        completions = manifest.completions(line, character, registry)
+    """
+
+    def __init__(self, content: str):
+        self.content = content
+        self.lines = content.split("\n")
+        self.data: Dict[str, Any] = {}
+        self.error: Optional[Dict] = None
+
+        try:
+            self.data = tomllib.loads(content)
+        except tomllib.TOMLDecodeError as e:
+            line, column = 0, 0
+            match = TOML_ERROR_POSITION.search(str(e))
+            if match:
+                line, column = int(match.group(1)) - 1, int(match.group(2)) - 1
+            message = TOML_ERROR_POSITION.sub("", str(e)).strip()
+            self.error = self._diagnostic(line, column, len(self._line(line)) or column + 1,
+                                          f"invalid TOML: {message}", 1)
+
+        # Section headers and keys, by line
+        self.headers: List[Tuple[str, int]] = []
+        self.keys: List[ManifestKey] = []
+        section = ""
+        for number, text in enumerate(self.lines):
+            header = HEADER_PATTERN.match(text)
+            if header:
+                section = self._unquote_path(header.group(2))
+                self.headers.append((section, number))
+                continue
+            key = KEY_PATTERN.match(text)
+            if key:
+                self.keys.append(ManifestKey(
+                    section, self._unquote_path(key.group(1)), number, text.index(key.group(1)[0])
+                ))
+
+    # ------------------------------------------------------------------
+    # Lookups
+    # ------------------------------------------------------------------
+
+    def section_at(self, line: int) -> str:
+        """Header of the table containing a 0-based line ("" before any header)."""
+        section = ""
+        for name, number in self.headers:
+            if number > line:
+                break
+            section = name
+        return section
+
+    def key_position(self, section: str, key: str) -> Tuple[int, int]:
+        """0-based position of a key, falling back to its section header."""
+        for entry in self.keys:
+            if entry.section == section and (entry.key == key or entry.key.startswith(f"{key}.")):
+                return entry.line, entry.column
+        for name, number in self.headers:
+            if name in (f"{section}.{key}", section):
+                return number, 0
+        return 0, 0
+
+    def dependency_tables(self) -> List[Tuple[str, Dict[str, Any]]]:
+        """(section name, table) for every dependency table, including target-specific ones."""
+        tables = []
+        for name in sorted(DEPENDENCY_SECTIONS):
+            if isinstance(self.data.get(name), dict):
+                tables.append((name, self.data[name]))
+
+        for target, spec in (self.data.get("target") or {}).items():
+            if not isinstance(spec, dict):
+                continue
+            for name in sorted(DEPENDENCY_SECTIONS):
+                if isinstance(spec.get(name), dict):
+                    tables.append((f"target.{target}.{name}", spec[name]))
+
+        workspace_deps = (self.data.get("workspace") or {}).get("dependencies")
+        if isinstance(workspace_deps, dict):
+            tables.append(("workspace.dependencies", workspace_deps))
+        return tables
+
+    def dependency_names(self) -> set:
+        """Crate names as referenced in code (`-` becomes `_`), across all dependency tables."""
+        return {
+            name.replace("-", "_")
+            for section, table in self.dependency_tables()
+            if section != "workspace.dependencies"
+            for name in table
+        }
+
+    def optional_dependencies(self) -> set:
+        """Names of dependencies declared with `optional = true`."""
+        return {
+            name
+            for section, table in self.dependency_tables()
+            if section != "workspace.dependencies"
+            for name, spec in table.items()
+            if isinstance(spec, dict) and spec.get("optional") is True
+        }
+
+    # ------------------------------------------------------------------
+    # Diagnostics
+    # ------------------------------------------------------------------
+
+    def diagnostics(
+        self,
+        registry: Optional[CrateRegistry] = None,
+        used_crates: Optional[Dict[str, Tuple[str, int]]] = None
+    ) -> List[Dict]:
+        """
+        Validate the manifest.
+
+        Args:
+            registry: Local index mirror used to check crates, versions and features
+            used_crates: Crate names imported with `use`, mapped to (file, 0-based line)
+
+        Returns:
+            List of LSP diagnostics
+        """
+        if self.error:
+            return [self.error]
+
+        diagnostics = []
+
+        for name, number in self.headers:
+            root = name.split(".", 1)[0]
+            if root not in KNOWN_TABLES:
+                diagnostics.append(self._diagnostic(
+                    number, 0, len(self._line(number)), f"unknown manifest section `[{name}]`", 2
+                ))
+
+        for section, table in self.dependency_tables():
+            for name, spec in table.items():
+                diagnostics.extend(self._check_dependency(section, name, spec, registry))
+
+        diagnostics.extend(self._check_features(registry))
+
+        if used_crates:
+            diagnostics.extend(self._check_used_crates(used_crates))
+
+        return diagnostics
+
+    def _check_dependency(
+        self,
+        section: str,
+        name: str,
+        spec: Any,
+        registry: Optional[CrateRegistry]
+    ) -> List[Dict]:
+        line, column = self.key_position(section, name)
+        found = []
+
+        def report(message: str, severity: int = 1):
+            found.append(self._diagnostic(line, column, len(self._line(line)), message, severity))
+
+        if isinstance(spec, str):
+            spec = {"version": spec}
+        elif not isinstance(spec, dict):
+            report(f"dependency `{name}` must be a version string or a table")
+            return found
+
+        unknown = [key for key in spec if key not in DEPENDENCY_KEYS and key != "default_features"]
+        for key in unknown:
+            report(f"unknown key `{key}` in dependency `{name}`", 2)
+
+        sources = [key for key in ("version", "path", "git", "workspace", "registry") if key in spec]
+        if not sources:
+            report(f"dependency `{name}` specifies no source (version, path, git or workspace)")
+
+        if spec.get("workspace") is True and section == "workspace.dependencies":
+            report(f"`{name}.workspace = true` is not allowed in [workspace.dependencies]")
+
+        git_refs = [key for key in ("branch", "tag", "rev") if key in spec]
+        if len(git_refs) > 1:
+            report(f"dependency `{name}` specifies more than one of branch, tag and rev")
+        if git_refs and "git" not in spec:
+            report(f"`{git_refs[0]}` in dependency `{name}` requires `git`")
+
+        if "optional" in spec and not isinstance(spec["optional"], bool):
+            report(f"`optional` in dependency `{name}` must be true or false")
+
+        features = spec.get("features", [])
+        if not isinstance(features, list) or not all(isinstance(f, str) for f in features):
+            report(f"`features` in dependency `{name}` must be an array of strings")
+            features = []
+
+        requirement = spec.get("version")
+        if requirement is not None:
+            if not isinstance(requirement, str):
+                report(f"`version` in dependency `{name}` must be a string")
+                requirement = None
+            else:
+                try:
+                    VersionReq(requirement)
+                except ValueError as e:
+                    report(str(e))
+                    requirement = None
+
+        from_registry = not any(key in spec for key in ("path", "git", "workspace", "registry"))
+        if registry is None or not from_registry or found:
+            return found
+
+        crate = spec.get("package", name)
+        if not registry.exists(crate):
+            report(f"crate `{crate}` not found in the registry index")
+            return found
+
+        if requirement and registry.latest(crate, requirement) is None:
+            latest = registry.latest(crate)
+            hint = f" (latest is {latest['vers']})" if latest else ""
+            report(f"no version of `{crate}` matches `{requirement}`{hint}")
+            return found
+
+        available = set(registry.features(crate, requirement))
+        for feature in features:
+            if feature not in available:
+                report(f"crate `{crate}` has no feature `{feature}`")
+
+        return found
+
+    def _check_features(self, registry: Optional[CrateRegistry]) -> List[Dict]:
+        features = self.data.get("features")
+        if not isinstance(features, dict):
+            return []
+
+        dependencies = {
+            name: spec
+            for section, table in self.dependency_tables()
+            if section != "workspace.dependencies"
+            for name, spec in table.items()
+        }
+        optional = self.optional_dependencies()
+        found = []
+
+        for feature, values in features.items():
+            line, column = self.key_position("features", feature)
+            if not isinstance(values, list):
+                found.append(self._diagnostic(line, column, len(self._line(line)),
+                                              f"feature `{feature}` must be an array", 1))
+                continue
+
+            for value in values:
+                message = self._check_feature_value(value, features, dependencies, optional, registry)
+                if message:
+                    ref_line, ref_column = self._string_position(f'"{value}"', line)
+                    found.append(self._diagnostic(
+                        ref_line, ref_column, ref_column + len(value) + 2,
+                        f"feature `{feature}`: {message}", 1
+                    ))
+
+        return found
+
+    def _check_feature_value(
+        self,
+        value: Any,
+        features: Dict[str, Any],
+        dependencies: Dict[str, Any],
+        optional: set,
+        registry: Optional[CrateRegistry]
+    ) -> Optional[str]:
+        """Explain what is wrong with one feature entry, or None if it is valid."""
+        if not isinstance(value, str):
+            return "entries must be strings"
+
+        if value.startswith("dep:"):
+            dep = value[4:]
+            if dep not in dependencies:
+                return f"`{value}` refers to unknown dependency `{dep}`"
+            if dep not in optional:
+                return f"`{value}` refers to `{dep}`, which is not optional"
+            return None
+
+        if "/" in value:
+            dep, dep_feature = value.split("/", 1)
+            weak = dep.endswith("?")
+            dep = dep.rstrip("?")
+            if dep not in dependencies:
+                return f"`{value}` refers to unknown dependency `{dep}`"
+            if weak and dep not in optional:
+                return f"`{value}` uses `?` but `{dep}` is not optional"
+
+            spec = dependencies[dep]
+            spec = {"version": spec} if isinstance(spec, str) else spec
+            if registry is not None and isinstance(spec, dict) \
+                    and not any(key in spec for key in ("path", "git", "workspace", "registry")):
+                crate = spec.get("package", dep)
+                available = registry.features(crate, spec.get("version"))
+                if available and dep_feature not in available:
+                    return f"crate `{crate}` has no feature `{dep_feature}`"
+            return None
+
+        if value not in features and value not in optional:
+            return f"unknown feature or optional dependency `{value}`"
+        return None
+
+    def _check_used_crates(self, used_crates: Dict[str, Tuple[str, int]]) -> List[Dict]:
+        declared = self.dependency_names()
+        package = (self.data.get("package") or {}).get("name", "")
+        lib = (self.data.get("lib") or {}).get("name", package)
+        own = {package.replace("-", "_"), str(lib).replace("-", "_")}
+
+        line = next((number for name, number in self.headers if name == "dependencies"), 0)
+        found = []
+        for crate, (file_path, use_line) in sorted(used_crates.items()):
+            if crate in declared or crate in own or crate in BUILTIN_CRATES:
+                continue
+            found.append(self._diagnostic(
+                line, 0, len(self._line(line)),
+                f"crate `{crate}` is used in {file_path}:{use_line + 1} but is not listed in [dependencies]",
+                2
+            ))
+        return found
+
+    # ------------------------------------------------------------------
+    # Completions
+    # ------------------------------------------------------------------
+
+    def completions(self, line: int, character: int, registry: Optional[CrateRegistry] = None) -> List[Dict]:
+        """
+        Completions at a 0-based position.
+
+        Offers section names in headers, table keys, crate names in
+        dependency tables and versions/features from the registry index.
+        """
+        text = self._line(line)[:character]
+        stripped = text.lstrip()
+
+        if stripped.startswith("["):
+            partial = stripped.lstrip("[").strip()
+            names = ARRAY_SECTIONS if stripped.startswith("[[") else TABLE_SECTIONS
+            return [
+                self._item(name, KIND_MODULE, "Cargo.toml section")
+                for name in names if name.startswith(partial)
+            ]
+
+        section = self.section_at(line)
+        root = section.split(".")
+        is_dependency_table = root[-1] in DEPENDENCY_SECTIONS or (len(root) > 1 and root[-2] in DEPENDENCY_SECTIONS)
+
+        # Inside a dependency spec: name = "...", name = { ... }, or [dependencies.name]
+        if root[-1] in DEPENDENCY_SECTIONS:
+            spec = re.match(r'^\s*([\w\-]+)\s*=\s*(.*)$', text)
+            if spec:
+                return self._dependency_value_completions(spec.group(1), spec.group(2), registry)
+            partial = re.search(r'([\w\-]*)$', text).group(1)
+            if registry is None or not partial:
+                return []
+            return [
+                self._item(name, KIND_MODULE, self._latest_detail(registry, name))
+                for name in registry.search(partial)
+            ]
+
+        if is_dependency_table:
+            crate = root[-1]
+            value = re.match(r'^\s*([\w\-]+)\s*=\s*(.*)$', text)
+            if value:
+                return self._dependency_value_completions(crate, f"{{ {value.group(1)} = {value.group(2)}", registry)
+            return self._key_items(DEPENDENCY_KEYS, text, section)
+
+        if section == "features":
+            if "=" not in text or not re.search(r'"[^"]*$', text):
+                return []
+            partial = re.search(r'"([^"]*)$', text).group(1)
+            # Read from the source lines: the array being typed is not valid TOML yet
+            options = [entry.key for entry in self.keys if entry.section == "features" and entry.line != line]
+            options += [
+                f"dep:{entry.key.split('.', 1)[0]}" for entry in self.keys
+                if entry.section.split(".")[-1] in DEPENDENCY_SECTIONS
+                and re.search(r'\boptional\s*=\s*true\b', self._line(entry.line))
+            ]
+            return [self._item(name, KIND_ENUM_MEMBER, "feature") for name in options if name.startswith(partial)]
+
+        if "=" in text:
+            key = text.split("=", 1)[0].strip()
+            if section in ("package", "workspace.package") and key == "edition":
+                return [self._item(e, KIND_VALUE, "edition") for e in EDITIONS]
+            if section == "workspace" and key == "resolver":
+                return [self._item(r, KIND_VALUE, "resolver") for r in ("1", "2", "3")]
+            return []
+
+        keys = {
+            "package": PACKAGE_KEYS,
+            "workspace.package": PACKAGE_KEYS,
+            "workspace": WORKSPACE_KEYS,
+            "lib": TARGET_KEYS,
+            "bin": TARGET_KEYS,
+            "example": TARGET_KEYS,
+            "test": TARGET_KEYS,
+            "bench": TARGET_KEYS,
+        }.get(section)
+        if keys is None and section.startswith("profile."):
+            keys = PROFILE_KEYS
+        return self._key_items(keys or [], text, section)
+
+    def _dependency_value_completions(
+        self,
+        crate: str,
+        value: str,
+        registry: Optional[CrateRegistry]
+    ) -> List[Dict]:
+        """Completions after `crate = ` (version strings, inline table keys, features)."""
+        if registry is None and not value.lstrip().startswith("{"):
+            return []
+
+        # serde = "1.   or   version = "1.
+        if re.fullmatch(r'\s*"[^"]*', value) or re.search(r'\bversion\s*=\s*"[^"]*$', value):
+            if registry is None:
+                return []
+            versions = [entry["vers"] for entry in registry.matching(crate)][:20]
+            return [
+                self._item(version, KIND_VALUE, f"{crate} {version}", sort=f"{i:04d}")
+                for i, version in enumerate(versions)
+            ]
+
+        # features = ["derive", "
+        features = re.search(r'\bfeatures\s*=\s*\[([^\]]*)$', value)
+        if features:
+            if registry is None or not re.search(r'"[^"]*$', features.group(1)):
+                return []
+            present = set(re.findall(r'"([^"]*)"', features.group(1)))
+            version = re.search(r'\bversion\s*=\s*"([^"]*)"', value)
+            available = registry.features(crate, version.group(1) if version else None)
+            return [self._item(f, KIND_ENUM_MEMBER, f"{crate} feature") for f in available if f not in present]
+
+        # { version = "1", |
+        if value.lstrip().startswith("{"):
+            inner = value.rsplit(",", 1)[-1].lstrip("{ ")
+            if "=" in inner:
+                return []
+            present = set(re.findall(r'([\w\-]+)\s*=', value))
+            return self._key_items([k for k in DEPENDENCY_KEYS if k not in present], inner, None)
+
+        return []
+
+    # ------------------------------------------------------------------
+    # Helpers
+    # ------------------------------------------------------------------
+
+    def _key_items(self, keys: Iterable[str], text: str, section: Optional[str]) -> List[Dict]:
+        """Keys starting with the partial word, skipping those already set in the section."""
+        partial = re.search(r'([\w\-]*)$', text).group(1)
+        defined = {entry.key.split(".", 1)[0] for entry in self.keys if entry.section == section}
+        return [
+            self._item(key, KIND_PROPERTY, "key", insert=f"{key} = ")
+            for key in keys if key.startswith(partial) and (key == partial or key not in defined)
+        ]
+
+    def _latest_detail(self, registry: CrateRegistry, name: str) -> str:
+        latest = registry.latest(name)
+        return f"{name} {latest['vers']}" if latest else name
+
+    @staticmethod
+    def _item(label: str, kind: int, detail: str, insert: Optional[str] = None, sort: Optional[str] = None) -> Dict:
+        item = {"label": label, "kind": kind, "detail": detail, "insertText": insert or label}
+        if sort:
+            item["sortText"] = sort
+        return item
+
+    def _line(self, line: int) -> str:
+        return self.lines[line] if 0 <= line < len(self.lines) else ""
+
+    def _string_position(self, literal: str, start: int) -> Tuple[int, int]:
+        """Position of a string literal at or after a 0-based line (for multi-line arrays)."""
+        for number in range(start, len(self.lines)):
+            column = self.lines[number].find(literal)
+            if column >= 0:
+                return number, column
+            if number > start and (HEADER_PATTERN.match(self.lines[number]) or KEY_PATTERN.match(self.lines[number])):
+                break
+        return start, 0
+
+    @staticmethod
+    def _unquote_path(path: str) -> str:
+        """Normalize a dotted TOML key: `"a" . b` -> `a.b`."""
+        parts = re.findall(r'"([^"]*)"|\'([^\']*)\'|([^.\s"\']+)', path)
+        return ".".join(next(p for p in part if p) for part in parts)
+
+    @staticmethod
+    def _diagnostic(line: int, start: int, end: int, message: str, severity: int) -> Dict:
+        return {
+            "range": {
+                "start": {"line": line, "character": start},
+                "end": {"line": line, "character": max(end, start)}
+            },
+            "severity": severity,
+            "message": message,
+            "source": "cargo"
+        }
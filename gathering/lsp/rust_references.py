@@ -0,0 +1,220 @@
+"""
+Rust References and Rename.
+
+Finds the references to an indexed Rust symbol across a workspace and
+builds rename WorkspaceEdits for the fallback Rust LSP server. Every
+occurrence of the symbol's name is resolved with the server's own path
+resolution (module scoping, `use` imports, aliases and re-exports), so
+same-named items in other modules are left untouched.
+
+Trait methods are found and renamed together with their implementations,
+and struct field shorthands (`Point { x }`) are expanded on rename.
+"""
+
+import logging
+import re
+from dataclasses import dataclass
+from typing import Optional, List, Dict, Callable, Set, Tuple
+
+from gathering.lsp.rust_code_actions import text_edit
+from gathering.lsp.rust_lexer import RUST_KEYWORDS, RustLexer, Token, TokenKind
+from gathering.lsp.rust_symbols import RustSymbol, RustSymbolIndex
+
+logger = logging.getLogger(__name__)
+
+IDENTIFIER = re.compile(r"[A-Za-z_][A-Za-z0-9_]*")
+
+# Resolves the identifier at a token index: (file_path, content, tokens, index) -> symbol
+TokenResolver = Callable[[str, str, List[Token], int], Optional[RustSymbol]]
+
+
+def symbol_key(symbol: RustSymbol) -> Tuple[str, int, int]:
+    """Identity of an indexed symbol (where its name is declared)."""
+    return symbol.file, symbol.line, symbol.column
+
+
+@dataclass
+class RustReference:
+    """An occurrence of a symbol in Rust source."""
+    file: str
+    token: Token
+    declaration: bool = False
+    shorthand: bool = False  # field shorthand, e.g. `Point { x }`
+
+    def location(self, workspace_uri: Callable[[str], str]) -> Dict:
+        """LSP Location of the occurrence."""
+        return {
+            "uri": workspace_uri(self.file),
+            "range": {
+                "start": {"line": self.token.line, "character": self.token.column},
+                "end": {"line": self.token.line, "character": self.token.column + len(self.token.text)}
+            }
+        }
+
+
+class RustReferences:
+    """
+    Workspace-wide reference search and rename over the symbol index.
+
+    Example:
+        finder = RustReferences(index, server._resolve_token)
+        refs = finder.references(symbol)
+        edit = finder.rename(symbol, "new_name")
+    """
+
+    def __init__(self, index: RustSymbolIndex, resolve: TokenResolver):
+        self.index = index
+        self.resolve = resolve
+
+    def uri(self, file_path: str) -> str:
+        """File URI for a workspace-relative path."""
+        return (self.index.workspace_path / file_path).absolute().as_uri()
+
+    def related(self, symbol: RustSymbol) -> List[RustSymbol]:
+        """
+        Symbols that must be renamed together: a trait method and every
+        implementation of it, or just the symbol itself.
+        """
+        if symbol.kind != "method":
+            return [symbol]
+
+        trait = self._trait_of(symbol)
+        if trait is None:
+            return [symbol]
+
+        related = [
+            method for method in self.index.lookup(symbol.name, {"method"})
+            if self._trait_of(method) == trait
+        ]
+        return related or [symbol]
+
+    def _trait_of(self, method: RustSymbol) -> Optional[str]:
+        """Trait declaring a method, or implemented by the impl block containing it."""
+        for block in self.index.file_symbols(method.file):
+            if block.kind in ("impl", "trait") and block.start_line <= method.line <= block.end_line:
+                return block.name if block.kind == "trait" else block.trait
+        return None
+
+    def _aliases(self, keys: Set[Tuple[str, int, int]]) -> Set[str]:
+        """Local names given to the symbols by `use ... as alias` imports."""
+        aliases = set()
+        for file_path in self.index.files():
+            for imported in self.index.imports(file_path):
+                if imported.alias == "*":
+                    continue
+                target = self.index.resolve(imported.path.split("::"), file_path, imported.module)
+                if target is not None and symbol_key(target) in keys and imported.alias != target.name:
+                    aliases.add(imported.alias)
+        return aliases
+
+    def references(
+        self,
+        symbol: RustSymbol,
+        documents: Optional[Dict[str, str]] = None,
+        include_declaration: bool = True
+    ) -> List[RustReference]:
+        """
+        Find every occurrence resolving to a symbol (or its related symbols).
+
+        Args:
+            symbol: Target symbol
+            documents: Unsaved buffer contents by workspace-relative path
+            include_declaration: Include the declaration(s) themselves
+        """
+        documents = documents or {}
+        targets = self.related(symbol)
+        keys = {symbol_key(target) for target in targets}
+        names = {target.name for target in targets} | self._aliases(keys)
+
+        references = []
+        for file_path in sorted(self.index.files()):
+            content = documents.get(file_path)
+            if content is None:
+                try:
+                    content = (self.index.workspace_path / file_path).read_text()
+                except (OSError, UnicodeDecodeError):
+                    continue
+            if not any(name in content for name in names):
+                continue
+
+            tokens = RustLexer(content).tokenize()
+            for i, token in enumerate(tokens):
+                if token.kind != TokenKind.IDENT or token.text not in names:
+                    continue
+
+                resolved = self.resolve(file_path, content, tokens, i)
+                if resolved is None or symbol_key(resolved) not in keys:
+                    continue
+
+                declaration = (file_path, token.line, token.column) in keys
+                if declaration and not include_declaration:
+                    continue
+
+                references.append(RustReference(
+                    file=file_path,
+                    token=token,
+                    declaration=declaration,
+                    shorthand=resolved.kind == "field" and not declaration and self._is_shorthand(tokens, i)
+                ))
+
+        return references
+
+    @staticmethod
+    def _is_shorthand(tokens: List[Token], i: int) -> bool:
+        """Whether a field name stands alone in a struct literal or pattern."""
+        previous = tokens[i - 1].text if i > 0 else ""
+        following = tokens[i + 1].text if i + 1 < len(tokens) else ""
+        return previous in ("{", ",") and following in (",", "}")
+
+    def rename(
+        self,
+        symbol: RustSymbol,
+        new_name: str,
+        documents: Optional[Dict[str, str]] = None
+    ) -> Dict:
+        """
+        Build a WorkspaceEdit renaming a symbol everywhere it is referenced.
+
+        Occurrences through `use ... as alias` keep the alias; only the
+        imported path is renamed.
+
+        Raises:
+            ValueError: If the new name is invalid or already taken, or the
+                symbol cannot be renamed (impl blocks, file modules)
+        """
+        if not IDENTIFIER.fullmatch(new_name) or new_name in RUST_KEYWORDS:
+            raise ValueError(f"`{new_name}` is not a valid Rust identifier")
+        if symbol.kind == "impl":
+            raise ValueError("impl blocks cannot be renamed")
+        if symbol.kind == "module" and self.index.module_files(symbol.path):
+            raise ValueError(f"Renaming file module `{symbol.path}` would require moving files")
+
+        targets = self.related(symbol)
+        for target in targets:
+            self._check_conflict(target, new_name)
+
+        old_names = {target.name for target in targets}
+        changes: Dict[str, List[Dict]] = {}
+        for reference in self.references(symbol, documents):
+            token = reference.token
+            if token.text not in old_names:
+                continue  # an alias of the symbol
+            new_text = f"{new_name}: {token.text}" if reference.shorthand else new_name
+            changes.setdefault(self.uri(reference.file), []).append(text_edit(
+                (token.line, token.column), (token.line, token.column + len(token.text)), new_text
+            ))
+
+        return {"changes": changes}
+
+    def _check_conflict(self, symbol: RustSymbol, new_name: str):
+        """Reject a rename onto a name already declared in the same scope."""
+        if symbol.container:
+            siblings = self.index.members(symbol.container)
+            scope = f"{symbol.module}::{symbol.container}"
+        else:
+            siblings = self.index.items(new_name, symbol.module)
+            scope = symbol.module
+
+        for sibling in siblings:
+            if sibling.name == new_name and symbol_key(sibling) != symbol_key(symbol):
+                raise ValueError(f"`{new_name}` is already defined in `{scope}`")
@@ -0,0 +1,916 @@
+"""
+Rust Symbol Index.
+
+Indexes the items declared in a crate's `.rs` files (functions, structs,
+enums, traits, impl methods, fields, consts and modules) using the token
+stream from the Rust lexer. The index powers symbol-aware completions in
+the fallback Rust LSP server and is updated file-by-file as the
+workspace changes.
+"""
+
+import logging
+import os
+import re
+import tomllib
+from dataclasses import dataclass
+from typing import Optional, List, Dict, Iterator
+from pathlib import Path
+
+from gathering.lsp.rust_lexer import RustLexer, Token, TokenKind
+from gathering.lsp import symbols as lsp_symbols
+
+logger = logging.getLogger(__name__)
+
+
+# Symbol kinds that can be named from outside their container
+ITEM_KINDS = {"function", "struct", "enum", "trait", "const", "static", "module", "type", "macro"}
+
+# Symbol kinds reachable as members of a type (via `.` or `::`)
+MEMBER_KINDS = {"method", "field", "variant", "const", "type"}
+
+# Directories never indexed
+IGNORED_DIRS = {"target", ".git", "node_modules"}
+
+# Indexed symbol kind -> LSP SymbolKind name
+OUTLINE_KINDS = {
+    "function": "function",
+    "method": "method",
+    "field": "field",
+    "variant": "enum_member",
+    "struct": "struct",
+    "enum": "enum",
+    "trait": "interface",
+    "const": "constant",
+    "static": "constant",
+    "module": "module",
+    "type": "type_parameter",
+    "macro": "function",
+    "impl": "object",
+}
+
+# Keywords that may prefix an item declaration
+ITEM_PREFIXES = {"pub", "async", "unsafe", "extern", "default"}
+
+
+@dataclass
+class RustSymbol:
+    """An item declared in Rust source (positions are 0-based)."""
+    name: str
+    kind: str
+    file: str
+    line: int
+    column: int
+    start_line: int
+    end_line: int
+    module: str = "crate"
+    container: Optional[str] = None
+    signature: str = ""
+    doc: str = ""
+    is_pub: bool = False
+    trait: Optional[str] = None  # for impl blocks: implemented trait
+
+    @property
+    def display_name(self) -> str:
+        """Name as shown in outlines, e.g. `impl Display for Circle` or `vec!`."""
+        if self.kind == "impl":
+            return f"impl {self.trait} for {self.name}" if self.trait else f"impl {self.name}"
+        if self.kind == "macro":
+            return f"{self.name}!"
+        return self.name
+
+    @property
+    def path(self) -> str:
+        """Fully qualified path, e.g. crate::shapes::Circle::area."""
+        parts = [self.module]
+        if self.container:
+            parts.append(self.container)
+        parts.append(self.name)
+        return "::".join(parts)
+
+    def to_dict(self) -> Dict:
+        """Convert symbol to dictionary."""
+        return {
+            "name": self.name,
+            "kind": self.kind,
+            "file": self.file,
+            "line": self.line,
+            "column": self.column,
+            "start_line": self.start_line,
+            "end_line": self.end_line,
+            "module": self.module,
+            "container": self.container,
+            "signature": self.signature,
+            "doc": self.doc,
+            "is_pub": self.is_pub,
+        }
+
+
+@dataclass
+class RustImport:
+    """A name brought into scope by a `use` declaration."""
+    alias: str  # local name ("*" for glob imports)
+    path: str   # full path as written, e.g. crate::shapes::Circle
+    file: str
+    line: int
+    column: int
+    module: str = "crate"
+
+
+@dataclass
+class _Scope:
+    """An open brace-delimited body while parsing."""
+    kind: str  # impl, trait, struct, enum, mod, fn, block
+    name: Optional[str] = None
+    symbol: Optional[RustSymbol] = None
+    parent_module: Optional[str] = None
+    nest: int = 0  # parenthesis/bracket depth inside this body
+
+
+class _SymbolParser:
+    """Single-pass item parser over the lexer's token stream."""
+
+    def __init__(self, source: str, file_path: str, module: str):
+        self.source = source
+        self.file_path = file_path
+        self.module = module
+        self.tokens = RustLexer(source).tokenize()
+        self.symbols: List[RustSymbol] = []
+        self.imports: List[RustImport] = []
+
+    def parse(self) -> List[RustSymbol]:
+        tokens = self.tokens
+        stack: List[_Scope] = []
+        docs: List[str] = []
+        item_start: Optional[Token] = None
+        is_pub = False
+        i = 0
+
+        def reset():
+            nonlocal docs, item_start, is_pub
+            docs, item_start, is_pub = [], None, False
+
+        while i < len(tokens):
+            tok = tokens[i]
+            top = stack[-1] if stack else None
+
+            if tok.kind == TokenKind.DOC_COMMENT:
+                if tok.text.startswith(("///", "/**")):
+                    docs.append(self._doc_text(tok.text))
+                i += 1
+                continue
+            if tok.is_comment or tok.kind == TokenKind.ATTRIBUTE:
+                i += 1
+                continue
+
+            text = tok.text
+
+            # Fields and enum variants at the top level of a body
+            if top and top.nest == 0 and top.kind in ("struct", "enum") and tok.kind == TokenKind.IDENT:
+                nxt = tokens[i + 1].text if i + 1 < len(tokens) else ""
+                prev = self._prev_code(i)
+                if top.kind == "struct" and nxt == ":":
+                    self._add(tok, "field", top.name, docs, is_pub,
+                              self._until(i, (",", "}")), item_start or tok, tok.line)
+                    reset()
+                    i += 1
+                    continue
+                if top.kind == "enum" and prev in ("{", ","):
+                    self._add(tok, "variant", top.name, docs, True,
+                              self._until(i, (",", "}")), tok, tok.line)
+                    reset()
+                    i += 1
+                    continue
+
+            if text in ("(", "[") and top:
+                top.nest += 1
+            elif text in (")", "]") and top:
+                top.nest -= 1
+
+            if tok.kind == TokenKind.KEYWORD and text == "pub":
+                item_start = item_start or tok
+                is_pub = True
+                # Skip pub(crate), pub(super), pub(in path)
+                if i + 1 < len(tokens) and tokens[i + 1].text == "(":
+                    i = self._matching(i + 1) + 1
+                    continue
+                i += 1
+                continue
+
+            if text in ITEM_PREFIXES or (text == "const" and self._next_text(i) in ("fn", "unsafe", "async", "extern")):
+                item_start = item_start or tok
+                i += 1
+                # extern "C"
+                if i < len(tokens) and tokens[i].kind == TokenKind.STRING:
+                    i += 1
+                continue
+
+            if tok.kind == TokenKind.KEYWORD and text in ("fn", "struct", "enum", "trait", "mod", "impl", "type", "const", "static") \
+                    or (text == "union" and self._next_kind(i) == TokenKind.IDENT) \
+                    or (text == "macro_rules" and self._next_text(i) == "!"):
+                i = self._item(i, stack, docs, item_start or tok, is_pub)
+                reset()
+                continue
+
+            if tok.kind == TokenKind.KEYWORD and text == "use":
+                end = self._statement_end(i)
+                self._use_tree(i + 1, end, [])
+                reset()
+                i = end + 1
+                continue
+
+            if text == "{":
+                stack.append(_Scope("block"))
+            elif text == "}":
+                if stack:
+                    scope = stack.pop()
+                    if scope.symbol:
+                        scope.symbol.end_line = tok.line
+                    if scope.kind == "mod":
+                        self.module = scope.parent_module
+                reset()
+            elif text in (";", ",") or tok.kind != TokenKind.KEYWORD:
+                reset()
+
+            i += 1
+
+        return self.symbols
+
+    def _item(self, i: int, stack: List[_Scope], docs: List[str], start: Token, is_pub: bool) -> int:
+        """Parse an item starting at keyword index i; returns the next index."""
+        tokens = self.tokens
+        keyword = tokens[i].text
+        top = stack[-1] if stack else None
+
+        if keyword == "impl":
+            end = self._header_end(i)
+            type_name, trait_name = self._impl_target(i + 1, end)
+            symbol = self._add(tokens[i], "impl", None, docs, False,
+                               self._slice(start, end), start, tokens[min(end, len(tokens) - 1)].line,
+                               name=type_name or "?")
+            symbol.trait = trait_name
+            if end < len(tokens) and tokens[end].text == "{":
+                stack.append(_Scope("impl", type_name, symbol))
+            return end + 1
+
+        name_index = i + 1
+        if keyword == "macro_rules":
+            name_index = i + 2
+        elif keyword == "static" and self._next_text(i) == "mut":
+            name_index = i + 2
+
+        if name_index >= len(tokens) or tokens[name_index].kind != TokenKind.IDENT:
+            # e.g. `const _: () = ...;` or a stray keyword
+            return self._statement_end(i) + 1 if keyword in ("const", "static", "type") else i + 1
+
+        name_tok = tokens[name_index]
+
+        if keyword in ("const", "static", "type"):
+            end = self._statement_end(i)
+        else:
+            end = self._header_end(name_index)
+
+        kind = {
+            "fn": "function",
+            "struct": "struct",
+            "union": "struct",
+            "enum": "enum",
+            "trait": "trait",
+            "mod": "module",
+            "type": "type",
+            "const": "const",
+            "static": "static",
+            "macro_rules": "macro",
+        }[keyword]
+
+        container = None
+        if top and top.kind in ("impl", "trait"):
+            container = top.name
+            if kind == "function":
+                kind = "method"
+
+        if kind in ("const", "static"):
+            signature = self._const_signature(start, end)
+        else:
+            signature = self._slice(start, end)
+        end_line = tokens[min(end, len(tokens) - 1)].line
+        symbol = self._add(name_tok, kind, container, docs, is_pub or (top is not None and top.kind == "trait"),
+                           signature, start, end_line)
+
+        if end < len(tokens) and tokens[end].text == "{" and keyword not in ("const", "static", "type"):
+            scope_kind = {"function": "fn", "method": "fn", "module": "mod", "macro": "block"}.get(kind, kind)
+            stack.append(_Scope(scope_kind, name_tok.text, symbol, parent_module=self.module))
+            if kind == "module":
+                self.module = f"{self.module}::{name_tok.text}"
+        return end + 1
+
+    def _use_tree(self, i: int, end: int, prefix: List[str]) -> int:
+        """
+        Expand a use tree (tokens i..end) into imports; returns the next index.
+
+        Handles paths, `{a, b::c}` groups, `self`, `as` aliases and globs.
+        """
+        tokens = self.tokens
+        segments = list(prefix)
+        last: Optional[Token] = None
+
+        while i < end:
+            text = tokens[i].text
+            if tokens[i].kind in (TokenKind.IDENT, TokenKind.KEYWORD) and text != "as":
+                if text == "self" and segments and segments == prefix:
+                    # `use a::{self}` imports the module itself
+                    last = tokens[i]
+                else:
+                    segments.append(text)
+                    last = tokens[i]
+                i += 1
+            elif text == "::":
+                i += 1
+            elif text == "*":
+                self._import("*", segments, tokens[i])
+                return i + 1
+            elif text == "{":
+                close = self._matching(i)
+                j = i + 1
+                while j < close:
+                    j = self._use_tree(j, close, segments)
+                    if j < close and tokens[j].text == ",":
+                        j += 1
+                return close + 1
+            elif text == "as":
+                alias = tokens[i + 1] if i + 1 < end else None
+                if alias is not None and alias.text != "_":
+                    self._import(alias.text, segments, alias)
+                return i + 2
+            elif text == ",":
+                break
+            else:
+                i += 1
+
+        if last is not None and segments:
+            self._import(segments[-1], segments, last)
+        return i
+
+    def _import(self, alias: str, segments: List[str], tok: Token):
+        self.imports.append(RustImport(
+            alias=alias,
+            path="::".join(segments),
+            file=self.file_path,
+            line=tok.line,
+            column=tok.column,
+            module=self.module,
+        ))
+
+    def _add(self, tok: Token, kind: str, container: Optional[str], docs: List[str], is_pub: bool,
+             signature: str, start: Token, end_line: int, name: Optional[str] = None) -> RustSymbol:
+        symbol = RustSymbol(
+            name=name or tok.text,
+            kind=kind,
+            file=self.file_path,
+            line=tok.line,
+            column=tok.column,
+            start_line=start.line,
+            end_line=end_line,
+            module=self.module,
+            container=container,
+            signature=signature,
+            doc="\n".join(docs),
+            is_pub=is_pub,
+        )
+        self.symbols.append(symbol)
+        return symbol
+
+    # ------------------------------------------------------------------
+    # Token helpers
+    # ------------------------------------------------------------------
+
+    def _next_text(self, i: int) -> str:
+        return self.tokens[i + 1].text if i + 1 < len(self.tokens) else ""
+
+    def _next_kind(self, i: int) -> Optional[TokenKind]:
+        return self.tokens[i + 1].kind if i + 1 < len(self.tokens) else None
+
+    def _prev_code(self, i: int) -> str:
+        j = i - 1
+        while j >= 0 and (self.tokens[j].is_comment or self.tokens[j].kind == TokenKind.ATTRIBUTE
+                          or self.tokens[j].text == "pub"):
+            j -= 1
+        return self.tokens[j].text if j >= 0 else ""
+
+    def _matching(self, i: int) -> int:
+        """Index of the bracket closing the one at index i."""
+        pairs = {"(": ")", "[": "]", "{": "}"}
+        opening = self.tokens[i].text
+        closing = pairs[opening]
+        depth = 0
+        for j in range(i, len(self.tokens)):
+            text = self.tokens[j].text
+            if text == opening:
+                depth += 1
+            elif text == closing:
+                depth -= 1
+                if depth == 0:
+                    return j
+        return len(self.tokens) - 1
+
+    def _header_end(self, i: int) -> int:
+        """Index of the `{` or `;` ending an item header (outside parens)."""
+        nest = 0
+        for j in range(i, len(self.tokens)):
+            text = self.tokens[j].text
+            if text in ("(", "["):
+                nest += 1
+            elif text in (")", "]"):
+                nest -= 1
+            elif nest == 0 and text in ("{", ";"):
+                return j
+            elif nest == 0 and text == "}":
+                return j - 1
+        return len(self.tokens)
+
+    def _statement_end(self, i: int) -> int:
+        """Index of the `;` ending a statement, skipping nested brackets."""
+        nest = 0
+        for j in range(i, len(self.tokens)):
+            text = self.tokens[j].text
+            if text in ("(", "[", "{"):
+                nest += 1
+            elif text in (")", "]", "}"):
+                nest -= 1
+                if nest < 0:
+                    return j - 1
+            elif nest == 0 and text == ";":
+                return j
+        return len(self.tokens)
+
+    def _until(self, i: int, stops: tuple) -> str:
+        """Source text from token i up to a stop token at nesting depth 0."""
+        nest = 0
+        for j in range(i, len(self.tokens)):
+            text = self.tokens[j].text
+            if text in ("(", "[", "{", "<"):
+                nest += 1
+            elif text in (")", "]", ">"):
+                nest -= 1
+            elif text == ">>":
+                nest -= 2
+            elif text == "}" and nest > 0:
+                nest -= 1
+            elif nest <= 0 and text in stops:
+                return self._slice(self.tokens[i], j)
+        return self._slice(self.tokens[i], len(self.tokens))
+
+    def _slice(self, start: Token, end_index: int) -> str:
+        """Whitespace-normalized source from a token to (excluding) end_index."""
+        end = self.tokens[end_index].start if end_index < len(self.tokens) else len(self.source)
+        end = max(end, start.end)
+        text = self.source[start.start:end]
+        text = re.sub(r"//[^\n]*", "", text)
+        return " ".join(text.split())
+
+    def _const_signature(self, start: Token, end: int) -> str:
+        """Signature of a const/static without a long initializer."""
+        text = self._slice(start, end)
+        if len(text) > 80 and "=" in text:
+            text = text.split("=", 1)[0].rstrip()
+        return text
+
+    def _impl_target(self, i: int, end: int) -> tuple:
+        """Extract (type name, trait name) from an impl header."""
+        header = [t for t in self.tokens[i:end] if not t.is_comment]
+
+        # Skip impl generics: impl<T: Clone>
+        if header and header[0].text == "<":
+            depth = 0
+            for j, t in enumerate(header):
+                depth += self._angle_delta(t.text)
+                if depth == 0:
+                    header = header[j + 1:]
+                    break
+
+        # Drop the where clause
+        for j, t in enumerate(header):
+            if t.text == "where":
+                header = header[:j]
+                break
+
+        trait_tokens: List[Token] = []
+        depth = 0
+        for j, t in enumerate(header):
+            depth += self._angle_delta(t.text)
+            if depth == 0 and t.text == "for":
+                trait_tokens, header = header[:j], header[j + 1:]
+                break
+
+        return self._path_name(header), self._path_name(trait_tokens)
+
+    def _path_name(self, tokens: List[Token]) -> Optional[str]:
+        """Last identifier of a path at angle depth 0 (Foo in a::Foo<T>)."""
+        name = None
+        depth = 0
+        for t in tokens:
+            depth += self._angle_delta(t.text)
+            if depth == 0 and t.kind == TokenKind.IDENT:
+                name = t.text
+        return name
+
+    @staticmethod
+    def _angle_delta(text: str) -> int:
+        return {"<": 1, ">": -1, ">>": -2, "<<": 2}.get(text, 0)
+
+    @staticmethod
+    def _doc_text(text: str) -> str:
+        """Strip doc comment markers."""
+        if text.startswith("/**"):
+            body = text[3:-2] if text.endswith("*/") else text[3:]
+            lines = [re.sub(r"^\s*\* ?", "", line) for line in body.strip().split("\n")]
+            return "\n".join(lines).strip()
+        body = text[3:]
+        return body[1:] if body.startswith(" ") else body
+
+
+def parse_symbols(source: str, file_path: str = "", module: str = "crate") -> List[RustSymbol]:
+    """Parse the items declared in Rust source."""
+    return _SymbolParser(source, file_path, module).parse()
+
+
+def parse_file(source: str, file_path: str = "", module: str = "crate") -> tuple:
+    """Parse the items and `use` imports declared in Rust source."""
+    parser = _SymbolParser(source, file_path, module)
+    symbols = parser.parse()
+    return symbols, parser.imports
+
+
+def _selection_range(symbol: RustSymbol) -> Dict:
+    """Range of the symbol's name (the `impl` keyword for impl blocks)."""
+    length = len("impl") if symbol.kind == "impl" else len(symbol.name)
+    return lsp_symbols.lsp_range(symbol.line, symbol.column, symbol.line, symbol.column + length)
+
+
+def outline(source: str, file_path: str = "", module: str = "crate") -> List[Dict]:
+    """DocumentSymbol tree for Rust source (items nested in impls, traits and modules)."""
+    lines = source.splitlines() or [""]
+    flat = []
+    for symbol in parse_symbols(source, file_path, module):
+        if symbol.kind in ("field", "variant"):
+            # Members may share a line (`struct P { x: i32, y: i32 }`): span the declaration only
+            declaration = symbol.signature.split("\n")[0]
+            range_ = lsp_symbols.lsp_range(
+                symbol.line, symbol.column, symbol.line, symbol.column + len(declaration)
+            )
+        else:
+            start_line = min(symbol.start_line, len(lines) - 1)
+            end_line = min(symbol.end_line, len(lines) - 1)
+            start_text = lines[start_line]
+            range_ = lsp_symbols.lsp_range(
+                start_line, len(start_text) - len(start_text.lstrip()),
+                end_line, len(lines[end_line].rstrip())
+            )
+        flat.append(lsp_symbols.document_symbol(
+            symbol.display_name, OUTLINE_KINDS.get(symbol.kind, "variable"), range_,
+            _selection_range(symbol), " ".join(symbol.signature.split())
+        ))
+    return lsp_symbols.nest(flat)
+
+
+class RustSymbolIndex:
+    """
+    Index of the items declared across a workspace's `.rs` files.
+
+    Example:
+        index = RustSymbolIndex("/path/to/crate")
+        index.build()
+        index.update_file("src/lib.rs")        # after an edit
+        index.members("Person")                # fields and methods
+    """
+
+    def __init__(self, workspace_path: str):
+        self.workspace_path = Path(workspace_path)
+        self._files: Dict[str, List[RustSymbol]] = {}
+        self._imports: Dict[str, List[RustImport]] = {}
+        self._crate_names: Optional[set] = None
+        self.built = False
+
+    def build(self):
+        """Index every .rs file in the workspace."""
+        self._files.clear()
+        self._imports.clear()
+        self._crate_names = None
+
+        for path in self._rust_files():
+            self.update_file(str(path.relative_to(self.workspace_path)))
+
+        self.built = True
+        logger.info(f"Indexed {len(self._files)} Rust files in {self.workspace_path}")
+
+    def ensure_built(self):
+        """Build the index on first use."""
+        if not self.built:
+            self.build()
+
+    def update_file(self, file_path: str, content: Optional[str] = None):
+        """
+        Re-index a single file.
+
+        Args:
+            file_path: Path relative to the workspace
+            content: Unsaved buffer content; read from disk if None
+        """
+        file_path = self.relative(file_path)
+        full_path = self.workspace_path / file_path
+
+        if content is None:
+            if not full_path.exists():
+                self.remove_file(file_path)
+                return
+            try:
+                content = full_path.read_text()
+            except (OSError, UnicodeDecodeError) as e:
+                logger.debug(f"Cannot index {file_path}: {e}")
+                return
+
+        try:
+            symbols, imports = parse_file(content, file_path, self.module_path(file_path))
+            self._files[file_path] = symbols
+            self._imports[file_path] = imports
+        except Exception as e:
+            logger.error(f"Failed to index {file_path}: {e}")
+
+    def remove_file(self, file_path: str):
+        """Drop a file from the index."""
+        file_path = self.relative(file_path)
+        self._files.pop(file_path, None)
+        self._imports.pop(file_path, None)
+
+    def files(self) -> List[str]:
+        """Indexed files, relative to the workspace."""
+        return list(self._files)
+
+    def file_symbols(self, file_path: str) -> List[RustSymbol]:
+        """Symbols declared in one file."""
+        return list(self._files.get(self.relative(file_path), []))
+
+    def symbols(self) -> Iterator[RustSymbol]:
+        """Iterate over all indexed symbols."""
+        for symbols in self._files.values():
+            yield from symbols
+
+    def lookup(self, name: str, kinds: Optional[set] = None) -> List[RustSymbol]:
+        """Find symbols by exact name."""
+        return [
+            s for s in self.symbols()
+            if s.name == name and (kinds is None or s.kind in kinds)
+        ]
+
+    def members(self, type_name: str) -> List[RustSymbol]:
+        """Fields, variants, methods and associated items of a type."""
+        return [
+            s for s in self.symbols()
+            if s.container == type_name and s.kind in MEMBER_KINDS
+        ]
+
+    def items(self, prefix: str = "", module: Optional[str] = None) -> List[RustSymbol]:
+        """Named items (not members), optionally restricted to a module."""
+        return [
+            s for s in self.symbols()
+            if s.kind in ITEM_KINDS and s.container is None
+            and s.name.startswith(prefix)
+            and (module is None or s.module == module)
+        ]
+
+    def workspace_symbols(self, query: str, limit: int = lsp_symbols.MAX_WORKSPACE_SYMBOLS) -> List[Dict]:
+        """SymbolInformation for indexed items and members matching a fuzzy query."""
+        candidates = (
+            {
+                "name": symbol.display_name,
+                "kind": lsp_symbols.SYMBOL_KINDS[OUTLINE_KINDS.get(symbol.kind, "variable")],
+                "location": {
+                    "uri": (self.workspace_path / symbol.file).absolute().as_uri(),
+                    "range": _selection_range(symbol)
+                },
+                "containerName": f"{symbol.module}::{symbol.container}" if symbol.container else symbol.module
+            }
+            for symbol in self.symbols()
+            if symbol.kind != "impl"
+        )
+        return lsp_symbols.search(candidates, query, limit)
+
+    def imports(self, file_path: str) -> List[RustImport]:
+        """`use` imports declared in one file."""
+        return list(self._imports.get(self.relative(file_path), []))
+
+    def module_files(self, module: str) -> List[str]:
+        """Files implementing a module (src/foo.rs or src/foo/mod.rs)."""
+        return [f for f in self._files if self.module_path(f) == module]
+
+    def module_symbol(self, module: str) -> Optional[RustSymbol]:
+        """
+        A symbol for a module: its file when it has one (`mod foo;`),
+        otherwise the inline `mod foo { ... }` declaration.
+        """
+        files = sorted(self.module_files(module), key=lambda f: not f.endswith("mod.rs"))
+        if files and module != "crate":
+            return RustSymbol(
+                name=module.rsplit("::", 1)[-1], kind="module", file=files[0],
+                line=0, column=0, start_line=0, end_line=0,
+                module=module.rsplit("::", 1)[0], signature=f"mod {module.rsplit('::', 1)[-1]}",
+                doc=self._module_doc(module),
+            )
+        for symbol in self.symbols():
+            if symbol.kind == "module" and symbol.path == module:
+                return symbol
+        return None
+
+    def resolve(self, segments: List[str], file_path: str, module: Optional[str] = None) -> Optional[RustSymbol]:
+        """
+        Resolve a path as written in a file to the symbol it names.
+
+        Follows `crate::`, `self::` and `super::` prefixes, `use` imports
+        (including aliases and re-exports through `pub use`), module files
+        and associated items (Type::method).
+
+        Args:
+            segments: Path segments, e.g. ["shapes", "Circle", "new"]
+            file_path: File the path appears in
+            module: Module the path appears in (defaults to the file's)
+        """
+        return self._resolve(list(segments), file_path, module or self.module_path(file_path), set())
+
+    def _resolve(self, segments: List[str], file_path: str, current: str, seen: set) -> Optional[RustSymbol]:
+        if not segments:
+            return None
+
+        key = (tuple(segments), current)
+        if key in seen:
+            return None  # import cycle
+        seen.add(key)
+
+        head = segments[0]
+
+        if head == "crate" or (head in self.crate_names() and len(segments) > 1):
+            # `my_lib::Item` from the package's binaries, tests and examples
+            return self._resolve_in("crate", segments[1:], seen)
+        if head in ("self", "super"):
+            base = current.split("::")
+            rest = segments
+            while rest and rest[0] in ("self", "super"):
+                if rest[0] == "super" and len(base) > 1:
+                    base = base[:-1]
+                rest = rest[1:]
+            return self._resolve_in("::".join(base), rest, seen)
+
+        # Imported names: `use crate::shapes::Circle;` then `Circle::new`
+        for imp in self._imports_in(current):
+            if imp.alias == head:
+                target = imp.path.split("::") + segments[1:]
+                found = self._resolve(target, imp.file, imp.module, seen)
+                if found:
+                    return found
+
+        # Items of the current module, then the crate root
+        for base in (current, "crate"):
+            found = self._resolve_in(base, segments, seen)
+            if found:
+                return found
+
+        # Glob imports: `use crate::shapes::*;`
+        for imp in self._imports_in(current):
+            if imp.alias == "*":
+                found = self._resolve(imp.path.split("::") + segments, imp.file, imp.module, seen)
+                if found:
+                    return found
+
+        return None
+
+    def _resolve_in(self, module: str, segments: List[str], seen: set) -> Optional[RustSymbol]:
+        """Resolve segments relative to a module path."""
+        modules = self.modules()
+
+        i = 0
+        while i < len(segments) and f"{module}::{segments[i]}" in modules:
+            module = f"{module}::{segments[i]}"
+            i += 1
+
+        if i == len(segments):
+            return self.module_symbol(module)
+
+        name = segments[i]
+        item = next(
+            (s for s in self.symbols()
+             if s.module == module and s.name == name and s.container is None and s.kind in ITEM_KINDS),
+            None
+        )
+
+        if item is None:
+            # Re-exports: `pub use inner::Thing;` in the target module
+            for imp in self._imports_in(module):
+                if imp.alias == name:
+                    return self._resolve(imp.path.split("::") + segments[i + 1:], imp.file, imp.module, seen)
+            return None
+
+        for segment in segments[i + 1:]:
+            member = next((m for m in self.members(item.name) if m.name == segment), None)
+            if member is None:
+                return None
+            item = member
+
+        return item
+
+    def _imports_in(self, module: str) -> List[RustImport]:
+        """Imports declared directly in a module."""
+        return [
+            imp for imports in self._imports.values() for imp in imports
+            if imp.module == module
+        ]
+
+    def _module_doc(self, module: str) -> str:
+        """Inner doc comment (//!) at the top of a module's file."""
+        for file_path in self.module_files(module):
+            full_path = self.workspace_path / file_path
+            try:
+                lines = full_path.read_text().split("\n")
+            except (OSError, UnicodeDecodeError):
+                continue
+            docs = []
+            for line in lines:
+                stripped = line.strip()
+                if not stripped.startswith("//!"):
+                    break
+                body = stripped[3:]
+                docs.append(body[1:] if body.startswith(" ") else body)
+            return "\n".join(docs)
+        return ""
+
+    def crate_names(self) -> set:
+        """Library crate names of the workspace's packages (`[lib] name` or package name)."""
+        if self._crate_names is None:
+            names = set()
+            manifests = [self.workspace_path / "Cargo.toml", *self.workspace_path.glob("*/Cargo.toml")]
+            for manifest in manifests:
+                try:
+                    data = tomllib.loads(manifest.read_text())
+                except (OSError, tomllib.TOMLDecodeError):
+                    continue
+                name = (data.get("lib") or {}).get("name") or (data.get("package") or {}).get("name")
+                if isinstance(name, str):
+                    names.add(name.replace("-", "_"))
+            self._crate_names = names
+        return self._crate_names
+
+    def modules(self) -> set:
+        """All known module paths: file modules and inline `mod` blocks."""
+        modules = {self.module_path(file_path) for file_path in self._files}
+        modules.update(s.path for s in self.symbols() if s.kind == "module")
+        return modules
+
+    def module_at(self, file_path: str, line: int) -> str:
+        """Module path at a 0-based line, accounting for inline `mod` blocks."""
+        module = self.module_path(file_path)
+        for symbol in self.file_symbols(file_path):
+            if symbol.kind == "module" and symbol.start_line < line <= symbol.end_line:
+                module = symbol.path
+        return module
+
+    def module_path(self, file_path: str) -> str:
+        """
+        Module path for a file, e.g. src/shapes/circle.rs -> crate::shapes::circle.
+
+        Crate roots (lib.rs, main.rs, and files under tests/, examples/,
+        benches/ or src/bin/) map to `crate`.
+        """
+        path = Path(self.relative(file_path))
+        parts = list(path.parts)
+
+        # Strip everything up to the package's src/ directory
+        if "src" in parts:
+            parts = parts[len(parts) - parts[::-1].index("src"):]
+        else:
+            return "crate"
+
+        if not parts or parts[0] == "bin":
+            return "crate"
+
+        stem = Path(parts[-1]).stem
+        parents = parts[:-1]
+        if len(parts) == 1 and stem in ("lib", "main"):
+            return "crate"
+        if stem == "mod":
+            return "::".join(["crate", *parents])
+        return "::".join(["crate", *parents, stem])
+
+    def relative(self, file_path: str) -> str:
+        """Normalize a path to be relative to the workspace."""
+        path = Path(file_path)
+        if path.is_absolute():
+            try:
+                path = path.relative_to(self.workspace_path.absolute())
+            except ValueError:
+                pass
+        return str(path)
+
+    def _rust_files(self) -> Iterator[Path]:
+        """Walk the workspace for .rs files, skipping build output."""
+        for directory, dirnames, filenames in os.walk(self.workspace_path):
+            dirnames[:] = [d for d in dirnames if d not in IGNORED_DIRS and not d.startswith(".")]
+            for name in filenames:
+                if name.endswith(".rs"):
+                    yield Path(directory) / name
@@ -0,0 +1,819 @@
+"""
+Stdio Language Server Client.
+
+Generic client for any standard language server speaking JSON-RPC over
+stdin/stdout (rust-analyzer, pylsp, gopls, clangd,
+typescript-language-server, ...).
+
+This provides:
+- Document sync (didOpen/didChange/didClose), incremental when the
+  server supports it
+- Completion, hover, definition, references and rename
+- Code actions, formatting and document/workspace symbols
+- Diagnostics pushed via textDocument/publishDiagnostics
+"""
+
+import asyncio
+import json
+import logging
+import fnmatch
+import shutil
+from typing import Awaitable, Callable, Optional, List, Dict, Any
+from pathlib import Path
+from urllib.parse import unquote, urlparse
+
+from gathering.lsp.text_edits import content_changes
+
+logger = logging.getLogger(__name__)
+
+
+class StdioLSPClient:
+    """
+    Client for a language server via stdio communication.
+
+    Communicates with the server subprocess using JSON-RPC over
+    stdin/stdout. A background reader task dispatches responses to the
+    pending requests and collects published diagnostics per document.
+
+    Subclasses for specific servers set the class attributes below;
+    anything else can be driven from configuration alone:
+
+    Example:
+        client = StdioLSPClient(
+            "/path/to/project",
+            command=["gopls", "serve"],
+            name="gopls",
+            language_id="go"
+        )
+        await client.start()
+    """
+
+    # Server name used in logs and errors
+    name: str = "language server"
+
+    # Default command when none is given
+    default_command: List[str] = []
+
+    # languageId sent with didOpen (see also `language_ids`)
+    language_id: str = "plaintext"
+
+    # How to install the server, shown when the binary is missing
+    install_hint: Optional[str] = None
+
+    def __init__(
+        self,
+        workspace_path: str,
+        command: Optional[List[str]] = None,
+        request_timeout: float = 30.0,
+        name: Optional[str] = None,
+        language_id: Optional[str] = None,
+        language_ids: Optional[Dict[str, str]] = None,
+        initialization_options: Optional[Dict[str, Any]] = None,
+        settings: Optional[Dict[str, Any]] = None
+    ):
+        """
+        Args:
+            workspace_path: Workspace root (rootUri)
+            command: Server executable and arguments
+            request_timeout: Seconds to wait for a response
+            name: Server name for logs
+            language_id: Default languageId for opened documents
+            language_ids: languageId per file glob, e.g. {"*.tsx": "typescriptreact"}
+            initialization_options: Sent as initializationOptions
+            settings: Answers to workspace/configuration requests, by section
+        """
+        self.workspace_path = Path(workspace_path)
+        self.command = command or list(self.default_command)
+        self.request_timeout = request_timeout
+        self.name = name or self.name
+        self.language_id = language_id or self.language_id
+        self.language_ids = language_ids or {}
+        self.initialization_options = initialization_options or {}
+        self.settings = settings or {}
+        self.process: Optional[asyncio.subprocess.Process] = None
+        self.request_id = 0
+        self.initialized = False
+        self.server_capabilities: Dict[str, Any] = {}
+
+        self._reader_task: Optional[asyncio.Task] = None
+        self._pending: Dict[int, asyncio.Future] = {}
+        self._documents: Dict[str, int] = {}  # uri -> version
+        self._texts: Dict[str, str] = {}  # uri -> content last synced to the server
+        self._diagnostics: Dict[str, List[Dict]] = {}
+        self._diagnostic_events: Dict[str, asyncio.Event] = {}
+
+        # Called with (workspace-relative path, diagnostics) on each publishDiagnostics
+        self.on_diagnostics: Optional[Callable[[str, List[Dict]], Awaitable[None]]] = None
+
+    @classmethod
+    def is_available(cls, command: Optional[List[str]] = None) -> bool:
+        """Check whether the server binary can be found."""
+        command = command or cls.default_command
+        return bool(command) and shutil.which(command[0]) is not None
+
+    def is_alive(self) -> bool:
+        """Whether the subprocess is running and its output is still being read."""
+        return (
+            self.process is not None
+            and self.process.returncode is None
+            and self._reader_task is not None
+            and not self._reader_task.done()
+        )
+
+    async def start(self):
+        """Start the server subprocess, restarting it if it has exited."""
+        if self.process and self.process.returncode is None:
+            return
+        if self.process:
+            # Crashed server: drop its reader task and state before restarting
+            await self._kill()
+            self._documents.clear()
+            self._texts.clear()
+
+        try:
+            self.process = await asyncio.create_subprocess_exec(
+                *self.command,
+                stdin=asyncio.subprocess.PIPE,
+                stdout=asyncio.subprocess.PIPE,
+                stderr=asyncio.subprocess.DEVNULL,
+                cwd=str(self.workspace_path)
+            )
+
+            logger.info(f"Started {self.name} process (PID: {self.process.pid})")
+
+            self._reader_task = asyncio.create_task(self._read_loop())
+
+            # Initialize the server
+            await self._initialize()
+
+        except FileNotFoundError:
+            hint = f" Install with: {self.install_hint}" if self.install_hint else ""
+            logger.error(f"{self.name} not found ({self.command[0]}).{hint}")
+            self.process = None
+            raise
+        except Exception as e:
+            logger.error(f"Failed to start {self.name}: {e}")
+            await self._kill()
+            raise
+
+    async def _initialize(self):
+        """Send LSP initialize request."""
+        init_params = {
+            "processId": None,
+            "rootUri": self.workspace_path.absolute().as_uri(),
+            "workspaceFolders": [
+                {
+                    "uri": self.workspace_path.absolute().as_uri(),
+                    "name": self.workspace_path.name
+                }
+            ],
+            "capabilities": {
+                "textDocument": {
+                    "synchronization": {"didSave": True},
+                    "completion": {
+                        "completionItem": {
+                            "snippetSupport": False,
+                            "documentationFormat": ["markdown", "plaintext"]
+                        }
+                    },
+                    "hover": {
+                        "contentFormat": ["markdown", "plaintext"]
+                    },
+                    "definition": {"linkSupport": True},
+                    "documentSymbol": {"hierarchicalDocumentSymbolSupport": True},
+                    "references": {},
+                    "rename": {"prepareSupport": False},
+                    "codeAction": {
+                        "codeActionLiteralSupport": {
+                            "codeActionKind": {
+                                "valueSet": ["", "quickfix", "refactor", "refactor.extract",
+                                             "refactor.inline", "refactor.rewrite", "source"]
+                            }
+                        },
+                        "resolveSupport": {"properties": ["edit"]}
+                    },
+                    "publishDiagnostics": {
+                        "relatedInformation": True,
+                        "versionSupport": True
+                    }
+                },
+                "workspace": {
+                    "symbol": {},
+                    "configuration": True,
+                    "workspaceFolders": True
+                }
+            },
+            "initializationOptions": self.initialization_options
+        }
+
+        response = await self._send_request("initialize", init_params)
+
+        if response is not None:
+            self.server_capabilities = response.get("capabilities", {})
+            # Send initialized notification
+            await self._send_notification("initialized", {})
+            if self.settings:
+                await self._send_notification("workspace/didChangeConfiguration", {"settings": self.settings})
+            self.initialized = True
+            logger.info(f"{self.name} initialized successfully")
+            return response
+
+        raise RuntimeError(f"Failed to initialize {self.name}")
+
+    async def _write_message(self, payload: Dict[str, Any]):
+        """Frame and write a JSON-RPC message to the server."""
+        if not self.process or not self.process.stdin:
+            raise ConnectionError(f"{self.name} is not running")
+
+        body = json.dumps(payload).encode("utf-8")
+        header = f"Content-Length: {len(body)}\r\n\r\n".encode("ascii")
+        self.process.stdin.write(header + body)
+        await self.process.stdin.drain()
+
+    async def _send_request(
+        self,
+        method: str,
+        params: Any,
+        timeout: Optional[float] = None
+    ) -> Optional[Any]:
+        """Send a JSON-RPC request and wait for its response."""
+        if not self.process:
+            return None
+
+        self.request_id += 1
+        request_id = self.request_id
+        future = asyncio.get_running_loop().create_future()
+        self._pending[request_id] = future
+
+        try:
+            await self._write_message({
+                "jsonrpc": "2.0",
+                "id": request_id,
+                "method": method,
+                "params": params
+            })
+            return await asyncio.wait_for(future, timeout or self.request_timeout)
+
+        except asyncio.TimeoutError:
+            logger.warning(f"{self.name} request timed out: {method}")
+            return None
+        except Exception as e:
+            logger.error(f"Error sending request {method}: {e}")
+            return None
+        finally:
+            self._pending.pop(request_id, None)
+
+    async def _send_notification(self, method: str, params: Any):
+        """Send a JSON-RPC notification (no response expected)."""
+        try:
+            await self._write_message({
+                "jsonrpc": "2.0",
+                "method": method,
+                "params": params
+            })
+        except Exception as e:
+            logger.error(f"Error sending notification {method}: {e}")
+
+    async def _read_message(self) -> Optional[Dict]:
+        """Read a single framed JSON-RPC message from stdout."""
+        stdout = self.process.stdout
+        headers = {}
+        while True:
+            line = await stdout.readline()
+            if not line:
+                return None  # EOF
+            line = line.decode("ascii").strip()
+            if not line:
+                break
+            if ":" in line:
+                key, value = line.split(":", 1)
+                headers[key.strip().lower()] = value.strip()
+
+        content_length = int(headers.get("content-length", 0))
+        if content_length <= 0:
+            return {}
+
+        content = await stdout.readexactly(content_length)
+        return json.loads(content.decode("utf-8"))
+
+    async def _read_loop(self):
+        """Dispatch incoming messages until the server exits."""
+        try:
+            while self.process and self.process.stdout:
+                message = await self._read_message()
+                if message is None:
+                    break
+                if message:
+                    await self._handle_message(message)
+        except asyncio.CancelledError:
+            raise
+        except Exception as e:
+            logger.error(f"{self.name} reader stopped: {e}")
+        finally:
+            self.initialized = False
+            for future in self._pending.values():
+                if not future.done():
+                    future.set_exception(ConnectionError(f"{self.name} exited"))
+
+    async def _handle_message(self, message: Dict[str, Any]):
+        """Route a message to a pending request or a notification handler."""
+        method = message.get("method")
+
+        # Response to one of our requests
+        if method is None:
+            future = self._pending.get(message.get("id"))
+            if future and not future.done():
+                if "error" in message:
+                    logger.error(f"LSP error: {message['error']}")
+                    future.set_result(None)
+                else:
+                    future.set_result(message.get("result"))
+            return
+
+        # Request from the server: must be answered
+        if "id" in message:
+            result = None
+            if method == "workspace/configuration":
+                items = message.get("params", {}).get("items", [])
+                result = [self._setting(item.get("section")) for item in items]
+            await self._write_message({
+                "jsonrpc": "2.0",
+                "id": message["id"],
+                "result": result
+            })
+            return
+
+        if method == "textDocument/publishDiagnostics":
+            params = message.get("params", {})
+            uri = params.get("uri", "")
+            self._diagnostics[uri] = params.get("diagnostics", [])
+            self._diagnostic_event(uri).set()
+
+            if self.on_diagnostics is not None:
+                try:
+                    await self.on_diagnostics(self._path(uri), self._diagnostics[uri])
+                except Exception as e:
+                    logger.error(f"Error publishing {self.name} diagnostics for {uri}: {e}")
+
+    def _setting(self, section: Optional[str]) -> Any:
+        """Configured value for a (dotted) settings section, or None."""
+        if not section:
+            return self.settings or None
+
+        value: Any = self.settings
+        for part in section.split("."):
+            if not isinstance(value, dict) or part not in value:
+                return None
+            value = value[part]
+        return value
+
+    def _language_id(self, file_path: str) -> str:
+        """languageId for a document: the first matching glob, else the default."""
+        name = Path(file_path).name
+        for pattern, language_id in self.language_ids.items():
+            if fnmatch.fnmatch(name, pattern) or fnmatch.fnmatch(file_path, pattern):
+                return language_id
+        return self.language_id
+
+    def _diagnostic_event(self, uri: str) -> asyncio.Event:
+        """Get the event signalled when diagnostics arrive for a document."""
+        if uri not in self._diagnostic_events:
+            self._diagnostic_events[uri] = asyncio.Event()
+        return self._diagnostic_events[uri]
+
+    def _uri(self, file_path: str) -> str:
+        """Resolve a workspace-relative path to a file URI."""
+        path = Path(file_path)
+        if not path.is_absolute():
+            path = self.workspace_path / path
+        return path.absolute().as_uri()
+
+    def _path(self, uri: str) -> str:
+        """Map a file URI back to a workspace-relative path (absolute if outside)."""
+        path = Path(unquote(urlparse(uri).path))
+        try:
+            return path.relative_to(self.workspace_path.absolute()).as_posix()
+        except ValueError:
+            return str(path)
+
+    def is_open(self, file_path: str) -> bool:
+        """Whether a document has been opened with the server."""
+        return self._uri(file_path) in self._documents
+
+    @property
+    def sync_kind(self) -> int:
+        """
+        TextDocumentSyncKind announced by the server: 0 (none),
+        1 (full text) or 2 (incremental).
+        """
+        sync = self.server_capabilities.get("textDocumentSync", 1)
+        if isinstance(sync, dict):
+            sync = sync.get("change", 1)
+        return sync if sync in (0, 1, 2) else 1
+
+    async def did_open(self, file_path: str, content: str):
+        """Notify that a document was opened."""
+        uri = self._uri(file_path)
+        self._documents[uri] = 1
+        self._texts[uri] = content
+
+        await self._send_notification("textDocument/didOpen", {
+            "textDocument": {
+                "uri": uri,
+                "languageId": self._language_id(file_path),
+                "version": 1,
+                "text": content
+            }
+        })
+
+    async def did_change(self, file_path: str, content: str) -> bool:
+        """
+        Sync a document's content, opening it first if needed.
+
+        Nothing is sent when the server already has this content. Servers
+        supporting incremental sync receive only the changed lines.
+
+        Returns:
+            True if a notification was sent
+        """
+        uri = self._uri(file_path)
+
+        if uri not in self._documents:
+            await self.did_open(file_path, content)
+            return True
+
+        previous = self._texts.get(uri)
+        if content == previous:
+            return False
+
+        if self.sync_kind == 2 and previous is not None:
+            changes = content_changes(previous, content)
+        else:
+            changes = [{"text": content}]
+        await self._send_changes(uri, changes, content)
+        return True
+
+    async def apply_changes(
+        self,
+        file_path: str,
+        changes: List[Dict],
+        previous: str,
+        content: str
+    ):
+        """
+        Forward editor contentChanges for a document.
+
+        Incremental changes are passed through as-is when the server
+        supports incremental sync and has the `previous` text; otherwise
+        the document is synced from `content`.
+
+        Args:
+            file_path: Document path
+            changes: contentChanges applied to `previous`
+            previous: Document text before the changes
+            content: Document text after the changes
+        """
+        uri = self._uri(file_path)
+
+        if uri not in self._documents or self._texts.get(uri) != previous:
+            await self.did_change(file_path, content)
+            return
+
+        if self.sync_kind != 2:
+            changes = [{"text": content}]
+        await self._send_changes(uri, changes, content)
+
+    async def _send_changes(self, uri: str, changes: List[Dict], content: str):
+        """Send a didChange notification with the next document version."""
+        self._documents[uri] += 1
+        self._texts[uri] = content
+
+        await self._send_notification("textDocument/didChange", {
+            "textDocument": {
+                "uri": uri,
+                "version": self._documents[uri]
+            },
+            "contentChanges": changes
+        })
+
+    async def did_close(self, file_path: str):
+        """Notify that a document was closed."""
+        uri = self._uri(file_path)
+        if self._documents.pop(uri, None) is None:
+            return
+        self._texts.pop(uri, None)
+
+        await self._send_notification("textDocument/didClose", {
+            "textDocument": {"uri": uri}
+        })
+
+    async def completion(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        content: str
+    ) -> List[Dict]:
+        """Get completion suggestions."""
+        if not self.initialized:
+            await self.start()
+
+        await self.did_change(file_path, content)
+
+        response = await self._send_request("textDocument/completion", {
+            "textDocument": {"uri": self._uri(file_path)},
+            "position": {"line": line - 1, "character": character}
+        })
+
+        if not response:
+            return []
+
+        # Handle both CompletionList and CompletionItem[] formats
+        items = response.get("items", []) if isinstance(response, dict) else response
+
+        completions = []
+        for item in items:
+            text_edit = item.get("textEdit") or {}
+            completions.append({
+                "label": item.get("label", ""),
+                "kind": item.get("kind", 1),
+                "insertText": item.get("insertText") or text_edit.get("newText") or item.get("label", ""),
+                "detail": item.get("detail"),
+                "documentation": self._extract_documentation(item.get("documentation"))
+            })
+
+        return completions
+
+    async def hover(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        content: str
+    ) -> Optional[Dict]:
+        """Get hover information."""
+        if not self.initialized:
+            await self.start()
+
+        await self.did_change(file_path, content)
+
+        response = await self._send_request("textDocument/hover", {
+            "textDocument": {"uri": self._uri(file_path)},
+            "position": {"line": line - 1, "character": character}
+        })
+
+        if not response or not response.get("contents"):
+            return None
+
+        contents = response["contents"]
+
+        # Extract markdown content
+        if isinstance(contents, dict):
+            value = contents.get("value", "")
+        elif isinstance(contents, list):
+            value = "\n".join(
+                item.get("value", "") if isinstance(item, dict) else str(item)
+                for item in contents
+            )
+        else:
+            value = str(contents)
+
+        return {"contents": {"kind": "markdown", "value": value}}
+
+    async def definition(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        content: str
+    ) -> Optional[Dict]:
+        """Get definition location."""
+        if not self.initialized:
+            await self.start()
+
+        await self.did_change(file_path, content)
+
+        response = await self._send_request("textDocument/definition", {
+            "textDocument": {"uri": self._uri(file_path)},
+            "position": {"line": line - 1, "character": character}
+        })
+
+        if not response:
+            return None
+
+        # Handle Location, Location[] and LocationLink[] formats
+        locations = response if isinstance(response, list) else [response]
+
+        if not locations:
+            return None
+
+        loc = locations[0]
+        if "targetUri" in loc:
+            return {
+                "uri": loc["targetUri"],
+                "range": loc.get("targetSelectionRange", loc.get("targetRange", {}))
+            }
+
+        return {
+            "uri": loc.get("uri", ""),
+            "range": loc.get("range", {})
+        }
+
+    async def code_actions(
+        self,
+        file_path: str,
+        range_: Dict,
+        diagnostics: List[Dict],
+        content: str
+    ) -> List[Dict]:
+        """
+        Get code actions for a 0-based range.
+
+        Actions returned without an edit are resolved with codeAction/resolve;
+        bare commands (which need server-side execution) are dropped.
+        """
+        if not self.initialized:
+            await self.start()
+
+        await self.did_change(file_path, content)
+
+        response = await self._send_request("textDocument/codeAction", {
+            "textDocument": {"uri": self._uri(file_path)},
+            "range": range_,
+            "context": {"diagnostics": diagnostics, "triggerKind": 1}
+        })
+
+        actions = []
+        for action in response or []:
+            if isinstance(action.get("command"), str):
+                continue
+            if "edit" not in action and "data" in action:
+                action = await self._send_request("codeAction/resolve", action) or action
+            if action.get("edit"):
+                actions.append(action)
+
+        return actions
+
+    async def references(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        content: str,
+        include_declaration: bool = True
+    ) -> List[Dict]:
+        """Find references to the symbol at a position (Location list)."""
+        if not self.initialized:
+            await self.start()
+
+        await self.did_change(file_path, content)
+
+        response = await self._send_request("textDocument/references", {
+            "textDocument": {"uri": self._uri(file_path)},
+            "position": {"line": line - 1, "character": character},
+            "context": {"includeDeclaration": include_declaration}
+        })
+
+        return response or []
+
+    async def rename(
+        self,
+        file_path: str,
+        line: int,
+        character: int,
+        new_name: str,
+        content: str
+    ) -> Optional[Dict]:
+        """Rename the symbol at a position, returning a WorkspaceEdit."""
+        if not self.initialized:
+            await self.start()
+
+        await self.did_change(file_path, content)
+
+        return await self._send_request("textDocument/rename", {
+            "textDocument": {"uri": self._uri(file_path)},
+            "position": {"line": line - 1, "character": character},
+            "newName": new_name
+        })
+
+    async def document_symbols(self, file_path: str, content: str) -> List[Dict]:
+        """Get the document outline (DocumentSymbol tree)."""
+        if not self.initialized:
+            await self.start()
+
+        await self.did_change(file_path, content)
+
+        response = await self._send_request("textDocument/documentSymbol", {
+            "textDocument": {"uri": self._uri(file_path)}
+        })
+
+        return response or []
+
+    async def workspace_symbols(self, query: str) -> List[Dict]:
+        """Search symbols across the workspace."""
+        if not self.initialized:
+            await self.start()
+
+        response = await self._send_request("workspace/symbol", {"query": query})
+
+        return response or []
+
+    async def formatting(
+        self,
+        file_path: str,
+        content: str,
+        options: Optional[Dict[str, Any]] = None
+    ) -> List[Dict]:
+        """Format a document, returning TextEdits."""
+        if not self.initialized:
+            await self.start()
+
+        await self.did_change(file_path, content)
+
+        response = await self._send_request("textDocument/formatting", {
+            "textDocument": {"uri": self._uri(file_path)},
+            "options": options or {"tabSize": 4, "insertSpaces": True}
+        })
+
+        return response or []
+
+    async def diagnostics(
+        self,
+        file_path: str,
+        content: str,
+        timeout: float = 5.0
+    ) -> List[Dict]:
+        """
+        Get diagnostics (errors/warnings).
+
+        Servers push diagnostics via publishDiagnostics after each
+        change, so this syncs the document and waits for the next batch.
+        If none arrives within the timeout, or the content did not change,
+        the last known batch is returned.
+        """
+        if not self.initialized:
+            await self.start()
+
+        uri = self._uri(file_path)
+        event = self._diagnostic_event(uri)
+        event.clear()
+
+        if not await self.did_change(file_path, content) and uri in self._diagnostics:
+            return list(self._diagnostics[uri])  # unchanged since the last batch
+
+        try:
+            await asyncio.wait_for(event.wait(), timeout)
+        except asyncio.TimeoutError:
+            logger.debug(f"No fresh diagnostics for {file_path} within {timeout}s")
+
+        return list(self._diagnostics.get(uri, []))
+
+    def _extract_documentation(self, doc: Any) -> Optional[str]:
+        """Extract documentation string from various formats."""
+        if not doc:
+            return None
+
+        if isinstance(doc, str):
+            return doc
+
+        if isinstance(doc, dict):
+            return doc.get("value")
+
+        return None
+
+    async def _kill(self):
+        """Forcefully stop the subprocess and the reader task."""
+        if self._reader_task:
+            self._reader_task.cancel()
+            try:
+                await self._reader_task
+            except (asyncio.CancelledError, Exception):
+                pass
+            self._reader_task = None
+
+        if self.process and self.process.returncode is None:
+            self.process.kill()
+            await self.process.wait()
+
+        self.process = None
+        self.initialized = False
+
+    async def shutdown(self):
+        """Shutdown the language server."""
+        if not self.process:
+            return
+
+        try:
+            await self._send_request("shutdown", None, timeout=5)
+            await self._send_notification("exit", None)
+
+            await asyncio.wait_for(self.process.wait(), timeout=5)
+            logger.info(f"{self.name} shut down successfully")
+
+        except Exception as e:
+            logger.error(f"Error during shutdown: {e}")
+
+        finally:
+            await self._kill()
+            self._documents.clear()
+            self._texts.clear()
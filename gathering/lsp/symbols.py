@@ -0,0 +1,179 @@
+"""
+Document and Workspace Symbols.
+
+Shared helpers for textDocument/documentSymbol and workspace/symbol:
+LSP SymbolKind values, nesting flat symbol lists into an outline tree,
+flattening outlines into SymbolInformation, and fuzzy query matching.
+"""
+
+import os
+from pathlib import Path
+from typing import Optional, List, Dict, Iterable, Iterator, Callable
+
+# LSP SymbolKind values
+SYMBOL_KINDS = {
+    "file": 1,
+    "module": 2,
+    "namespace": 3,
+    "package": 4,
+    "class": 5,
+    "method": 6,
+    "property": 7,
+    "field": 8,
+    "constructor": 9,
+    "enum": 10,
+    "interface": 11,
+    "function": 12,
+    "variable": 13,
+    "constant": 14,
+    "object": 19,
+    "enum_member": 22,
+    "struct": 23,
+    "type_parameter": 26,
+}
+
+# SymbolKind value -> name, for human/agent-facing output
+SYMBOL_KIND_NAMES = {value: name for name, value in SYMBOL_KINDS.items()}
+
+# Upper bound on workspace/symbol results
+MAX_WORKSPACE_SYMBOLS = 256
+
+# Directories never searched for workspace symbols
+IGNORED_DIRS = {".git", "node_modules", "__pycache__", "venv", ".venv", "target", "dist", "build"}
+
+
+def lsp_range(start_line: int, start_character: int, end_line: int, end_character: int) -> Dict:
+    """An LSP Range from 0-based positions."""
+    return {
+        "start": {"line": start_line, "character": start_character},
+        "end": {"line": end_line, "character": end_character}
+    }
+
+
+def document_symbol(
+    name: str,
+    kind: str,
+    range_: Dict,
+    selection_range: Dict,
+    detail: str = ""
+) -> Dict:
+    """A DocumentSymbol without children (see `nest`)."""
+    return {
+        "name": name,
+        "detail": detail,
+        "kind": SYMBOL_KINDS[kind],
+        "range": range_,
+        "selectionRange": selection_range,
+        "children": []
+    }
+
+
+def _start(symbol: Dict) -> tuple:
+    start = symbol["range"]["start"]
+    return start["line"], start["character"]
+
+
+def _end(symbol: Dict) -> tuple:
+    end = symbol["range"]["end"]
+    return end["line"], end["character"]
+
+
+def nest(symbols: List[Dict]) -> List[Dict]:
+    """
+    Build an outline tree from flat DocumentSymbols by range containment.
+
+    Symbols with identical ranges keep their input order, so a parent must
+    be listed before members declared on the same line.
+    """
+    ordered = sorted(
+        enumerate(symbols),
+        key=lambda item: (_start(item[1]), tuple(-n for n in _end(item[1])), item[0])
+    )
+
+    roots: List[Dict] = []
+    stack: List[Dict] = []
+    for _, symbol in ordered:
+        while stack and _end(stack[-1]) < _end(symbol):
+            stack.pop()
+        (stack[-1]["children"] if stack else roots).append(symbol)
+        stack.append(symbol)
+
+    return roots
+
+
+def flatten(symbols: List[Dict], uri: str, container: Optional[str] = None) -> List[Dict]:
+    """Flatten an outline into SymbolInformation entries for one document."""
+    flat = []
+    for symbol in symbols:
+        flat.append({
+            "name": symbol["name"],
+            "kind": symbol["kind"],
+            "location": {"uri": uri, "range": symbol["selectionRange"]},
+            "containerName": container
+        })
+        flat.extend(flatten(symbol.get("children", []), uri, symbol["name"]))
+    return flat
+
+
+def _match_rank(name: str, query: str) -> Optional[int]:
+    """
+    Rank a symbol name against a query: 0 exact, 1 prefix, 2 substring,
+    3 subsequence (e.g. "gcf" matches "get_config_file"), None if no match.
+    Matching is case-insensitive.
+    """
+    name = name.lower()
+    query = query.lower()
+    if not query or name == query:
+        return 0
+    if name.startswith(query):
+        return 1
+    if query in name:
+        return 2
+
+    position = 0
+    for char in query:
+        position = name.find(char, position) + 1
+        if position == 0:
+            return None
+    return 3
+
+
+def search(symbols: Iterable[Dict], query: str, limit: int = MAX_WORKSPACE_SYMBOLS) -> List[Dict]:
+    """Filter SymbolInformation entries by a fuzzy query, best matches first."""
+    ranked = []
+    for symbol in symbols:
+        rank = _match_rank(symbol["name"], query)
+        if rank is not None:
+            ranked.append((rank, len(symbol["name"]), symbol["name"], symbol))
+
+    ranked.sort(key=lambda item: item[:3])
+    return [item[3] for item in ranked[:limit]]
+
+
+def source_files(root: Path, suffixes: Iterable[str]) -> Iterator[Path]:
+    """Files under root with one of the given suffixes, skipping ignored directories."""
+    suffixes = tuple(suffixes)
+    for directory, dirs, files in os.walk(root):
+        dirs[:] = sorted(d for d in dirs if d not in IGNORED_DIRS and not d.startswith("."))
+        for name in sorted(files):
+            if name.endswith(suffixes):
+                yield Path(directory) / name
+
+
+def workspace_search(
+    root: Path,
+    suffixes: Iterable[str],
+    outline: Callable[[str], List[Dict]],
+    query: str,
+    limit: int = MAX_WORKSPACE_SYMBOLS
+) -> List[Dict]:
+    """Outline every matching source file under root and search the symbols."""
+    def candidates() -> Iterator[Dict]:
+        for path in source_files(root, suffixes):
+            try:
+                source = path.read_text()
+            except (OSError, UnicodeDecodeError):
+                continue
+            yield from flatten(outline(source), path.absolute().as_uri())
+
+    return search(candidates(), query, limit)
@@ -0,0 +1,175 @@
+"""
+Text Edit Utilities.
+
+Helpers for LSP TextEdits: computing minimal line-based edits between two
+versions of a document, applying edits back to text, applying
+didChange contentChanges, and reading the per-document edits of a
+WorkspaceEdit.
+
+Positions use the LSP default encoding: `character` counts UTF-16 code
+units, so characters outside the Basic Multilingual Plane count twice.
+"""
+
+import difflib
+from typing import List, Dict, Tuple
+
+
+def utf16_length(text: str) -> int:
+    """Length of `text` in UTF-16 code units."""
+    return len(text) + sum(1 for char in text if ord(char) > 0xFFFF)
+
+
+def _position(lines: List[str], index: int) -> Tuple[int, int]:
+    """
+    Position of the start of line `index` (0-based), where `lines` keep
+    their line endings. Past the last line this is the end of the document.
+    """
+    if index < len(lines) or not lines or lines[-1].endswith("\n"):
+        return index, 0
+    return len(lines) - 1, utf16_length(lines[-1])
+
+
+def diff_edits(original: str, updated: str) -> List[Dict]:
+    """
+    Minimal line-based TextEdits turning `original` into `updated`.
+
+    Unchanged lines are left alone, so editors keep cursors, folds and
+    marks outside the changed regions.
+    """
+    old_lines = original.splitlines(keepends=True)
+    new_lines = updated.splitlines(keepends=True)
+
+    edits = []
+    matcher = difflib.SequenceMatcher(a=old_lines, b=new_lines, autojunk=False)
+    for tag, i1, i2, j1, j2 in matcher.get_opcodes():
+        if tag == "equal":
+            continue
+        start = _position(old_lines, i1)
+        end = _position(old_lines, i2)
+        edits.append({
+            "range": {
+                "start": {"line": start[0], "character": start[1]},
+                "end": {"line": end[0], "character": end[1]}
+            },
+            "newText": "".join(new_lines[j1:j2])
+        })
+
+    return edits
+
+
+def offset_at(content: str, line: int, character: int) -> int:
+    """
+    Offset of a 0-based (line, character) position, clamped to the document.
+
+    `character` is in UTF-16 code units; the returned offset indexes the
+    Python string.
+    """
+    offset = 0
+    for _ in range(line):
+        newline = content.find("\n", offset)
+        if newline == -1:
+            return len(content)
+        offset = newline + 1
+
+    end_of_line = content.find("\n", offset)
+    if end_of_line == -1:
+        end_of_line = len(content)
+
+    units = 0
+    while offset < end_of_line and units < character:
+        units += 2 if ord(content[offset]) > 0xFFFF else 1
+        offset += 1
+    return offset
+
+
+def apply_edits(content: str, edits: List[Dict]) -> str:
+    """
+    Apply TextEdits to a document.
+
+    Edits are applied from the end of the document backwards, so every
+    range refers to the original text (as the LSP specification requires).
+    """
+    def start_key(edit: Dict) -> Tuple[int, int]:
+        start = edit["range"]["start"]
+        return start["line"], start["character"]
+
+    for edit in sorted(edits, key=start_key, reverse=True):
+        start = edit["range"]["start"]
+        end = edit["range"]["end"]
+        begin = offset_at(content, start["line"], start["character"])
+        finish = offset_at(content, end["line"], end["character"])
+        content = content[:begin] + edit["newText"] + content[finish:]
+
+    return content
+
+
+def apply_content_changes(content: str, changes: List[Dict]) -> str:
+    """
+    Apply didChange contentChanges to a document.
+
+    Unlike TextEdits, changes apply in order, each range referring to the
+    text produced by the previous change. A change without a range
+    replaces the whole document.
+    """
+    for change in changes:
+        if "range" not in change or change["range"] is None:
+            content = change["text"]
+            continue
+        start = change["range"]["start"]
+        end = change["range"]["end"]
+        begin = offset_at(content, start["line"], start["character"])
+        finish = offset_at(content, end["line"], end["character"])
+        if finish < begin:
+            raise ValueError(f"Invalid change range: {change['range']}")
+        content = content[:begin] + change["text"] + content[finish:]
+
+    return content
+
+
+def content_changes(original: str, updated: str) -> List[Dict]:
+    """
+    Incremental contentChanges turning `original` into `updated`.
+
+    The line-based edits are listed from the end of the document
+    backwards, so each range is still valid when its change is applied.
+    """
+    return [
+        {"range": edit["range"], "text": edit["newText"]}
+        for edit in reversed(diff_edits(original, updated))
+    ]
+
+
+def edits_in_range(edits: List[Dict], start_line: int, end_line: int) -> List[Dict]:
+    """Edits touching any 0-based line in [start_line, end_line]."""
+    selected = []
+    for edit in edits:
+        first = edit["range"]["start"]["line"]
+        last = edit["range"]["end"]["line"]
+        if edit["range"]["end"]["character"] == 0 and last > first:
+            last -= 1  # range ends at the start of the following line
+        if first <= end_line and last >= start_line:
+            selected.append(edit)
+    return selected
+
+
+def workspace_edit_changes(edit: Dict) -> Dict[str, List[Dict]]:
+    """
+    TextEdits per document URI from a WorkspaceEdit.
+
+    Accepts both the `changes` map and `documentChanges` lists of
+    TextDocumentEdits.
+
+    Raises:
+        ValueError: For resource operations (create, rename or delete file)
+    """
+    changes: Dict[str, List[Dict]] = {}
+    for uri, edits in (edit.get("changes") or {}).items():
+        changes.setdefault(uri, []).extend(edits)
+
+    for document_change in edit.get("documentChanges") or []:
+        if "kind" in document_change:
+            raise ValueError(f"Unsupported resource operation: {document_change['kind']}")
+        uri = document_change["textDocument"]["uri"]
+        changes.setdefault(uri, []).extend(document_change.get("edits", []))
+
+    return changes
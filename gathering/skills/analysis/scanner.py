@@ -1,17 +1,29 @@
 """
 Code Analysis Skill for GatheRing.
 Provides code analysis, linting, and security scanning for agents.
+
+Rust projects are linted with clippy and rustfmt, audited with cargo
+audit (or the advisory-db checkout directly) and their Cargo.lock is
+checked against the local crates.io index mirror. Complexity, metrics,
+dead code and duplicate analyses cover Rust, JavaScript/TypeScript and
+Go through the tree-sitter layer in ``syntax``.
 """
 
 import ast
+import math
+import os
 import re
 import subprocess
 import json
+import tomllib
 from pathlib import Path
 from typing import Dict, Any, List, Optional
 from datetime import datetime
 
 from gathering.skills.base import BaseSkill, SkillResponse, SkillPermission
+from gathering.skills.analysis import syntax
+from gathering.lsp import javascript_symbols, python_symbols, rust_symbols
+from gathering.lsp.symbols import SYMBOL_KIND_NAMES, flatten, search, source_files
 
 
 class CodeAnalysisSkill(BaseSkill):
@@ -20,11 +32,12 @@ class CodeAnalysisSkill(BaseSkill):
 
     Provides tools for:
     - Static code analysis (AST-based)
-    - Linting with popular tools (ruff, flake8, pylint)
-    - Security vulnerability scanning
-    - Code complexity metrics
-    - Dependency analysis
+    - Linting with popular tools (ruff, flake8, pylint, clippy, rustfmt)
+    - Security vulnerability scanning (patterns, bandit, cargo audit)
+    - Code complexity metrics (Python via ast; Rust, JS/TS and Go via tree-sitter)
+    - Dependency analysis (requirements files, Cargo.lock)
     - Type checking
+    - Symbol outlines and search (code navigation)
     """
 
     name = "analysis"
@@ -59,34 +72,65 @@ class CodeAnalysisSkill(BaseSkill):
         ],
     }
 
+    # File extension -> outline builder for analysis_symbols
+    OUTLINERS = {
+        ".rs": rust_symbols.outline,
+        ".py": python_symbols.outline,
+        ".js": javascript_symbols.outline,
+        ".jsx": javascript_symbols.outline,
+        ".mjs": javascript_symbols.outline,
+        ".ts": javascript_symbols.outline,
+        ".tsx": javascript_symbols.outline,
+    }
+
+    # cargo builds the project before linting: allow more time than Python linters
+    CARGO_TIMEOUT = 600
+
+    # Build output and vendored sources are not analyzed for complexity or duplicates
+    SYNTAX_SKIP_DIRS = {"target", "node_modules", "vendor", ".git"}
+
+    # CVSS v3 base metric weights (Scope changed values for PR in PR_CHANGED)
+    CVSS_WEIGHTS = {
+        "AV": {"N": 0.85, "A": 0.62, "L": 0.55, "P": 0.2},
+        "AC": {"L": 0.77, "H": 0.44},
+        "PR": {"N": 0.85, "L": 0.62, "H": 0.27},
+        "UI": {"N": 0.85, "R": 0.62},
+        "C": {"H": 0.56, "L": 0.22, "N": 0.0},
+        "I": {"H": 0.56, "L": 0.22, "N": 0.0},
+        "A": {"H": 0.56, "L": 0.22, "N": 0.0},
+    }
+    PR_CHANGED = {"N": 0.85, "L": 0.68, "H": 0.5}
+
     def __init__(self, config: Optional[Dict[str, Any]] = None):
         super().__init__(config)
         self.working_dir = config.get("working_dir") if config else None
         self.exclude_patterns = config.get("exclude_patterns", ["venv", "node_modules", "__pycache__", ".git"]) if config else []
+        self.advisory_db = config.get("advisory_db") if config else None
+        self.registry_index = config.get("registry_index") if config else None
 
     def get_tools_definition(self) -> List[Dict[str, Any]]:
         return [
             {
                 "name": "analysis_lint",
-                "description": "Run linting on Python code",
+                "description": "Run linting on Python code, or clippy and rustfmt --check on Rust crates",
                 "input_schema": {
                     "type": "object",
                     "properties": {
                         "path": {"type": "string", "description": "File or directory to lint"},
                         "tool": {
                             "type": "string",
-                            "enum": ["ruff", "flake8", "pylint", "auto"],
-                            "description": "Linting tool to use",
+                            "enum": ["ruff", "flake8", "pylint", "clippy", "rustfmt", "auto"],
+                            "description": "Linting tool to use (auto: clippy and rustfmt for Rust crates)",
                             "default": "auto"
                         },
-                        "fix": {"type": "boolean", "description": "Auto-fix issues (ruff only)", "default": False}
+                        "fix": {"type": "boolean", "description": "Auto-fix issues (ruff, cargo clippy --fix, cargo fmt)", "default": False}
                     },
                     "required": ["path"]
                 }
             },
             {
                 "name": "analysis_security",
-                "description": "Scan code for security vulnerabilities",
+                "description": "Scan code for security vulnerabilities (Rust crates: cargo audit against a local advisory-db)",
                 "input_schema": {
                     "type": "object",
                     "properties": {
@@ -96,7 +140,8 @@ class CodeAnalysisSkill(BaseSkill):
                             "enum": ["low", "medium", "high", "critical"],
                             "description": "Minimum severity to report",
                             "default": "low"
-                        }
+                        },
+                        "advisory_db": {"type": "string", "description": "RustSec advisory-db checkout (default: configured or ~/.cargo/advisory-db)"}
                     },
                     "required": ["path"]
                 }
@@ -115,13 +160,15 @@ class CodeAnalysisSkill(BaseSkill):
             },
             {
                 "name": "analysis_dependencies",
-                "description": "Analyze project dependencies",
+                "description": "Analyze project dependencies (requirements files, or Cargo.lock for duplicate, outdated and yanked crates)",
                 "input_schema": {
                     "type": "object",
                     "properties": {
                         "path": {"type": "string", "description": "Project path"},
                         "check_vulnerabilities": {"type": "boolean", "description": "Check for known vulnerabilities", "default": True},
-                        "check_outdated": {"type": "boolean", "description": "Check for outdated packages", "default": True}
+                        "check_outdated": {"type": "boolean", "description": "Check for outdated packages", "default": True},
+                        "registry_index": {"type": "string", "description": "Local crates.io index mirror for outdated and yanked crates (default: configured)"},
+                        "advisory_db": {"type": "string", "description": "RustSec advisory-db checkout (default: configured or ~/.cargo/advisory-db)"}
                     },
                     "required": ["path"]
                 }
@@ -156,7 +203,8 @@ class CodeAnalysisSkill(BaseSkill):
                     "type": "object",
                     "properties": {
                         "path": {"type": "string", "description": "Directory to analyze"},
-                        "min_lines": {"type": "integer", "description": "Minimum lines to consider duplicate", "default": 5}
+                        "min_lines": {"type": "integer", "description": "Minimum lines to consider duplicate", "default": 5},
+                        "min_tokens": {"type": "integer", "description": "Minimum matching tokens for Rust, JavaScript/TypeScript and Go", "default": 50}
                     },
                     "required": ["path"]
                 }
@@ -172,6 +220,19 @@ class CodeAnalysisSkill(BaseSkill):
                     "required": ["path"]
                 }
             },
+            {
+                "name": "analysis_symbols",
+                "description": "Outline a source file (types, functions, methods), or search symbols by name across a directory. Supports Rust, Python, JavaScript and TypeScript",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {
+                        "path": {"type": "string", "description": "File to outline, or directory to search"},
+                        "query": {"type": "string", "description": "Fuzzy symbol name to search for in a directory (empty lists everything)", "default": ""},
+                        "limit": {"type": "integer", "description": "Maximum search results", "default": 50}
+                    },
+                    "required": ["path"]
+                }
+            },
         ]
 
     def execute(self, tool_name: str, tool_input: Dict[str, Any]) -> SkillResponse:
@@ -190,6 +251,7 @@ class CodeAnalysisSkill(BaseSkill):
                 "analysis_dead_code": self._analysis_dead_code,
                 "analysis_duplicates": self._analysis_duplicates,
                 "analysis_metrics": self._analysis_metrics,
+                "analysis_symbols": self._analysis_symbols,
             }
 
             if tool_name not in handlers:
@@ -227,6 +289,44 @@ class CodeAnalysisSkill(BaseSkill):
         """Check if path should be excluded."""
         return any(excl in str(path) for excl in self.exclude_patterns)
 
+    def _python_files(self, path: Path) -> List[Path]:
+        """Python sources under path (a single non-Python file yields none)."""
+        if path.is_dir():
+            return list(path.rglob("*.py"))
+        return [] if syntax.language_for(path) else [path]
+
+    def _syntax_files(self, path: Path) -> List[Path]:
+        """Rust, JavaScript/TypeScript and Go sources handled by the tree-sitter layer."""
+        if not path.is_dir():
+            return [path] if syntax.language_for(path) else []
+
+        return sorted(
+            file_path for file_path in path.rglob("*")
+            if syntax.language_for(file_path)
+            and file_path.is_file()
+            and not self.SYNTAX_SKIP_DIRS.intersection(file_path.relative_to(path).parts)
+            and not self._should_exclude(file_path)
+        )
+
+    def _syntax_metrics(self, files: List[Path]) -> Dict[Path, "syntax.SourceMetrics"]:
+        """Parse files with tree-sitter; files whose grammar is unavailable are left out."""
+        parsed = {}
+        for file_path in files:
+            metrics = syntax.analyze_file(file_path)
+            if metrics is not None:
+                parsed[file_path] = metrics
+        return parsed
+
+    def _syntax_note(self, files: List[Path], parsed: Dict[Path, Any]) -> Optional[str]:
+        """Explain skipped sources when tree-sitter or a grammar is missing."""
+        skipped = len(files) - len(parsed)
+        if not skipped:
+            return None
+        return (
+            f"{skipped} Rust/JavaScript/TypeScript/Go files skipped: "
+            "install tree-sitter and the grammar packages (pip install gathering[syntax])"
+        )
+
     def _analysis_lint(self, tool_input: Dict[str, Any]) -> SkillResponse:
         """Run linting."""
         path = self._get_path(tool_input)
@@ -236,6 +336,10 @@ class CodeAnalysisSkill(BaseSkill):
         if not path.exists():
             return SkillResponse(success=False, message=f"Path not found: {path}", error="not_found")
 
+        is_rust = path.suffix == ".rs" or (path.is_dir() and (path / "Cargo.toml").exists())
+        if tool in ("clippy", "rustfmt") or (tool == "auto" and is_rust):
+            return self._rust_lint(path, tool, fix)
+
         # Auto-detect available linter
         if tool == "auto":
             for linter in ["ruff", "flake8", "pylint"]:
@@ -383,6 +487,16 @@ class CodeAnalysisSkill(BaseSkill):
             if severity_levels.get(f["issue_severity"].lower(), 0) >= min_level
         ]
 
+        # Rust crates: advisories for the locked dependencies
+        rust_audit = None
+        lockfile = self._cargo_lockfile(path)
+        if lockfile is not None:
+            rust_audit = self._cargo_audit(lockfile, tool_input)
+            all_findings.extend(
+                f for f in rust_audit.pop("findings") if severity_levels.get(f["severity"], 0) >= min_level
+            )
+            rust_audit["lockfile"] = str(lockfile)
+
         # Sort by severity
         all_findings.sort(key=lambda x: -severity_levels.get(x["severity"], 0))
 
@@ -397,6 +511,7 @@ class CodeAnalysisSkill(BaseSkill):
                     for sev in severity_levels.keys()
                 },
                 "files_scanned": len(files),
+                "rust_audit": rust_audit,
             }
         )
 
@@ -409,7 +524,7 @@ class CodeAnalysisSkill(BaseSkill):
             return SkillResponse(success=False, message=f"Path not found: {path}", error="not_found")
 
         results = []
-        files = list(path.rglob("*.py")) if path.is_dir() else [path]
+        files = self._python_files(path)
 
         for file_path in files:
             if self._should_exclude(file_path):
@@ -420,38 +535,67 @@ class CodeAnalysisSkill(BaseSkill):
                     source = f.read()
 
                 tree = ast.parse(source)
+                lines = source.splitlines()
 
                 for node in ast.walk(tree):
                     if isinstance(node, (ast.FunctionDef, ast.AsyncFunctionDef)):
                         complexity = self._calculate_complexity(node)
 
                         if complexity >= threshold:
+                            end_line = getattr(node, "end_lineno", node.lineno)
                             results.append({
                                 "file": str(file_path),
                                 "function": node.name,
                                 "line": node.lineno,
                                 "complexity": complexity,
                                 "over_threshold": complexity >= threshold,
+                                "lines": end_line - node.lineno + 1,
+                                "loc": sum(
+                                    1 for line in lines[node.lineno - 1:end_line]
+                                    if line.strip() and not line.strip().startswith("#")
+                                ),
+                                "nesting_depth": self._nesting_depth(node),
                             })
 
             except (SyntaxError, UnicodeDecodeError):
                 continue
 
+        syntax_files = self._syntax_files(path)
+        parsed = self._syntax_metrics(syntax_files)
+        for file_path, metrics in parsed.items():
+            for function in metrics.functions:
+                if function.complexity >= threshold:
+                    results.append({
+                        "file": str(file_path),
+                        "function": function.name,
+                        "line": function.line,
+                        "complexity": function.complexity,
+                        "over_threshold": function.complexity >= threshold,
+                        "lines": function.length,
+                        "loc": function.loc,
+                        "nesting_depth": function.nesting_depth,
+                    })
+
         # Sort by complexity
         results.sort(key=lambda x: -x["complexity"])
 
         avg_complexity = sum(r["complexity"] for r in results) / len(results) if results else 0
 
+        data = {
+            "complex_functions": results[:20],
+            "total_functions": len(results),
+            "over_threshold": len([r for r in results if r["over_threshold"]]),
+            "average_complexity": round(avg_complexity, 2),
+            "threshold": threshold,
+        }
+        note = self._syntax_note(syntax_files, parsed)
+        if note:
+            data["note"] = note
+
         return SkillResponse(
             success=True,
-            message=f"Analyzed {len(files)} files",
-            data={
-                "complex_functions": results[:20],
-                "total_functions": len(results),
-                "over_threshold": len([r for r in results if r["over_threshold"]]),
-                "average_complexity": round(avg_complexity, 2),
-                "threshold": threshold,
-            }
+            message=f"Analyzed {len(files) + len(syntax_files)} files",
+            data=data,
         )
 
     def _calculate_complexity(self, node: ast.AST) -> int:
@@ -472,6 +616,23 @@ class CodeAnalysisSkill(BaseSkill):
 
         return complexity
 
+    def _nesting_depth(self, node: ast.AST, depth: int = 0) -> int:
+        """Deepest block nesting inside a function (elif chains stay at one level)."""
+        deepest = depth
+        for child in ast.iter_child_nodes(node):
+            if isinstance(child, (ast.FunctionDef, ast.AsyncFunctionDef, ast.ClassDef, ast.Lambda)):
+                continue
+            nested = isinstance(child, (
+                ast.If, ast.For, ast.AsyncFor, ast.While, ast.With, ast.AsyncWith, ast.Try, ast.TryStar, ast.Match,
+            ))
+            if (
+                isinstance(child, ast.If) and isinstance(node, ast.If)
+                and len(node.orelse) == 1 and node.orelse[0] is child
+            ):
+                nested = False
+            deepest = max(deepest, self._nesting_depth(child, depth + 1 if nested else depth))
+        return deepest
+
     def _analysis_dependencies(self, tool_input: Dict[str, Any]) -> SkillResponse:
         """Analyze project dependencies."""
         path = self._get_path(tool_input)
@@ -510,6 +671,11 @@ class CodeAnalysisSkill(BaseSkill):
                 except (UnicodeDecodeError, PermissionError):
                     continue
 
+        # Rust projects: checked from Cargo.lock (Python requirement files are still listed)
+        lockfile = self._cargo_lockfile(path)
+        if lockfile is not None:
+            return self._cargo_dependencies(lockfile, req_files, dependencies, tool_input)
+
         # Check for vulnerabilities using pip-audit if available
         vulnerabilities = []
         if check_vulnerabilities:
@@ -606,6 +772,30 @@ class CodeAnalysisSkill(BaseSkill):
         if not path.exists():
             return SkillResponse(success=False, message=f"Path not found: {path}", error="not_found")
 
+        # Rust, JavaScript/TypeScript and Go: imports never referenced in their file
+        syntax_files = self._syntax_files(path)
+        parsed = self._syntax_metrics(syntax_files)
+        syntax_unused = [
+            {"file": str(file_path), "import": name, "line": line}
+            for file_path, metrics in parsed.items()
+            for name, line in metrics.unused_imports
+        ]
+        note = self._syntax_note(syntax_files, parsed)
+
+        if not self._python_files(path):
+            data = {
+                "unused_imports": syntax_unused[:50],
+                "total": len(syntax_unused),
+                "tool": "tree-sitter",
+            }
+            if note:
+                data["note"] = note
+            return SkillResponse(
+                success=True,
+                message=f"Found {len(syntax_unused)} potentially unused imports",
+                data=data,
+            )
+
         # Try vulture if available
         try:
             result = subprocess.run(
@@ -624,21 +814,28 @@ class CodeAnalysisSkill(BaseSkill):
                         "line": int(match.group(2)),
                         "message": match.group(3),
                     })
+            dead_code.extend(
+                {"file": item["file"], "line": item["line"], "message": f"unused import '{item['import']}'"}
+                for item in syntax_unused
+            )
 
+            data = {
+                "dead_code": dead_code[:50],
+                "total": len(dead_code),
+                "tool": "vulture",
+            }
+            if note:
+                data["note"] = note
             return SkillResponse(
                 success=True,
                 message=f"Found {len(dead_code)} unused code items",
-                data={
-                    "dead_code": dead_code[:50],
-                    "total": len(dead_code),
-                    "tool": "vulture",
-                }
+                data=data,
             )
 
         except FileNotFoundError:
             # Fallback: basic unused import detection
             unused_imports = []
-            files = list(path.rglob("*.py")) if path.is_dir() else [path]
+            files = self._python_files(path)
 
             for file_path in files:
                 if self._should_exclude(file_path):
@@ -676,6 +873,7 @@ class CodeAnalysisSkill(BaseSkill):
                 except (SyntaxError, UnicodeDecodeError):
                     continue
 
+            unused_imports.extend(syntax_unused)
             return SkillResponse(
                 success=True,
                 message=f"Found {len(unused_imports)} potentially unused imports",
@@ -683,7 +881,7 @@ class CodeAnalysisSkill(BaseSkill):
                     "unused_imports": unused_imports[:50],
                     "total": len(unused_imports),
                     "tool": "ast",
-                    "note": "Install vulture for more comprehensive analysis",
+                    "note": "Install vulture for more comprehensive analysis" + (f". {note}" if note else ""),
                 }
             )
 
@@ -691,6 +889,7 @@ class CodeAnalysisSkill(BaseSkill):
         """Find duplicate code."""
         path = self._get_path(tool_input)
         min_lines = tool_input.get("min_lines", 5)
+        min_tokens = tool_input.get("min_tokens", 50)
 
         if not path.exists():
             return SkillResponse(success=False, message=f"Path not found: {path}", error="not_found")
@@ -699,7 +898,7 @@ class CodeAnalysisSkill(BaseSkill):
         code_blocks = {}
         duplicates = []
 
-        files = list(path.rglob("*.py")) if path.is_dir() else [path]
+        files = self._python_files(path)
 
         for file_path in files:
             if self._should_exclude(file_path):
@@ -739,15 +938,32 @@ class CodeAnalysisSkill(BaseSkill):
             except (UnicodeDecodeError, PermissionError):
                 continue
 
+        # Rust, JavaScript/TypeScript and Go: normalized token windows, so renamed
+        # identifiers and changed literals still match
+        syntax_files = self._syntax_files(path)
+        parsed = self._syntax_metrics(syntax_files)
+        duplicates.extend(syntax.find_duplicates(
+            {str(file_path): metrics.tokens for file_path, metrics in parsed.items()},
+            min_tokens=min_tokens,
+            min_lines=min_lines,
+        ))
+
+        data = {
+            "duplicates": duplicates[:20],
+            "total": len(duplicates),
+            "min_lines": min_lines,
+            "files_scanned": len(files) + len(parsed),
+        }
+        if syntax_files:
+            data["min_tokens"] = min_tokens
+        note = self._syntax_note(syntax_files, parsed)
+        if note:
+            data["note"] = note
+
         return SkillResponse(
             success=True,
             message=f"Found {len(duplicates)} duplicate blocks",
-            data={
-                "duplicates": duplicates[:20],
-                "total": len(duplicates),
-                "min_lines": min_lines,
-                "files_scanned": len(files),
-            }
+            data=data,
         )
 
     def _analysis_metrics(self, tool_input: Dict[str, Any]) -> SkillResponse:
@@ -817,11 +1033,589 @@ class CodeAnalysisSkill(BaseSkill):
             except (UnicodeDecodeError, PermissionError):
                 continue
 
+        # Rust, JavaScript/TypeScript and Go through tree-sitter
+        syntax_files = self._syntax_files(path)
+        parsed = self._syntax_metrics(syntax_files)
+        for source_metrics in parsed.values():
+            metrics["total_classes"] += source_metrics.classes
+            metrics["total_functions"] += len(source_metrics.functions)
+            function_lengths.extend(function.length for function in source_metrics.functions)
+
         if function_lengths:
             metrics["avg_function_length"] = round(sum(function_lengths) / len(function_lengths), 1)
 
+        note = self._syntax_note(syntax_files, parsed)
+        if note:
+            metrics["note"] = note
+
         return SkillResponse(
             success=True,
             message=f"Analyzed {metrics['total_files']} files",
             data=metrics
         )
+
+    def _analysis_symbols(self, tool_input: Dict[str, Any]) -> SkillResponse:
+        """Outline a file or search symbols across a directory."""
+        path = self._get_path(tool_input)
+
+        if not path.exists():
+            return SkillResponse(success=False, message=f"Path not found: {path}", error="not_found")
+
+        def compact(symbol: Dict[str, Any]) -> Dict[str, Any]:
+            entry = {
+                "name": symbol["name"],
+                "kind": SYMBOL_KIND_NAMES.get(symbol["kind"], "symbol"),
+                "line": symbol["range"]["start"]["line"] + 1,
+                "end_line": symbol["range"]["end"]["line"] + 1,
+            }
+            if symbol.get("detail"):
+                entry["detail"] = symbol["detail"]
+            if symbol.get("children"):
+                entry["children"] = [compact(child) for child in symbol["children"]]
+            return entry
+
+        if path.is_file():
+            outliner = self.OUTLINERS.get(path.suffix.lower())
+            if outliner is None:
+                return SkillResponse(
+                    success=False,
+                    message=f"Unsupported file type: {path.suffix}",
+                    error="unsupported_language",
+                )
+
+            symbols = outliner(path.read_text(encoding="utf-8"))
+            return SkillResponse(
+                success=True,
+                message=f"Outlined {len(symbols)} top-level symbols in {path.name}",
+                data={"file": str(path), "symbols": [compact(s) for s in symbols]},
+            )
+
+        def candidates():
+            for file_path in source_files(path, self.OUTLINERS):
+                if self._should_exclude(file_path):
+                    continue
+                try:
+                    source = file_path.read_text(encoding="utf-8")
+                except (OSError, UnicodeDecodeError):
+                    continue
+                outline = self.OUTLINERS[file_path.suffix.lower()](source)
+                yield from flatten(outline, str(file_path.relative_to(path)))
+
+        matches = search(candidates(), tool_input.get("query", ""), tool_input.get("limit", 50))
+        symbols = [
+            {
+                "name": symbol["name"],
+                "kind": SYMBOL_KIND_NAMES.get(symbol["kind"], "symbol"),
+                "file": symbol["location"]["uri"],
+                "line": symbol["location"]["range"]["start"]["line"] + 1,
+                "container": symbol["containerName"],
+            }
+            for symbol in matches
+        ]
+
+        return SkillResponse(
+            success=True,
+            message=f"Found {len(symbols)} symbols",
+            data={"symbols": symbols, "total": len(symbols)},
+        )
+
+    # =========================================================================
+    # Rust (cargo)
+    # =========================================================================
+
+    @staticmethod
+    def _cargo_package_root(path: Path) -> Optional[Path]:
+        """Nearest directory at or above path with a Cargo.toml."""
+        start = path if path.is_dir() else path.parent
+        for directory in [start, *start.parents]:
+            if (directory / "Cargo.toml").exists():
+                return directory
+        return None
+
+    def _cargo_lockfile(self, path: Path) -> Optional[Path]:
+        """Cargo.lock of the package (or workspace) containing path."""
+        if path.name == "Cargo.lock":
+            return path
+        root = self._cargo_package_root(path)
+        if root is None:
+            return None
+        for directory in [root, *root.parents]:
+            if (directory / "Cargo.lock").exists():
+                return directory / "Cargo.lock"
+        return None
+
+    @staticmethod
+    def _run_cargo(args: List[str], cwd: Path, timeout: int) -> subprocess.CompletedProcess:
+        """Run a cargo command with plain (uncolored) output."""
+        return subprocess.run(
+            ["cargo", *args],
+            cwd=str(cwd),
+            capture_output=True,
+            text=True,
+            timeout=timeout,
+            env={**os.environ, "CARGO_TERM_COLOR": "never"},
+        )
+
+    def _cargo_workspace_root(self, root: Path) -> Path:
+        """
+        Cargo workspace root of a package, from `cargo metadata`.
+
+        Falls back to the package itself when cargo cannot tell.
+        """
+        try:
+            result = self._run_cargo(["metadata", "--no-deps", "--format-version", "1"], root, 60)
+            return Path(json.loads(result.stdout)["workspace_root"])
+        except (subprocess.TimeoutExpired, OSError, json.JSONDecodeError, KeyError, TypeError):
+            return root
+
+    def _parse_clippy_output(self, output: str, root: Path) -> List[Dict[str, Any]]:
+        """
+        Issues from `cargo clippy --message-format=json` output.
+
+        Span paths are relative to the Cargo workspace root, passed as
+        root. The same warning reported for several targets (lib and lib
+        test) is kept once.
+        """
+        issues = []
+        seen = set()
+
+        for line in output.splitlines():
+            try:
+                message = json.loads(line)
+            except json.JSONDecodeError:
+                continue
+            if message.get("reason") != "compiler-message":
+                continue
+
+            diagnostic = message["message"]
+            spans = [span for span in diagnostic.get("spans", []) if span.get("is_primary")]
+            if not spans:
+                continue  # "N warnings emitted" summaries
+
+            span = spans[0]
+            code = (diagnostic.get("code") or {}).get("code")
+            suggestion = next((
+                child_span["suggested_replacement"]
+                for child in diagnostic.get("children", [])
+                for child_span in child.get("spans", [])
+                if child_span.get("suggested_replacement") is not None
+            ), None)
+
+            issue = {
+                "file": str(root / span["file_name"]),
+                "line": span["line_start"],
+                "column": span["column_start"],
+                "end_line": span["line_end"],
+                "end_column": span["column_end"],
+                "code": code,
+                "level": diagnostic["level"],
+                "message": diagnostic["message"],
+                "suggestion": suggestion,
+                "source": "clippy",
+            }
+            key = (issue["file"], issue["line"], issue["column"], code, issue["message"])
+            if key not in seen:
+                seen.add(key)
+                issues.append(issue)
+
+        return issues
+
+    @staticmethod
+    def _parse_rustfmt_check(output: str) -> List[Dict[str, Any]]:
+        """One issue per "Diff in <file>:<line>:" hunk of `rustfmt --check`."""
+        issues = []
+        for match in re.finditer(r"^Diff in (.+?):(\d+):\n(.*?)(?=^Diff in |\Z)", output, re.MULTILINE | re.DOTALL):
+            issues.append({
+                "file": match.group(1),
+                "line": int(match.group(2)),
+                "column": 1,
+                "code": "rustfmt",
+                "level": "warning",
+                "message": "Formatting differs from rustfmt",
+                "diff": match.group(3).rstrip("\n"),
+                "source": "rustfmt",
+            })
+        return issues
+
+    def _rust_lint(self, path: Path, tool: str, fix: bool) -> SkillResponse:
+        """Lint a Rust crate with clippy and/or rustfmt --check."""
+        root = self._cargo_package_root(path)
+        if root is None:
+            return SkillResponse(success=False, message=f"No Cargo.toml found for {path}", error="not_a_cargo_project")
+
+        tools = ["clippy", "rustfmt"] if tool == "auto" else [tool]
+        issues: List[Dict[str, Any]] = []
+        errors = []
+
+        try:
+            if "clippy" in tools:
+                if fix:
+                    # Like ruff --fix: apply fixes regardless of the VCS state
+                    result = self._run_cargo(
+                        ["clippy", "--fix", "--allow-dirty", "--allow-staged", "--allow-no-vcs", "--all-targets"],
+                        root, self.CARGO_TIMEOUT
+                    )
+                    if result.returncode != 0:
+                        errors.append(result.stderr.strip()[-2000:])
+                result = self._run_cargo(["clippy", "--all-targets", "--message-format=json"], root, self.CARGO_TIMEOUT)
+                issues.extend(self._parse_clippy_output(result.stdout, self._cargo_workspace_root(root)))
+                if result.returncode != 0 and not any(i["level"] == "error" for i in issues):
+                    errors.append(result.stderr.strip()[-2000:])
+
+            if "rustfmt" in tools:
+                if path.suffix == ".rs":
+                    # A single file: rustfmt directly, with the package edition
+                    edition = self._cargo_edition(root)
+                    cmd = ["rustfmt", "--edition", edition, "--color", "never", str(path)]
+                    if not fix:
+                        cmd.insert(1, "--check")
+                    result = subprocess.run(cmd, capture_output=True, text=True, timeout=120)
+                else:
+                    args = ["fmt"] if fix else ["fmt", "--check", "--", "--color", "never"]
+                    result = self._run_cargo(args, root, 120)
+                issues.extend(self._parse_rustfmt_check(result.stdout))
+                if result.returncode not in (0, 1) or (result.returncode == 1 and not result.stdout.strip()):
+                    errors.append(result.stderr.strip()[-2000:])
+
+        except subprocess.TimeoutExpired:
+            return SkillResponse(success=False, message="Linting timed out", error="timeout")
+        except FileNotFoundError:
+            return SkillResponse(
+                success=False,
+                message="cargo not installed",
+                error="tool_not_found",
+                data={"install_command": "rustup component add clippy rustfmt"}
+            )
+
+        # Crate-wide tools: report only the requested file or directory
+        if path != root:
+            target = path.resolve()
+            issues = [i for i in issues if Path(i["file"]).resolve() == target or target in Path(i["file"]).resolve().parents]
+
+        return SkillResponse(
+            success=not issues and not errors,
+            message=f"Found {len(issues)} issues" if issues else ("Linting failed" if errors else "No issues found"),
+            data={
+                "tool": tools[0],
+                "tools": tools,
+                "issues": issues[:100],  # Limit output
+                "total_issues": len(issues),
+                "by_code": self._count_by(issues, "code"),
+                "errors": errors,
+                "fixed": fix,
+                "path": str(path),
+            }
+        )
+
+    @staticmethod
+    def _count_by(items: List[Dict[str, Any]], key: str) -> Dict[str, int]:
+        """Number of items per value of a key."""
+        counts: Dict[str, int] = {}
+        for item in items:
+            value = item.get(key) or "unknown"
+            counts[value] = counts.get(value, 0) + 1
+        return counts
+
+    @staticmethod
+    def _cargo_edition(root: Path) -> str:
+        """Edition of a package, following `edition.workspace = true`."""
+        for directory in [root, *root.parents]:
+            try:
+                manifest = tomllib.loads((directory / "Cargo.toml").read_text())
+            except (OSError, tomllib.TOMLDecodeError):
+                continue
+            package = manifest.get("package") if directory == root else (manifest.get("workspace") or {}).get("package")
+            edition = (package or {}).get("edition")
+            if isinstance(edition, str):
+                return edition
+            if not isinstance(edition, dict):
+                break
+        return "2015"
+
+    def _cvss_severity(self, vector: Optional[str]) -> tuple:
+        """
+        (base score, severity) of a CVSS v3 vector, e.g.
+        "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H" -> (9.8, "critical").
+
+        Unscored advisories (or other CVSS versions) count as medium.
+        """
+        metrics = dict(part.split(":", 1) for part in (vector or "").split("/")[1:] if ":" in part)
+        if not (vector or "").startswith("CVSS:3") or not all(m in metrics for m in [*self.CVSS_WEIGHTS, "S"]):
+            return None, "medium"
+
+        try:
+            weight = {m: self.CVSS_WEIGHTS[m][metrics[m]] for m in self.CVSS_WEIGHTS}
+            if metrics["S"] == "C":
+                weight["PR"] = self.PR_CHANGED[metrics["PR"]]
+        except KeyError:
+            return None, "medium"
+
+        iss = 1 - (1 - weight["C"]) * (1 - weight["I"]) * (1 - weight["A"])
+        if metrics["S"] == "U":
+            impact = 6.42 * iss
+        else:
+            impact = 7.52 * (iss - 0.029) - 3.25 * (iss - 0.02) ** 15
+        exploitability = 8.22 * weight["AV"] * weight["AC"] * weight["PR"] * weight["UI"]
+
+        def roundup(value: float) -> float:
+            scaled = round(value * 100000)
+            return scaled / 100000 if scaled % 10000 == 0 else (math.floor(scaled / 10000) + 1) / 10
+
+        if impact <= 0:
+            score = 0.0
+        elif metrics["S"] == "U":
+            score = roundup(min(impact + exploitability, 10))
+        else:
+            score = roundup(min(1.08 * (impact + exploitability), 10))
+
+        if score >= 9.0:
+            return score, "critical"
+        if score >= 7.0:
+            return score, "high"
+        if score >= 4.0:
+            return score, "medium"
+        return score, "low" if score > 0 else "none"
+
+    @staticmethod
+    def _parse_cargo_lock(lockfile: Path) -> List[Dict[str, Any]]:
+        """[[package]] entries of a Cargo.lock, with their line numbers."""
+        content = lockfile.read_text()
+        packages = tomllib.loads(content).get("package", [])
+
+        # tomllib has no positions: find each `name = "x"` / `version = "y"` pair
+        lines = content.split("\n")
+        positions = {}
+        for number, line in enumerate(lines):
+            name = re.match(r'name = "(.+)"$', line)
+            if name and number + 1 < len(lines):
+                version = re.match(r'version = "(.+)"$', lines[number + 1])
+                if version:
+                    positions[(name.group(1), version.group(1))] = number + 1
+
+        for package in packages:
+            package["line"] = positions.get((package["name"], package["version"]))
+        return packages
+
+    def _advisory_db_path(self, tool_input: Dict[str, Any]) -> Path:
+        """Advisory-db checkout: tool input, skill config, settings, then cargo audit's default."""
+        path = tool_input.get("advisory_db") or self.advisory_db
+        if not path:
+            try:
+                from gathering.core.config import get_settings
+                path = get_settings().cargo_advisory_db
+            except Exception:
+                path = None
+        return Path(path or Path.home() / ".cargo" / "advisory-db").expanduser()
+
+    def _load_advisories(self, db_path: Path, names: set) -> Dict[str, List[Dict[str, Any]]]:
+        """RustSec advisories (TOML front matter of crates/<name>/*.md) for the given crates."""
+        advisories: Dict[str, List[Dict[str, Any]]] = {}
+        for name in names:
+            directory = db_path / "crates" / name
+            if not directory.is_dir():
+                continue
+            for file_path in sorted(directory.glob("*.md")):
+                text = file_path.read_text(errors="replace")
+                front = re.match(r"\s*```toml\n(.*?)\n```\s*(.*)", text, re.DOTALL)
+                if not front:
+                    continue
+                try:
+                    data = tomllib.loads(front.group(1))
+                except tomllib.TOMLDecodeError:
+                    continue
+                advisory = data.get("advisory", {})
+                if advisory.get("withdrawn"):
+                    continue
+                title = re.match(r"#\s*(.+)", front.group(2).strip())
+                advisory["title"] = advisory.get("title") or (title.group(1).strip() if title else advisory.get("id"))
+                advisories.setdefault(name, []).append({"advisory": advisory, "versions": data.get("versions", {})})
+        return advisories
+
+    @staticmethod
+    def _version_matches(version: str, requirements: List[str]) -> bool:
+        """Whether a version satisfies any of the requirements."""
+        from gathering.lsp.rust_manifest import VersionReq
+
+        for requirement in requirements:
+            try:
+                if VersionReq(requirement).matches(version):
+                    return True
+            except ValueError:
+                continue
+        return False
+
+    def _cargo_audit(self, lockfile: Path, tool_input: Dict[str, Any]) -> Dict[str, Any]:
+        """
+        Vulnerable and unmaintained crates of a Cargo.lock.
+
+        Runs `cargo audit` offline against the advisory-db checkout; without
+        cargo-audit the checkout is matched against Cargo.lock directly.
+
+        Returns:
+            {"tool", "advisory_db", "findings", "error"}
+        """
+        db_path = self._advisory_db_path(tool_input)
+        audit = {"tool": None, "advisory_db": str(db_path), "findings": [], "error": None}
+        if not db_path.is_dir():
+            audit["error"] = f"advisory-db not found at {db_path}"
+            return audit
+
+        packages = self._parse_cargo_lock(lockfile)
+        lines = {(p["name"], p["version"]): p["line"] for p in packages}
+
+        def finding(advisory: Dict[str, Any], name: str, version: str, patched: List[str], source: str) -> Dict[str, Any]:
+            informational = advisory.get("informational")
+            score, severity = self._cvss_severity(advisory.get("cvss"))
+            return {
+                "file": str(lockfile),
+                "line": lines.get((name, version)),
+                "category": advisory.get("id"),
+                "severity": "low" if informational else severity,
+                "code": f"{name} {version}",
+                "message": advisory.get("title"),
+                "kind": informational or "vulnerability",
+                "cvss_score": score,
+                "patched_versions": patched,
+                "url": advisory.get("url") or f"https://rustsec.org/advisories/{advisory.get('id')}",
+                "source": source,
+            }
+
+        try:
+            result = self._run_cargo(
+                ["audit", "--json", "--no-fetch", "--stale", "--db", str(db_path), "--file", str(lockfile)],
+                lockfile.parent, 120
+            )
+            report = json.loads(result.stdout)
+        except (FileNotFoundError, subprocess.TimeoutExpired, json.JSONDecodeError):
+            report = None
+
+        if report is not None:
+            audit["tool"] = "cargo-audit"
+            entries = list((report.get("vulnerabilities") or {}).get("list", []))
+            for warnings in (report.get("warnings") or {}).values():
+                entries.extend(w for w in warnings if w.get("advisory"))
+            for entry in entries:
+                package = entry.get("package", {})
+                audit["findings"].append(finding(
+                    entry["advisory"], package.get("name"), package.get("version"),
+                    (entry.get("versions") or {}).get("patched", []), "cargo-audit"
+                ))
+            return audit
+
+        # No cargo-audit: match the advisories ourselves
+        audit["tool"] = "advisory-db"
+        registry_packages = [p for p in packages if str(p.get("source", "")).startswith("registry+")]
+        advisories = self._load_advisories(db_path, {p["name"] for p in registry_packages})
+        for package in registry_packages:
+            for entry in advisories.get(package["name"], []):
+                versions = entry["versions"]
+                patched = versions.get("patched", [])
+                if self._version_matches(package["version"], patched + versions.get("unaffected", [])):
+                    continue
+                audit["findings"].append(finding(entry["advisory"], package["name"], package["version"], patched, "advisory-db"))
+
+        return audit
+
+    def _crate_registry(self, tool_input: Dict[str, Any]):
+        """Local crates.io index mirror: tool input, skill config, then settings."""
+        from gathering.lsp.rust_manifest import CrateRegistry
+
+        path = tool_input.get("registry_index") or self.registry_index
+        if not path:
+            try:
+                from gathering.core.config import get_settings
+                path = get_settings().cargo_registry_index
+            except Exception:
+                path = None
+        if path and Path(path).is_dir():
+            return CrateRegistry(str(path))
+        return None
+
+    def _cargo_dependencies(
+        self,
+        lockfile: Path,
+        req_files: List[Path],
+        python_dependencies: List[Dict[str, Any]],
+        tool_input: Dict[str, Any],
+    ) -> SkillResponse:
+        """Duplicate, outdated, yanked and vulnerable crates of a Cargo.lock."""
+        from gathering.lsp.rust_manifest import parse_version
+
+        packages = self._parse_cargo_lock(lockfile)
+        # Workspace members and path dependencies have no source
+        external = [p for p in packages if p.get("source")]
+
+        dependencies = python_dependencies + [
+            {
+                "name": p["name"],
+                "version_spec": "=",
+                "version": p["version"],
+                "source": str(lockfile),
+                "registry": p["source"].split("+", 1)[0],
+            }
+            for p in external
+        ]
+
+        versions: Dict[str, List[str]] = {}
+        for package in external:
+            versions.setdefault(package["name"], []).append(package["version"])
+        duplicates = [
+            {"name": name, "versions": sorted(found, key=lambda v: parse_version(v) or (0, 0, 0, v))}
+            for name, found in sorted(versions.items())
+            if len(found) > 1
+        ]
+
+        outdated = []
+        yanked = []
+        registry = self._crate_registry(tool_input)
+        if registry is not None:
+            for package in external:
+                if not package["source"].startswith("registry+"):
+                    continue
+                name, version = package["name"], package["version"]
+                published = {entry.get("vers"): entry for entry in registry.versions(name)}
+                if published.get(version, {}).get("yanked"):
+                    yanked.append({"name": name, "version": version, "line": package["line"]})
+
+                if not tool_input.get("check_outdated", True):
+                    continue
+                latest = registry.latest(name)
+                current = parse_version(version)
+                if latest and current and parse_version(latest["vers"])[:3] > current[:3]:
+                    # Newest version `cargo update` can reach without editing Cargo.toml
+                    compatible = registry.latest(name, f"^{version}")
+                    outdated.append({
+                        "name": name,
+                        "version": version,
+                        "latest_version": latest["vers"],
+                        "compatible_version": compatible["vers"] if compatible and compatible["vers"] != version else None,
+                    })
+
+        vulnerabilities = []
+        audit = None
+        if tool_input.get("check_vulnerabilities", True):
+            audit = self._cargo_audit(lockfile, tool_input)
+            vulnerabilities = audit.pop("findings")
+
+        manifests = sorted(
+            p for p in lockfile.parent.rglob("Cargo.toml")
+            if "target" not in p.relative_to(lockfile.parent).parts
+        )
+
+        return SkillResponse(
+            success=not yanked and not vulnerabilities,
+            message=(
+                f"Found {len(dependencies)} dependencies: {len(duplicates)} duplicated, "
+                f"{len(outdated)} outdated, {len(yanked)} yanked"
+            ),
+            data={
+                "dependencies": dependencies,
+                "vulnerabilities": vulnerabilities,
+                "outdated": outdated,
+                "duplicates": duplicates,
+                "yanked": yanked,
+                "requirement_files": [str(f) for f in req_files] + [str(m) for m in manifests] + [str(lockfile)],
+                "registry_index": str(registry.index_path) if registry else None,
+                "audit": audit,
+            }
+        )
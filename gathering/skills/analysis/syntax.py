@@ -0,0 +1,601 @@
+"""
+Tree-sitter backed source metrics for non-Python languages.
+
+Parses Rust, JavaScript/TypeScript and Go and reports, per function,
+cyclomatic complexity, length, nesting depth and lines of code, along
+with class-like definitions, unused imports and a normalized token
+stream for duplicate detection. Python files keep going through ``ast``
+in the analysis skill; this module covers everything else.
+
+Grammars are loaded from the per-language wheels (``tree-sitter-rust``,
+``tree-sitter-javascript``, ``tree-sitter-typescript``,
+``tree-sitter-go``) or, failing that, from
+``tree-sitter-language-pack``. Without tree-sitter every entry point
+returns None and callers skip the file.
+"""
+
+import importlib
+import logging
+import re
+from dataclasses import dataclass, field
+from functools import lru_cache
+from pathlib import Path
+from typing import Any, Dict, FrozenSet, Iterator, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+try:
+    from tree_sitter import Language, Parser
+
+    TREE_SITTER_AVAILABLE = True
+except ImportError:
+    TREE_SITTER_AVAILABLE = False
+
+
+# File suffix -> grammar name
+EXTENSIONS = {
+    ".rs": "rust",
+    ".js": "javascript",
+    ".jsx": "javascript",
+    ".mjs": "javascript",
+    ".cjs": "javascript",
+    ".ts": "typescript",
+    ".mts": "typescript",
+    ".cts": "typescript",
+    ".tsx": "tsx",
+    ".go": "go",
+}
+
+# Grammar name -> (wheel module, language factory)
+GRAMMARS = {
+    "rust": ("tree_sitter_rust", "language"),
+    "javascript": ("tree_sitter_javascript", "language"),
+    "typescript": ("tree_sitter_typescript", "language_typescript"),
+    "tsx": ("tree_sitter_typescript", "language_tsx"),
+    "go": ("tree_sitter_go", "language"),
+}
+
+
+@dataclass(frozen=True)
+class LanguageSpec:
+    """Node types that drive the metrics for one grammar."""
+
+    functions: FrozenSet[str]
+    # Each occurrence adds a path through the function
+    decisions: FrozenSet[str]
+    # Each occurrence removes one path (the arm that is taken by default)
+    switches: FrozenSet[str]
+    boolean_operators: FrozenSet[str]
+    nesting: FrozenSet[str]
+    classes: FrozenSet[str]
+    comments: FrozenSet[str]
+    literals: FrozenSet[str]
+    imports: FrozenSet[str]
+    # Scopes whose name qualifies the functions defined inside them
+    containers: FrozenSet[str] = frozenset()
+
+
+_JS_SPEC = LanguageSpec(
+    functions=frozenset({
+        "function_declaration", "generator_function_declaration", "function_expression",
+        "function", "generator_function", "arrow_function", "method_definition",
+    }),
+    decisions=frozenset({
+        "if_statement", "for_statement", "for_in_statement", "while_statement",
+        "do_statement", "catch_clause", "ternary_expression", "switch_case",
+    }),
+    switches=frozenset(),
+    boolean_operators=frozenset({"&&", "||", "??"}),
+    nesting=frozenset({
+        "if_statement", "for_statement", "for_in_statement", "while_statement",
+        "do_statement", "switch_statement", "try_statement",
+    }),
+    classes=frozenset({"class_declaration", "class", "abstract_class_declaration"}),
+    comments=frozenset({"comment", "html_comment"}),
+    literals=frozenset({"string", "template_string", "number", "regex"}),
+    imports=frozenset({"import_statement"}),
+    containers=frozenset({"class_declaration", "class", "abstract_class_declaration"}),
+)
+
+SPECS = {
+    "rust": LanguageSpec(
+        functions=frozenset({"function_item"}),
+        decisions=frozenset({
+            "if_expression", "while_expression", "for_expression",
+            "match_arm", "last_match_arm",
+        }),
+        switches=frozenset({"match_expression"}),
+        boolean_operators=frozenset({"&&", "||"}),
+        nesting=frozenset({
+            "if_expression", "while_expression", "for_expression", "loop_expression",
+            "match_expression", "closure_expression",
+        }),
+        classes=frozenset({"struct_item", "enum_item", "union_item", "trait_item"}),
+        comments=frozenset({"line_comment", "block_comment"}),
+        literals=frozenset({
+            "string_literal", "raw_string_literal", "char_literal",
+            "integer_literal", "float_literal", "boolean_literal",
+        }),
+        imports=frozenset({"use_declaration"}),
+        containers=frozenset({"impl_item", "trait_item"}),
+    ),
+    "javascript": _JS_SPEC,
+    "typescript": _JS_SPEC,
+    "tsx": _JS_SPEC,
+    "go": LanguageSpec(
+        functions=frozenset({"function_declaration", "method_declaration"}),
+        decisions=frozenset({
+            "if_statement", "for_statement", "expression_case", "type_case", "communication_case",
+        }),
+        switches=frozenset(),
+        boolean_operators=frozenset({"&&", "||"}),
+        nesting=frozenset({
+            "if_statement", "for_statement", "expression_switch_statement",
+            "type_switch_statement", "select_statement", "func_literal",
+        }),
+        classes=frozenset({"type_spec"}),
+        comments=frozenset({"comment"}),
+        literals=frozenset({
+            "interpreted_string_literal", "raw_string_literal", "rune_literal",
+            "int_literal", "float_literal", "imaginary_literal",
+        }),
+        imports=frozenset({"import_declaration"}),
+    ),
+}
+
+
+@dataclass
+class FunctionMetrics:
+    """Metrics for one function or method."""
+
+    name: str
+    line: int
+    end_line: int
+    complexity: int
+    nesting_depth: int
+    loc: int
+
+    @property
+    def length(self) -> int:
+        return self.end_line - self.line + 1
+
+
+@dataclass
+class SourceMetrics:
+    """Everything the analysis skill needs from one parsed file."""
+
+    language: str
+    functions: List[FunctionMetrics] = field(default_factory=list)
+    classes: int = 0
+    # (name, line) of imports never referenced in the file
+    unused_imports: List[Tuple[str, int]] = field(default_factory=list)
+    # (normalized token, line); identifiers become "ID" and literals "LIT"
+    tokens: List[Tuple[str, int]] = field(default_factory=list)
+    has_errors: bool = False
+
+
+def language_for(path: Path) -> Optional[str]:
+    """Grammar name for a file, or None if it is not handled here."""
+    return EXTENSIONS.get(Path(path).suffix.lower())
+
+
+@lru_cache(maxsize=None)
+def _parser(language: str) -> Optional[Any]:
+    if not TREE_SITTER_AVAILABLE or language not in GRAMMARS:
+        return None
+
+    module_name, factory = GRAMMARS[language]
+    grammar = None
+    try:
+        module = importlib.import_module(module_name)
+        grammar = Language(getattr(module, factory)())
+    except ImportError:
+        for pack in ("tree_sitter_language_pack", "tree_sitter_languages"):
+            try:
+                grammar = importlib.import_module(pack).get_language(language)
+                break
+            except ImportError:
+                continue
+
+    if grammar is None:
+        logger.warning(f"tree-sitter grammar for {language} not available")
+        return None
+
+    try:
+        return Parser(grammar)
+    except TypeError:
+        # py-tree-sitter < 0.22
+        parser = Parser()
+        parser.set_language(grammar)
+        return parser
+
+
+def is_available(language: str) -> bool:
+    """Whether tree-sitter and the grammar for ``language`` can be loaded."""
+    return _parser(language) is not None
+
+
+def analyze_file(path: Path) -> Optional[SourceMetrics]:
+    """Parse a file and compute its metrics (None if unsupported or unreadable)."""
+    language = language_for(path)
+    if language is None or not is_available(language):
+        return None
+    try:
+        source = Path(path).read_bytes()
+    except OSError:
+        return None
+    return analyze_source(source, language)
+
+
+def analyze_source(source: Any, language: str) -> Optional[SourceMetrics]:
+    """Parse source text (str or bytes) with the given grammar and compute its metrics."""
+    parser = _parser(language)
+    if parser is None:
+        return None
+    if isinstance(source, str):
+        source = source.encode("utf-8")
+
+    root = parser.parse(source).root_node
+    spec = SPECS[language]
+    metrics = SourceMetrics(language=language, has_errors=root.has_error)
+
+    for node in _walk(root):
+        # `function` and `class` are also the names of JavaScript's keyword tokens
+        if not node.is_named:
+            continue
+        if node.type in spec.functions:
+            metrics.functions.append(_function_metrics(node, spec, source))
+        elif node.type in spec.classes:
+            metrics.classes += 1
+
+    metrics.tokens = list(_tokens(root, spec, source))
+    metrics.unused_imports = _unused_imports(root, language, spec, source)
+    return metrics
+
+
+def find_duplicates(
+    token_streams: Dict[str, List[Tuple[str, int]]],
+    min_tokens: int = 50,
+    min_lines: int = 5,
+) -> List[Dict[str, Any]]:
+    """
+    Find repeated token sequences across files.
+
+    Every window of ``min_tokens`` tokens is hashed; windows that repeat an
+    earlier one are chained while consecutive windows keep matching, so a
+    copied block is reported once at its full extent. Blocks spanning fewer
+    than ``min_lines`` lines are dropped, as are overlapping repeats within
+    one file.
+    """
+    seen: Dict[Tuple[str, ...], Tuple[str, int]] = {}
+    # (orig file, orig start, dup file, dup start) of the window that ends each open block
+    open_blocks: Dict[Tuple[str, int, str, int], Dict[str, Any]] = {}
+    blocks = []
+
+    for file_name, tokens in token_streams.items():
+        kinds = [kind for kind, _ in tokens]
+        for start in range(len(tokens) - min_tokens + 1):
+            window = tuple(kinds[start:start + min_tokens])
+            if window not in seen:
+                seen[window] = (file_name, start)
+                continue
+
+            orig_file, orig_start = seen[window]
+            if orig_file == file_name and start - orig_start < min_tokens:
+                continue
+
+            block = open_blocks.pop((orig_file, orig_start - 1, file_name, start - 1), None)
+            if block is None:
+                block = {
+                    "original": (orig_file, orig_start),
+                    "duplicate": (file_name, start),
+                    "tokens": min_tokens,
+                }
+                blocks.append(block)
+            else:
+                block["tokens"] += 1
+            open_blocks[(orig_file, orig_start, file_name, start)] = block
+
+    duplicates = []
+    for block in blocks:
+        located = {}
+        for role in ("original", "duplicate"):
+            file_name, start = block[role]
+            tokens = token_streams[file_name]
+            located[role] = {
+                "file": file_name,
+                "start_line": tokens[start][1],
+                "end_line": tokens[start + block["tokens"] - 1][1],
+            }
+        lines = located["duplicate"]["end_line"] - located["duplicate"]["start_line"] + 1
+        if lines >= min_lines:
+            duplicates.append({**located, "lines": lines, "tokens": block["tokens"]})
+
+    return duplicates
+
+
+# ----------------------------------------------------------------------
+# Tree walking
+# ----------------------------------------------------------------------
+
+
+def _walk(node: Any) -> Iterator[Any]:
+    stack = [node]
+    while stack:
+        current = stack.pop()
+        yield current
+        stack.extend(reversed(current.children))
+
+
+def _text(node: Any, source: bytes) -> str:
+    return source[node.start_byte:node.end_byte].decode("utf-8", "replace")
+
+
+def _is_else_if(node: Any) -> bool:
+    """An ``else if`` continues its parent's chain rather than nesting inside it."""
+    parent = node.parent
+    if parent is None:
+        return False
+    if parent.type == "else_clause":
+        return True
+    alternative = parent.child_by_field_name("alternative") if parent.type == node.type else None
+    return alternative is not None and alternative.start_byte == node.start_byte
+
+
+def _function_metrics(node: Any, spec: LanguageSpec, source: bytes) -> FunctionMetrics:
+    complexity = 1
+    max_depth = 0
+    code_lines = set()
+
+    # Complexity and nesting stop at nested functions, which are reported on their own
+    stack = [(child, 0) for child in node.children]
+    while stack:
+        current, depth = stack.pop()
+        kind = current.type
+
+        if kind in spec.comments:
+            continue
+        if current.child_count == 0:
+            code_lines.update(range(current.start_point[0], current.end_point[0] + 1))
+        if kind in spec.functions and current.is_named:
+            code_lines.update(_code_lines(current, spec))
+            continue
+
+        if kind in spec.decisions:
+            complexity += 1
+        elif kind in spec.switches:
+            complexity -= 1
+        elif kind == "binary_expression":
+            operator = current.child_by_field_name("operator")
+            if operator is not None and operator.type in spec.boolean_operators:
+                complexity += 1
+
+        if kind in spec.nesting and not _is_else_if(current):
+            depth += 1
+            max_depth = max(max_depth, depth)
+
+        stack.extend((child, depth) for child in current.children)
+
+    return FunctionMetrics(
+        name=_function_name(node, spec, source),
+        line=node.start_point[0] + 1,
+        end_line=node.end_point[0] + 1,
+        complexity=max(complexity, 1),
+        nesting_depth=max_depth,
+        loc=len(code_lines),
+    )
+
+
+def _code_lines(node: Any, spec: LanguageSpec) -> set:
+    lines = set()
+    stack = [node]
+    while stack:
+        current = stack.pop()
+        if current.type in spec.comments:
+            continue
+        if current.child_count == 0:
+            lines.update(range(current.start_point[0], current.end_point[0] + 1))
+        stack.extend(current.children)
+    return lines
+
+
+def _function_name(node: Any, spec: LanguageSpec, source: bytes) -> str:
+    name_node = node.child_by_field_name("name")
+    name = _text(name_node, source) if name_node is not None else None
+
+    parent = node.parent
+    if name is None and parent is not None:
+        # const handler = () => ..., { key: function () ... }, obj.prop = function () ...
+        for parent_type, field_name in (
+            ("variable_declarator", "name"),
+            ("pair", "key"),
+            ("assignment_expression", "left"),
+            ("field_definition", "property"),
+            ("public_field_definition", "name"),
+        ):
+            if parent.type == parent_type:
+                target = parent.child_by_field_name(field_name)
+                if target is not None:
+                    name = _text(target, source)
+                break
+    if name is None:
+        name = "<anonymous>"
+
+    container = _container_name(node, spec, source)
+    return f"{container}.{name}" if container else name
+
+
+def _container_name(node: Any, spec: LanguageSpec, source: bytes) -> Optional[str]:
+    if node.type == "method_declaration":
+        # Go: func (s *Server) Run() -> Server.Run
+        receiver = node.child_by_field_name("receiver")
+        if receiver is not None:
+            for child in _walk(receiver):
+                if child.type == "type_identifier":
+                    return _text(child, source)
+        return None
+
+    ancestor = node.parent
+    while ancestor is not None:
+        if ancestor.type in spec.containers:
+            # impl blocks are named by their (possibly generic) self type
+            target = ancestor.child_by_field_name("type" if ancestor.type == "impl_item" else "name")
+            if target is not None:
+                return _text(target, source).split("<")[0].strip()
+        ancestor = ancestor.parent
+    return None
+
+
+def _tokens(root: Any, spec: LanguageSpec, source: bytes) -> Iterator[Tuple[str, int]]:
+    stack = [root]
+    while stack:
+        node = stack.pop()
+        if node.type in spec.comments:
+            continue
+        line = node.start_point[0] + 1
+        if node.type in spec.literals:
+            yield "LIT", line
+        elif node.child_count == 0:
+            if node.is_named and node.type.endswith("identifier"):
+                yield "ID", line
+            elif node.is_named:
+                yield _text(node, source), line
+            else:
+                yield node.type, line
+        else:
+            stack.extend(reversed(node.children))
+
+
+# ----------------------------------------------------------------------
+# Unused imports
+# ----------------------------------------------------------------------
+
+
+# Rust traits usually imported only to bring their methods into scope
+# (`use std::io::Write;` for `file.write_all(..)`). Their names never show
+# up in the code, so they are not reported while the file calls methods.
+# Extension traits (`StreamExt`, `AsyncReadExt`, ...) are matched by suffix.
+RUST_METHOD_TRAITS = frozenset({
+    "Read", "Write", "BufRead", "Seek", "FromStr", "Hash", "Hasher",
+    "Borrow", "BorrowMut", "AsRawFd", "FromRawFd", "IntoRawFd",
+    "Itertools", "Rng", "SeedableRng", "Digest", "Context", "WrapErr",
+})
+
+
+def _is_rust_method_trait(name: str) -> bool:
+    return name in RUST_METHOD_TRAITS or (name[:1].isupper() and name.endswith("Ext"))
+
+
+def _is_method_call(node: Any) -> bool:
+    if node.type != "call_expression":
+        return False
+    function = node.child_by_field_name("function")
+    if function is not None and function.type == "generic_function":
+        function = function.child_by_field_name("function")  # value.collect::<Vec<_>>()
+    return function is not None and function.type == "field_expression"
+
+
+def _unused_imports(root: Any, language: str, spec: LanguageSpec, source: bytes) -> List[Tuple[str, int]]:
+    imported: List[Tuple[str, int]] = []
+    referenced = set()
+    method_calls = False
+
+    stack = [root]
+    while stack:
+        node = stack.pop()
+        if node.type in spec.imports:
+            imported.extend(_imported_names(node, language, source))
+            continue
+        if node.child_count == 0 and node.type.endswith("identifier"):
+            referenced.add(_text(node, source))
+        method_calls = method_calls or _is_method_call(node)
+        stack.extend(reversed(node.children))
+
+    unused = [(name, line) for name, line in imported if name not in referenced]
+    if language == "rust" and method_calls:
+        unused = [(name, line) for name, line in unused if not _is_rust_method_trait(name)]
+    return unused
+
+
+def _imported_names(node: Any, language: str, source: bytes) -> List[Tuple[str, int]]:
+    if language == "rust":
+        # Re-exports are part of the module's interface
+        if any(child.type == "visibility_modifier" for child in node.children):
+            return []
+        argument = node.child_by_field_name("argument")
+        return _rust_use_names(argument, None, source) if argument is not None else []
+    if language == "go":
+        return _go_import_names(node, source)
+    return _js_import_names(node, source)
+
+
+def _rust_use_names(node: Any, path: Optional[Any], source: bytes) -> List[Tuple[str, int]]:
+    line = node.start_point[0] + 1
+    if node.type == "identifier":
+        return [(_text(node, source), line)]
+    if node.type == "self" and path is not None:
+        # use std::fmt::{self, Display} brings `fmt` into scope
+        return [(_text(path, source).rsplit("::", 1)[-1], line)]
+    if node.type == "scoped_identifier":
+        name = node.child_by_field_name("name")
+        return [(_text(name, source), line)] if name is not None else []
+    if node.type == "use_as_clause":
+        alias = node.child_by_field_name("alias")
+        if alias is None or _text(alias, source) == "_":
+            return []
+        return [(_text(alias, source), line)]
+    if node.type == "scoped_use_list":
+        items = node.child_by_field_name("list")
+        return _rust_use_names(items, node.child_by_field_name("path"), source) if items is not None else []
+    if node.type == "use_list":
+        names = []
+        for child in node.children:
+            if child.is_named:
+                names.extend(_rust_use_names(child, path, source))
+        return names
+    return []
+
+
+def _js_import_names(node: Any, source: bytes) -> List[Tuple[str, int]]:
+    names = []
+    for child in _walk(node):
+        line = child.start_point[0] + 1
+        if child.type == "import_clause":
+            names.extend(
+                (_text(item, source), line) for item in child.children if item.type == "identifier"
+            )
+        elif child.type == "namespace_import":
+            names.extend(
+                (_text(item, source), line) for item in child.children if item.type == "identifier"
+            )
+        elif child.type == "import_specifier":
+            target = child.child_by_field_name("alias")
+            if target is None:
+                target = child.child_by_field_name("name")
+            if target is not None:
+                names.append((_text(target, source), line))
+    return names
+
+
+def _go_import_names(node: Any, source: bytes) -> List[Tuple[str, int]]:
+    names = []
+    for spec in _walk(node):
+        if spec.type != "import_spec":
+            continue
+        line = spec.start_point[0] + 1
+        alias = spec.child_by_field_name("name")
+        if alias is not None:
+            if alias.type == "package_identifier":
+                names.append((_text(alias, source), line))
+            continue
+
+        import_path = spec.child_by_field_name("path")
+        if import_path is None:
+            continue
+        segments = _text(import_path, source).strip("\"`").split("/")
+        # example.com/mod/v2 is imported as `mod`; gopkg.in/yaml.v3 as `yaml`
+        name = segments[-1]
+        if len(segments) > 1 and re.fullmatch(r"v\d+", name):
+            name = segments[-2]
+        names.append((name.split(".")[0].replace("-", "_"), line))
+    return names
@@ -1,15 +1,28 @@
 """Sandboxed code execution skill."""
 
 import ast
+import hashlib
+import json
 import os
+import re
+import shutil
 import subprocess
 import sys
 import tempfile
+import tomllib
 from dataclasses import dataclass, field
+from pathlib import Path
 from typing import Any
 
 from gathering.skills.base import BaseSkill
 
+try:
+    import resource
+except ImportError:  # Windows
+    resource = None
+
+RUST_EDITIONS = ["2015", "2018", "2021", "2024"]
+
 
 @dataclass
 class CodeConfig:
@@ -37,9 +50,25 @@ class CodeConfig:
 
     # Allowed languages
     allowed_languages: list[str] = field(default_factory=lambda: [
-        "python", "javascript", "bash", "sql"
+        "python", "javascript", "bash", "sql", "rust"
     ])
 
+    # Crates Rust snippets may depend on (empty = rustc only, no dependencies)
+    rust_allowed_crates: list[str] = field(default_factory=list)
+
+    # `cargo vendor` directory the allowed crates are built from (offline)
+    rust_vendor_dir: str | None = None
+
+    # Default Rust edition for snippets
+    rust_edition: str = "2021"
+
+    # Maximum memory per rustc/cargo process in MB (Linux only). Builds run
+    # untrusted code too (build.rs, proc macros) but rustc needs more than max_memory_mb
+    rust_build_memory_mb: int = 2048
+
+    # Snippet builds kept under work_dir/rust; the least recently used are removed
+    rust_cache_max_entries: int = 64
+
     # Working directory for code execution
     work_dir: str = "/tmp/gathering_code"
 
@@ -64,6 +93,7 @@ class CodeExecutionSkill(BaseSkill):
         super().__init__(config if isinstance(config, dict) else None)
         self.code_config = config if isinstance(config, CodeConfig) else CodeConfig()
         os.makedirs(self.code_config.work_dir, exist_ok=True)
+        self._rustc_version_string: str | None = None
 
     def get_tools_definition(self) -> list[dict[str, Any]]:
         return [
@@ -138,6 +168,43 @@ class CodeExecutionSkill(BaseSkill):
                     "required": ["script"]
                 }
             },
+            {
+                "name": "rust_exec",
+                "description": (
+                    "Compile and run a Rust snippet. Statements without a `fn main` are wrapped in one. "
+                    "Dependencies (from the allowed, vendored crates) can be given as input or in a "
+                    "cargo-script `---cargo` frontmatter block"
+                ),
+                "input_schema": {
+                    "type": "object",
+                    "properties": {
+                        "code": {
+                            "type": "string",
+                            "description": "Rust source to compile and run"
+                        },
+                        "dependencies": {
+                            "type": "object",
+                            "description": "Crate name -> version requirement, or {version, features, default-features}"
+                        },
+                        "edition": {
+                            "type": "string",
+                            "description": "Rust edition",
+                            "enum": RUST_EDITIONS
+                        },
+                        "release": {
+                            "type": "boolean",
+                            "description": "Build with optimizations",
+                            "default": False
+                        },
+                        "timeout": {
+                            "type": "integer",
+                            "description": "Timeout in seconds, applied to the build and to the run",
+                            "default": 30
+                        }
+                    },
+                    "required": ["code"]
+                }
+            },
             {
                 "name": "sql_exec",
                 "description": "Execute SQL query (SELECT only, read-only)",
@@ -188,7 +255,13 @@ class CodeExecutionSkill(BaseSkill):
                         "language": {
                             "type": "string",
                             "description": "Programming language",
-                            "enum": ["python", "javascript", "json"]
+                            "enum": ["python", "javascript", "json", "rust"]
+                        },
+                        "edition": {
+                            "type": "string",
+                            "description": "Rust edition used by rustfmt",
+                            "enum": RUST_EDITIONS,
+                            "default": "2021"
                         }
                     },
                     "required": ["code", "language"]
@@ -583,6 +656,306 @@ try {{
         except Exception as e:
             return {"success": False, "error": str(e)}
 
+    def _execute_rust(
+        self,
+        code: str,
+        timeout: int,
+        dependencies: dict[str, Any] | None = None,
+        edition: str | None = None,
+        release: bool = False,
+    ) -> dict[str, Any]:
+        """Compile a Rust snippet with rustc (or cargo for dependencies) and run it."""
+        edition = edition or self.code_config.rust_edition
+        if edition not in RUST_EDITIONS:
+            # Interpolated into the generated Cargo.toml: only known values
+            return {
+                "success": False,
+                "error": f"Unsupported Rust edition: {edition!r} (expected one of {', '.join(RUST_EDITIONS)})",
+            }
+
+        try:
+            code, declared = self._split_rust_frontmatter(code)
+        except ValueError as e:
+            return {"success": False, "error": str(e)}
+        declared.update(dependencies or {})
+
+        error = self._check_rust_dependencies(declared)
+        if error:
+            return {"success": False, "error": error}
+
+        # Bare statements run inside a generated main; diagnostics keep the snippet's line numbers
+        line_offset = 0
+        if not re.search(r"\bfn\s+main\s*\(", code):
+            code = f"fn main() {{\n{code}\n}}\n"
+            line_offset = 1
+
+        rustc_version = self._rustc_version()
+        if rustc_version is None:
+            return {"success": False, "error": "Rust toolchain (rustc) is not installed"}
+
+        key = hashlib.sha256(json.dumps(
+            [code, sorted(declared.items()), edition, release, rustc_version], default=str
+        ).encode()).hexdigest()[:16]
+        build_dir = Path(self.code_config.work_dir) / "rust" / key
+        binary = build_dir / "snippet"
+        diagnostics_file = build_dir / "diagnostics.json"
+
+        cached = binary.exists() and diagnostics_file.exists()
+        if cached:
+            build = json.loads(diagnostics_file.read_text())
+            os.utime(build_dir)  # mark as recently used
+        else:
+            self._evict_rust_cache(build_dir)
+            try:
+                build = self._build_rust(code, declared, edition, release, build_dir, timeout)
+            except subprocess.TimeoutExpired:
+                return {"success": False, "error": f"Compilation timed out after {timeout} seconds"}
+            except FileNotFoundError as e:
+                return {"success": False, "error": f"Rust toolchain not found: {e.filename}"}
+            for diagnostic in build["diagnostics"]:
+                if diagnostic["line"] is not None:
+                    diagnostic["line"] = max(diagnostic["line"] - line_offset, 1)
+            if build["success"]:
+                diagnostics_file.write_text(json.dumps(build))
+
+        response = {
+            "build": build["build"],
+            "cache_key": key,
+            "cached": cached,
+            "diagnostics": build["diagnostics"],
+            "compiler_output": build["compiler_output"][:self.code_config.max_output_size],
+        }
+        if not build["success"]:
+            errors = [d for d in build["diagnostics"] if d["level"] == "error"]
+            return {
+                **response,
+                "success": False,
+                "error": f"Compilation failed with {len(errors)} error(s)" if errors else "Compilation failed",
+            }
+
+        try:
+            result = subprocess.run(
+                [str(binary)],
+                capture_output=True,
+                text=True,
+                timeout=timeout,
+                cwd=self.code_config.work_dir,
+                env={"PATH": "/usr/bin:/bin", "HOME": self.code_config.work_dir, "RUST_BACKTRACE": "0"},
+                preexec_fn=self._memory_limiter(),
+            )
+        except subprocess.TimeoutExpired:
+            return {**response, "success": False, "error": f"Execution timed out after {timeout} seconds"}
+
+        return {
+            **response,
+            "success": result.returncode == 0,
+            "output": result.stdout[:self.code_config.max_output_size],
+            "error": result.stderr[:self.code_config.max_output_size] if result.stderr else None,
+            "return_code": result.returncode,
+        }
+
+    def _split_rust_frontmatter(self, code: str) -> tuple[str, dict[str, Any]]:
+        """
+        Take dependencies from a cargo-script frontmatter block::
+
+            ---cargo
+            [dependencies]
+            regex = "1"
+            ---
+
+        The block is blanked out so line numbers are unchanged.
+        """
+        match = re.match(r"\A(\s*)---[ \t]*(cargo)?[ \t]*\n(.*?)^---[ \t]*$", code, re.DOTALL | re.MULTILINE)
+        if not match:
+            return code, {}
+
+        try:
+            manifest = tomllib.loads(match.group(3))
+        except tomllib.TOMLDecodeError as e:
+            raise ValueError(f"Invalid cargo frontmatter: {e}")
+
+        blank = "\n" * match.group(0).count("\n")
+        return blank + code[match.end():], dict(manifest.get("dependencies", {}))
+
+    def _check_rust_dependencies(self, dependencies: dict[str, Any]) -> str | None:
+        """Return an error unless every dependency is an allowed, vendored registry crate."""
+        if not dependencies:
+            return None
+
+        allowed = set(self.code_config.rust_allowed_crates)
+        denied = sorted(name for name in dependencies if name not in allowed)
+        if denied:
+            return (
+                f"Crates not allowed: {', '.join(denied)}"
+                f" (allowed: {', '.join(sorted(allowed)) or 'none'})"
+            )
+        if not self.code_config.rust_vendor_dir or not Path(self.code_config.rust_vendor_dir).is_dir():
+            return "No vendored crates directory configured for Rust dependencies"
+
+        for name, spec in dependencies.items():
+            if isinstance(spec, dict):
+                extra = set(spec) - {"version", "features", "default-features"}
+                if extra:
+                    return f"Dependency '{name}' may only set version, features and default-features"
+            elif not isinstance(spec, str):
+                return f"Dependency '{name}' must be a version requirement"
+        return None
+
+    def _rustc_version(self) -> str | None:
+        """rustc version string (part of the build cache key)."""
+        if self._rustc_version_string is None:
+            try:
+                result = subprocess.run(
+                    ["rustc", "--version"], capture_output=True, text=True, timeout=30, env=self._rust_env()
+                )
+            except (FileNotFoundError, subprocess.TimeoutExpired):
+                return None
+            if result.returncode == 0:
+                self._rustc_version_string = result.stdout.strip()
+        return self._rustc_version_string
+
+    def _rust_env(self) -> dict[str, str]:
+        """Toolchain environment isolated from the user's cargo configuration."""
+        root = Path(self.code_config.work_dir) / "rust"
+        return {
+            "PATH": os.environ.get("PATH", ""),
+            "HOME": self.code_config.work_dir,
+            "RUSTUP_HOME": os.environ.get("RUSTUP_HOME", os.path.expanduser("~/.rustup")),
+            "CARGO_HOME": str(root / "cargo-home"),
+            "CARGO_TARGET_DIR": str(root / "target"),
+            "CARGO_TERM_COLOR": "never",
+        }
+
+    def _build_rust(
+        self,
+        code: str,
+        dependencies: dict[str, Any],
+        edition: str,
+        release: bool,
+        build_dir: Path,
+        timeout: int,
+    ) -> dict[str, Any]:
+        """Compile into build_dir/snippet, collecting rustc's JSON diagnostics."""
+        if build_dir.exists():
+            shutil.rmtree(build_dir)
+        (build_dir / "src").mkdir(parents=True)
+        source = build_dir / "src" / "main.rs"
+        source.write_text(code)
+        env = self._rust_env()
+
+        if not dependencies:
+            result = subprocess.run(
+                [
+                    "rustc", "--edition", edition, "--crate-name", "snippet", "--error-format=json",
+                    "-C", f"opt-level={3 if release else 0}", "-o", "snippet", "src/main.rs",
+                ],
+                capture_output=True, text=True, timeout=timeout, cwd=str(build_dir), env=env,
+                preexec_fn=self._memory_limiter(self.code_config.rust_build_memory_mb),
+            )
+            messages = [json.loads(line) for line in result.stderr.splitlines() if line.startswith("{")]
+            build = {"build": "rustc", "success": result.returncode == 0}
+            build.update(self._rust_diagnostics(messages, "src/main.rs"))
+            # Driver errors (bad flags, unreadable files) are plain text, not JSON
+            build["compiler_output"] += "\n".join(
+                line for line in result.stderr.splitlines() if line.strip() and not line.startswith("{")
+            )
+            return build
+
+        manifest = [
+            "[package]", 'name = "snippet"', 'version = "0.1.0"', f'edition = "{edition}"', "",
+            "[dependencies]",
+        ]
+        for name, spec in sorted(dependencies.items()):
+            if isinstance(spec, str):
+                spec = {"version": spec}
+            fields = ", ".join(f"{key} = {json.dumps(value)}" for key, value in spec.items())
+            manifest.append(f"{name} = {{ {fields} }}")
+        (build_dir / "Cargo.toml").write_text("\n".join(manifest) + "\n")
+        (build_dir / ".cargo").mkdir()
+        (build_dir / ".cargo" / "config.toml").write_text(
+            '[source.crates-io]\nreplace-with = "vendored"\n\n'
+            f"[source.vendored]\ndirectory = {json.dumps(str(Path(self.code_config.rust_vendor_dir).resolve()))}\n"
+        )
+
+        cmd = ["cargo", "build", "--offline", "--message-format=json"]
+        if release:
+            cmd.append("--release")
+        result = subprocess.run(
+            cmd, capture_output=True, text=True, timeout=timeout, cwd=str(build_dir), env=env,
+            preexec_fn=self._memory_limiter(self.code_config.rust_build_memory_mb),
+        )
+
+        messages = []
+        executable = None
+        for line in result.stdout.splitlines():
+            try:
+                message = json.loads(line)
+            except json.JSONDecodeError:
+                continue
+            if message.get("reason") == "compiler-message":
+                messages.append(message["message"])
+            elif message.get("reason") == "compiler-artifact" and message.get("target", {}).get("name") == "snippet":
+                executable = message.get("executable")
+
+        build = {"build": "cargo", "success": result.returncode == 0 and executable is not None}
+        build.update(self._rust_diagnostics(messages, "src/main.rs"))
+        if result.returncode != 0 and not build["diagnostics"]:
+            # Resolution and manifest errors come from cargo itself, not rustc
+            build["compiler_output"] += "\n".join(
+                line for line in result.stderr.splitlines() if not line.strip().startswith(("Compiling", "Locking"))
+            )
+        if build["success"]:
+            # The shared target dir is reused by every snippet, so keep a private copy
+            shutil.copy2(executable, build_dir / "snippet")
+        return build
+
+    def _evict_rust_cache(self, keep: Path) -> None:
+        """Remove the least recently used snippet builds (by directory mtime) beyond rust_cache_max_entries."""
+        if not keep.parent.is_dir():
+            return
+        entries = [
+            entry for entry in keep.parent.iterdir()
+            if entry != keep and entry.is_dir() and re.fullmatch(r"[0-9a-f]{16}", entry.name)
+        ]
+        excess = len(entries) + 1 - self.code_config.rust_cache_max_entries
+        if excess <= 0:
+            return
+        entries.sort(key=lambda entry: entry.stat().st_mtime)
+        for entry in entries[:excess]:
+            shutil.rmtree(entry, ignore_errors=True)
+
+    @staticmethod
+    def _rust_diagnostics(messages: list[dict[str, Any]], source: str) -> dict[str, Any]:
+        """Split rustc JSON messages into structured diagnostics and rendered text."""
+        diagnostics = []
+        rendered = []
+        for message in messages:
+            rendered.append(message.get("rendered") or "")
+            if message.get("level") not in ("error", "warning") or not message.get("spans"):
+                # "aborting due to ..." and summary notes carry no location
+                continue
+            span = next((s for s in message["spans"] if s.get("is_primary")), message["spans"][0])
+            in_snippet = span.get("file_name") == source
+            diagnostics.append({
+                "level": message["level"],
+                "code": (message.get("code") or {}).get("code"),
+                "message": message["message"],
+                "line": span.get("line_start") if in_snippet else None,
+                "column": span.get("column_start") if in_snippet else None,
+            })
+        return {"diagnostics": diagnostics, "compiler_output": "".join(rendered)}
+
+    def _memory_limiter(self, memory_mb: int | None = None):
+        """preexec_fn capping the child's address space at memory_mb, default max_memory_mb (Linux only)."""
+        if resource is None or not sys.platform.startswith("linux"):
+            return None
+        limit = (memory_mb or self.code_config.max_memory_mb) * 1024 * 1024
+
+        def apply_limit():
+            resource.setrlimit(resource.RLIMIT_AS, (limit, limit))
+
+        return apply_limit
+
     def _execute_sql(self, query: str, database_url: str) -> dict[str, Any]:
         """Execute SQL query (SELECT only)."""
         # Only allow SELECT queries
@@ -709,7 +1082,7 @@ try {{
 
         return {"success": False, "error": f"Unsupported language: {language}"}
 
-    def _format_code(self, code: str, language: str) -> dict[str, Any]:
+    def _format_code(self, code: str, language: str, edition: str = "2021") -> dict[str, Any]:
         """Format code according to language standards."""
         if language == "python":
             try:
@@ -726,6 +1099,24 @@ try {{
             # Could use prettier via subprocess if available
             return {"success": False, "error": "JavaScript formatting requires prettier"}
 
+        elif language == "rust":
+            try:
+                result = subprocess.run(
+                    ["rustfmt", "--emit", "stdout", "--edition", edition],
+                    input=code,
+                    capture_output=True,
+                    text=True,
+                    timeout=self.code_config.timeout
+                )
+            except FileNotFoundError:
+                return {"success": False, "error": "Rust formatting requires rustfmt"}
+            except subprocess.TimeoutExpired:
+                return {"success": False, "error": "rustfmt timed out"}
+
+            if result.returncode != 0:
+                return {"success": False, "error": result.stderr.strip() or "rustfmt failed"}
+            return {"success": True, "formatted": result.stdout}
+
         elif language == "json":
             try:
                 import json
@@ -761,6 +1152,15 @@ try {{
                 tool_input.get("timeout", self.code_config.timeout)
             )
 
+        elif tool_name == "rust_exec":
+            return self._execute_rust(
+                tool_input["code"],
+                tool_input.get("timeout", self.code_config.timeout),
+                dependencies=tool_input.get("dependencies"),
+                edition=tool_input.get("edition"),
+                release=tool_input.get("release", False),
+            )
+
         elif tool_name == "sql_exec":
             return self._execute_sql(
                 tool_input["query"],
@@ -776,7 +1176,8 @@ try {{
         elif tool_name == "code_format":
             return self._format_code(
                 tool_input["code"],
-                tool_input["language"]
+                tool_input["language"],
+                tool_input.get("edition", "2021")
             )
 
         elif tool_name == "repl_session":
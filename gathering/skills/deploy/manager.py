@@ -5,6 +5,8 @@ Provides CI/CD and deployment operations for agents.
 
 import subprocess
 import json
+import re
+import tomllib
 from pathlib import Path
 from typing import Dict, Any, List, Optional
 from datetime import datetime
@@ -12,6 +14,44 @@ from datetime import datetime
 from gathering.skills.base import BaseSkill, SkillResponse, SkillPermission
 
 
+# Manifests identifying each language deploy_generate_dockerfile supports, in detection order
+DOCKERFILE_MANIFESTS = {
+    "rust": ["Cargo.toml"],
+    "python": ["pyproject.toml", "requirements.txt", "setup.py"],
+    "node": ["package.json"],
+}
+
+DOCKERIGNORE_PATTERNS = {
+    "rust": ["target/", ".git/", "Dockerfile", ".dockerignore"],
+    "python": [".git/", "__pycache__/", "*.pyc", ".venv/", "venv/", ".pytest_cache/", ".mypy_cache/", "dist/", "build/"],
+    "node": ["node_modules/", ".git/", "coverage/", "npm-debug.log*", "Dockerfile", ".dockerignore"],
+}
+
+CARGO_CHEF_IMAGE = "lukemathwalker/cargo-chef:latest-rust-1"
+MUSL_TARGET = "x86_64-unknown-linux-musl"
+RUST_RUNTIME_IMAGES = {
+    "distroless": "gcr.io/distroless/cc-debian12:nonroot",
+    # Fully static musl build: nothing in the image but the binary and CA certificates
+    "scratch": "scratch",
+}
+
+# Binaries a Dockerfile builds or copies: `--bin name` and `target/[triple/]release/name`
+DOCKERFILE_BIN_RE = re.compile(r"--bin[ =]([\w-]+)|target/(?:[\w-]+/)?release/([\w-]+)")
+
+PYTHON_ENTRYPOINTS = ["main.py", "app.py", "manage.py"]
+
+# lockfile: (install, drop dev dependencies, script runner)
+NODE_PACKAGE_MANAGERS = {
+    "pnpm-lock.yaml": ("corepack enable && pnpm install --frozen-lockfile", "pnpm prune --prod", "pnpm"),
+    "yarn.lock": (
+        "corepack enable && yarn install --frozen-lockfile",
+        "yarn install --production --frozen-lockfile --ignore-scripts --prefer-offline",
+        "yarn",
+    ),
+    "package-lock.json": ("npm ci", "npm prune --omit=dev", "npm"),
+}
+
+
 class DeploySkill(BaseSkill):
     """
     CI/CD and deployment operations skill.
@@ -53,6 +93,34 @@ class DeploySkill(BaseSkill):
                     "required": ["path", "tag"]
                 }
             },
+            {
+                "name": "deploy_generate_dockerfile",
+                "description": "Generate a multi-stage Dockerfile for a Rust, Python or Node project",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {
+                        "path": {"type": "string", "description": "Project directory"},
+                        "language": {
+                            "type": "string",
+                            "enum": ["rust", "python", "node"],
+                            "description": "Override the language detected from the manifests"
+                        },
+                        "binary": {
+                            "type": "string",
+                            "description": "Entrypoint: Cargo [[bin]] target, Python console script or package.json bin/script"
+                        },
+                        "runtime": {
+                            "type": "string",
+                            "enum": ["distroless", "scratch"],
+                            "description": "Runtime base image for Rust binaries (scratch builds a static musl binary)",
+                            "default": "distroless"
+                        },
+                        "port": {"type": "integer", "description": "Port to EXPOSE"},
+                        "dockerfile": {"type": "string", "description": "Dockerfile name", "default": "Dockerfile"}
+                    },
+                    "required": ["path"]
+                }
+            },
             {
                 "name": "deploy_docker_push",
                 "description": "Push Docker image to registry",
@@ -200,6 +268,7 @@ class DeploySkill(BaseSkill):
         try:
             handlers = {
                 "deploy_docker_build": self._docker_build,
+                "deploy_generate_dockerfile": self._generate_dockerfile,
                 "deploy_docker_push": self._docker_push,
                 "deploy_docker_run": self._docker_run,
                 "deploy_docker_compose": self._docker_compose,
@@ -291,6 +360,419 @@ class DeploySkill(BaseSkill):
             }
         )
 
+    def _generate_dockerfile(self, tool_input: Dict[str, Any]) -> SkillResponse:
+        """Generate a multi-stage Dockerfile from the project manifests."""
+        from gathering.workspace.manager import WorkspaceManager, WorkspaceType
+
+        root = Path(tool_input["path"]).resolve()
+        language = tool_input.get("language")
+        binary = tool_input.get("binary")
+        runtime = tool_input.get("runtime", "distroless")
+        port = tool_input.get("port")
+        dockerfile_path = root / tool_input.get("dockerfile", "Dockerfile")
+
+        if not root.is_dir():
+            return SkillResponse(success=False, message=f"Path not found: {root}", error="not_found")
+
+        workspace_type = WorkspaceManager.detect_type(str(root))
+        if workspace_type != WorkspaceType.DEVELOPMENT:
+            return SkillResponse(
+                success=False,
+                message=f"Not a development project ({workspace_type.value}): {root}",
+                error="unsupported_workspace",
+            )
+
+        if language is None:
+            language = next(
+                (lang for lang, manifests in DOCKERFILE_MANIFESTS.items()
+                 if any((root / m).exists() for m in manifests)),
+                None,
+            )
+            if language is None:
+                return SkillResponse(
+                    success=False,
+                    message=f"No Cargo.toml, pyproject.toml, requirements.txt or package.json in {root}",
+                    error="unsupported_language",
+                )
+        elif language not in DOCKERFILE_MANIFESTS:
+            return SkillResponse(success=False, message=f"Unsupported language: {language}", error="unsupported_language")
+        elif not any((root / m).exists() for m in DOCKERFILE_MANIFESTS[language]):
+            return SkillResponse(
+                success=False,
+                message=f"No {' or '.join(DOCKERFILE_MANIFESTS[language])} in {root}",
+                error="manifest_not_found",
+            )
+
+        if runtime not in RUST_RUNTIME_IMAGES:
+            return SkillResponse(success=False, message=f"Unknown runtime: {runtime}", error="unknown_runtime")
+
+        if language == "rust":
+            generated = self._rust_dockerfile(root, binary, runtime)
+        elif language == "python":
+            generated = self._python_dockerfile(root, binary)
+        else:
+            generated = self._node_dockerfile(root, binary)
+        if isinstance(generated, SkillResponse):
+            return generated
+
+        lines = generated["lines"]
+        if port:
+            # EXPOSE goes right before the final ENTRYPOINT/CMD
+            lines.insert(len(lines) - 1, f"EXPOSE {port}")
+        content = "\n".join(lines) + "\n"
+
+        data = {
+            "path": str(dockerfile_path),
+            "content": content,
+            "language": language,
+            "workspace_type": workspace_type.value,
+            "binary": generated["binary"],
+            "binaries": generated["binaries"],
+            "stages": re.findall(r"^FROM \S+ AS (\S+)$", content, re.MULTILINE),
+            "dockerignore": None,
+        }
+
+        if language == "rust":
+            # The Dockerfile must only build and copy real [[bin]] targets
+            unknown = self._dockerfile_unknown_binaries(content, generated["binaries"])
+            if unknown:
+                return SkillResponse(
+                    success=False,
+                    message=f"Generated Dockerfile references unknown binaries: {', '.join(unknown)}",
+                    error="invalid_dockerfile",
+                    data=data,
+                )
+            if dockerfile_path.exists():
+                data["existing_unknown_binaries"] = self._dockerfile_unknown_binaries(
+                    dockerfile_path.read_text(errors="replace"), generated["binaries"]
+                )
+
+        if not (root / ".dockerignore").exists():
+            data["dockerignore"] = {
+                "path": str(root / ".dockerignore"),
+                "content": "\n".join(DOCKERIGNORE_PATTERNS[language]) + "\n",
+            }
+
+        action = "Overwrite" if dockerfile_path.exists() else "Create"
+        return SkillResponse(
+            success=True,
+            message=f"Generated {language} Dockerfile for {generated['binary']} ({', '.join(data['stages'])})",
+            needs_confirmation=True,
+            confirmation_type="write_file",
+            confirmation_message=f"{action} {dockerfile_path}?",
+            data=data,
+        )
+
+    @staticmethod
+    def _load_toml(path: Path) -> Dict[str, Any]:
+        """Parsed TOML file, or {} when missing or invalid."""
+        try:
+            return tomllib.loads(path.read_text())
+        except (OSError, tomllib.TOMLDecodeError):
+            return {}
+
+    def _cargo_binaries(self, root: Path) -> List[Dict[str, Any]]:
+        """
+        [[bin]] targets of a package or workspace, including the ones
+        Cargo infers from src/main.rs and src/bin/.
+        """
+        manifest = self._load_toml(root / "Cargo.toml")
+        workspace = manifest.get("workspace", {})
+        package_dirs = [root] if "package" in manifest else []
+        excluded = {(root / e).resolve() for e in workspace.get("exclude", [])}
+        for pattern in workspace.get("members", []):
+            for member in sorted(root.glob(pattern)):
+                if member.resolve() not in excluded and (member / "Cargo.toml").exists() and member not in package_dirs:
+                    package_dirs.append(member)
+
+        binaries = []
+        for directory in package_dirs:
+            package_manifest = manifest if directory == root else self._load_toml(directory / "Cargo.toml")
+            package = package_manifest.get("package", {})
+            name = package.get("name")
+            if not name:
+                continue
+
+            targets = {}
+            for target in package_manifest.get("bin", []):
+                target_path = target.get("path") or f"src/bin/{target.get('name')}.rs"
+                targets[target.get("name") or Path(target_path).stem] = target_path
+
+            if package.get("autobins", True):
+                inferred = {}
+                if (directory / "src" / "main.rs").exists():
+                    inferred[name] = "src/main.rs"
+                bin_dir = directory / "src" / "bin"
+                if bin_dir.is_dir():
+                    for entry in sorted(bin_dir.iterdir()):
+                        if entry.suffix == ".rs":
+                            inferred[entry.stem] = f"src/bin/{entry.name}"
+                        elif (entry / "main.rs").exists():
+                            inferred[entry.name] = f"src/bin/{entry.name}/main.rs"
+                explicit_paths = set(targets.values())
+                for bin_name, bin_path in inferred.items():
+                    if bin_name not in targets and bin_path not in explicit_paths:
+                        targets[bin_name] = bin_path
+
+            relative = directory.relative_to(root).as_posix()
+            for bin_name, bin_path in targets.items():
+                binaries.append({
+                    "name": bin_name,
+                    "package": name,
+                    "path": bin_path if relative == "." else f"{relative}/{bin_path}",
+                    "default": package.get("default-run") == bin_name,
+                })
+        return binaries
+
+    def _rust_toolchain(self, root: Path) -> Optional[str]:
+        """Pinned Rust release from rust-toolchain(.toml), if it is a version number."""
+        channel = self._load_toml(root / "rust-toolchain.toml").get("toolchain", {}).get("channel")
+        if channel is None and (root / "rust-toolchain").is_file():
+            legacy = (root / "rust-toolchain").read_text().strip()
+            channel = self._load_toml(root / "rust-toolchain").get("toolchain", {}).get("channel") or legacy
+        if channel and re.fullmatch(r"\d+\.\d+(\.\d+)?", channel):
+            return channel
+        return None
+
+    def _rust_dockerfile(self, root: Path, binary: Optional[str], runtime: str) -> Any:
+        """cargo-chef multi-stage build of one [[bin]] target."""
+        binaries = self._cargo_binaries(root)
+        names = sorted({b["name"] for b in binaries})
+        if not binaries:
+            return SkillResponse(
+                success=False,
+                message=f"No binary target in {root / 'Cargo.toml'} (library crate?)",
+                error="no_binary",
+            )
+
+        if binary:
+            target = next((b for b in binaries if b["name"] == binary), None)
+            if target is None:
+                return SkillResponse(
+                    success=False,
+                    message=f"Unknown binary '{binary}', available: {', '.join(names)}",
+                    error="unknown_binary",
+                    data={"binaries": names},
+                )
+        else:
+            defaults = [b for b in binaries if b["default"]]
+            candidates = binaries if len(binaries) == 1 else defaults
+            if len(candidates) != 1:
+                return SkillResponse(
+                    success=False,
+                    message=f"Several binaries, choose one with 'binary': {', '.join(names)}",
+                    error="ambiguous_binary",
+                    data={"binaries": names},
+                )
+            target = candidates[0]
+
+        name = target["name"]
+        static = runtime == "scratch"
+        locked = " --locked" if (root / "Cargo.lock").exists() else ""
+        target_flag = f" --target {MUSL_TARGET}" if static else ""
+        package_flag = f" -p {target['package']}" if "workspace" in self._load_toml(root / "Cargo.toml") else ""
+        release_dir = f"target/{MUSL_TARGET}/release" if static else "target/release"
+
+        toolchain = self._rust_toolchain(root)
+        if toolchain:
+            lines = [
+                f"FROM rust:{toolchain}-slim-bookworm AS chef",
+                "RUN cargo install cargo-chef --locked",
+            ]
+        else:
+            lines = [f"FROM {CARGO_CHEF_IMAGE} AS chef"]
+        lines += [
+            "WORKDIR /app",
+            "",
+            "FROM chef AS planner",
+            "COPY . .",
+            "RUN cargo chef prepare --recipe-path recipe.json",
+            "",
+            "FROM chef AS builder",
+        ]
+        if static:
+            lines += [
+                f"RUN rustup target add {MUSL_TARGET} \\",
+                "    && apt-get update && apt-get install -y --no-install-recommends musl-tools \\",
+                "    && rm -rf /var/lib/apt/lists/*",
+            ]
+        lines += [
+            "COPY --from=planner /app/recipe.json recipe.json",
+            "# Dependencies only: this layer stays cached until Cargo.toml or Cargo.lock change",
+            f"RUN cargo chef cook --release{locked}{target_flag} --recipe-path recipe.json",
+            "COPY . .",
+            f"RUN cargo build --release{locked}{target_flag}{package_flag} --bin {name}",
+            "",
+            f"FROM {RUST_RUNTIME_IMAGES[runtime]} AS runtime",
+        ]
+        if static:
+            lines.append("COPY --from=builder /etc/ssl/certs/ca-certificates.crt /etc/ssl/certs/")
+        lines.append(f"COPY --from=builder /app/{release_dir}/{name} /usr/local/bin/{name}")
+        if static:
+            lines.append("USER 65532:65532")
+        lines.append(f'ENTRYPOINT ["/usr/local/bin/{name}"]')
+
+        return {"lines": lines, "binary": name, "binaries": names}
+
+    @staticmethod
+    def _dockerfile_unknown_binaries(content: str, binaries: List[str]) -> List[str]:
+        """Binaries a Dockerfile builds or copies that are not Cargo targets."""
+        referenced = {a or b for a, b in DOCKERFILE_BIN_RE.findall(content)}
+        return sorted(referenced - set(binaries))
+
+    def _python_dockerfile(self, root: Path, binary: Optional[str]) -> Any:
+        """Virtualenv built in a slim builder stage, copied into a slim runtime."""
+        pyproject = self._load_toml(root / "pyproject.toml")
+        project = pyproject.get("project", {})
+        scripts = {
+            **pyproject.get("tool", {}).get("poetry", {}).get("scripts", {}),
+            **project.get("scripts", {}),
+        }
+        entrypoints = sorted(scripts) + [f for f in PYTHON_ENTRYPOINTS if (root / f).exists()]
+
+        if binary is None:
+            binary = next(iter(scripts), None) or next(iter(entrypoints), None)
+            if binary is None:
+                return SkillResponse(
+                    success=False,
+                    message=f"No [project.scripts] or {', '.join(PYTHON_ENTRYPOINTS)} in {root}, pass 'binary'",
+                    error="no_entrypoint",
+                )
+        elif binary not in scripts and not (binary.endswith(".py") and (root / binary).is_file()):
+            return SkillResponse(
+                success=False,
+                message=f"Unknown entrypoint '{binary}', available: {', '.join(entrypoints) or 'none'}",
+                error="unknown_binary",
+                data={"binaries": entrypoints},
+            )
+        command = [binary] if binary in scripts else ["python", binary]
+
+        version = None
+        if (root / ".python-version").is_file():
+            version = re.match(r"\d+\.\d+", (root / ".python-version").read_text().strip())
+        if version is None:
+            version = re.search(r"\d+\.\d+", project.get("requires-python", ""))
+        image = f"python:{version.group(0) if version else '3.12'}-slim"
+
+        installable = (root / "pyproject.toml").exists() or (root / "setup.py").exists()
+        lines = [f"FROM {image} AS builder", "WORKDIR /app"]
+        if (root / "uv.lock").exists():
+            lines += [
+                "COPY --from=ghcr.io/astral-sh/uv:latest /uv /usr/local/bin/uv",
+                "ENV UV_COMPILE_BYTECODE=1 UV_LINK_MODE=copy UV_PROJECT_ENVIRONMENT=/opt/venv",
+                "COPY pyproject.toml uv.lock ./",
+                "RUN uv sync --frozen --no-dev --no-install-project",
+                "COPY . .",
+                "RUN uv sync --frozen --no-dev --no-editable",
+            ]
+        else:
+            lines += [
+                "ENV PIP_NO_CACHE_DIR=1 PIP_DISABLE_PIP_VERSION_CHECK=1",
+                "RUN python -m venv /opt/venv",
+                'ENV PATH="/opt/venv/bin:$PATH"',
+            ]
+            if (root / "requirements.txt").exists():
+                lines += [
+                    "COPY requirements.txt ./",
+                    "RUN pip install -r requirements.txt",
+                    "COPY . .",
+                ]
+                if installable:
+                    lines.append("RUN pip install --no-deps .")
+            else:
+                lines += ["COPY . .", "RUN pip install ."]
+        lines += [
+            "",
+            f"FROM {image} AS runtime",
+            'ENV PYTHONDONTWRITEBYTECODE=1 PYTHONUNBUFFERED=1 PATH="/opt/venv/bin:$PATH"',
+            "RUN useradd --create-home --uid 10001 app",
+            "WORKDIR /app",
+            "COPY --from=builder /opt/venv /opt/venv",
+            "COPY --from=builder --chown=app:app /app /app",
+            "USER app",
+            f"CMD {json.dumps(command)}",
+        ]
+
+        return {"lines": lines, "binary": binary, "binaries": entrypoints}
+
+    def _node_dockerfile(self, root: Path, binary: Optional[str]) -> Any:
+        """Dependencies, build and pruned runtime stages for a package.json project."""
+        try:
+            package = json.loads((root / "package.json").read_text())
+        except (OSError, json.JSONDecodeError) as e:
+            return SkillResponse(success=False, message=f"Invalid package.json: {e}", error="invalid_manifest")
+
+        lockfile = next((f for f in NODE_PACKAGE_MANAGERS if (root / f).exists()), None)
+        install, prune, runner = NODE_PACKAGE_MANAGERS.get(lockfile, ("npm install", "npm prune --omit=dev", "npm"))
+        scripts = package.get("scripts", {})
+        bins = package.get("bin", {})
+        if isinstance(bins, str):
+            bins = {package.get("name", "app").split("/")[-1]: bins}
+        entrypoints = sorted(bins) + sorted(scripts)
+
+        if binary is None:
+            if "start" in scripts:
+                binary = "start"
+            elif package.get("main"):
+                binary = package["main"]
+            elif bins:
+                binary = next(iter(bins))
+            else:
+                return SkillResponse(
+                    success=False,
+                    message=f"No start script, main or bin in {root / 'package.json'}, pass 'binary'",
+                    error="no_entrypoint",
+                )
+        elif binary not in bins and binary not in scripts:
+            return SkillResponse(
+                success=False,
+                message=f"Unknown entrypoint '{binary}', available: {', '.join(entrypoints) or 'none'}",
+                error="unknown_binary",
+                data={"binaries": entrypoints},
+            )
+
+        if binary in bins:
+            command = ["node", bins[binary]]
+        elif binary in scripts:
+            command = [runner, "run", binary]
+        else:
+            command = ["node", binary]
+
+        version = None
+        if (root / ".nvmrc").is_file():
+            version = re.match(r"v?(\d+)", (root / ".nvmrc").read_text().strip())
+        if version is None:
+            version = re.search(r"(\d+)", package.get("engines", {}).get("node", ""))
+        image = f"node:{version.group(1) if version else '20'}-slim"
+
+        lines = [
+            f"FROM {image} AS deps",
+            "WORKDIR /app",
+            f"COPY package.json {lockfile} ./" if lockfile else "COPY package.json ./",
+            f"RUN {install}",
+            "",
+            "FROM deps AS build",
+            "COPY . .",
+        ]
+        if "build" in scripts:
+            lines.append(f"RUN {runner} run build")
+        lines += [
+            f"RUN {prune}",
+            "",
+            f"FROM {image} AS runtime",
+            "ENV NODE_ENV=production",
+            "WORKDIR /app",
+        ]
+        if runner != "npm" and command[0] == runner:
+            lines.append("RUN corepack enable")
+        lines += [
+            "COPY --from=build --chown=node:node /app ./",
+            "USER node",
+            f"CMD {json.dumps(command)}",
+        ]
+
+        return {"lines": lines, "binary": binary, "binaries": entrypoints}
+
     def _docker_push(self, tool_input: Dict[str, Any]) -> SkillResponse:
         """Push Docker image."""
         docker_error = self._check_docker()
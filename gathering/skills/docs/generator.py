@@ -4,8 +4,10 @@ Provides documentation generation and management for agents.
 """
 
 import ast
+import difflib
 import subprocess
 import re
+import tomllib
 from pathlib import Path
 from typing import Dict, Any, List, Optional
 from datetime import datetime
@@ -13,6 +15,60 @@ from datetime import datetime
 from gathering.skills.base import BaseSkill, SkillResponse, SkillPermission
 
 
+# Conventional Commits: `type(scope)!: description` and `BREAKING CHANGE:` footers
+CONVENTIONAL_COMMIT_RE = re.compile(r"^(?P<type>\w+)(?:\((?P<scope>[^)]*)\))?(?P<breaking>!)?:\s*(?P<description>.+)$")
+BREAKING_FOOTER_RE = re.compile(r"^BREAKING[ -]CHANGE:\s*(.+)$", re.MULTILINE)
+
+# Keep a Changelog sections, in order, and the commit types listed in each
+# (build, chore, ci, docs, style and test commits are left out)
+KEEP_A_CHANGELOG_SECTIONS = ["Added", "Changed", "Deprecated", "Removed", "Fixed", "Security"]
+CHANGELOG_SECTIONS = {
+    "feat": "Added",
+    "perf": "Changed",
+    "refactor": "Changed",
+    "revert": "Changed",
+    "deprecate": "Deprecated",
+    "remove": "Removed",
+    "fix": "Fixed",
+    "security": "Security",
+}
+
+# Rust items documented on their own, and the kinds listed under a type
+RUST_DOC_ITEM_KINDS = {"module", "struct", "enum", "trait", "function", "type", "const", "static", "macro"}
+RUST_DOC_MEMBER_KINDS = {"field", "variant", "method", "const", "type"}
+
+# Directories of a crate that are not part of its public API
+RUST_DOC_SKIP_DIRS = {"target", "tests", "examples", "benches"}
+
+# Functions returning Result need `# Errors`; ones that may panic need `# Panics`
+RUST_RESULT_RE = re.compile(r"->\s*(?:[\w:]+::)?Result\b")
+RUST_PANIC_RE = re.compile(
+    r"\b(?:panic|assert|assert_eq|assert_ne|unreachable|todo|unimplemented)!|\.(?:unwrap|expect)\("
+)
+
+# Intra-doc links: [text](target), [text][ref], [`Item`] and `[ref]: target` definitions
+RUST_INLINE_LINK_RE = re.compile(r"\[([^\]]+)\]\(([^)\s]+)\)")
+RUST_REFERENCE_LINK_RE = re.compile(r"\[([^\]]+)\]\[([^\]]*)\]")
+RUST_SHORTCUT_LINK_RE = re.compile(r"(?<![\w\]])\[([^\]]+)\](?![(\[:])")
+RUST_LINK_DEFINITION_RE = re.compile(r"^\s*\[([^\]]+)\]:\s*(\S+)")
+RUST_DOC_PATH_RE = re.compile(r"^[A-Za-z_]\w*(?:::[A-Za-z_]\w*)*$")
+RUST_DISAMBIGUATOR_RE = re.compile(
+    r"^(?:struct|enum|union|trait|fn|method|tymethod|mod|module|const|constant|static|type|macro|"
+    r"field|variant|derive|prim|primitive|value)@"
+)
+
+# Names rustdoc resolves without a path: primitives and the prelude
+RUST_PRELUDE = {
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize",
+    "i8", "i16", "i32", "i64", "i128", "isize", "f32", "f64",
+    "Option", "Some", "None", "Result", "Ok", "Err", "Vec", "String", "Box",
+    "Clone", "Copy", "Send", "Sync", "Sized", "Drop", "Fn", "FnMut", "FnOnce",
+    "Default", "Debug", "Eq", "PartialEq", "Ord", "PartialOrd", "Hash",
+    "Iterator", "IntoIterator", "From", "Into", "TryFrom", "TryInto",
+    "AsRef", "AsMut", "ToString", "ToOwned", "Self", "self", "crate", "super",
+}
+
+
 class DocsSkill(BaseSkill):
     """
     Documentation generation and management skill.
@@ -23,6 +79,7 @@ class DocsSkill(BaseSkill):
     - API documentation generation
     - Markdown file management
     - Code documentation analysis
+    - Rustdoc extraction, linting and API references for Cargo crates
     """
 
     name = "docs"
@@ -51,7 +108,7 @@ class DocsSkill(BaseSkill):
             },
             {
                 "name": "docs_generate_docstring",
-                "description": "Generate docstring for a function or class",
+                "description": "Generate docstring for a function or class, or a rustdoc comment for a Rust item",
                 "input_schema": {
                     "type": "object",
                     "properties": {
@@ -59,8 +116,13 @@ class DocsSkill(BaseSkill):
                         "style": {
                             "type": "string",
                             "enum": ["google", "numpy", "sphinx"],
-                            "description": "Docstring style",
+                            "description": "Docstring style (Python only)",
                             "default": "google"
+                        },
+                        "language": {
+                            "type": "string",
+                            "enum": ["python", "rust"],
+                            "description": "Source language (detected from the code if omitted)"
                         }
                     },
                     "required": ["code"]
@@ -87,7 +149,7 @@ class DocsSkill(BaseSkill):
             },
             {
                 "name": "docs_extract",
-                "description": "Extract documentation from source files",
+                "description": "Extract documentation from source files (Python docstrings, or /// and //! docs of public items for .rs files and Cargo crates)",
                 "input_schema": {
                     "type": "object",
                     "properties": {
@@ -104,7 +166,7 @@ class DocsSkill(BaseSkill):
             },
             {
                 "name": "docs_generate_api",
-                "description": "Generate API documentation for a module",
+                "description": "Generate API documentation for a module, or a Markdown API reference for a Cargo crate",
                 "input_schema": {
                     "type": "object",
                     "properties": {
@@ -117,7 +179,7 @@ class DocsSkill(BaseSkill):
             },
             {
                 "name": "docs_lint",
-                "description": "Check documentation for issues",
+                "description": "Check documentation for issues (Markdown files; for Rust: missing docs on pub items, # Errors / # Panics sections, broken intra-doc links)",
                 "input_schema": {
                     "type": "object",
                     "properties": {
@@ -130,24 +192,33 @@ class DocsSkill(BaseSkill):
             },
             {
                 "name": "docs_changelog",
-                "description": "Generate or update a CHANGELOG entry",
+                "description": "Generate or update a CHANGELOG entry, from explicit changes or from the Conventional Commits between two tags (with a semver bump recommendation)",
                 "input_schema": {
                     "type": "object",
                     "properties": {
                         "path": {"type": "string", "description": "Project path"},
-                        "version": {"type": "string", "description": "Version number"},
+                        "version": {"type": "string", "description": "Version number (default: the recommended next version)"},
+                        "from_ref": {"type": "string", "description": "Start of the git range (default: latest tag)"},
+                        "to_ref": {"type": "string", "description": "End of the git range", "default": "HEAD"},
+                        "update_cargo": {
+                            "type": "boolean",
+                            "description": "Also propose the new version for Cargo.toml and workspace members (dry-run diff)",
+                            "default": False
+                        },
                         "changes": {
                             "type": "object",
                             "properties": {
                                 "added": {"type": "array", "items": {"type": "string"}},
                                 "changed": {"type": "array", "items": {"type": "string"}},
+                                "deprecated": {"type": "array", "items": {"type": "string"}},
+                                "removed": {"type": "array", "items": {"type": "string"}},
                                 "fixed": {"type": "array", "items": {"type": "string"}},
-                                "removed": {"type": "array", "items": {"type": "string"}}
+                                "security": {"type": "array", "items": {"type": "string"}}
                             },
-                            "description": "Changes by category"
+                            "description": "Changes by category (default: parsed from git history)"
                         }
                     },
-                    "required": ["path", "version", "changes"]
+                    "required": ["path"]
                 }
             },
         ]
@@ -282,6 +353,9 @@ class DocsSkill(BaseSkill):
         code = tool_input["code"]
         style = tool_input.get("style", self.doc_style)
 
+        if (tool_input.get("language") or self._detect_language(code)) == "rust":
+            return self._rust_generate_docstring(code)
+
         try:
             tree = ast.parse(code)
         except SyntaxError as e:
@@ -506,6 +580,9 @@ This project is licensed under the MIT License - see the [LICENSE](LICENSE) file
         if not path.exists():
             return SkillResponse(success=False, message=f"Path not found: {path}", error="not_found")
 
+        if self._is_rust(path):
+            return self._rust_extract(path, output_format)
+
         docs = []
 
         files = list(path.rglob("*.py")) if path.is_dir() else [path]
@@ -639,6 +716,9 @@ This project is licensed under the MIT License - see the [LICENSE](LICENSE) file
         if not path.exists():
             return SkillResponse(success=False, message=f"Path not found: {path}", error="not_found")
 
+        if self._is_rust(path):
+            return self._rust_generate_api(path, output_dir, include_private)
+
         # Check if pdoc or sphinx is available
         try:
             subprocess.run(["pdoc", "--version"], capture_output=True, check=True)
@@ -682,7 +762,10 @@ This project is licensed under the MIT License - see the [LICENSE](LICENSE) file
         issues = []
 
         # Check markdown files
-        md_files = list(path.rglob("*.md")) if path.is_dir() else [path]
+        if path.is_dir():
+            md_files = list(path.rglob("*.md"))
+        else:
+            md_files = [] if path.suffix == ".rs" else [path]
 
         for md_file in md_files:
             try:
@@ -734,50 +817,90 @@ This project is licensed under the MIT License - see the [LICENSE](LICENSE) file
                     "message": "Unable to read file (encoding issue)",
                 })
 
+        files_checked = len(md_files)
+        if self._is_rust(path):
+            rust_issues, rust_files = self._rust_lint(path, check_links)
+            issues.extend(rust_issues)
+            files_checked += rust_files
+
         return SkillResponse(
             success=len(issues) == 0,
             message=f"Found {len(issues)} documentation issues",
             data={
                 "issues": issues,
-                "files_checked": len(md_files),
+                "files_checked": files_checked,
             }
         )
 
     def _docs_changelog(self, tool_input: Dict[str, Any]) -> SkillResponse:
-        """Generate or update changelog."""
+        """
+        Generate or update changelog.
+
+        Entries come from `changes` when given, otherwise from the Conventional
+        Commits between `from_ref` (default: latest tag before `to_ref`) and `to_ref`.
+        """
         path = self._get_path(tool_input)
-        version = tool_input["version"]
-        changes = tool_input["changes"]
+        changes = tool_input.get("changes")
+        to_ref = tool_input.get("to_ref", "HEAD")
 
         changelog_path = path / "CHANGELOG.md"
         today = datetime.now().strftime("%Y-%m-%d")
+        data: Dict[str, Any] = {}
 
-        # Build new entry
-        entry_lines = [f"## [{version}] - {today}", ""]
+        if changes is None:
+            try:
+                # From the parent: when to_ref is itself tagged, describe would return that tag
+                from_ref = tool_input.get("from_ref") or self._latest_tag(path, f"{to_ref}^")
+                commits, skipped = self._conventional_commits(path, from_ref, to_ref)
+                today = self._run_git(["log", "-1", "--format=%cs", to_ref], path) or today
+            except (subprocess.CalledProcessError, FileNotFoundError) as e:
+                return SkillResponse(
+                    success=False,
+                    message=f"Cannot read git history: {getattr(e, 'stderr', None) or e}",
+                    error="git_error"
+                )
+
+            current = self._current_version(path, from_ref)
+            bump, recommended = self._semver_bump(current, commits)
+            changes = {}
+            for commit in commits:
+                section = CHANGELOG_SECTIONS.get(commit["type"])
+                if section:
+                    changes.setdefault(section.lower(), []).append(self._changelog_entry(commit))
+            data.update({
+                "from_ref": from_ref,
+                "to_ref": to_ref,
+                "commits": commits,
+                "skipped_commits": skipped,
+                "current_version": current,
+                "bump": bump,
+                "recommended_version": recommended,
+            })
+            # Releasing a tag: its name is the version
+            default_version = recommended
+            if to_ref != "HEAD" and re.fullmatch(r"v?\d+\.\d+\.\d+\S*", to_ref):
+                default_version = to_ref.lstrip("v")
+        else:
+            default_version = None
 
-        if changes.get("added"):
-            entry_lines.append("### Added")
-            for item in changes["added"]:
-                entry_lines.append(f"- {item}")
-            entry_lines.append("")
-
-        if changes.get("changed"):
-            entry_lines.append("### Changed")
-            for item in changes["changed"]:
-                entry_lines.append(f"- {item}")
-            entry_lines.append("")
-
-        if changes.get("fixed"):
-            entry_lines.append("### Fixed")
-            for item in changes["fixed"]:
-                entry_lines.append(f"- {item}")
-            entry_lines.append("")
-
-        if changes.get("removed"):
-            entry_lines.append("### Removed")
-            for item in changes["removed"]:
-                entry_lines.append(f"- {item}")
-            entry_lines.append("")
+        version = tool_input.get("version") or default_version
+        if not version or not any(changes.get(section.lower()) for section in KEEP_A_CHANGELOG_SECTIONS):
+            return SkillResponse(
+                success=False,
+                message="No releasable changes since the last tag",
+                error="no_changes",
+                data=data,
+            )
+
+        # Build new entry (Keep a Changelog sections, in their order)
+        entry_lines = [f"## [{version}] - {today}", ""]
+        for section in KEEP_A_CHANGELOG_SECTIONS:
+            items = changes.get(section.lower())
+            if items:
+                entry_lines.append(f"### {section}")
+                for item in items:
+                    entry_lines.append(f"- {item}")
+                entry_lines.append("")
 
         new_entry = "\n".join(entry_lines)
 
@@ -786,8 +909,12 @@ This project is licensed under the MIT License - see the [LICENSE](LICENSE) file
             with open(changelog_path, "r", encoding="utf-8") as f:
                 existing = f.read()
 
+            # Insert above the latest release, keeping [Unreleased] on top
+            release = re.search(r"^## \[(?!Unreleased\])", existing, re.MULTILINE)
+            if release:
+                updated = existing[:release.start()] + new_entry + "\n" + existing[release.start():]
             # Insert after header
-            if "# Changelog" in existing:
+            elif "# Changelog" in existing:
                 parts = existing.split("\n", 2)
                 if len(parts) >= 2:
                     updated = parts[0] + "\n\n" + new_entry + "\n" + parts[2] if len(parts) > 2 else parts[0] + "\n\n" + new_entry
@@ -796,7 +923,25 @@ This project is licensed under the MIT License - see the [LICENSE](LICENSE) file
             else:
                 updated = "# Changelog\n\n" + new_entry + "\n\n" + existing
         else:
-            updated = "# Changelog\n\nAll notable changes to this project will be documented in this file.\n\n" + new_entry
+            updated = (
+                "# Changelog\n\nAll notable changes to this project will be documented in this file.\n\n"
+                "The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),\n"
+                "and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).\n\n"
+                + new_entry
+            )
+
+        data.update({
+            "path": str(changelog_path),
+            "content": updated,
+            "entry": new_entry,
+            "version": version,
+        })
+
+        # Cargo manifests: proposed version bump, never written here
+        if tool_input.get("update_cargo") and (path / "Cargo.toml").exists():
+            manifests = self._cargo_version_updates(path, version)
+            data["manifests"] = manifests
+            data["manifest_diff"] = "".join(m["diff"] for m in manifests)
 
         return SkillResponse(
             success=True,
@@ -804,10 +949,688 @@ This project is licensed under the MIT License - see the [LICENSE](LICENSE) file
             needs_confirmation=True,
             confirmation_type="write_file",
             confirmation_message=f"Update CHANGELOG.md with v{version} entry?",
+            data=data,
+        )
+
+    def _run_git(self, args: List[str], cwd: Path) -> str:
+        """Run a git command in a repository and return its stripped output."""
+        result = subprocess.run(
+            ["git"] + args, cwd=cwd, capture_output=True, text=True, timeout=60, check=True
+        )
+        return result.stdout.strip()
+
+    def _latest_tag(self, path: Path, ref: str) -> Optional[str]:
+        """Most recent tag reachable from a ref (None when the history has no tags)."""
+        try:
+            return self._run_git(["describe", "--tags", "--abbrev=0", ref], path) or None
+        except subprocess.CalledProcessError:
+            return None
+
+    def _conventional_commits(self, path: Path, from_ref: Optional[str], to_ref: str) -> tuple:
+        """
+        Parse the commits in from_ref..to_ref (oldest first).
+
+        Returns:
+            (commits, skipped) - parsed Conventional Commits and the subjects
+            of commits that do not follow the format
+        """
+        revision = f"{from_ref}..{to_ref}" if from_ref else to_ref
+        output = self._run_git(["log", "--reverse", "--format=%h%x1f%s%x1f%b%x1e", revision], path)
+
+        commits, skipped = [], []
+        for record in output.split("\x1e"):
+            fields = record.strip("\n").split("\x1f")
+            if len(fields) < 3:
+                continue
+            commit = self._parse_conventional_commit(fields[0], fields[1], fields[2])
+            if commit:
+                commits.append(commit)
+            else:
+                skipped.append(fields[1])
+        return commits, skipped
+
+    @staticmethod
+    def _parse_conventional_commit(sha: str, subject: str, body: str) -> Optional[Dict[str, Any]]:
+        """Parse `type(scope)!: description` plus `BREAKING CHANGE:` footers."""
+        match = CONVENTIONAL_COMMIT_RE.match(subject.strip())
+        if not match:
+            return None
+
+        notes = [note.strip() for note in BREAKING_FOOTER_RE.findall(body)]
+        return {
+            "hash": sha,
+            "type": match.group("type").lower(),
+            "scope": match.group("scope") or None,
+            "description": match.group("description").strip(),
+            "breaking": bool(match.group("breaking") or notes),
+            "breaking_notes": notes,
+        }
+
+    @staticmethod
+    def _changelog_entry(commit: Dict[str, Any]) -> str:
+        """One changelog line: `**BREAKING** **scope:** description (hash)`."""
+        text = commit["description"]
+        if commit["scope"]:
+            text = f"**{commit['scope']}:** {text}"
+        if commit["breaking"]:
+            text = f"**BREAKING** {text}"
+            for note in commit["breaking_notes"]:
+                if note != commit["description"]:
+                    text += f" - {note}"
+        return f"{text} ({commit['hash']})"
+
+    def _current_version(self, path: Path, tag: Optional[str]) -> str:
+        """Version of the last release: the tag, else Cargo.toml, else 0.0.0."""
+        from gathering.lsp.rust_manifest import parse_version
+
+        if tag and parse_version(tag.lstrip("v")):
+            return tag.lstrip("v")
+
+        try:
+            data = tomllib.loads((path / "Cargo.toml").read_text())
+        except (OSError, tomllib.TOMLDecodeError):
+            return "0.0.0"
+        for table in (data.get("package", {}), data.get("workspace", {}).get("package", {})):
+            version = table.get("version")
+            if isinstance(version, str) and parse_version(version):
+                return version
+        return "0.0.0"
+
+    @staticmethod
+    def _semver_bump(current: str, commits: List[Dict[str, Any]]) -> tuple:
+        """
+        Recommend the next version.
+
+        Breaking changes bump major, features minor and fixes/performance
+        patch. Before 1.0.0 everything shifts down one level, as Cargo
+        treats 0.x minor releases as breaking.
+
+        Returns:
+            (bump, version) - bump is "major", "minor", "patch" or None
+        """
+        from gathering.lsp.rust_manifest import parse_version
+
+        if any(c["breaking"] for c in commits):
+            bump = "major"
+        elif any(c["type"] == "feat" for c in commits):
+            bump = "minor"
+        elif any(CHANGELOG_SECTIONS.get(c["type"]) for c in commits):
+            bump = "patch"
+        else:
+            return None, None
+
+        major, minor, patch, pre = parse_version(current) or (0, 0, 0, "")
+        if major == 0:
+            bump = {"major": "minor", "minor": "patch", "patch": "patch"}[bump]
+
+        if pre:
+            # 1.0.0-rc.1 releases as 1.0.0 whatever the changes
+            return bump, f"{major}.{minor}.{patch}"
+        if bump == "major":
+            return bump, f"{major + 1}.0.0"
+        if bump == "minor":
+            return bump, f"{major}.{minor + 1}.0"
+        return bump, f"{major}.{minor}.{patch + 1}"
+
+    def _cargo_version_updates(self, path: Path, version: str) -> List[Dict[str, Any]]:
+        """
+        Set `version` in the root Cargo.toml and workspace members (dry run).
+
+        Updates `[package]` and `[workspace.package]` versions and the
+        `version` of path dependencies on workspace members; members using
+        `version.workspace = true` follow the workspace.
+
+        Returns:
+            One {"path", "content", "diff"} per changed manifest
+        """
+        root_manifest = path / "Cargo.toml"
+        root = tomllib.loads(root_manifest.read_text())
+        workspace = root.get("workspace", {})
+
+        manifests = [root_manifest]
+        excluded = {(path / e).resolve() for e in workspace.get("exclude", [])}
+        for pattern in workspace.get("members", []):
+            for member in sorted(path.glob(pattern)):
+                if (member / "Cargo.toml").exists() and member.resolve() not in excluded and member != path:
+                    manifests.append(member / "Cargo.toml")
+
+        crates = set()
+        for manifest in manifests:
+            try:
+                name = tomllib.loads(manifest.read_text()).get("package", {}).get("name")
+            except tomllib.TOMLDecodeError:
+                continue
+            if name:
+                crates.add(name)
+
+        updates = []
+        for manifest in manifests:
+            old = manifest.read_text()
+            new_lines = []
+            table = ""
+            for line in old.split("\n"):
+                header = re.match(r"^\s*\[\[?([^\]]+)\]", line)
+                if header:
+                    table = header.group(1).strip()
+                elif table in ("package", "workspace.package"):
+                    line = re.sub(r'^(\s*version\s*=\s*")[^"]*(")', rf"\g<1>{version}\g<2>", line)
+                elif table.endswith("dependencies"):
+                    # member = { path = "../member", version = "0.1.0" }
+                    dependency = re.match(r'^\s*([\w-]+)\s*=\s*\{(.*)\}', line)
+                    if dependency and "path" in dependency.group(2):
+                        package = re.search(r'package\s*=\s*"([^"]+)"', dependency.group(2))
+                        if (package.group(1) if package else dependency.group(1)) in crates:
+                            line = re.sub(r'(\bversion\s*=\s*")[^"]*(")', rf"\g<1>{version}\g<2>", line)
+                new_lines.append(line)
+            new = "\n".join(new_lines)
+
+            if new != old:
+                relative = manifest.relative_to(path).as_posix()
+                diff = "".join(difflib.unified_diff(
+                    old.splitlines(keepends=True),
+                    new.splitlines(keepends=True),
+                    fromfile=f"a/{relative}",
+                    tofile=f"b/{relative}",
+                ))
+                updates.append({"path": str(manifest), "content": new, "diff": diff})
+        return updates
+
+    # =========================================================================
+    # Rust documentation (rustdoc)
+    # =========================================================================
+
+    @staticmethod
+    def _is_rust(path: Path) -> bool:
+        """A .rs file, a Cargo package/workspace directory or a directory of .rs sources."""
+        if path.is_dir():
+            return (path / "Cargo.toml").exists() or any(path.glob("*.rs"))
+        return path.suffix == ".rs"
+
+    @staticmethod
+    def _detect_language(code: str) -> str:
+        """Python unless the code does not parse and declares a Rust item."""
+        try:
+            ast.parse(code)
+            return "python"
+        except SyntaxError:
+            if re.search(r"\b(?:fn|struct|enum|trait|impl)\s+\w+", code):
+                return "rust"
+            return "python"
+
+    @staticmethod
+    def _rust_package_root(path: Path) -> Path:
+        """Directory of the Cargo.toml owning a path (else the path's directory)."""
+        for directory in [path, *path.parents]:
+            if (directory / "Cargo.toml").exists():
+                return directory
+        return path if path.is_dir() else path.parent
+
+    @staticmethod
+    def _rust_source_files(path: Path) -> List[Path]:
+        """Library and binary sources, without tests, examples, benches or build output."""
+        if not path.is_dir():
+            return [path]
+        return sorted(
+            file for file in path.rglob("*.rs")
+            if not any(part in RUST_DOC_SKIP_DIRS or part.startswith(".")
+                       for part in file.relative_to(path).parts[:-1])
+        )
+
+    @staticmethod
+    def _rust_crate_name(root: Path) -> str:
+        """`[lib] name`, else the package name, else the directory name."""
+        try:
+            data = tomllib.loads((root / "Cargo.toml").read_text())
+        except (OSError, tomllib.TOMLDecodeError):
+            return root.name
+        return (data.get("lib") or {}).get("name") or (data.get("package") or {}).get("name") or root.name
+
+    @staticmethod
+    def _rust_inner_doc(source: str) -> str:
+        """Leading `//!` doc comment of a file (after `#![...]` attributes)."""
+        docs = []
+        for line in source.split("\n"):
+            stripped = line.strip()
+            if stripped.startswith("//!"):
+                body = stripped[3:]
+                docs.append(body[1:] if body.startswith(" ") else body)
+            elif docs or (stripped and not stripped.startswith("#![")):
+                break
+        return "\n".join(docs).strip()
+
+    @staticmethod
+    def _rust_in_tests(symbol) -> bool:
+        """Whether a symbol is a `mod tests` or lives inside one."""
+        return "tests" in symbol.module.split("::") or (symbol.kind == "module" and symbol.name == "tests")
+
+    @staticmethod
+    def _rust_is_public(symbol) -> bool:
+        """`pub` items, trait items and enum variants; not `pub(crate)` and friends."""
+        return symbol.is_pub and not symbol.signature.startswith("pub(")
+
+    def _rust_public_types(self, index) -> set:
+        """Names of the public structs, enums and traits of a crate."""
+        return {
+            s.name for s in index.symbols()
+            if s.kind in ("struct", "enum", "trait") and self._rust_is_public(s) and not self._rust_in_tests(s)
+        }
+
+    def _rust_documented(self, symbol, public_types: set, include_private: bool = False) -> bool:
+        """Whether a symbol belongs to the crate's (public) API."""
+        if symbol.kind == "impl" or self._rust_in_tests(symbol):
+            return False
+        if symbol.container:
+            if symbol.kind not in RUST_DOC_MEMBER_KINDS:
+                return False
+            return include_private or (symbol.container in public_types and self._rust_is_public(symbol))
+        return symbol.kind in RUST_DOC_ITEM_KINDS and (include_private or self._rust_is_public(symbol))
+
+    @staticmethod
+    def _rust_item_doc(symbol, index) -> str:
+        """Outer docs of an item; for `mod foo;`, the //! docs of its file as a fallback."""
+        if symbol.kind == "module" and not symbol.doc.strip():
+            module = index.module_symbol(symbol.path)
+            return module.doc if module else ""
+        return symbol.doc
+
+    def _rust_index(self, root: Path):
+        """Symbol index of a crate (lazy import: the LSP package is optional)."""
+        from gathering.lsp.rust_symbols import RustSymbolIndex
+
+        index = RustSymbolIndex(str(root))
+        index.build()
+        return index
+
+    def _rust_api(self, path: Path, include_private: bool = False) -> List[Dict[str, Any]]:
+        """Documented items per source file: module docs, items and their members."""
+        root = self._rust_package_root(path)
+        index = self._rust_index(root)
+        public_types = self._rust_public_types(index)
+
+        docs = []
+        for file_path in self._rust_source_files(path):
+            relative = index.relative(str(file_path))
+            try:
+                source = file_path.read_text(encoding="utf-8")
+            except (OSError, UnicodeDecodeError):
+                continue
+
+            items = []
+            for symbol in index.file_symbols(relative):
+                if symbol.container or not self._rust_documented(symbol, public_types, include_private):
+                    continue
+                item = {
+                    "name": symbol.name,
+                    "kind": symbol.kind,
+                    "path": symbol.path,
+                    "signature": symbol.signature,
+                    "doc": self._rust_item_doc(symbol, index),
+                    "line": symbol.line + 1,
+                    "members": [],
+                    "traits": [],
+                }
+                if symbol.kind in ("struct", "enum", "trait"):
+                    item["members"] = [
+                        {
+                            "name": member.name,
+                            "kind": member.kind,
+                            "signature": member.signature,
+                            "doc": member.doc,
+                            "line": member.line + 1,
+                        }
+                        for member in sorted(index.members(symbol.name), key=lambda m: (m.file, m.line))
+                        if self._rust_documented(member, public_types, include_private)
+                    ]
+                    item["traits"] = sorted({
+                        s.trait for s in index.symbols()
+                        if s.kind == "impl" and s.name == symbol.name and s.trait
+                    })
+                items.append(item)
+
+            docs.append({
+                "file": str(file_path),
+                "module": index.module_path(relative),
+                "module_docstring": self._rust_inner_doc(source),
+                "items": items,
+            })
+        return docs
+
+    def _rust_extract(self, path: Path, output_format: str) -> SkillResponse:
+        """Extract `///` and `//!` docs of a crate's public items."""
+        docs = self._rust_api(path)
+
+        if output_format == "markdown":
+            output = self._format_rust_docs_markdown(docs)
+        elif output_format == "html":
+            output = self._format_rust_docs_html(docs)
+        else:
+            output = docs
+
+        return SkillResponse(
+            success=True,
+            message=f"Extracted documentation from {len(docs)} files",
             data={
-                "path": str(changelog_path),
-                "content": updated,
-                "entry": new_entry,
-                "version": version,
+                "documentation": output,
+                "format": output_format,
+                "files_processed": len(docs),
+                "language": "rust",
+            }
+        )
+
+    def _format_rust_docs_markdown(self, docs: List[Dict], heading: int = 1) -> str:
+        """Format Rust docs as markdown: one section per module, one subsection per item."""
+        h = "#" * heading
+        lines = []
+
+        for module_doc in docs:
+            if not module_doc["items"] and not module_doc["module_docstring"]:
+                continue
+            lines.append(f"{h} Module `{module_doc['module']}`")
+            lines.append("")
+            if module_doc["module_docstring"]:
+                lines.append(module_doc["module_docstring"])
+                lines.append("")
+
+            for item in module_doc["items"]:
+                lines.append(f"{h}# {item['kind']} `{item['name']}`")
+                lines.append("")
+                lines.extend(["```rust", item["signature"], "```", ""])
+                if item["doc"]:
+                    lines.append(item["doc"])
+                    lines.append("")
+
+                for kind, title in (("field", "Fields"), ("variant", "Variants"),
+                                    ("method", "Methods"), ("const", "Associated constants"),
+                                    ("type", "Associated types")):
+                    members = [m for m in item["members"] if m["kind"] == kind]
+                    if not members:
+                        continue
+                    lines.append(f"**{title}**")
+                    lines.append("")
+                    for member in members:
+                        summary = member["doc"].split("\n\n")[0].replace("\n", " ") if member["doc"] else ""
+                        entry = f"- `{member['signature'] or member['name']}`"
+                        lines.append(f"{entry} - {summary}" if summary else entry)
+                    lines.append("")
+
+                if item["traits"]:
+                    lines.append("**Implements:** " + ", ".join(f"`{t}`" for t in item["traits"]))
+                    lines.append("")
+
+        return "\n".join(lines)
+
+    def _format_rust_docs_html(self, docs: List[Dict]) -> str:
+        """Format Rust docs as HTML."""
+        html_parts = ["<html><body>"]
+
+        for module_doc in docs:
+            html_parts.append(f"<h1>Module {module_doc['module']}</h1>")
+            if module_doc["module_docstring"]:
+                html_parts.append(f"<p>{module_doc['module_docstring']}</p>")
+
+            for item in module_doc["items"]:
+                html_parts.append(f"<h2>{item['kind']} {item['name']}</h2>")
+                html_parts.append(f"<pre><code>{item['signature']}</code></pre>")
+                if item["doc"]:
+                    html_parts.append(f"<p>{item['doc']}</p>")
+                for member in item["members"]:
+                    html_parts.append(f"<h3>{member['name']}</h3>")
+                    if member["doc"]:
+                        html_parts.append(f"<p>{member['doc']}</p>")
+
+        html_parts.append("</body></html>")
+        return "\n".join(html_parts)
+
+    def _rust_generate_api(self, path: Path, output_dir: str, include_private: bool) -> SkillResponse:
+        """Render a Markdown API reference for a crate."""
+        root = self._rust_package_root(path)
+        crate = self._rust_crate_name(root)
+        docs = self._rust_api(path, include_private)
+
+        content = f"# `{crate}` API Reference\n\n" + self._format_rust_docs_markdown(docs, heading=2)
+
+        output_path = Path(output_dir)
+        if not output_path.is_absolute():
+            output_path = root / output_path
+        output_path = output_path / f"{crate}.md"
+        item_count = sum(len(d["items"]) for d in docs)
+
+        return SkillResponse(
+            success=True,
+            message=f"Generated API reference for {crate} ({item_count} items)",
+            needs_confirmation=True,
+            confirmation_type="write_file",
+            confirmation_message=f"Write API reference to {output_path}?",
+            data={
+                "path": str(output_path),
+                "content": content,
+                "crate": crate,
+                "items": item_count,
+                "tool": "rustdoc-markdown",
+            }
+        )
+
+    def _rust_lint(self, path: Path, check_links: bool) -> tuple:
+        """
+        Lint the rustdoc of a crate's public items.
+
+        Returns:
+            (issues, files_checked)
+        """
+        from gathering.lsp.rust_manifest import CargoManifest
+
+        root = self._rust_package_root(path)
+        index = self._rust_index(root)
+        public_types = self._rust_public_types(index)
+        try:
+            dependencies = CargoManifest((root / "Cargo.toml").read_text()).dependency_names()
+        except OSError:
+            dependencies = set()
+
+        issues = []
+        files = self._rust_source_files(path)
+
+        for file_path in files:
+            relative = index.relative(str(file_path))
+            try:
+                source = file_path.read_text(encoding="utf-8")
+            except (OSError, UnicodeDecodeError):
+                continue
+            lines = source.split("\n")
+            module = index.module_path(relative)
+
+            def issue(line: int, kind: str, message: str) -> None:
+                issues.append({"file": str(file_path), "line": line, "type": kind, "message": message})
+
+            inner = self._rust_inner_doc(source)
+            if module == "crate" and file_path.name == "lib.rs" and not inner:
+                issue(1, "missing_docs", "Missing crate-level documentation (//!)")
+            if check_links:
+                for target in self._rust_broken_links(inner, index, relative, module, None, dependencies):
+                    issue(1, "broken_intra_doc_link", f"Unresolved link to `{target}`")
+
+            for symbol in index.file_symbols(relative):
+                if not self._rust_documented(symbol, public_types):
+                    continue
+                line = symbol.line + 1
+                label = f"{symbol.kind} `{symbol.container + '::' if symbol.container else ''}{symbol.name}`"
+
+                if not self._rust_item_doc(symbol, index).strip():
+                    issue(line, "missing_docs", f"Missing documentation for public {label}")
+                    continue
+
+                if symbol.kind in ("function", "method"):
+                    if RUST_RESULT_RE.search(symbol.signature) and not re.search(r"^#\s+Errors\s*$", symbol.doc, re.MULTILINE):
+                        issue(line, "missing_errors_doc", f"{label} returns a Result but has no `# Errors` section")
+                    body = re.sub(r"//[^\n]*", "", "\n".join(lines[symbol.start_line:symbol.end_line + 1]))
+                    if RUST_PANIC_RE.search(body) and not re.search(r"^#\s+Panics\s*$", symbol.doc, re.MULTILINE):
+                        issue(line, "missing_panics_doc", f"{label} may panic but has no `# Panics` section")
+
+                if check_links:
+                    owner = symbol.container or (symbol.name if symbol.kind in ("struct", "enum", "trait") else None)
+                    for target in self._rust_broken_links(symbol.doc, index, relative, symbol.module, owner, dependencies):
+                        issue(line, "broken_intra_doc_link", f"Unresolved link to `{target}` in docs of {label}")
+
+        return issues, len(files)
+
+    @staticmethod
+    def _rust_doc_links(doc: str) -> List[str]:
+        """Intra-doc link targets of a doc comment (code blocks and URLs skipped)."""
+        prose = []
+        fenced = False
+        for line in doc.split("\n"):
+            if line.strip().startswith(("```", "~~~")):
+                fenced = not fenced
+                continue
+            if not fenced:
+                prose.append(line)
+
+        definitions = {}
+        for line in prose:
+            match = RUST_LINK_DEFINITION_RE.match(line)
+            if match:
+                definitions[match.group(1)] = match.group(2)
+        text = "\n".join(line for line in prose if not RUST_LINK_DEFINITION_RE.match(line))
+        # Code spans that are not link text (`a[i]`) never hold links
+        text = re.sub(
+            r"`[^`]*`",
+            lambda m: m.group() if text[m.start() - 1:m.start()] == "[" and text[m.end():m.end() + 1] == "]" else "",
+            text,
+        )
+
+        targets = list(definitions.values())
+        for match in RUST_INLINE_LINK_RE.finditer(text):
+            targets.append(match.group(2))
+        for match in RUST_REFERENCE_LINK_RE.finditer(text):
+            reference = match.group(2) or match.group(1)
+            if reference not in definitions:
+                targets.append(reference)
+        text = RUST_REFERENCE_LINK_RE.sub("", RUST_INLINE_LINK_RE.sub("", text))
+        for match in RUST_SHORTCUT_LINK_RE.finditer(text):
+            if match.group(1) not in definitions:
+                targets.append(match.group(1))
+
+        links = []
+        for target in targets:
+            if re.match(r"^[a-z][a-z0-9+.-]*:(?!:)", target) or target.startswith(("#", "/", ".")) or "." in target:
+                continue  # URLs, anchors and relative files
+            target = RUST_DISAMBIGUATOR_RE.sub("", target.strip("`").strip())
+            target = re.sub(r"(?:\(\)|!)$", "", target)
+            if RUST_DOC_PATH_RE.match(target):
+                links.append(target)
+        return links
+
+    def _rust_broken_links(self, doc: str, index, file_path: str, module: str,
+                           owner: Optional[str], dependencies: set) -> List[str]:
+        """Link targets of a doc comment that do not resolve in the crate."""
+        broken = []
+        for target in self._rust_doc_links(doc):
+            segments = target.split("::")
+            if segments[0] == "Self" and owner:
+                segments[0] = owner
+            if segments[0] in ("std", "core", "alloc") or segments[0] in dependencies:
+                continue
+            if len(segments) == 1 and segments[0] in RUST_PRELUDE:
+                continue
+            if index.resolve(segments, file_path, module) is None:
+                broken.append(target)
+        return broken
+
+    @staticmethod
+    def _rust_fn_params(signature: str) -> List[str]:
+        """Parameter names of a fn signature, without the `self` receiver."""
+        match = re.search(r"\bfn\s+\w+\s*", signature)
+        if not match:
+            return []
+
+        # Skip generics, which may hold parentheses: fn map<F: Fn(u8) -> u8>(f: F)
+        start = match.end()
+        if signature[start:start + 1] == "<":
+            depth = 0
+            for i in range(start, len(signature)):
+                if signature[i] == "<":
+                    depth += 1
+                elif signature[i] == ">" and signature[i - 1] != "-":
+                    depth -= 1
+                    if depth == 0:
+                        start = i + 1
+                        break
+        start = signature.find("(", start)
+        if start < 0:
+            return []
+
+        params, current, depth = [], "", 0
+        for i, ch in enumerate(signature[start + 1:], start + 1):
+            if ch in "(<[":
+                depth += 1
+            elif ch in ")]" or (ch == ">" and signature[i - 1] != "-"):
+                if depth == 0:
+                    break
+                depth -= 1
+            elif ch == "," and depth == 0:
+                params.append(current)
+                current = ""
+                continue
+            current += ch
+        params.append(current)
+
+        names = []
+        for param in params:
+            param = param.strip()
+            if not param or re.match(r"^(?:&\s*(?:'\w+\s+)?)?(?:mut\s+)?self\b", param):
+                continue
+            names.append(re.sub(r"^mut\s+", "", param.split(":", 1)[0].strip()))
+        return names
+
+    def _rust_generate_docstring(self, code: str) -> SkillResponse:
+        """Draft a rustdoc comment for the first item in Rust code."""
+        from gathering.lsp.rust_symbols import parse_symbols
+
+        item = next((s for s in parse_symbols(code) if s.kind != "impl"), None)
+        if item is None:
+            return SkillResponse(
+                success=False,
+                message="No Rust item found in code",
+                error="no_definition_found"
+            )
+
+        code_lines = code.split("\n")
+        lines = ["Brief description."]
+
+        if item.kind in ("function", "method"):
+            params = self._rust_fn_params(item.signature)
+            if params:
+                lines.extend(["", "# Arguments", ""])
+                lines.extend(f"* `{name}` - Description." for name in params)
+            if RUST_RESULT_RE.search(item.signature):
+                lines.extend(["", "# Errors", "", "Returns an error if ..."])
+            body = re.sub(r"//[^\n]*", "", "\n".join(code_lines[item.start_line:item.end_line + 1]))
+            if RUST_PANIC_RE.search(body):
+                lines.extend(["", "# Panics", "", "Panics if ..."])
+            if re.search(r"\bunsafe\s+fn\b", item.signature):
+                lines.extend(["", "# Safety", "", "The caller must ensure ..."])
+
+            receiver = re.search(r"\(\s*&?\s*(?:'\w+\s+)?(?:mut\s+)?self\b", item.signature)
+            if receiver:
+                call = f"value.{item.name}(...)"
+            elif item.container:
+                call = f"{item.container}::{item.name}(...)"
+            else:
+                call = f"{item.name}(...)"
+            lines.extend(["", "# Examples", "", "```", f"// let result = {call};", "```"])
+        elif item.kind in ("struct", "enum"):
+            lines.extend(["", "# Examples", "", "```", f"// let value = {item.name}::new(...);", "```"])
+
+        indent = re.match(r"\s*", code_lines[item.start_line]).group()
+        docstring = "\n".join(f"{indent}/// {line}".rstrip() for line in lines)
+
+        return SkillResponse(
+            success=True,
+            message=f"Generated rustdoc comment for {item.name}",
+            data={
+                "docstring": docstring,
+                "style": "rustdoc",
+                "name": item.name,
+                "type": item.kind,
             }
         )
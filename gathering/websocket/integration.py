@@ -56,6 +56,30 @@ async def _broadcast_event_to_websocket(event: Event) -> None:
     await manager.broadcast(ws_message)
 
 
+async def _send_diagnostics_to_subscribers(event: Event) -> None:
+    """
+    Event handler that sends LSP diagnostics to the clients subscribed
+    to the event's project and file.
+
+    Args:
+        event: LSP_DIAGNOSTICS event.
+    """
+    manager = get_connection_manager()
+
+    if manager.get_client_count() == 0 or event.project_id is None:
+        return
+
+    ws_message = {
+        "type": EventType.LSP_DIAGNOSTICS.value,
+        "data": {"project_id": event.project_id, **event.data},
+        "project_id": event.project_id,
+        "event_id": event.id,
+        "timestamp": event.timestamp.isoformat() if event.timestamp else None,
+    }
+
+    await manager.broadcast_to_subscribers(event.project_id, event.data.get("file_path"), ws_message)
+
+
 def setup_websocket_broadcasting(
     event_types: Optional[List[EventType]] = None,
 ) -> None:
@@ -81,6 +105,9 @@ def setup_websocket_broadcasting(
     for event_type in events_to_broadcast:
         event_bus.subscribe(event_type, _broadcast_event_to_websocket)
 
+    # Diagnostics only go to clients subscribed to the project/file
+    event_bus.subscribe(EventType.LSP_DIAGNOSTICS, _send_diagnostics_to_subscribers)
+
     print(f"[WebSocket] Broadcasting enabled for {len(events_to_broadcast)} event types")
 
 
@@ -6,7 +6,7 @@ Non-blocking, async-first, supports multiple concurrent connections.
 """
 
 import asyncio
-from typing import Dict, Optional, Any
+from typing import Dict, List, Optional, Any, Tuple
 from datetime import datetime, timezone
 
 try:
@@ -25,6 +25,7 @@ class ConnectionManager:
     Features:
     - Multiple concurrent connections
     - Broadcasting to all clients
+    - Project/file subscriptions for targeted updates (LSP diagnostics)
     - Graceful disconnect handling
     - JSON message serialization
     - Connection tracking and stats
@@ -41,6 +42,10 @@ class ConnectionManager:
         # Send to specific client
         await manager.send_personal(message, websocket)
 
+        # Send to clients subscribed to a project file
+        manager.subscribe(websocket, project_id=1, file_path="src/lib.rs")
+        await manager.broadcast_to_subscribers(1, "src/lib.rs", message)
+
         # Disconnect
         await manager.disconnect(websocket)
     """
@@ -74,6 +79,8 @@ class ConnectionManager:
             "connected_at": datetime.now(timezone.utc),
             "messages_received": 0,
             "messages_sent": 0,
+            # (project_id, file_path) pairs; file_path None covers the whole project
+            "subscriptions": set(),
         }
 
         self.total_connections += 1
@@ -194,6 +201,108 @@ class ConnectionManager:
             print(f"[WebSocket] Send error: {e}")
             return False
 
+    def subscribe(self, websocket: WebSocket, project_id: int, file_path: Optional[str] = None) -> bool:
+        """
+        Subscribe a client to updates for a project, or one of its files.
+
+        Args:
+            websocket: Subscribing WebSocket.
+            project_id: Project identifier.
+            file_path: Workspace-relative file path (None for every file).
+
+        Returns:
+            True if the client is connected.
+        """
+        if websocket not in self.active_connections:
+            return False
+
+        self.active_connections[websocket]["subscriptions"].add((project_id, file_path))
+        return True
+
+    def unsubscribe(
+        self,
+        websocket: WebSocket,
+        project_id: Optional[int] = None,
+        file_path: Optional[str] = None,
+    ) -> int:
+        """
+        Remove a client's subscriptions.
+
+        Args:
+            websocket: Subscribed WebSocket.
+            project_id: Only remove subscriptions to this project (None for all).
+            file_path: Only remove the subscription to this file.
+
+        Returns:
+            Number of subscriptions removed.
+        """
+        if websocket not in self.active_connections:
+            return 0
+
+        subscriptions = self.active_connections[websocket]["subscriptions"]
+        removed = {
+            (project, path) for project, path in subscriptions
+            if (project_id is None or project == project_id)
+            and (file_path is None or path == file_path)
+        }
+        subscriptions.difference_update(removed)
+        return len(removed)
+
+    @staticmethod
+    def _is_subscribed(subscriptions: set, project_id: int, file_path: Optional[str]) -> bool:
+        """Whether subscriptions cover a project file."""
+        return (project_id, None) in subscriptions or (
+            file_path is not None and (project_id, file_path) in subscriptions
+        )
+
+    def get_subscribers(self, project_id: int, file_path: Optional[str] = None) -> List[WebSocket]:
+        """
+        Get clients subscribed to a project file.
+
+        Args:
+            project_id: Project identifier.
+            file_path: Workspace-relative file path.
+
+        Returns:
+            Subscribed WebSockets.
+        """
+        return [
+            websocket
+            for websocket, info in self.active_connections.items()
+            if self._is_subscribed(info["subscriptions"], project_id, file_path)
+        ]
+
+    async def broadcast_to_subscribers(
+        self,
+        project_id: int,
+        file_path: Optional[str],
+        message: Dict[str, Any],
+    ) -> int:
+        """
+        Send message to the clients subscribed to a project file.
+
+        Args:
+            project_id: Project identifier.
+            file_path: Workspace-relative file path the message is about.
+            message: Message dict to send.
+
+        Returns:
+            Number of clients that received the message.
+        """
+        subscribers = self.get_subscribers(project_id, file_path)
+        if not subscribers:
+            return 0
+
+        if "timestamp" not in message:
+            message["timestamp"] = datetime.now(timezone.utc).isoformat()
+
+        results = await asyncio.gather(
+            *(self._send_with_error_handling(websocket, message) for websocket in subscribers),
+            return_exceptions=True,
+        )
+
+        return sum(1 for result in results if result is True)
+
     async def broadcast_event(self, event_type: str, data: Dict[str, Any]) -> int:
         """
         Broadcast an event to all clients.
@@ -233,11 +342,20 @@ class ConnectionManager:
                     "connected_at": info["connected_at"].isoformat(),
                     "messages_sent": info["messages_sent"],
                     "messages_received": info["messages_received"],
+                    "subscriptions": [
+                        {"project_id": project_id, "file_path": file_path}
+                        for project_id, file_path in self._sorted_subscriptions(info["subscriptions"])
+                    ],
                 }
                 for info in self.active_connections.values()
             ],
         }
 
+    @staticmethod
+    def _sorted_subscriptions(subscriptions: set) -> List[Tuple[int, Optional[str]]]:
+        """Subscriptions ordered by project, whole-project first."""
+        return sorted(subscriptions, key=lambda sub: (sub[0], sub[1] is not None, sub[1] or ""))
+
     def get_client_count(self) -> int:
         """Get number of active connections."""
         return len(self.active_connections)
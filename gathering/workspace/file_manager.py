@@ -5,11 +5,15 @@ Handles file tree generation, reading, writing with git status integration.
 """
 
 from pathlib import Path
-from typing import Dict, Any, Optional
+from typing import Dict, Any, List, Optional, Tuple
+from urllib.parse import unquote, urlparse
+import difflib
 import mimetypes
 import logging
 import subprocess
 
+from gathering.lsp.text_edits import apply_edits, workspace_edit_changes
+
 logger = logging.getLogger(__name__)
 
 
@@ -364,6 +368,88 @@ class FileManager:
             "deleted": True,
         }
 
+    @classmethod
+    def _edited_files(
+        cls,
+        project_path: str,
+        edit: Dict[str, Any],
+    ) -> List[Tuple[str, str, str]]:
+        """
+        Resolve a WorkspaceEdit to (relative path, original, updated) per file.
+
+        Documents are addressed by file URI or by path relative to the project.
+        """
+        root = Path(project_path).resolve()
+        files = []
+
+        for uri, edits in workspace_edit_changes(edit).items():
+            path = Path(unquote(urlparse(uri).path)) if uri.startswith("file:") else root / uri
+            try:
+                relative = str(path.resolve().relative_to(root))
+            except ValueError:
+                raise ValueError(f"File path outside project: {uri}")
+
+            original = cls.read_file(project_path, relative)
+            if original["type"] != "text":
+                raise ValueError(f"Cannot edit binary file: {relative}")
+
+            files.append((relative, original["content"], apply_edits(original["content"], edits)))
+
+        return files
+
+    @classmethod
+    def preview_workspace_edit(cls, project_path: str, edit: Dict[str, Any]) -> Dict[str, Any]:
+        """
+        Preview an LSP WorkspaceEdit (e.g. a rename) as unified diffs.
+
+        Args:
+            project_path: Path to project.
+            edit: WorkspaceEdit with `changes` or `documentChanges`.
+
+        Returns:
+            Per-file diffs; nothing is written.
+        """
+        files = []
+        for relative, original, updated in cls._edited_files(project_path, edit):
+            diff = "".join(difflib.unified_diff(
+                original.splitlines(keepends=True),
+                updated.splitlines(keepends=True),
+                fromfile=f"a/{relative}",
+                tofile=f"b/{relative}",
+            ))
+            files.append({"path": relative, "diff": diff, "changed": original != updated})
+
+        return {"files": files, "count": len(files)}
+
+    @classmethod
+    def apply_workspace_edit(
+        cls,
+        project_path: str,
+        edit: Dict[str, Any],
+        create_backup: bool = True,
+    ) -> Dict[str, Any]:
+        """
+        Apply an LSP WorkspaceEdit across files.
+
+        Every file is read and edited in memory before anything is written,
+        so an invalid edit leaves the workspace untouched.
+
+        Args:
+            project_path: Path to project.
+            edit: WorkspaceEdit with `changes` or `documentChanges`.
+            create_backup: Create backups of the original files.
+
+        Returns:
+            Write results for the changed files.
+        """
+        files = [
+            cls.write_file(project_path, relative, updated, create_backup=create_backup)
+            for relative, original, updated in cls._edited_files(project_path, edit)
+            if original != updated
+        ]
+
+        return {"success": True, "files": files, "count": len(files)}
+
     @classmethod
     def get_file_language(cls, file_path: str) -> str:
         """
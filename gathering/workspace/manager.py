@@ -38,6 +38,7 @@ class WorkspaceManager:
             "files": [
                 "package.json",
                 "requirements.txt",
+                "pyproject.toml",
                 "Cargo.toml",
                 "pom.xml",
                 "build.gradle",
@@ -159,6 +159,44 @@ class TestFileManagement:
 
         assert response.status_code == 404
 
+    def test_preview_workspace_edit(self, test_workspace):
+        """Test previewing a WorkspaceEdit returns diffs without writing."""
+        client = TestClient(app)
+        edit = {"changes": {"src/main.py": [{
+            "range": {"start": {"line": 0, "character": 4}, "end": {"line": 0, "character": 9}},
+            "newText": "greet",
+        }]}}
+
+        response = client.post("/workspace/1/edit/preview", json={"edit": edit})
+
+        assert response.status_code == 200
+        data = response.json()
+        assert data["count"] == 1
+        assert "+def greet():" in data["files"][0]["diff"]
+        assert "hello" in (Path(test_workspace) / "src" / "main.py").read_text()
+
+    def test_apply_workspace_edit(self, test_workspace):
+        """Test applying a WorkspaceEdit writes the changed files."""
+        client = TestClient(app)
+        edit = {"changes": {"src/main.py": [{
+            "range": {"start": {"line": 0, "character": 4}, "end": {"line": 0, "character": 9}},
+            "newText": "greet",
+        }]}}
+
+        response = client.post("/workspace/1/edit/apply", json={"edit": edit, "create_backup": False})
+
+        assert response.status_code == 200
+        assert response.json()["count"] == 1
+        assert (Path(test_workspace) / "src" / "main.py").read_text().startswith("def greet():")
+
+    def test_apply_workspace_edit_missing_file(self, test_workspace):
+        """Test edits to missing files are rejected."""
+        client = TestClient(app)
+
+        response = client.post("/workspace/1/edit/apply", json={"edit": {"changes": {"missing.py": []}}})
+
+        assert response.status_code == 404
+
 
 class TestGitOperations:
     """Test Git-related endpoints."""
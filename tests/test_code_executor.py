@@ -3,9 +3,16 @@ Tests for Code Executor skill.
 Tests the sandboxed code execution with security controls.
 """
 
+import os
+import shutil
+import sys
+from pathlib import Path
+
 import pytest
 from gathering.skills.code.executor import CodeExecutionSkill, CodeConfig
 
+requires_rustc = pytest.mark.skipif(shutil.which("rustc") is None, reason="rustc not installed")
+
 
 class TestCodeExecutorSafety:
     """Tests for code execution safety features."""
@@ -223,6 +230,146 @@ class TestCodeExecutorToolDefinitions:
             assert "input_schema" in tool
 
 
+class TestCodeExecutorRust:
+    """Tests for compiling and running Rust snippets."""
+
+    @pytest.fixture
+    def vendor_dir(self, tmp_path):
+        """A `cargo vendor` style directory holding one local crate."""
+        crate = tmp_path / "vendor" / "greet"
+        (crate / "src").mkdir(parents=True)
+        (crate / "Cargo.toml").write_text('[package]\nname = "greet"\nversion = "0.1.0"\nedition = "2021"\n')
+        (crate / "src" / "lib.rs").write_text('pub fn hello() -> &\'static str { "hi" }\n')
+        (crate / ".cargo-checksum.json").write_text('{"files": {}, "package": null}')
+        return tmp_path / "vendor"
+
+    @pytest.fixture
+    def executor(self, tmp_path, vendor_dir):
+        """Create a Rust-capable executor with one allowed crate."""
+        return CodeExecutionSkill(CodeConfig(
+            work_dir=str(tmp_path / "work"),
+            rust_allowed_crates=["greet"],
+            rust_vendor_dir=str(vendor_dir),
+        ))
+
+    def test_rust_exec_tool_exists(self, executor):
+        """Test that rust_exec tool exists."""
+        tool_names = [t["name"] for t in executor.get_tools_definition()]
+        assert "rust_exec" in tool_names
+
+    def test_frontmatter_dependencies(self, executor):
+        """Test cargo-script frontmatter is read and blanked out."""
+        code = '---cargo\n[dependencies]\ngreet = "0.1"\n---\nfn main() {}\n'
+        stripped, dependencies = executor._split_rust_frontmatter(code)
+        assert dependencies == {"greet": "0.1"}
+        assert stripped == "\n\n\n\nfn main() {}\n"
+
+    def test_blocks_unlisted_crates(self, executor):
+        """Test dependencies outside the allow-list are rejected before building."""
+        result = executor.execute("rust_exec", {"code": "fn main() {}", "dependencies": {"serde": "1"}})
+        assert result["success"] is False
+        assert "serde" in result["error"]
+
+        result = executor.execute("rust_exec", {
+            "code": "fn main() {}", "dependencies": {"greet": {"path": "/etc"}}
+        })
+        assert result["success"] is False
+        assert "may only set" in result["error"]
+
+    def test_rejects_unknown_edition(self, executor):
+        """Test the edition is checked before it reaches rustc or Cargo.toml."""
+        injected = '2021"\n\n[dependencies]\nserde = { path = "/etc'
+        for edition in (injected, "2099"):
+            result = executor.execute("rust_exec", {"code": "fn main() {}", "edition": edition})
+            assert result["success"] is False
+            assert "Unsupported Rust edition" in result["error"]
+
+    @requires_rustc
+    def test_rustc_snippet_and_cache(self, executor):
+        """Test bare statements are wrapped in main and the binary is cached."""
+        code = 'let unused = 1;\nprintln!("{}", (1..=4).sum::<i32>());'
+
+        first = executor.execute("rust_exec", {"code": code})
+        assert first["success"] is True
+        assert first["output"] == "10\n"
+        assert first["build"] == "rustc" and first["cached"] is False
+        # Line numbers refer to the snippet, not the generated main
+        assert first["diagnostics"] == [{
+            "level": "warning", "code": "unused_variables",
+            "message": "unused variable: `unused`", "line": 1, "column": 5,
+        }]
+
+        second = executor.execute("rust_exec", {"code": code})
+        assert second["cached"] is True
+        assert second["cache_key"] == first["cache_key"]
+        assert second["output"] == "10\n"
+
+    @requires_rustc
+    def test_compile_errors_separate_from_output(self, executor):
+        """Test compiler diagnostics are returned without running the program."""
+        result = executor.execute("rust_exec", {"code": 'fn main() {\n    let y: u32 = "a";\n}\n'})
+        assert result["success"] is False
+        assert "output" not in result
+        assert result["diagnostics"][0]["code"] == "E0308"
+        assert result["diagnostics"][0]["line"] == 2
+        assert "mismatched types" in result["compiler_output"]
+
+    def test_build_cache_eviction(self, executor):
+        """Test the least recently used snippet builds are removed beyond the cap."""
+        root = Path(executor.code_config.work_dir) / "rust"
+        for name in ("target", "cargo-home"):
+            (root / name).mkdir(parents=True)
+        keys = [f"{i:016x}" for i in range(4)]
+        for age, key in enumerate(keys):
+            (root / key).mkdir()
+            os.utime(root / key, (1000 - age, 1000 - age))
+
+        executor.code_config.rust_cache_max_entries = 3
+        executor._evict_rust_cache(root / "ffffffffffffffff")
+
+        # keys[3] and keys[2] were the oldest; the new build takes the third slot
+        assert sorted(p.name for p in root.iterdir()) == [keys[0], keys[1], "cargo-home", "target"]
+
+    @requires_rustc
+    def test_plain_text_compiler_errors(self, executor, tmp_path):
+        """Test rustc errors outside the JSON stream end up in compiler_output."""
+        build = executor._build_rust("fn main() {}\n", {}, "2099", False, tmp_path / "build", 60)
+        assert build["success"] is False
+        assert build["diagnostics"] == []
+        assert "--edition" in build["compiler_output"]
+
+    @requires_rustc
+    def test_runtime_limits(self, executor):
+        """Test the run honors the timeout and memory limit."""
+        result = executor.execute("rust_exec", {"code": "fn main() { loop {} }", "timeout": 1})
+        assert result["success"] is False
+        assert "timed out" in result["error"]
+
+        if sys.platform.startswith("linux"):
+            result = executor.execute("rust_exec", {"code": "let v = vec![1u8; 1 << 30]; println!(\"{}\", v[0]);"})
+            assert result["success"] is False
+            assert "memory allocation" in result["error"]
+
+    @requires_rustc
+    @pytest.mark.skipif(not sys.platform.startswith("linux"), reason="memory limit is Linux only")
+    def test_build_memory_limit(self, executor):
+        """Test the compiler runs under rust_build_memory_mb."""
+        executor.code_config.rust_build_memory_mb = 64
+        result = executor.execute("rust_exec", {"code": "fn main() {}"})
+        assert result["success"] is False
+        assert result["error"] == "Compilation failed"
+
+    @requires_rustc
+    @pytest.mark.skipif(shutil.which("cargo") is None, reason="cargo not installed")
+    def test_cargo_with_vendored_dependency(self, executor):
+        """Test snippets with allowed dependencies build offline with cargo."""
+        code = '---cargo\n[dependencies]\ngreet = "0.1"\n---\nfn main() { println!("{}", greet::hello()); }\n'
+        result = executor.execute("rust_exec", {"code": code, "timeout": 120})
+        assert result["success"] is True, result
+        assert result["build"] == "cargo"
+        assert result["output"] == "hi\n"
+
+
 class TestCodeConfig:
     """Tests for CodeConfig dataclass."""
 
@@ -234,6 +381,7 @@ class TestCodeConfig:
         assert config.max_memory_mb == 256
         assert "python" in config.allowed_languages
         assert "subprocess" in config.blocked_imports
+        assert config.rust_allowed_crates == []
 
     def test_custom_config(self):
         """Test custom configuration."""
@@ -89,7 +89,8 @@ class TestGracefulShutdown:
         3. scheduler.stop(timeout=10)
         4. asyncio.sleep(2)  -- in-flight task drain
         5. executor.shutdown(timeout=30)
-        6. async_db.shutdown()
+        6. LSPManager.shutdown_all()
+        7. async_db.shutdown()
         """
         call_order = []
 
@@ -129,6 +130,9 @@ class TestGracefulShutdown:
             real_set_shutting_down()
             call_order.append("set_shutting_down")
 
+        async def mock_lsp_shutdown():
+            call_order.append("lsp_shutdown")
+
         async def mock_sleep(seconds):
             call_order.append(f"sleep({int(seconds)})")
 
@@ -148,6 +152,8 @@ class TestGracefulShutdown:
             side_effect=mock_set_shutting_down,
         ), patch(
             "gathering.api.async_db.AsyncDatabaseService", mock_async_db_cls
+        ), patch(
+            "gathering.lsp.manager.LSPManager.shutdown_all", side_effect=mock_lsp_shutdown
         ):
             app = create_app(
                 enable_websocket=False,
@@ -168,6 +174,7 @@ class TestGracefulShutdown:
                 "scheduler_stop",
                 "sleep(2)",
                 "executor_shutdown",
+                "lsp_shutdown",
                 "async_db_shutdown",
             ]
 
@@ -8,6 +8,8 @@ Covers:
 - Plugin discovery
 """
 
+import json
+
 import pytest
 from unittest.mock import Mock, patch, AsyncMock, MagicMock
 from pathlib import Path
@@ -59,6 +61,35 @@ class TestBaseLSPServer:
         with pytest.raises(NotImplementedError):
             await server.get_definition("file.py", 1, 0)
 
+    @pytest.mark.asyncio
+    async def test_code_actions_default_empty(self):
+        """Test servers without code actions return an empty list."""
+        from gathering.lsp.manager import BaseLSPServer
+
+        server = BaseLSPServer("/path/to/workspace")
+
+        assert await server.get_code_actions("file.py", 1, 0) == []
+
+    @pytest.mark.asyncio
+    async def test_symbols_default_empty(self):
+        """Test servers without symbol support return empty lists."""
+        from gathering.lsp.manager import BaseLSPServer
+
+        server = BaseLSPServer("/path/to/workspace")
+
+        assert await server.get_document_symbols("file.py") == []
+        assert await server.workspace_symbols("anything") == []
+
+    @pytest.mark.asyncio
+    async def test_references_default_empty(self):
+        """Test servers without reference support find nothing and cannot rename."""
+        from gathering.lsp.manager import BaseLSPServer
+
+        server = BaseLSPServer("/path/to/workspace")
+
+        assert await server.get_references("file.py", 1, 0) == []
+        assert await server.rename("file.py", 1, 0, "other") is None
+
 
 class TestLSPPluginRegistry:
     """Test LSPPluginRegistry."""
@@ -292,6 +323,278 @@ class TestLSPManager:
         assert "1:shutdownlang" not in LSPManager._servers
 
 
+class TestLSPServerPool:
+    """Test pool limits, idle eviction, health checks and restarts."""
+
+    def setup_method(self):
+        """Register a fake server language and reset the pool."""
+        from gathering.lsp.manager import LSPManager, BaseLSPServer
+        from gathering.lsp.plugin_system import LSPPluginRegistry
+
+        LSPManager._servers.clear()
+        LSPManager._states.clear()
+        self.limits = (LSPManager.max_servers, LSPManager.idle_timeout, LSPManager.health_interval)
+
+        class PoolServer(BaseLSPServer):
+            alive = True
+            restart_error = None
+
+            async def initialize(self, workspace_path):
+                self.initialized = True
+                self.starts = getattr(self, "starts", 0) + 1
+                return {"capabilities": {}}
+
+            async def shutdown(self):
+                self.stopped = True
+                await super().shutdown()
+
+            async def health_check(self):
+                return self.alive
+
+            async def restart(self):
+                if self.restart_error:
+                    raise RuntimeError(self.restart_error)
+                self.alive = True
+                return await super().restart()
+
+        LSPPluginRegistry._plugins["poollang"] = PoolServer
+
+    def teardown_method(self):
+        """Restore pool limits."""
+        from gathering.lsp.manager import LSPManager
+        from gathering.lsp.plugin_system import LSPPluginRegistry
+
+        LSPManager.configure_pool(*self.limits)
+        LSPManager._servers.clear()
+        LSPManager._states.clear()
+        LSPPluginRegistry._plugins.pop("poollang", None)
+
+    @pytest.mark.asyncio
+    async def test_pool_evicts_least_recently_used(self):
+        """Test a full pool shuts down the least recently used server."""
+        import asyncio
+        from gathering.lsp.manager import LSPManager
+
+        LSPManager.configure_pool(max_servers=2)
+        first = LSPManager.get_server(1, "poollang", "/a")
+        second = LSPManager.get_server(2, "poollang", "/b")
+        LSPManager.get_server(1, "poollang")  # project 1 used again
+        LSPManager.get_server(3, "poollang", "/c")
+
+        assert sorted(LSPManager._servers) == ["1:poollang", "3:poollang"]
+        await asyncio.gather(*LSPManager._retiring)
+        assert second.stopped is True
+        assert not getattr(first, "stopped", False)
+
+    @pytest.mark.asyncio
+    async def test_evict_idle(self):
+        """Test servers idle past the timeout are shut down."""
+        from gathering.lsp.manager import LSPManager
+
+        LSPManager.configure_pool(idle_timeout=60)
+        idle = LSPManager.get_server(1, "poollang", "/a")
+        LSPManager.get_server(2, "poollang", "/b")
+        LSPManager._states["1:poollang"].last_used -= 120
+
+        assert await LSPManager.evict_idle() == ["1:poollang"]
+        assert idle.stopped is True
+        assert list(LSPManager._servers) == ["2:poollang"]
+
+    @pytest.mark.asyncio
+    async def test_unhealthy_server_restarts(self):
+        """Test a crashed server is restarted by the health check."""
+        from gathering.lsp.manager import LSPManager
+
+        await LSPManager.initialize_server(1, "poollang", "/a")
+        server = LSPManager.get_server(1, "poollang")
+        server.alive = False
+
+        assert await LSPManager.check_health() == {"1:poollang": True}
+        assert server.starts == 2
+        assert LSPManager.server_status("1:poollang")["restarts"] == 1
+
+    @pytest.mark.asyncio
+    async def test_failed_restarts_back_off(self):
+        """Test failed restarts wait with exponential backoff, then give up."""
+        from gathering.lsp.manager import LSPManager
+
+        await LSPManager.initialize_server(1, "poollang", "/a")
+        server = LSPManager.get_server(1, "poollang")
+        server.alive = False
+        server.restart_error = "binary missing"
+        state = LSPManager._states["1:poollang"]
+
+        assert await LSPManager.check_health() == {"1:poollang": False}
+        assert state.failures == 1
+        assert state.last_error == "binary missing"
+
+        # Within the backoff window: no new attempt
+        await LSPManager.check_health()
+        assert state.failures == 1
+
+        for _ in range(LSPManager.max_restart_attempts + 2):
+            state.next_restart = 0.0
+            await LSPManager.check_health()
+        assert state.failures == LSPManager.max_restart_attempts
+
+    @pytest.mark.asyncio
+    async def test_uninitialized_servers_not_probed(self):
+        """Test servers that were never initialized are not restarted."""
+        from gathering.lsp.manager import LSPManager
+
+        server = LSPManager.get_server(1, "poollang", "/a")
+        server.alive = False
+
+        assert await LSPManager.check_health() == {}
+
+    @pytest.mark.asyncio
+    async def test_shutdown_all_awaits_servers(self):
+        """Test shutdown_all shuts every server down and stops maintenance."""
+        from gathering.lsp.manager import LSPManager
+
+        servers = [LSPManager.get_server(i, "poollang", "/w") for i in range(3)]
+        LSPManager.start_maintenance()
+
+        await LSPManager.shutdown_all()
+
+        assert all(server.stopped for server in servers)
+        assert LSPManager._servers == {}
+        assert LSPManager._maintenance_task is None
+
+    def test_status(self):
+        """Test pool status lists a project's servers."""
+        from gathering.lsp.manager import LSPManager
+
+        LSPManager.get_server(1, "poollang", "/a")
+        LSPManager.get_server(2, "poollang", "/b")
+
+        status = LSPManager.status(1)
+        assert status["size"] == 2
+        assert [s["project_id"] for s in status["servers"]] == [1]
+        assert status["servers"][0]["healthy"] is True
+        assert LSPManager.server_status("9:poollang") is None
+
+
+class TestDiagnosticsPush:
+    """Test pushed diagnostics and debounced re-analysis."""
+
+    def setup_method(self):
+        """Register a fake server language and subscribe to diagnostics events."""
+        from gathering.events import event_bus, EventType
+        from gathering.lsp.manager import LSPManager, BaseLSPServer
+        from gathering.lsp.plugin_system import LSPPluginRegistry
+
+        LSPManager._servers.clear()
+        LSPManager._states.clear()
+        LSPManager._published.clear()
+        self.delay = LSPManager.analysis_delay
+
+        class PushServer(BaseLSPServer):
+            async def initialize(self, workspace_path):
+                self.initialized = True
+                self.analyzed = []
+                return {"capabilities": {}}
+
+            def handles(self, file_path):
+                return file_path.endswith(".rs")
+
+            async def get_diagnostics(self, file_path, content=None):
+                self.analyzed.append(file_path)
+                return [{"message": f"issue in {file_path}", "severity": 2}]
+
+        LSPPluginRegistry._plugins["pushlang"] = PushServer
+
+        self.events = []
+        self.subscription = event_bus.subscribe(EventType.LSP_DIAGNOSTICS, self.events.append)
+
+    def teardown_method(self):
+        """Reset the pool and drop the subscription."""
+        from gathering.events import event_bus
+        from gathering.lsp.manager import LSPManager
+        from gathering.lsp.plugin_system import LSPPluginRegistry
+
+        event_bus.unsubscribe(self.subscription)
+        LSPManager.configure_pool(analysis_delay=self.delay)
+        LSPManager._servers.clear()
+        LSPManager._states.clear()
+        LSPManager._published.clear()
+        LSPPluginRegistry._plugins.pop("pushlang", None)
+
+    @pytest.mark.asyncio
+    async def test_publish_skips_unchanged_results(self):
+        """Test identical diagnostics for a file are only published once."""
+        from gathering.lsp.manager import LSPManager
+
+        diagnostics = [{"message": "unused variable", "severity": 2}]
+        assert await LSPManager.publish_diagnostics(1, "rust", "src/lib.rs", diagnostics) is True
+        assert await LSPManager.publish_diagnostics(1, "rust", "src/lib.rs", list(diagnostics)) is False
+        assert await LSPManager.publish_diagnostics(1, "rust", "src/lib.rs", []) is True
+
+        assert [event.data["count"] for event in self.events] == [1, 0]
+        assert self.events[0].project_id == 1
+        assert self.events[0].data["file_path"] == "src/lib.rs"
+        assert LSPManager.published_diagnostics(1, "src/lib.rs") == [
+            {"file_path": "src/lib.rs", "language": "rust", "diagnostics": []}
+        ]
+
+    @pytest.mark.asyncio
+    async def test_file_changes_debounced(self):
+        """Test rapid changes to a file trigger a single re-analysis."""
+        import asyncio
+        from gathering.lsp.manager import LSPManager
+
+        LSPManager.configure_pool(analysis_delay=0.05)
+        await LSPManager.initialize_server(1, "pushlang", "/w")
+        server = LSPManager.get_server(1, "pushlang")
+
+        for _ in range(3):
+            await LSPManager.notify_file_changed(1, "src/lib.rs")
+        await LSPManager.notify_file_changed(1, "README.md")  # not handled
+        await asyncio.gather(*LSPManager._analysis_tasks.values())
+
+        assert server.analyzed == ["src/lib.rs"]
+        assert [event.data["file_path"] for event in self.events] == ["src/lib.rs"]
+        assert self.events[0].data["language"] == "pushlang"
+
+    @pytest.mark.asyncio
+    async def test_deleted_file_clears_diagnostics(self):
+        """Test deleting a file publishes an empty diagnostics list."""
+        from gathering.lsp.manager import LSPManager
+
+        await LSPManager.initialize_server(1, "pushlang", "/w")
+        server = LSPManager.get_server(1, "pushlang")
+        await server.analyze("src/lib.rs")
+
+        await LSPManager.notify_file_changed(1, "src/lib.rs", deleted=True)
+
+        assert [event.data["count"] for event in self.events] == [1, 0]
+        assert LSPManager.published_diagnostics(1) == []
+        assert LSPManager._analysis_tasks == {}
+
+    @pytest.mark.asyncio
+    async def test_stdio_client_forwards_published_diagnostics(self, tmp_path):
+        """Test publishDiagnostics notifications reach the client's callback."""
+        from gathering.lsp.stdio_client import StdioLSPClient
+
+        client = StdioLSPClient(str(tmp_path), command=["true"])
+        received = []
+
+        async def on_diagnostics(file_path, diagnostics):
+            received.append((file_path, diagnostics))
+
+        client.on_diagnostics = on_diagnostics
+        await client._handle_message({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": {
+                "uri": (tmp_path / "src" / "main.rs").as_uri(),
+                "diagnostics": [{"message": "expected `;`"}]
+            }
+        })
+
+        assert received == [("src/main.rs", [{"message": "expected `;`"}])]
+
+
 class TestPluginDiscovery:
     """Test plugin auto-discovery."""
 
@@ -357,9 +660,27 @@ class TestLSPRouterIntegration:
 
         assert response.status_code == 200
         data = response.json()
-        # Response includes: active, project_id, language
+        # Response includes: active, project_id, language and the pool state
         assert "active" in data
         assert data["project_id"] == 1
+        assert "max_servers" in data["pool"]
+
+    def test_list_plugins_endpoint(self, client):
+        """Test GET /lsp/plugins lists metadata and missing dependencies."""
+        from gathering.lsp.external_server import server_schema
+        from gathering.lsp.plugin_system import LSPPluginRegistry, PluginMetadata
+
+        LSPPluginRegistry.register_external(PluginMetadata(
+            name="Missing", version="1.0.0", author="Test", description="", language="missinglang",
+            config_schema=server_schema(command="missing-language-server")
+        ))
+
+        response = client.get("/lsp/plugins")
+
+        assert response.status_code == 200
+        plugin = response.json()["plugins"][0]
+        assert plugin["language"] == "missinglang"
+        assert plugin["missing_dependencies"] == ["missing-language-server"]
 
     def test_initialize_endpoint_unsupported_language(self, client):
         """Test POST /lsp/{project_id}/initialize with unsupported language."""
@@ -372,6 +693,85 @@ class TestLSPRouterIntegration:
         assert response.status_code == 400
         assert "Unsupported language" in response.json()["detail"]
 
+    def test_initialize_endpoint_rejects_command_option(self, client):
+        """Test /initialize options cannot choose the executable."""
+        from gathering.lsp.external_server import server_schema
+        from gathering.lsp.plugin_system import LSPPluginRegistry, PluginMetadata
+
+        LSPPluginRegistry.register_external(PluginMetadata(
+            name="Zig", version="1.0.0", author="Test", description="", language="ziglang",
+            config_schema=server_schema(command="zls")
+        ))
+
+        response = client.post("/lsp/1/initialize", json={
+            "language": "ziglang",
+            "workspace_path": "/tmp",
+            "options": {"command": "sh", "args": ["-c", "touch /tmp/pwned"]},
+        })
+
+        assert response.status_code == 400
+        assert "cannot be overridden" in response.json()["detail"]
+
+    def test_code_actions_endpoint(self, client):
+        """Test POST /lsp/{project_id}/code-actions forwards the range."""
+        from gathering.lsp.manager import LSPManager
+
+        server = Mock()
+        server.get_code_actions = AsyncMock(return_value=[{"title": "Fix", "kind": "quickfix"}])
+        LSPManager._servers["1:rust"] = server
+
+        response = client.post(
+            "/lsp/1/code-actions?language=rust",
+            json={"file_path": "src/main.rs", "line": 3, "character": 4, "end_line": 5}
+        )
+
+        assert response.status_code == 200
+        assert response.json()["count"] == 1
+        kwargs = server.get_code_actions.call_args.kwargs
+        assert (kwargs["line"], kwargs["end_line"], kwargs["end_character"]) == (3, 5, None)
+
+    def test_document_sync_endpoints(self, client):
+        """Test did-open/did-change keep a document that later requests use."""
+        from gathering.lsp.manager import LSPManager, BaseLSPServer
+
+        server = BaseLSPServer("/tmp")
+        server.get_completions = AsyncMock(return_value=[])
+        LSPManager._servers["1:rust"] = server
+
+        response = client.post("/lsp/1/did-open?language=rust", json={"file_path": "src/main.rs", "content": "fn main() {}\n"})
+        assert response.json() == {"file_path": "src/main.rs", "version": 1}
+
+        change = {"range": {"start": {"line": 0, "character": 3}, "end": {"line": 0, "character": 7}}, "text": "run"}
+        response = client.post(
+            "/lsp/1/did-change?language=rust",
+            json={"file_path": "src/main.rs", "version": 2, "content_changes": [change]}
+        )
+        assert response.json()["version"] == 2
+
+        client.post("/lsp/1/completions?language=rust", json={"file_path": "src/main.rs", "line": 1, "character": 3})
+        assert server.get_completions.call_args.kwargs["content"] == "fn run() {}\n"
+
+        # Stale version
+        response = client.post(
+            "/lsp/1/did-change?language=rust",
+            json={"file_path": "src/main.rs", "version": 2, "content_changes": [change]}
+        )
+        assert response.status_code == 400
+
+    def test_workspace_symbols_endpoint(self, client):
+        """Test GET /lsp/{project_id}/workspace-symbols passes the query."""
+        from gathering.lsp.manager import LSPManager
+
+        server = Mock()
+        server.workspace_symbols = AsyncMock(return_value=[{"name": "Circle", "kind": 23}])
+        LSPManager._servers["1:rust"] = server
+
+        response = client.get("/lsp/1/workspace-symbols?language=rust&query=circ")
+
+        assert response.status_code == 200
+        assert response.json()["count"] == 1
+        server.workspace_symbols.assert_awaited_once_with("circ")
+
 
 class TestMockLSPServer:
     """Test with a fully mocked LSP server."""
@@ -456,3 +856,1861 @@ class TestMockLSPServer:
         # Shutdown
         await server.shutdown()
         assert server.initialized is False
+
+
+# Minimal LSP server speaking JSON-RPC over stdio, standing in for rust-analyzer
+STUB_RUST_ANALYZER = r'''
+import json
+import sys
+
+
+def read_message():
+    headers = {}
+    while True:
+        line = sys.stdin.buffer.readline()
+        if not line:
+            return None
+        line = line.decode().strip()
+        if not line:
+            break
+        key, value = line.split(":", 1)
+        headers[key.strip().lower()] = value.strip()
+    return json.loads(sys.stdin.buffer.read(int(headers["content-length"])))
+
+
+def send(payload):
+    body = json.dumps(payload).encode()
+    sys.stdout.buffer.write(b"Content-Length: %d\r\n\r\n" % len(body) + body)
+    sys.stdout.buffer.flush()
+
+
+while True:
+    msg = read_message()
+    if msg is None:
+        break
+    method = msg.get("method")
+    if method == "initialize":
+        send({"jsonrpc": "2.0", "id": msg["id"], "result": {"capabilities": {"hoverProvider": True}}})
+    elif method == "initialized":
+        # Server-to-client request the client must answer
+        send({"jsonrpc": "2.0", "id": "cfg-1", "method": "workspace/configuration",
+              "params": {"items": [{"section": "rust-analyzer"}]}})
+    elif method in ("textDocument/didOpen", "textDocument/didChange"):
+        uri = msg["params"]["textDocument"]["uri"]
+        send({"jsonrpc": "2.0", "method": "textDocument/publishDiagnostics", "params": {
+            "uri": uri,
+            "diagnostics": [{
+                "range": {"start": {"line": 38, "character": 8}, "end": {"line": 38, "character": 13}},
+                "severity": 2,
+                "message": "unused variable: `value`",
+                "source": "rustc",
+            }],
+        }})
+    elif method == "textDocument/completion":
+        send({"jsonrpc": "2.0", "id": msg["id"], "result": {"isIncomplete": False, "items": [
+            {"label": "push", "kind": 2, "detail": "fn(&mut self, T)", "textEdit": {"newText": "push($0)"}},
+        ]}})
+    elif method == "textDocument/hover":
+        send({"jsonrpc": "2.0", "id": msg["id"], "result": {
+            "contents": {"kind": "markdown", "value": "```rust\nfn calculate_sum(numbers: &[i32]) -> i32\n```"}}})
+    elif method == "textDocument/definition":
+        send({"jsonrpc": "2.0", "id": msg["id"], "result": [{
+            "targetUri": msg["params"]["textDocument"]["uri"],
+            "targetRange": {"start": {"line": 47, "character": 0}, "end": {"line": 57, "character": 1}},
+            "targetSelectionRange": {"start": {"line": 47, "character": 3}, "end": {"line": 47, "character": 16}},
+        }]})
+    elif method == "shutdown":
+        send({"jsonrpc": "2.0", "id": msg["id"], "result": None})
+    elif method == "exit":
+        break
+'''
+
+TEST_RUST_FILE = Path(__file__).parent.parent / "workspace" / "test_lsp" / "test_rust.rs"
+
+
+class TestRustAnalyzerClient:
+    """Test the rust-analyzer client against a stub server."""
+
+    @pytest.fixture
+    def stub_command(self, tmp_path):
+        """Command launching the stub language server."""
+        import sys
+
+        script = tmp_path / "stub_rust_analyzer.py"
+        script.write_text(STUB_RUST_ANALYZER)
+        return [sys.executable, str(script)]
+
+    @pytest.mark.asyncio
+    async def test_requests_round_trip(self, stub_command):
+        """Test completion, hover, definition and diagnostics."""
+        from gathering.lsp.rust_analyzer_client import RustAnalyzerClient
+
+        workspace = TEST_RUST_FILE.parent
+        content = TEST_RUST_FILE.read_text()
+        client = RustAnalyzerClient(str(workspace), command=stub_command, request_timeout=5)
+
+        try:
+            await client.start()
+            assert client.initialized is True
+            assert client.server_capabilities["hoverProvider"] is True
+
+            completions = await client.completion("test_rust.rs", 21, 12, content)
+            assert completions[0]["label"] == "push"
+            assert completions[0]["insertText"] == "push($0)"
+
+            hover = await client.hover("test_rust.rs", 48, 5, content)
+            assert "calculate_sum" in hover["contents"]["value"]
+
+            definition = await client.definition("test_rust.rs", 48, 5, content)
+            assert definition["uri"] == TEST_RUST_FILE.absolute().as_uri()
+            assert definition["range"]["start"] == {"line": 47, "character": 3}
+
+            diagnostics = await client.diagnostics("test_rust.rs", content, timeout=5)
+            assert diagnostics[0]["message"] == "unused variable: `value`"
+        finally:
+            await client.shutdown()
+
+        assert client.process is None
+        assert client.initialized is False
+
+    @pytest.mark.asyncio
+    async def test_start_after_crash(self, stub_command):
+        """Test start() launches a new process once the previous one has exited."""
+        from gathering.lsp.rust_analyzer_client import RustAnalyzerClient
+
+        client = RustAnalyzerClient(str(TEST_RUST_FILE.parent), command=stub_command, request_timeout=5)
+
+        try:
+            await client.start()
+            crashed = client.process
+            crashed.kill()
+            await crashed.wait()
+
+            await client.start()
+            assert client.process is not crashed
+            assert client.is_alive()
+            assert client.initialized is True
+        finally:
+            await client.shutdown()
+
+    @pytest.mark.asyncio
+    async def test_server_uses_analyzer_for_cargo_workspace(self, stub_command, tmp_path):
+        """Test RustLSPServer delegates to rust-analyzer in a Cargo workspace."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "demo"\nversion = "0.1.0"\n')
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "main.rs").write_text(TEST_RUST_FILE.read_text())
+
+        server = RustLSPServer(str(tmp_path))
+        server.analyzer_command = stub_command
+
+        try:
+            caps = await server.initialize(str(tmp_path))
+            assert caps["backend"] == "rust-analyzer"
+            assert caps["capabilities"]["hoverProvider"] is True
+
+            hover = await server.get_hover("src/main.rs", 48, 5)
+            assert "calculate_sum" in hover["contents"]["value"]
+        finally:
+            await server.shutdown()
+
+    @pytest.mark.asyncio
+    async def test_crashed_analyzer_restarts(self, stub_command, tmp_path):
+        """Test the health check notices a crashed rust-analyzer and restart brings it back."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "demo"\nversion = "0.1.0"\n')
+        server = RustLSPServer(str(tmp_path))
+        server.analyzer_command = stub_command
+
+        try:
+            await server.initialize(str(tmp_path))
+            assert await server.health_check() is True
+
+            server.client.process.kill()
+            await server.client.process.wait()
+            assert await server.health_check() is False
+
+            await server.restart()
+            assert server.client.is_alive()
+            assert await server.health_check() is True
+
+            server.client.process.kill()
+            await server.client.process.wait()
+            await server.health_check()
+            server.analyzer_command = ["rust-analyzer-does-not-exist"]
+            with pytest.raises(RuntimeError):
+                await server.restart()
+            assert await server.health_check() is False
+        finally:
+            await server.shutdown()
+
+    @pytest.mark.asyncio
+    async def test_server_falls_back_without_binary(self, tmp_path):
+        """Test the keyword completer is used when rust-analyzer is missing."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "demo"\n')
+
+        server = RustLSPServer(str(tmp_path))
+        server.analyzer_command = ["rust-analyzer-does-not-exist"]
+
+        caps = await server.initialize(str(tmp_path))
+        assert caps["backend"] == "keyword"
+        assert server.client is None
+
+        completions = await server.get_completions("src/main.rs", 1, 3, content="le")
+        assert "let" in [c["label"] for c in completions]
+        assert await server.get_hover("src/main.rs", 1, 0, content="let") is None
+
+
+class TestExternalLSPServer:
+    """Test external stdio language servers configured from metadata alone."""
+
+    def setup_method(self):
+        """Remember the registry so registered test languages can be dropped."""
+        from gathering.lsp.plugin_system import LSPPluginRegistry
+        self.plugins = dict(LSPPluginRegistry._plugins)
+
+    def teardown_method(self):
+        """Restore the registry."""
+        from gathering.lsp.plugin_system import LSPPluginRegistry
+        LSPPluginRegistry._plugins.clear()
+        LSPPluginRegistry._plugins.update(self.plugins)
+
+    @pytest.fixture
+    def metadata(self, tmp_path):
+        """Metadata for the stub server, handling .rs files."""
+        import sys
+        from gathering.lsp.external_server import server_schema
+        from gathering.lsp.plugin_system import PluginMetadata
+
+        script = tmp_path / "stub_server.py"
+        script.write_text(STUB_RUST_ANALYZER)
+        return PluginMetadata(
+            name="Stub LSP",
+            version="1.0.0",
+            author="Test",
+            description="Stub language server",
+            language="stublang",
+            config_schema=server_schema(
+                command=sys.executable,
+                args=[str(script)],
+                file_globs=["*.rs"],
+                settings={"rust-analyzer": {"checkOnSave": False}},
+                request_timeout=5.0
+            )
+        )
+
+    @pytest.mark.asyncio
+    async def test_register_and_use(self, metadata):
+        """Test a registered external server answers through the standard interface."""
+        from gathering.lsp.external_server import ExternalLSPServer
+        from gathering.lsp.plugin_system import LSPPluginRegistry
+
+        server_class = LSPPluginRegistry.register_external(metadata)
+        assert issubclass(server_class, ExternalLSPServer)
+        assert LSPPluginRegistry.get_plugin("stublang") is server_class
+        assert metadata.dependencies == [metadata.config_schema["properties"]["command"]["default"]]
+
+        workspace = TEST_RUST_FILE.parent
+        server = server_class(str(workspace))
+        try:
+            caps = await server.initialize(str(workspace))
+            assert caps["capabilities"]["hoverProvider"] is True
+            assert await server.health_check() is True
+
+            hover = await server.get_hover("test_rust.rs", 48, 5)
+            assert "calculate_sum" in hover["contents"]["value"]
+
+            completions = await server.get_completions("test_rust.rs", 21, 12)
+            assert completions[0]["label"] == "push"
+
+            diagnostics = await server.get_diagnostics("test_rust.rs")
+            assert diagnostics[0]["message"] == "unused variable: `value`"
+
+            # Files outside the globs are not sent to the server
+            assert await server.get_completions("notes.txt", 1, 0, content="x") == []
+            assert await server.get_hover("notes.txt", 1, 0, content="x") is None
+        finally:
+            await server.shutdown()
+
+        assert server.client is None
+        assert await server.health_check() is False
+
+    def test_configuration_validated(self, metadata):
+        """Test options must match the configuration schema."""
+        from gathering.lsp.plugin_system import LSPPluginRegistry
+
+        server = LSPPluginRegistry.register_external(metadata)("/workspace")
+
+        server.configure({"file_globs": ["*.zig"], "request_timeout": 5})
+        assert server.handles("src/main.zig")
+        assert not server.handles("src/main.rs")
+
+        with pytest.raises(ValueError, match="Unknown setting"):
+            server.configure({"port": 9000})
+        with pytest.raises(ValueError, match="must be of type array"):
+            server.configure({"file_globs": "*.zig"})
+
+        # The executable only comes from the plugin metadata
+        for options in ({"command": "sh"}, {"args": ["-c", "id"]}, {"install_hint": "curl | sh"}):
+            with pytest.raises(ValueError, match="cannot be overridden"):
+                server.configure(options)
+        assert server.config["command"] == metadata.config_schema["properties"]["command"]["default"]
+
+    def test_register_requires_command(self):
+        """Test a definition without a command is rejected."""
+        from gathering.lsp.plugin_system import LSPPluginRegistry, PluginMetadata
+
+        with pytest.raises(ValueError, match="command"):
+            LSPPluginRegistry.register_external(PluginMetadata(
+                name="Broken", version="1.0.0", author="Test", description="", language="broken"
+            ))
+
+    @pytest.mark.asyncio
+    async def test_missing_binary(self):
+        """Test initializing without the server installed names the missing executable."""
+        from gathering.lsp.external_server import server_schema
+        from gathering.lsp.plugin_system import LSPPluginRegistry, PluginMetadata
+
+        metadata = PluginMetadata(
+            name="Missing", version="1.0.0", author="Test", description="", language="missinglang",
+            config_schema=server_schema(command="missing-language-server", install_hint="cargo install it")
+        )
+        server = LSPPluginRegistry.register_external(metadata)("/workspace")
+
+        assert metadata.missing_dependencies() == ["missing-language-server"]
+        assert LSPPluginRegistry.missing_dependencies("missinglang") == ["missing-language-server"]
+        with pytest.raises(RuntimeError, match="cargo install it"):
+            await server.initialize("/workspace")
+
+    def test_discover_json_definitions(self, tmp_path):
+        """Test JSON metadata files in the plugin directory register external servers."""
+        from gathering.lsp.plugin_system import LSPPluginRegistry
+
+        (tmp_path / "zig.json").write_text(json.dumps({
+            "name": "Zig Language Server",
+            "language": "ziglang",
+            "config_schema": {"properties": {
+                "command": {"type": "string", "default": "zls"},
+                "file_globs": {"type": "array", "default": ["*.zig"]},
+            }},
+        }))
+
+        LSPPluginRegistry.discover_plugins(str(tmp_path))
+
+        server = LSPPluginRegistry.get_plugin("ziglang")("/workspace")
+        assert server.command == ["zls"]
+        assert server.handles("build.zig")
+        info = {p["language"]: p for p in LSPPluginRegistry.plugin_info()}
+        assert info["ziglang"]["name"] == "Zig Language Server"
+        assert info["ziglang"]["dependencies"] == ["zls"]
+        assert info["ziglang"]["config_schema"]["properties"]["args"]["default"] == []
+
+    def test_builtin_servers_registered(self):
+        """Test gopls and clangd are defined as external servers."""
+        from gathering.lsp.external_server import ExternalLSPServer
+        from gathering.lsp.plugin_system import LSPPluginRegistry
+        from gathering.lsp.plugins.external_servers import EXTERNAL_SERVERS
+
+        assert {metadata.language for metadata in EXTERNAL_SERVERS} >= {"go", "cpp"}
+        for metadata in EXTERNAL_SERVERS:
+            LSPPluginRegistry.register_external(metadata)
+
+        go = LSPPluginRegistry.get_plugin("go")("/workspace")
+        assert isinstance(go, ExternalLSPServer)
+        assert go.command == ["gopls", "serve"]
+        assert go.handles("cmd/main.go") and not go.handles("main.rs")
+
+    def test_client_settings_and_language_ids(self):
+        """Test workspace/configuration answers and per-file languageIds."""
+        from gathering.lsp.stdio_client import StdioLSPClient
+
+        client = StdioLSPClient(
+            "/workspace",
+            command=["server"],
+            language_id="typescript",
+            language_ids={"*.tsx": "typescriptreact"},
+            settings={"typescript": {"format": {"semicolons": "remove"}}}
+        )
+
+        assert client._setting("typescript.format") == {"semicolons": "remove"}
+        assert client._setting("javascript") is None
+        assert client._language_id("src/App.tsx") == "typescriptreact"
+        assert client._language_id("src/index.ts") == "typescript"
+
+    def test_metadata_dependencies(self):
+        """Test dependencies are checked as executables or Python distributions."""
+        from gathering.lsp.plugin_system import PluginMetadata
+
+        metadata = PluginMetadata(
+            name="Deps", version="1.0.0", author="Test", description="", language="deps",
+            dependencies=["python3", "pip>=20", "surely-not-installed-lsp[all]"]
+        )
+        assert metadata.missing_dependencies() == ["surely-not-installed-lsp[all]"]
+
+
+CARGO_CLIPPY_MESSAGE = {
+    "reason": "compiler-message",
+    "manifest_path": "{root}/Cargo.toml",
+    "message": {
+        "message": "length comparison to zero",
+        "level": "warning",
+        "code": {"code": "clippy::len_zero", "explanation": None},
+        "spans": [{
+            "file_name": "src/lib.rs", "is_primary": True, "label": None,
+            "line_start": 3, "line_end": 3, "column_start": 8, "column_end": 20,
+            "suggested_replacement": None, "suggestion_applicability": None, "expansion": None,
+        }],
+        "children": [
+            {"level": "help", "message": "for further information visit https://rust-lang.github.io/rust-clippy/",
+             "spans": []},
+            {"level": "help", "message": "using `is_empty` is clearer and more explicit", "spans": [{
+                "file_name": "src/lib.rs", "is_primary": True, "label": None,
+                "line_start": 3, "line_end": 3, "column_start": 8, "column_end": 20,
+                "suggested_replacement": "v.is_empty()", "suggestion_applicability": "MachineApplicable",
+                "expansion": None,
+            }]},
+        ],
+    },
+}
+
+CARGO_ERROR_MESSAGE = {
+    "reason": "compiler-message",
+    "manifest_path": "{root}/Cargo.toml",
+    "message": {
+        "message": "mismatched types",
+        "level": "error",
+        "code": {"code": "E0308", "explanation": "..."},
+        "spans": [
+            {"file_name": "src/lib.rs", "is_primary": True, "label": "expected `i32`, found `&str`",
+             "line_start": 2, "line_end": 2, "column_start": 18, "column_end": 21,
+             "suggested_replacement": None, "suggestion_applicability": None, "expansion": None},
+            {"file_name": "src/lib.rs", "is_primary": False, "label": "expected due to this",
+             "line_start": 2, "line_end": 2, "column_start": 12, "column_end": 15,
+             "suggested_replacement": None, "suggestion_applicability": None, "expansion": None},
+        ],
+        "children": [],
+    },
+}
+
+
+class TestCargoDiagnostics:
+    """Test cargo check JSON output mapping."""
+
+    @pytest.fixture
+    def crate(self, tmp_path):
+        """A minimal crate on disk."""
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "demo"\nversion = "0.1.0"\n')
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "lib.rs").write_text("pub fn f() {}\n")
+
+        lines = [
+            json.dumps(message).replace("{root}", str(tmp_path))
+            for message in (CARGO_CLIPPY_MESSAGE, CARGO_ERROR_MESSAGE)
+        ]
+        lines.append(json.dumps({"reason": "build-finished", "success": False}))
+        return tmp_path, "\n".join(lines)
+
+    def test_parse_output(self, crate):
+        """Test compiler messages map to LSP diagnostics per file."""
+        from gathering.lsp.rust_cargo_check import CargoDiagnostics
+
+        root, output = crate
+        cargo = CargoDiagnostics(str(root), command="clippy")
+
+        by_file = cargo.parse_output(output, root)
+        diagnostics = by_file[str(root / "src" / "lib.rs")]
+        assert len(diagnostics) == 2
+
+        lint, error = diagnostics
+        assert lint["severity"] == 2
+        assert lint["source"] == "clippy"
+        assert lint["code"] == "clippy::len_zero"
+        assert lint["range"]["start"] == {"line": 2, "character": 7}
+        assert "for further information" not in lint["message"]
+        suggestion = lint["data"]["suggestions"][0]
+        assert suggestion["replacement"] == "v.is_empty()"
+        assert suggestion["applicability"] == "MachineApplicable"
+
+        assert error["severity"] == 1
+        assert error["source"] == "rustc"
+        assert error["message"] == "mismatched types\nexpected `i32`, found `&str`"
+        assert error["codeDescription"]["href"].endswith("E0308.html")
+        assert error["relatedInformation"][0]["message"] == "expected due to this"
+
+    @pytest.mark.asyncio
+    async def test_results_cached_by_content_hash(self, crate):
+        """Test cargo only runs again when the file changes."""
+        from gathering.lsp.rust_cargo_check import CargoDiagnostics
+
+        root, output = crate
+        cargo = CargoDiagnostics(str(root))
+        cargo.run = AsyncMock(side_effect=lambda manifest: cargo.parse_output(output, root))
+
+        first = await cargo.get_diagnostics("src/lib.rs")
+        second = await cargo.get_diagnostics("src/lib.rs")
+        assert first == second
+        assert cargo.run.await_count == 1
+
+        # Unsaved buffer: cargo cannot see it, so no result
+        assert await cargo.get_diagnostics("src/lib.rs", content="pub fn g() {}\n") is None
+
+    @pytest.mark.asyncio
+    async def test_results_pushed_after_run(self, crate):
+        """Test each run reports every file, clearing fixed ones."""
+        from gathering.lsp.rust_cargo_check import CargoDiagnostics
+
+        root, output = crate
+        (root / "src" / "main.rs").write_text("fn main() {}\n")
+        pushed = []
+
+        async def on_results(by_file):
+            pushed.append({Path(path).name: len(diagnostics) for path, diagnostics in by_file.items()})
+
+        cargo = CargoDiagnostics(str(root), on_results=on_results)
+        cargo.run = AsyncMock(side_effect=lambda manifest: cargo.parse_output(output, root))
+        await cargo.get_diagnostics("src/lib.rs")
+
+        # The error is fixed and cargo runs again for another file
+        cargo.run = AsyncMock(return_value={})
+        await cargo.get_diagnostics("src/main.rs")
+
+        assert pushed == [{"lib.rs": 2}, {"lib.rs": 0, "main.rs": 0}]
+
+    @pytest.mark.asyncio
+    async def test_no_manifest(self, tmp_path):
+        """Test files outside a Cargo package are not analyzed."""
+        from gathering.lsp.rust_cargo_check import CargoDiagnostics
+
+        (tmp_path / "main.rs").write_text("fn main() {}\n")
+        cargo = CargoDiagnostics(str(tmp_path))
+
+        assert await cargo.get_diagnostics("main.rs") is None
+
+    @pytest.fixture
+    def member(self, tmp_path):
+        """A Cargo workspace with one member crate."""
+        (tmp_path / "Cargo.toml").write_text('[workspace]\nmembers = ["crates/foo"]\n')
+        crate = tmp_path / "crates" / "foo"
+        (crate / "src").mkdir(parents=True)
+        (crate / "Cargo.toml").write_text('[package]\nname = "foo"\nversion = "0.1.0"\n')
+        (crate / "src" / "lib.rs").write_text("pub fn f() {}\n")
+
+        message = json.loads(json.dumps(CARGO_ERROR_MESSAGE).replace("{root}", str(crate)))
+        for span in message["message"]["spans"]:
+            span["file_name"] = "crates/foo/src/lib.rs"
+        return tmp_path, crate, json.dumps(message)
+
+    @pytest.mark.asyncio
+    async def test_member_opened_as_workspace(self, member):
+        """Test spans relative to the Cargo workspace root resolve above the editor root."""
+        from gathering.lsp.rust_cargo_check import CargoDiagnostics
+
+        root, crate, output = member
+        cargo = CargoDiagnostics(str(crate))
+        cargo.locate_workspace = AsyncMock(return_value=root)
+        cargo.run = AsyncMock(side_effect=lambda manifest: cargo.parse_output(output, crate, root))
+
+        diagnostics = await cargo.get_diagnostics("src/lib.rs")
+        assert [d["message"] for d in diagnostics] == ["mismatched types\nexpected `i32`, found `&str`"]
+
+    @pytest.mark.asyncio
+    @pytest.mark.skipif(not __import__("shutil").which("cargo"), reason="cargo not installed")
+    async def test_locate_workspace(self, member):
+        """Test the Cargo workspace root is found from a member manifest."""
+        from gathering.lsp.rust_cargo_check import CargoDiagnostics
+
+        root, crate, _ = member
+        cargo = CargoDiagnostics(str(crate))
+
+        assert await cargo.locate_workspace(crate / "Cargo.toml") == root
+
+    def test_unsupported_command(self):
+        """Test only check and clippy are accepted."""
+        from gathering.lsp.rust_cargo_check import CargoDiagnostics
+
+        with pytest.raises(ValueError, match="Unsupported cargo command"):
+            CargoDiagnostics("/workspace", command="build")
+
+
+class TestRustLexer:
+    """Test the fallback Rust tokenizer."""
+
+    def test_comments_and_strings(self):
+        """Test nested block comments, raw strings and doc comments."""
+        from gathering.lsp.rust_lexer import tokenize, TokenKind
+
+        tokens = tokenize(
+            '/// doc .unwrap()\n'
+            '/* a /* nested */ b */\n'
+            'let s = r#"say "hi""#;\n'
+            'let b = b"\\x00";\n'
+        )
+        kinds = [(t.kind, t.text) for t in tokens]
+
+        assert kinds[0] == (TokenKind.DOC_COMMENT, "/// doc .unwrap()")
+        assert kinds[1] == (TokenKind.BLOCK_COMMENT, "/* a /* nested */ b */")
+        assert (TokenKind.RAW_STRING, 'r#"say "hi""#') in kinds
+        assert (TokenKind.STRING, 'b"\\x00"') in kinds
+
+    def test_char_vs_lifetime(self):
+        """Test char literals are told apart from lifetimes."""
+        from gathering.lsp.rust_lexer import tokenize, TokenKind
+
+        tokens = tokenize("fn f<'a>(x: &'a str) -> char { let c = '\\''; 'x' }")
+        by_text = {t.text: t.kind for t in tokens}
+
+        assert by_text["'a"] == TokenKind.LIFETIME
+        assert by_text["'\\''"] == TokenKind.CHAR
+        assert by_text["'x'"] == TokenKind.CHAR
+
+    def test_attributes_and_raw_identifiers(self):
+        """Test attributes span to their matching bracket."""
+        from gathering.lsp.rust_lexer import tokenize, TokenKind
+
+        tokens = tokenize('#[cfg(feature = "a]")]\nlet r#match = 1..=2;')
+
+        assert tokens[0].kind == TokenKind.ATTRIBUTE
+        assert tokens[0].text == '#[cfg(feature = "a]")]'
+        assert (TokenKind.IDENT, "r#match") in [(t.kind, t.text) for t in tokens]
+        assert "..=" in [t.text for t in tokens]
+
+    def test_context_at(self):
+        """Test cursor context detection."""
+        from gathering.lsp.rust_lexer import RustLexer, TokenKind
+
+        source = 'let s = "abc"; // note'
+        lexer = RustLexer(source)
+
+        assert lexer.context_at(source.index("b")) == TokenKind.STRING
+        assert lexer.context_at(len(source)) == TokenKind.LINE_COMMENT
+        assert lexer.context_at(source.index(";")) is None
+
+
+class TestRustFallback:
+    """Test the keyword completer and heuristic diagnostics."""
+
+    @pytest.mark.asyncio
+    async def test_no_diagnostics_in_strings_or_comments(self, tmp_path):
+        """Test unwrap/println inside literals and comments are ignored."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        content = (
+            'fn main() {\n'
+            '    let s = "x.unwrap() and println!";\n'
+            '    let r = r#"println!("raw")"#;\n'
+            '    /* y.unwrap() /* nested */ */\n'
+            '    /// z.unwrap()\n'
+            '    let v = Some(1).unwrap();\n'
+            '}\n'
+        )
+        server = RustLSPServer(str(tmp_path))
+
+        diagnostics = await server.get_diagnostics("main.rs", content)
+
+        assert len(diagnostics) == 1
+        assert diagnostics[0]["range"]["start"] == {"line": 5, "character": 19}
+        assert "unwrap()" in diagnostics[0]["message"]
+
+    @pytest.mark.asyncio
+    async def test_multiline_let_not_flagged(self, tmp_path):
+        """Test statements spanning lines only need one terminating semicolon."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        content = (
+            'fn main() {\n'
+            '    let add = |x: i32| {\n'
+            '        x + 1\n'
+            '    };\n'
+            '    if let Some(x) = None::<i32> {}\n'
+            '    let missing = 5\n'
+            '}\n'
+        )
+        server = RustLSPServer(str(tmp_path))
+
+        diagnostics = await server.get_diagnostics("main.rs", content)
+
+        assert [d["range"]["start"]["line"] for d in diagnostics] == [5]
+        assert "semicolon" in diagnostics[0]["message"]
+
+    @pytest.mark.asyncio
+    async def test_no_completions_inside_string(self, tmp_path):
+        """Test keywords are not suggested inside a string literal."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        server = RustLSPServer(str(tmp_path))
+
+        assert await server.get_completions("main.rs", 1, 12, content='let s = "le') == []
+        completions = await server.get_completions("main.rs", 1, 10, content="let s = le")
+        assert "let" in [c["label"] for c in completions]
+
+    @pytest.mark.asyncio
+    async def test_derive_completions_in_attribute(self, tmp_path):
+        """Test derivable traits are offered inside #[derive(...)]."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        server = RustLSPServer(str(tmp_path))
+
+        completions = await server.get_completions("main.rs", 1, 18, content="#[derive(Debug, Cl")
+        assert [c["label"] for c in completions] == ["Clone"]
+
+
+class TestRustSymbolIndex:
+    """Test the Rust item index."""
+
+    def test_parse_test_file(self):
+        """Test items in the sample Rust file are indexed."""
+        from gathering.lsp.rust_symbols import parse_symbols
+
+        symbols = {
+            s.path: s for s in parse_symbols(TEST_RUST_FILE.read_text(), "src/main.rs")
+            if s.kind != "impl"
+        }
+
+        assert symbols["crate::calculate_sum"].kind == "function"
+        assert symbols["crate::calculate_sum"].signature == "fn calculate_sum(numbers: &[i32]) -> i32"
+        assert symbols["crate::Person"].kind == "struct"
+        assert symbols["crate::Person::name"].kind == "field"
+        assert symbols["crate::Person::greet"].kind == "method"
+        assert symbols["crate::Person::new"].line == 66
+
+    def test_items_docs_and_modules(self):
+        """Test traits, enums, impls, consts, inline modules and doc comments."""
+        from gathering.lsp.rust_symbols import parse_symbols
+
+        source = (
+            "/// A shape.\n"
+            "pub trait Shape { fn area(&self) -> f64; }\n"
+            "pub enum Color { Red, Green(u8) }\n"
+            "impl<T: Clone> std::fmt::Display for Wrapper<T> { }\n"
+            "pub mod inner {\n"
+            "    pub const MAX: usize = 10;\n"
+            "}\n"
+            "mod other;\n"
+        )
+        symbols = {s.path: s for s in parse_symbols(source, "src/lib.rs")}
+
+        assert symbols["crate::Shape"].doc == "A shape."
+        assert symbols["crate::Shape::area"].kind == "method"
+        assert symbols["crate::Color::Green"].kind == "variant"
+        assert symbols["crate::Wrapper"].kind == "impl"
+        assert symbols["crate::Wrapper"].trait == "Display"
+        assert symbols["crate::inner::MAX"].kind == "const"
+        assert symbols["crate::other"].kind == "module"
+
+    def test_module_path(self, tmp_path):
+        """Test file paths map to module paths."""
+        from gathering.lsp.rust_symbols import RustSymbolIndex
+
+        index = RustSymbolIndex(str(tmp_path))
+
+        assert index.module_path("src/lib.rs") == "crate"
+        assert index.module_path("src/shapes.rs") == "crate::shapes"
+        assert index.module_path("src/shapes/mod.rs") == "crate::shapes"
+        assert index.module_path("src/shapes/circle.rs") == "crate::shapes::circle"
+        assert index.module_path("src/bin/tool.rs") == "crate"
+
+    def test_incremental_update(self, tmp_path):
+        """Test files can be re-indexed and removed individually."""
+        from gathering.lsp.rust_symbols import RustSymbolIndex
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "lib.rs").write_text("pub fn one() {}\n")
+        (tmp_path / "target").mkdir()
+        (tmp_path / "target" / "gen.rs").write_text("pub fn generated() {}\n")
+
+        index = RustSymbolIndex(str(tmp_path))
+        index.build()
+        assert [s.name for s in index.items()] == ["one"]
+
+        (tmp_path / "src" / "lib.rs").write_text("pub fn two() {}\n")
+        index.update_file("src/lib.rs")
+        assert [s.name for s in index.items()] == ["two"]
+
+        index.remove_file("src/lib.rs")
+        assert index.items() == []
+
+
+class TestRustSymbolCompletions:
+    """Test symbol-aware completions in the fallback server."""
+
+    @pytest.fixture
+    def server(self, tmp_path):
+        """Server over a crate containing the sample file and a module."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "main.rs").write_text(TEST_RUST_FILE.read_text())
+        (tmp_path / "src" / "shapes.rs").write_text(
+            "pub struct Circle { pub radius: f64 }\n"
+            "impl Circle {\n"
+            "    pub fn new(radius: f64) -> Self { Circle { radius } }\n"
+            "    pub fn area(&self) -> f64 { self.radius }\n"
+            "}\n"
+        )
+        return RustLSPServer(str(tmp_path))
+
+    async def _complete(self, server, inserted: str, after_line: int) -> list:
+        """Insert a line into main.rs and complete at its end."""
+        lines = TEST_RUST_FILE.read_text().split("\n")
+        lines.insert(after_line, inserted)
+        completions = await server.get_completions(
+            "src/main.rs", after_line + 1, len(inserted), content="\n".join(lines)
+        )
+        return [c["label"] for c in completions]
+
+    @pytest.mark.asyncio
+    async def test_user_functions(self, server):
+        """Test functions defined in the crate are suggested."""
+        assert "calculate_sum" in await self._complete(server, "    calc", 45)
+
+    @pytest.mark.asyncio
+    async def test_self_members(self, server):
+        """Test fields and methods are offered after self."""
+        labels = await self._complete(server, "        self.", 71)
+        assert labels == ["name", "age", "greet"]
+
+    @pytest.mark.asyncio
+    async def test_type_and_module_paths(self, server):
+        """Test associated items after Type:: and items after module::."""
+        assert "new" in await self._complete(server, "    Person::", 45)
+        assert await self._complete(server, "    shapes::", 45) == ["Circle"]
+
+    @pytest.mark.asyncio
+    async def test_local_variable_members(self, server):
+        """Test members of a local whose type is known from its initializer."""
+        labels = await self._complete(server, "    let c = shapes::Circle::new(1.0); c.", 45)
+        assert labels == ["radius", "area"]
+
+    @pytest.mark.asyncio
+    async def test_index_follows_workspace_changes(self, server, tmp_path):
+        """Test file change notifications update the index."""
+        from gathering.lsp.manager import LSPManager
+
+        LSPManager._servers.clear()
+        LSPManager._servers["7:rust"] = server
+        assert await self._complete(server, "    shapes::", 45) == ["Circle"]
+
+        (tmp_path / "src" / "shapes.rs").write_text("pub struct Square;\n")
+        await LSPManager.notify_file_changed(7, "src/shapes.rs")
+        assert await self._complete(server, "    shapes::", 45) == ["Square"]
+
+        LSPManager._servers.clear()
+
+
+class TestRustNavigation:
+    """Test hover and go-to-definition from the symbol index."""
+
+    @pytest.fixture
+    def server(self, tmp_path):
+        """Server over a crate with a `mod foo;` module tree and imports."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        (tmp_path / "src" / "geo").mkdir(parents=True)
+        (tmp_path / "src" / "main.rs").write_text(
+            "mod geo;\n"
+            "mod util {\n"
+            "    /// Doubles a number.\n"
+            "    pub fn double(x: i32) -> i32 { x * 2 }\n"
+            "}\n"
+            "\n"
+            "use geo::shapes::{Circle, Square as Sq};\n"
+            "\n"
+            "fn main() {\n"
+            "    let c = Circle::new(1.0);\n"
+            "    let a = c.area();\n"
+            "    let s = Sq { side: 2.0 };\n"
+            "    println!(\"{}\", util::double(3));\n"
+            "}\n"
+        )
+        (tmp_path / "src" / "geo" / "mod.rs").write_text("//! Geometry primitives.\npub mod shapes;\n")
+        (tmp_path / "src" / "geo" / "shapes.rs").write_text(
+            "/// A circle.\n"
+            "pub struct Circle { pub radius: f64 }\n"
+            "pub struct Square { pub side: f64 }\n"
+            "impl Circle {\n"
+            "    /// Creates a circle.\n"
+            "    pub fn new(radius: f64) -> Self { Self { radius } }\n"
+            "    pub fn area(&self) -> f64 { self.radius }\n"
+            "}\n"
+        )
+        server = RustLSPServer(str(tmp_path))
+        server.analyzer_command = ["rust-analyzer-does-not-exist"]
+        return server
+
+    @staticmethod
+    def _location(definition: dict) -> tuple:
+        """(file name, 0-based line) of a definition result."""
+        return definition["uri"].rsplit("/", 1)[-1], definition["range"]["start"]["line"]
+
+    @pytest.mark.asyncio
+    async def test_hover_signature_and_doc(self, server):
+        """Test hover shows the item's module, signature and doc comment."""
+        hover = await server.get_hover("src/main.rs", 10, 21)
+        value = hover["contents"]["value"]
+        assert "crate::geo::shapes::Circle" in value
+        assert "pub fn new(radius: f64) -> Self" in value
+        assert "Creates a circle." in value
+
+    @pytest.mark.asyncio
+    async def test_hover_unknown_symbol(self, server):
+        """Test hover on keywords and unknown names returns None."""
+        assert await server.get_hover("src/main.rs", 9, 1) is None
+        assert await server.get_hover("src/main.rs", 13, 6) is None
+
+    @pytest.mark.asyncio
+    async def test_definition_local_item(self, server):
+        """Test inline module paths resolve within the same file."""
+        definition = await server.get_definition("src/main.rs", 13, 25)
+        assert self._location(definition) == ("main.rs", 3)
+        assert definition["range"]["start"]["character"] == 11
+
+    @pytest.mark.asyncio
+    async def test_definition_through_use(self, server):
+        """Test imported and aliased names resolve to their declarations."""
+        assert self._location(await server.get_definition("src/main.rs", 10, 13)) == ("shapes.rs", 1)
+        assert self._location(await server.get_definition("src/main.rs", 12, 13)) == ("shapes.rs", 2)
+
+    @pytest.mark.asyncio
+    async def test_definition_of_method_on_local(self, server):
+        """Test `.method` resolves using the local's inferred type."""
+        assert self._location(await server.get_definition("src/main.rs", 11, 15)) == ("shapes.rs", 6)
+
+    @pytest.mark.asyncio
+    async def test_definition_of_module_declaration(self, server):
+        """Test `mod foo;` and module path segments map to module files."""
+        assert self._location(await server.get_definition("src/main.rs", 1, 5)) == ("mod.rs", 0)
+        assert self._location(await server.get_definition("src/main.rs", 7, 10)) == ("shapes.rs", 0)
+
+        hover = await server.get_hover("src/main.rs", 1, 5)
+        assert "Geometry primitives." in hover["contents"]["value"]
+
+
+REGISTRY_ENTRIES = {
+    "se/rd/serde": [
+        {"name": "serde", "vers": "1.0.100", "deps": [], "features": {"default": ["std"], "std": []}, "yanked": False},
+        {"name": "serde", "vers": "1.0.200", "deps": [{"name": "serde_derive", "optional": True}],
+         "features": {"default": ["std"], "std": [], "derive": ["serde_derive"]}, "yanked": False},
+        {"name": "serde", "vers": "1.0.201", "deps": [], "features": {}, "yanked": True},
+    ],
+    "to/ki/tokio": [
+        {"name": "tokio", "vers": "1.38.0", "deps": [{"name": "bytes", "optional": True}],
+         "features": {"full": ["rt"], "rt": []}, "features2": {"io-util": ["dep:bytes"]}, "yanked": False},
+    ],
+    "3/l/log": [
+        {"name": "log", "vers": "0.4.21", "deps": [], "features": {"std": []}, "yanked": False},
+    ],
+}
+
+
+class TestCargoManifest:
+    """Test Cargo.toml completions and validation."""
+
+    @pytest.fixture
+    def registry(self, tmp_path):
+        """Local crates.io index mirror."""
+        from gathering.lsp.rust_manifest import CrateRegistry
+
+        for path, entries in REGISTRY_ENTRIES.items():
+            file_path = tmp_path / "index" / path
+            file_path.parent.mkdir(parents=True, exist_ok=True)
+            file_path.write_text("\n".join(json.dumps(entry) for entry in entries) + "\n")
+        return CrateRegistry(str(tmp_path / "index"))
+
+    def _messages(self, content: str, registry=None, used_crates=None) -> list:
+        from gathering.lsp.rust_manifest import CargoManifest
+
+        return [d["message"] for d in CargoManifest(content).diagnostics(registry, used_crates)]
+
+    def test_version_requirements(self):
+        """Test caret, tilde, comparison and wildcard requirements."""
+        from gathering.lsp.rust_manifest import VersionReq
+
+        assert VersionReq("1.2").matches("1.9.0")
+        assert not VersionReq("1.2").matches("2.0.0")
+        assert VersionReq("0.4").matches("0.4.21")
+        assert not VersionReq("0.4").matches("0.5.0")
+        assert not VersionReq("~1.2.3").matches("1.3.0")
+        assert VersionReq(">=1, <1.5").matches("1.4.9")
+        assert VersionReq("1.*").matches("1.99.0")
+        assert not VersionReq("1").matches("1.0.0-alpha")
+        with pytest.raises(ValueError):
+            VersionReq("one.two")
+
+    def test_registry_lookups(self, registry):
+        """Test versions, yanked releases and implicit features from the index."""
+        assert registry.crate_path("serde") == "se/rd/serde"
+        assert registry.crate_path("log") == "3/l/log"
+        assert registry.latest("serde")["vers"] == "1.0.200"
+        assert registry.latest("serde", "=1.0.100")["vers"] == "1.0.100"
+        assert registry.features("serde") == ["default", "derive", "serde_derive", "std"]
+        assert registry.features("tokio") == ["full", "io-util", "rt"]
+        assert registry.search("to") == ["tokio"]
+        assert not registry.exists("left-pad")
+
+    def test_dependency_diagnostics(self, registry):
+        """Test dependency sources, versions and features are validated."""
+        messages = self._messages(
+            '[dependencies]\n'
+            'serde = { version = "1.0", features = ["derive", "nope"] }\n'
+            'tokio = "2"\n'
+            'log = { git = "https://example.com/log", branch = "a", tag = "b" }\n'
+            'left-pad = "1"\n'
+            'broken = { optional = true }\n',
+            registry
+        )
+        assert messages == [
+            "crate `serde` has no feature `nope`",
+            "no version of `tokio` matches `2` (latest is 1.38.0)",
+            "dependency `log` specifies more than one of branch, tag and rev",
+            "crate `left-pad` not found in the registry index",
+            "dependency `broken` specifies no source (version, path, git or workspace)",
+        ]
+
+    def test_feature_references(self):
+        """Test feature entries must name features or (optional) dependencies."""
+        from gathering.lsp.rust_manifest import CargoManifest
+
+        manifest = CargoManifest(
+            '[dependencies]\n'
+            'log = "0.4"\n'
+            'serde = { version = "1", optional = true }\n'
+            '\n'
+            '[features]\n'
+            'default = ["extra", "dep:serde", "serde?/derive"]\n'
+            'extra = [\n'
+            '    "dep:log",\n'
+            '    "nothing",\n'
+            ']\n'
+        )
+        diagnostics = manifest.diagnostics()
+        assert [(d["range"]["start"]["line"], d["message"]) for d in diagnostics] == [
+            (7, "feature `extra`: `dep:log` refers to `log`, which is not optional"),
+            (8, "feature `extra`: unknown feature or optional dependency `nothing`"),
+        ]
+
+    def test_syntax_error(self):
+        """Test invalid TOML is reported at its position."""
+        from gathering.lsp.rust_manifest import CargoManifest
+
+        diagnostics = CargoManifest('[package]\nname = "x"\nversion =\n').diagnostics()
+        assert len(diagnostics) == 1
+        assert diagnostics[0]["range"]["start"]["line"] == 2
+        assert diagnostics[0]["message"].startswith("invalid TOML")
+
+    def test_completions(self, registry):
+        """Test section, key, crate, version and feature completions."""
+        from gathering.lsp.rust_manifest import CargoManifest
+
+        def labels(content: str) -> list:
+            lines = content.split("\n")
+            items = CargoManifest(content).completions(len(lines) - 1, len(lines[-1]), registry)
+            return [item["label"] for item in items]
+
+        assert "profile.release" in labels("[prof")
+        assert labels("[[b") == ["bin", "bench"]
+        assert labels("[package]\nna") == ["name"]
+        assert labels("[package]\nname = \"x\"\nna") == []
+        assert labels("[dependencies]\ntok") == ["tokio"]
+        assert labels('[dependencies]\nserde = "') == ["1.0.200", "1.0.100"]
+        assert labels('[dependencies]\nserde = { version = "1", features = ["std", "') == \
+            ["default", "derive", "serde_derive"]
+        assert "default-features" in labels('[dependencies]\nserde = { version = "1", ')
+        assert labels('[dependencies.tokio]\nfeatures = ["') == ["full", "io-util", "rt"]
+        assert labels('[features]\nx = []\ny = ["') == ["x"]
+
+    @pytest.mark.asyncio
+    async def test_missing_crates_from_use(self, tmp_path, registry):
+        """Test the server flags crates imported in sources but not declared."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        crate = tmp_path / "crate"
+        (crate / "src").mkdir(parents=True)
+        (crate / "Cargo.toml").write_text(
+            '[package]\nname = "demo-app"\nversion = "0.1.0"\n\n[dependencies]\nserde = "1"\n'
+        )
+        (crate / "src" / "main.rs").write_text(
+            "mod util;\n"
+            "use serde::Serialize;\n"
+            "use rand::Rng;\n"
+            "use util::helper;\n"
+            "use demo_app::thing;\n"
+            "use std::fmt;\n"
+            "fn main() {}\n"
+        )
+        (crate / "src" / "util.rs").write_text("pub fn helper() {}\n")
+
+        server = RustLSPServer(str(crate))
+        server.analyzer_command = ["rust-analyzer-does-not-exist"]
+        server.configure({"registry_index": str(tmp_path / "index")})
+
+        diagnostics = await server.get_diagnostics("Cargo.toml")
+        assert [d["message"] for d in diagnostics] == [
+            "crate `rand` is used in src/main.rs:3 but is not listed in [dependencies]"
+        ]
+        assert diagnostics[0]["range"]["start"]["line"] == 4
+
+        completions = await server.get_completions("Cargo.toml", 7, 3, content="[dependencies]\nserde = \"1\"\n\n\n\n\nlo")
+        assert [c["label"] for c in completions] == ["log"]
+
+
+class TestRustCodeActions:
+    """Test Rust quick fixes and refactorings."""
+
+    SOURCE = (
+        "//! Demo.\n"
+        "mod shapes;\n"
+        "\n"
+        "use std::fmt;\n"
+        "\n"
+        "struct Point { x: i32 }\n"
+        "\n"
+        "#[derive(PartialEq)]\n"
+        "enum Color { Red }\n"
+        "\n"
+        "fn load(path: &str) -> Result<String, std::io::Error> {\n"
+        "    let text = std::fs::read_to_string(path).unwrap();\n"
+        "    let map: HashMap<String, Circle> = HashMap::new();\n"
+        "    Ok(text)\n"
+        "}\n"
+        "\n"
+        "fn main() {\n"
+        "    let v = load(\"x\").unwrap();\n"
+        "}\n"
+    )
+
+    @pytest.fixture
+    def server(self, tmp_path):
+        """Server over a crate with a sibling module."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "main.rs").write_text(self.SOURCE)
+        (tmp_path / "src" / "shapes.rs").write_text("pub struct Circle;\n")
+        server = RustLSPServer(str(tmp_path))
+        server.analyzer_command = ["rust-analyzer-does-not-exist"]
+        server.diagnostics_mode = "heuristic"
+        return server
+
+    async def _actions(self, server, line: int, diagnostics=None) -> dict:
+        """Actions for a whole (1-indexed) line, keyed by title."""
+        actions = await server.get_code_actions("src/main.rs", line, 0, line, 80, diagnostics=diagnostics)
+        return {
+            action["title"]: list(action["edit"]["changes"].values())[0]
+            for action in actions
+        }
+
+    @pytest.mark.asyncio
+    async def test_unwrap_in_fallible_function(self, server):
+        """Test `.unwrap()` can become `.expect(...)` or `?` when the function returns Result."""
+        actions = await self._actions(server, 12)
+
+        expect = actions["Replace `.unwrap()` with `.expect(\"...\")`"][0]
+        assert expect["newText"] == 'expect("read_to_string failed")'
+        assert expect["range"]["start"] == {"line": 11, "character": 45}
+
+        question = actions["Replace `.unwrap()` with `?`"][0]
+        assert question["newText"] == "?"
+        assert question["range"]["start"] == {"line": 11, "character": 44}
+        assert question["range"]["end"] == {"line": 11, "character": 53}
+
+    @pytest.mark.asyncio
+    async def test_unwrap_in_infallible_function(self, server):
+        """Test `?` is not offered in functions that do not return Result/Option."""
+        actions = await self._actions(server, 18)
+        assert list(actions) == ["Replace `.unwrap()` with `.expect(\"...\")`"]
+
+    @pytest.mark.asyncio
+    async def test_add_missing_use(self, server):
+        """Test unresolved names can be imported from std or the crate."""
+        actions = await self._actions(server, 13)
+
+        assert actions["Import `std::collections::HashMap`"] == [{
+            "range": {"start": {"line": 4, "character": 0}, "end": {"line": 4, "character": 0}},
+            "newText": "use std::collections::HashMap;\n"
+        }]
+        assert actions["Import `crate::shapes::Circle`"][0]["newText"] == "use crate::shapes::Circle;\n"
+        assert not any("String" in title for title in actions)
+
+    @pytest.mark.asyncio
+    async def test_derive_without_attribute(self, server):
+        """Test a derive attribute is inserted above the struct."""
+        actions = await self._actions(server, 6)
+        edit = actions["Derive `Debug`, `Clone` for `Point`"][0]
+        assert edit["newText"] == "#[derive(Debug, Clone)]\n"
+        assert edit["range"]["start"] == {"line": 5, "character": 0}
+
+    @pytest.mark.asyncio
+    async def test_derive_extends_existing_attribute(self, server):
+        """Test traits are appended to an existing derive list."""
+        actions = await self._actions(server, 9)
+        edit = actions["Derive `Debug` for `Color`"][0]
+        assert edit["newText"] == ", Debug"
+        assert edit["range"]["start"] == {"line": 7, "character": 18}
+
+    @pytest.mark.asyncio
+    async def test_machine_applicable_suggestions(self, server, tmp_path):
+        """Test clippy suggestions become workspace edits; others are ignored."""
+        main = str(tmp_path / "src" / "main.rs")
+
+        def diagnostic(line: int, applicability: str) -> dict:
+            span = {"start": {"line": line, "character": 4}, "end": {"line": line, "character": 12}}
+            return {
+                "range": span, "severity": 2, "message": "lint", "source": "clippy",
+                "data": {"suggestions": [{
+                    "message": "try", "file": main, "range": span,
+                    "replacement": "fixed", "applicability": applicability
+                }]}
+            }
+
+        diagnostics = [diagnostic(12, "MachineApplicable"), diagnostic(13, "MaybeIncorrect"),
+                       diagnostic(17, "MachineApplicable")]
+        actions = await server.get_code_actions("src/main.rs", 13, 0, diagnostics=diagnostics)
+
+        fix = next(a for a in actions if a["title"] == "Try: `fixed` (clippy)")
+        assert fix["isPreferred"] is True
+        assert fix["diagnostics"] == [diagnostics[0]]
+        assert fix["edit"]["changes"] == {Path(main).as_uri(): [{"range": diagnostics[0]["range"], "newText": "fixed"}]}
+
+        fix_all = next(a for a in actions if a["kind"] == "source.fixAll")
+        assert len(fix_all["edit"]["changes"][Path(main).as_uri()]) == 2
+
+
+class TestTextEdits:
+    """Test minimal text edit computation and application."""
+
+    def test_diff_edits_roundtrip(self):
+        """Test edits only touch changed lines and reproduce the target."""
+        from gathering.lsp.text_edits import apply_edits, diff_edits
+
+        original = "a\nb\nc\nd\n"
+        updated = "a\nB\nc\nd\ne\n"
+        edits = diff_edits(original, updated)
+
+        assert edits == [
+            {"range": {"start": {"line": 1, "character": 0}, "end": {"line": 2, "character": 0}}, "newText": "B\n"},
+            {"range": {"start": {"line": 4, "character": 0}, "end": {"line": 4, "character": 0}}, "newText": "e\n"},
+        ]
+        assert apply_edits(original, edits) == updated
+
+    def test_missing_final_newline(self):
+        """Test a last line without a newline is addressed by its end position."""
+        from gathering.lsp.text_edits import apply_edits, diff_edits
+
+        edits = diff_edits("x\ny", "x\ny\n")
+        assert edits[0]["range"]["start"] == {"line": 1, "character": 0}
+        assert edits[0]["range"]["end"] == {"line": 1, "character": 1}
+        assert apply_edits("x\ny", edits) == "x\ny\n"
+
+    def test_edits_in_range(self):
+        """Test range selection keeps only edits touching the lines."""
+        from gathering.lsp.text_edits import diff_edits, edits_in_range
+
+        edits = diff_edits("a\nb\nc\nd\n", "A\nb\nc\nD\n")
+        assert [e["newText"] for e in edits_in_range(edits, 0, 1)] == ["A\n"]
+        assert [e["newText"] for e in edits_in_range(edits, 3, 3)] == ["D\n"]
+
+    def test_workspace_edit_changes(self):
+        """Test `changes` and `documentChanges` are merged per document."""
+        from gathering.lsp.text_edits import workspace_edit_changes
+
+        edit = {"range": {"start": {"line": 0, "character": 0}, "end": {"line": 0, "character": 1}}, "newText": "x"}
+        merged = workspace_edit_changes({
+            "changes": {"file:///a.rs": [edit]},
+            "documentChanges": [{"textDocument": {"uri": "file:///a.rs", "version": 1}, "edits": [edit]}],
+        })
+        assert merged == {"file:///a.rs": [edit, edit]}
+
+        with pytest.raises(ValueError):
+            workspace_edit_changes({"documentChanges": [{"kind": "create", "uri": "file:///b.rs"}]})
+
+    def test_apply_content_changes(self):
+        """Test contentChanges apply in order, each against the previous result."""
+        from gathering.lsp.text_edits import apply_content_changes
+
+        def change(line, start, end, text):
+            return {"range": {"start": {"line": line, "character": start}, "end": {"line": line, "character": end}}, "text": text}
+
+        content = "fn main() {\n    let x = 1;\n}\n"
+        updated = apply_content_changes(content, [
+            change(1, 8, 9, "value"),
+            change(1, 17, 17, "0"),  # position valid only after the first change
+        ])
+        assert updated == "fn main() {\n    let value = 10;\n}\n"
+        assert apply_content_changes(updated, [{"text": "fn f() {}\n"}]) == "fn f() {}\n"
+
+    def test_content_changes_roundtrip(self):
+        """Test computed incremental changes reproduce the new text."""
+        from gathering.lsp.text_edits import apply_content_changes, content_changes
+
+        original = "a\nb\nc\nd\n"
+        updated = "A\nb\nc\nD\ne\n"
+        changes = content_changes(original, updated)
+
+        assert [c["range"]["start"]["line"] for c in changes] == [3, 0]
+        assert apply_content_changes(original, changes) == updated
+
+    def test_utf16_positions(self):
+        """Test characters count UTF-16 code units, astral characters counting twice."""
+        from gathering.lsp.text_edits import apply_content_changes, diff_edits, offset_at
+
+        content = 'let s = "🦀";\nlet t = 1;'
+        assert offset_at(content, 0, 10) == content.index('"', 9)
+        assert offset_at(content, 0, 12) == content.index(";")
+
+        # Replace the emoji (character 9 to 11) with text
+        change = {"range": {"start": {"line": 0, "character": 9}, "end": {"line": 0, "character": 11}}, "text": "crab"}
+        assert apply_content_changes(content, [change]) == 'let s = "crab";\nlet t = 1;'
+
+        edits = diff_edits('x = "🦀"', 'x = "🦀"\n')
+        assert edits[0]["range"]["end"] == {"line": 0, "character": 8}
+
+
+class TestDocumentSync:
+    """Test open document tracking and incremental sync."""
+
+    @staticmethod
+    def change(line, start, end, text):
+        return {"range": {"start": {"line": line, "character": start}, "end": {"line": line, "character": end}}, "text": text}
+
+    def test_document_store_versions(self):
+        """Test versions increase with each change and stale ones are rejected."""
+        from gathering.lsp.documents import DocumentStore
+
+        store = DocumentStore()
+        store.open("./src/lib.rs", "pub fn f() {}\n", version=3)
+
+        document = store.change("src/lib.rs", [self.change(0, 7, 8, "g")])
+        assert (document.text, document.version) == ("pub fn g() {}\n", 4)
+        assert store.change("src/lib.rs", [self.change(0, 7, 8, "h")], version=9).version == 9
+
+        with pytest.raises(ValueError, match="Stale version"):
+            store.change("src/lib.rs", [self.change(0, 7, 8, "i")], version=9)
+        assert store.text("src/lib.rs") == "pub fn h() {}\n"
+
+        assert store.close("src/lib.rs") is True
+        with pytest.raises(KeyError):
+            store.change("src/lib.rs", [{"text": ""}])
+
+    @pytest.mark.asyncio
+    async def test_server_opens_from_disk(self, tmp_path):
+        """Test did_open without content reads the workspace file."""
+        from gathering.lsp.manager import BaseLSPServer
+
+        (tmp_path / "main.py").write_text("print(1)\n")
+        server = BaseLSPServer(str(tmp_path))
+
+        document = await server.did_open("main.py")
+        assert (document.text, document.version) == ("print(1)\n", 1)
+        await server.did_change("main.py", [self.change(0, 6, 7, "2")])
+        assert server.documents.text("main.py") == "print(2)\n"
+
+        with pytest.raises(FileNotFoundError):
+            await server.did_open("missing.py")
+
+    @pytest.mark.asyncio
+    async def test_client_sends_incremental_changes(self, tmp_path):
+        """Test servers with incremental sync only receive the changed lines."""
+        from gathering.lsp.stdio_client import StdioLSPClient
+
+        client = StdioLSPClient(str(tmp_path), command=["true"])
+        client.server_capabilities = {"textDocumentSync": {"openClose": True, "change": 2}}
+        client._send_notification = AsyncMock()
+
+        await client.did_change("a.rs", "fn a() {}\nfn b() {}\n")
+        assert await client.did_change("a.rs", "fn a() {}\nfn b() {}\n") is False  # unchanged
+        await client.did_change("a.rs", "fn a() {}\nfn c() {}\n")
+
+        methods = [call.args[0] for call in client._send_notification.await_args_list]
+        assert methods == ["textDocument/didOpen", "textDocument/didChange"]
+        params = client._send_notification.await_args.args[1]
+        assert params["textDocument"]["version"] == 2
+        assert params["contentChanges"] == [{
+            "range": {"start": {"line": 1, "character": 0}, "end": {"line": 2, "character": 0}},
+            "text": "fn c() {}\n"
+        }]
+
+    @pytest.mark.asyncio
+    async def test_client_forwards_editor_changes(self, tmp_path):
+        """Test editor changes pass through, or fall back to full text sync."""
+        from gathering.lsp.stdio_client import StdioLSPClient
+
+        client = StdioLSPClient(str(tmp_path), command=["true"])
+        client.server_capabilities = {"textDocumentSync": 2}
+        client._send_notification = AsyncMock()
+        await client.did_open("a.rs", "let x = 1;\n")
+
+        edit = [self.change(0, 4, 5, "y")]
+        await client.apply_changes("a.rs", edit, "let x = 1;\n", "let y = 1;\n")
+        assert client._send_notification.await_args.args[1]["contentChanges"] == edit
+
+        client.server_capabilities = {"textDocumentSync": 1}  # full sync only
+        await client.apply_changes("a.rs", [self.change(0, 8, 9, "2")], "let y = 1;\n", "let y = 2;\n")
+        assert client._send_notification.await_args.args[1]["contentChanges"] == [{"text": "let y = 2;\n"}]
+
+        # Out of sync with the editor: resync instead of applying the ranges
+        await client.apply_changes("a.rs", [self.change(0, 8, 9, "3")], "stale\n", "let y = 3;\n")
+        assert client._send_notification.await_args.args[1]["contentChanges"] == [{"text": "let y = 3;\n"}]
+        assert client._send_notification.await_args.args[1]["textDocument"]["version"] == 4
+
+    @pytest.mark.asyncio
+    async def test_external_server_forwards_changes(self, tmp_path):
+        """Test an external server keeps its client in sync with editor edits."""
+        from gathering.lsp.external_server import ExternalLSPServer
+
+        server = ExternalLSPServer(str(tmp_path))
+        server.config.update({"command": "stub", "file_globs": ["*.go"]})
+        server.initialized = True
+        server.client = Mock()
+        server.client.did_change = AsyncMock()
+        server.client.apply_changes = AsyncMock()
+        server.client.did_close = AsyncMock()
+
+        await server.did_open("main.go", "package main\n")
+        server.client.did_change.assert_awaited_once_with("main.go", "package main\n")
+
+        edit = [self.change(0, 8, 12, "app")]
+        await server.did_change("main.go", edit)
+        server.client.apply_changes.assert_awaited_once_with("main.go", edit, "package main\n", "package app\n")
+
+        await server.did_close("main.go")
+        assert "main.go" not in server.documents
+        server.client.did_close.assert_awaited_once_with("main.go")
+
+
+@pytest.mark.skipif(not __import__("shutil").which("rustfmt"), reason="rustfmt not installed")
+class TestRustFormatting:
+    """Test rustfmt-backed document and range formatting."""
+
+    UNFORMATTED = "fn one(){let a=1;}\n\nfn two() {\n    let b = 2;\n}\n\nfn three(){let c=3;}\n"
+
+    @pytest.fixture
+    def server(self, tmp_path):
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "demo"\nversion = "0.1.0"\nedition = "2021"\n')
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "main.rs").write_text(self.UNFORMATTED)
+        server = RustLSPServer(str(tmp_path))
+        server.analyzer_command = ["rust-analyzer-does-not-exist"]
+        return server
+
+    @pytest.mark.asyncio
+    async def test_document_formatting(self, server):
+        """Test only the unformatted functions are edited."""
+        from gathering.lsp.text_edits import apply_edits
+
+        edits = await server.get_formatting("src/main.rs")
+
+        assert [e["range"]["start"]["line"] for e in edits] == [0, 6]
+        assert apply_edits(self.UNFORMATTED, edits) == (
+            "fn one() {\n    let a = 1;\n}\n\nfn two() {\n    let b = 2;\n}\n\nfn three() {\n    let c = 3;\n}\n"
+        )
+
+    @pytest.mark.asyncio
+    async def test_range_formatting(self, server):
+        """Test range formatting leaves lines outside the range alone."""
+        edits = await server.get_range_formatting("src/main.rs", 7, 7)
+        assert len(edits) == 1
+        assert edits[0]["range"]["start"]["line"] == 6
+
+    @pytest.mark.asyncio
+    async def test_rustfmt_toml_is_honored(self, server, tmp_path):
+        """Test the project's rustfmt.toml is passed to rustfmt."""
+        from gathering.lsp.text_edits import apply_edits
+
+        (tmp_path / "rustfmt.toml").write_text("tab_spaces = 2\n")
+        edits = await server.get_formatting("src/main.rs")
+        assert "  let a = 1;\n" in apply_edits(self.UNFORMATTED, edits)
+
+    @pytest.mark.asyncio
+    async def test_syntax_error_returns_no_edits(self, server):
+        """Test unparsable source yields no edits instead of an error."""
+        assert await server.get_formatting("src/main.rs", content="fn main( {") == []
+
+
+class TestDocumentSymbols:
+    """Test document outlines and workspace symbol search."""
+
+    RUST_SOURCE = (
+        "pub struct Point { pub x: i32, y: i32 }\n"
+        "\n"
+        "impl std::fmt::Display for Point {\n"
+        "    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {\n"
+        "        write!(f, \"{}\", self.x)\n"
+        "    }\n"
+        "}\n"
+        "\n"
+        "pub mod shapes {\n"
+        "    pub enum Shape { Circle(f64), Square(f64) }\n"
+        "    pub fn area(shape: &Shape) -> f64 { 0.0 }\n"
+        "}\n"
+    )
+
+    @staticmethod
+    def tree(symbols):
+        """Reduce an outline to (name, kind, children) tuples."""
+        return [(s["name"], s["kind"], TestDocumentSymbols.tree(s["children"])) for s in symbols]
+
+    def test_nest_and_search(self):
+        """Test containment nesting and fuzzy ranking."""
+        from gathering.lsp.symbols import document_symbol, flatten, lsp_range, nest, search
+
+        outer = document_symbol("Outer", "class", lsp_range(0, 0, 9, 0), lsp_range(0, 6, 0, 11))
+        inner = document_symbol("get_config_file", "method", lsp_range(2, 4, 4, 0), lsp_range(2, 8, 2, 23))
+        after = document_symbol("config", "function", lsp_range(10, 0, 12, 0), lsp_range(10, 4, 10, 10))
+        tree = nest([inner, after, outer])
+
+        assert [s["name"] for s in tree] == ["Outer", "config"]
+        assert tree[0]["children"] == [inner]
+
+        flat = flatten(tree, "file:///a.py")
+        assert flat[1]["containerName"] == "Outer"
+        assert [s["name"] for s in search(flat, "config")] == ["config", "get_config_file"]
+        assert [s["name"] for s in search(flat, "gcf")] == ["get_config_file"]
+        assert len(search(flat, "")) == 3
+
+    def test_rust_outline(self):
+        """Test Rust items nest under impls and modules, fields stay siblings."""
+        from gathering.lsp.rust_symbols import outline
+
+        assert self.tree(outline(self.RUST_SOURCE)) == [
+            ("Point", 23, [("x", 8, []), ("y", 8, [])]),
+            ("impl Display for Point", 19, [("fmt", 6, [])]),
+            ("shapes", 2, [
+                ("Shape", 10, [("Circle", 22, []), ("Square", 22, [])]),
+                ("area", 12, []),
+            ]),
+        ]
+
+    @pytest.mark.asyncio
+    async def test_rust_server_symbols(self, tmp_path):
+        """Test the Rust server outlines files and searches the crate index."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "lib.rs").write_text(self.RUST_SOURCE)
+        server = RustLSPServer(str(tmp_path))
+        server.analyzer_command = ["rust-analyzer-does-not-exist"]
+
+        outline = await server.get_document_symbols("src/lib.rs")
+        assert outline[0]["selectionRange"]["start"] == {"line": 0, "character": 11}
+
+        results = await server.workspace_symbols("area")
+        assert results[0]["name"] == "area"
+        assert results[0]["containerName"] == "crate::shapes"
+        assert results[0]["location"]["uri"].endswith("src/lib.rs")
+        assert all(not s["name"].startswith("impl ") for s in await server.workspace_symbols(""))
+
+    @pytest.mark.asyncio
+    async def test_python_symbols(self, tmp_path):
+        """Test the Python outline and workspace search work without pylsp."""
+        from gathering.lsp.plugins.python_pylsp import PythonPylspServer
+
+        source = (
+            "MAX_SIZE = 10\n"
+            "\n"
+            "class Cache(dict):\n"
+            "    ttl: int = 60\n"
+            "\n"
+            "    def __init__(self, size):\n"
+            "        self.size = size\n"
+            "\n"
+            "    @property\n"
+            "    async def load(self, key: str) -> bytes:\n"
+            "        value = 1\n"
+            "        return value\n"
+        )
+        (tmp_path / "cache.py").write_text(source)
+        (tmp_path / "node_modules").mkdir()
+        (tmp_path / "node_modules" / "vendored.py").write_text("def load(): pass\n")
+        server = PythonPylspServer(str(tmp_path))
+
+        outline = await server.get_document_symbols("cache.py")
+        assert self.tree(outline) == [
+            ("MAX_SIZE", 14, []),
+            ("Cache", 5, [("ttl", 8, []), ("__init__", 9, []), ("load", 6, [])]),
+        ]
+        load = outline[1]["children"][2]
+        assert load["detail"] == "(self, key: str) -> bytes"
+        assert load["range"]["start"]["line"] == 8  # includes the decorator
+        assert load["selectionRange"]["start"] == {"line": 9, "character": 14}
+
+        assert await server.get_document_symbols("cache.py", content="def broken(") == []
+
+        results = await server.workspace_symbols("load")
+        assert [(s["name"], s["containerName"]) for s in results] == [("load", "Cache")]
+
+    @pytest.mark.asyncio
+    async def test_javascript_symbols(self):
+        """Test the JavaScript outline ignores braces in strings, comments and regexes."""
+        from gathering.lsp.plugins.javascript_lsp import JavaScriptLSPServer
+
+        source = (
+            "// function commented() {\n"
+            "const PATTERN = /[{]/g;\n"
+            "let label = \"{ not a block\";\n"
+            "export class Store extends Base {\n"
+            "  #items = [];\n"
+            "  constructor(opts) { super(opts); }\n"
+            "  get size() { return this.#items.length; }\n"
+            "  async load(url) {\n"
+            "    const body = `${url}{`;\n"
+            "  }\n"
+            "}\n"
+            "function render(store) {\n"
+            "  function row() {}\n"
+            "}\n"
+            "const double = (x) => x * 2;\n"
+        )
+        server = JavaScriptLSPServer("/tmp")
+
+        outline = await server.get_document_symbols("store.js", content=source)
+        assert self.tree(outline) == [
+            ("PATTERN", 14, []),
+            ("label", 13, []),
+            ("Store", 5, [("#items", 7, []), ("constructor", 9, []), ("size", 6, []), ("load", 6, [])]),
+            ("render", 12, [("row", 12, [])]),
+            ("double", 12, []),
+        ]
+        assert outline[2]["detail"] == "extends Base"
+        assert outline[2]["range"]["end"]["line"] == 10
+
+    @pytest.mark.asyncio
+    async def test_typescript_symbols(self, tmp_path):
+        """Test TypeScript declarations and workspace search over .ts files."""
+        from gathering.lsp.plugins.javascript_lsp import TypeScriptLSPServer
+
+        (tmp_path / "types.ts").write_text(
+            "export interface Props { name: string }\n"
+            "export enum Color { Red, Green = 'g' }\n"
+            "export type Id = string | number;\n"
+        )
+        server = TypeScriptLSPServer(str(tmp_path))
+
+        outline = await server.get_document_symbols("types.ts")
+        assert self.tree(outline) == [
+            ("Props", 11, []),
+            ("Color", 10, [("Red", 22, []), ("Green", 22, [])]),
+            ("Id", 26, []),
+        ]
+        assert [s["name"] for s in await server.workspace_symbols("col")] == ["Color"]
+
+
+class TestAnalysisSymbolsTool:
+    """Test the analysis_symbols code-navigation tool."""
+
+    def test_outline_and_search(self, tmp_path):
+        """Test outlining a Rust file and searching a directory."""
+        from gathering.skills.analysis.scanner import CodeAnalysisSkill
+
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "lib.rs").write_text(TestDocumentSymbols.RUST_SOURCE)
+        skill = CodeAnalysisSkill()
+
+        result = skill.execute("analysis_symbols", {"path": str(tmp_path / "src" / "lib.rs")})
+        assert result.success
+        point = result.data["symbols"][0]
+        assert (point["name"], point["kind"], point["line"]) == ("Point", "struct", 1)
+        assert [c["name"] for c in point["children"]] == ["x", "y"]
+
+        result = skill.execute("analysis_symbols", {"path": str(tmp_path), "query": "shape"})
+        assert result.data["symbols"][0] == {
+            "name": "Shape", "kind": "enum", "file": "src/lib.rs", "line": 10, "container": "shapes"
+        }
+
+        result = skill.execute("analysis_symbols", {"path": str(tmp_path / "README")})
+        assert result.error == "not_found"
+
+
+class TestRustReferences:
+    """Test find-references and rename across a Rust workspace."""
+
+    @pytest.fixture
+    def server(self, tmp_path):
+        """Server over a crate with re-exports, aliases, a trait and a binary."""
+        from gathering.lsp.plugins.rust_lsp import RustLSPServer
+
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "shapes-demo"\nversion = "0.1.0"\n')
+        (tmp_path / "src" / "geo").mkdir(parents=True)
+        (tmp_path / "src" / "lib.rs").write_text(
+            "pub mod geo;\n"
+            "pub use geo::shapes::Circle;\n"
+            "\n"
+            "pub trait Area {\n"
+            "    fn area(&self) -> f64;\n"
+            "}\n"
+            "\n"
+            "pub fn total(c: &Circle) -> f64 {\n"
+            "    c.area() + c.radius\n"
+            "}\n"
+        )
+        (tmp_path / "src" / "geo" / "mod.rs").write_text(
+            "pub mod shapes;\n"
+            "\n"
+            "pub fn area() -> f64 {\n"
+            "    0.0\n"
+            "}\n"
+        )
+        (tmp_path / "src" / "geo" / "shapes.rs").write_text(
+            "use crate::Area;\n"
+            "\n"
+            "pub struct Circle {\n"
+            "    pub radius: f64,\n"
+            "}\n"
+            "\n"
+            "impl Circle {\n"
+            "    pub fn new(radius: f64) -> Self {\n"
+            "        Circle { radius }\n"
+            "    }\n"
+            "\n"
+            "    pub fn grow(&mut self) {\n"
+            "        self.radius *= 2.0;\n"
+            "    }\n"
+            "}\n"
+            "\n"
+            "impl Area for Circle {\n"
+            "    fn area(&self) -> f64 {\n"
+            "        3.14 * self.radius * self.radius\n"
+            "    }\n"
+            "}\n"
+        )
+        (tmp_path / "src" / "main.rs").write_text(
+            "use shapes_demo::Circle as Round;\n"
+            "use shapes_demo::geo::area;\n"
+            "\n"
+            "fn main() {\n"
+            "    let c = Round::new(1.0);\n"
+            "    let Round { radius } = c;\n"
+            "    println!(\"{} {}\", radius, area());\n"
+            "}\n"
+        )
+        server = RustLSPServer(str(tmp_path))
+        server.analyzer_command = ["rust-analyzer-does-not-exist"]
+        return server
+
+    @staticmethod
+    def _locations(references: list) -> list:
+        """(file name, 0-based line, character) of each reference."""
+        return [
+            (r["uri"].rsplit("/", 1)[-1], r["range"]["start"]["line"], r["range"]["start"]["character"])
+            for r in references
+        ]
+
+    @pytest.mark.asyncio
+    async def test_references_through_reexport_and_alias(self, server):
+        """Test references follow `pub use` re-exports and `use ... as` aliases."""
+        references = self._locations(await server.get_references("src/geo/shapes.rs", 3, 11))
+
+        assert ("shapes.rs", 2, 11) in references  # declaration
+        assert ("lib.rs", 1, 21) in references  # pub use
+        assert ("lib.rs", 7, 17) in references
+        assert ("main.rs", 0, 17) in references  # use shapes_demo::Circle as Round
+        assert ("main.rs", 4, 12) in references  # Round::new
+
+    @pytest.mark.asyncio
+    async def test_references_exclude_declaration(self, server):
+        """Test the declaration can be left out."""
+        references = self._locations(
+            await server.get_references("src/geo/shapes.rs", 3, 11, include_declaration=False)
+        )
+        assert ("shapes.rs", 2, 11) not in references
+        assert references
+
+    @pytest.mark.asyncio
+    async def test_field_references(self, server):
+        """Test field references include shorthands and `self.` accesses, not same-named locals."""
+        references = self._locations(await server.get_references("src/geo/shapes.rs", 4, 9))
+
+        assert ("shapes.rs", 8, 17) in references  # Circle { radius }
+        assert ("shapes.rs", 12, 13) in references  # self.radius
+        assert ("lib.rs", 8, 17) in references  # c.radius
+        assert ("main.rs", 5, 16) in references  # let Round { radius }
+        assert ("shapes.rs", 7, 15) not in references  # the `radius` parameter
+
+    @pytest.mark.asyncio
+    async def test_same_name_in_other_module(self, server):
+        """Test a free function is not confused with a trait method of the same name."""
+        trait_method = self._locations(await server.get_references("src/lib.rs", 5, 7))
+        function = self._locations(await server.get_references("src/geo/mod.rs", 3, 7))
+
+        assert ("shapes.rs", 17, 7) in trait_method  # the impl
+        assert ("main.rs", 6, 30) not in trait_method
+        assert ("main.rs", 6, 30) in function
+        assert ("shapes.rs", 17, 7) not in function
+
+    @pytest.mark.asyncio
+    async def test_rename_field_expands_shorthand(self, server, tmp_path):
+        """Test renaming a field rewrites shorthands as `new: old`."""
+        from gathering.lsp.text_edits import apply_edits
+
+        edit = await server.rename("src/geo/shapes.rs", 4, 9, "r")
+        changes = {uri.rsplit("/", 1)[-1]: edits for uri, edits in edit["changes"].items()}
+
+        shapes = apply_edits((tmp_path / "src" / "geo" / "shapes.rs").read_text(), changes["shapes.rs"])
+        main = apply_edits((tmp_path / "src" / "main.rs").read_text(), changes["main.rs"])
+        assert "pub r: f64" in shapes
+        assert "Circle { r: radius }" in shapes
+        assert "self.r * self.r" in shapes
+        assert "let Round { r: radius } = c;" in main
+
+    @pytest.mark.asyncio
+    async def test_rename_trait_method_with_impls(self, server, tmp_path):
+        """Test renaming a trait method renames its implementations and calls."""
+        edit = await server.rename("src/lib.rs", 5, 7, "surface")
+        files = sorted(uri.rsplit("/", 1)[-1] for uri in edit["changes"])
+
+        assert files == ["lib.rs", "shapes.rs"]
+        assert len(edit["changes"][(tmp_path / "src" / "lib.rs").as_uri()]) == 2
+
+    @pytest.mark.asyncio
+    async def test_rename_keeps_alias(self, server, tmp_path):
+        """Test renaming through an alias only touches the imported path."""
+        from gathering.lsp.text_edits import apply_edits
+
+        edit = await server.rename("src/geo/shapes.rs", 3, 11, "Ring")
+        main = (tmp_path / "src" / "main.rs").as_uri()
+
+        updated = apply_edits((tmp_path / "src" / "main.rs").read_text(), edit["changes"][main])
+        assert "use shapes_demo::Ring as Round;" in updated
+        assert "Round::new(1.0)" in updated
+
+    @pytest.mark.asyncio
+    async def test_rename_rejects_invalid_names(self, server):
+        """Test keywords, malformed identifiers and conflicts are rejected."""
+        for new_name in ("fn", "1x", "grow"):
+            with pytest.raises(ValueError):
+                await server.rename("src/geo/shapes.rs", 8, 12, new_name)
+
+    @pytest.mark.asyncio
+    async def test_rename_unknown_symbol(self, server):
+        """Test renaming something that is not an indexed symbol returns None."""
+        assert await server.rename("src/main.rs", 4, 9, "d") is None
+
+    @pytest.mark.asyncio
+    async def test_apply_rename(self, server, tmp_path):
+        """Test a rename edit previews as diffs and applies across files."""
+        from gathering.workspace.file_manager import FileManager
+
+        edit = await server.rename("src/geo/shapes.rs", 3, 11, "Ring")
+        preview = FileManager.preview_workspace_edit(str(tmp_path), edit)
+
+        assert preview["count"] == 3
+        assert all(f["changed"] for f in preview["files"])
+        assert "+pub use geo::shapes::Ring;" in "".join(f["diff"] for f in preview["files"])
+        assert "Circle" in (tmp_path / "src" / "lib.rs").read_text()
+
+        result = FileManager.apply_workspace_edit(str(tmp_path), edit, create_backup=False)
+        assert result["count"] == 3
+        assert "pub struct Ring {" in (tmp_path / "src" / "geo" / "shapes.rs").read_text()
+
+    def test_workspace_edit_outside_project(self, tmp_path):
+        """Test edits addressing files outside the project are refused."""
+        from gathering.workspace.file_manager import FileManager
+
+        edit = {"changes": {"file:///etc/hosts": []}}
+        with pytest.raises(ValueError):
+            FileManager.preview_workspace_edit(str(tmp_path), edit)
@@ -5,12 +5,18 @@ Tests for gathering/agents/project_context.py - Project Context.
 import pytest
 from datetime import datetime, timezone
 import tempfile
+import json
 import os
+import sys
 from pathlib import Path
+from unittest.mock import MagicMock, patch
 
 from gathering.agents.project_context import (
     ProjectContext,
+    SubProject,
     GATHERING_PROJECT,
+    load_project_from_db,
+    load_project_from_yaml,
 )
 
 
@@ -401,6 +407,316 @@ class TestFromPath:
             assert ctx.git_branch is not None or ctx.git_remote is not None
 
 
+WORKSPACE_MANIFEST = """\
+[workspace]
+members = ["crates/*"]
+exclude = ["crates/scratch"]
+
+[workspace.package]
+edition = "2021"
+rust-version = "1.74"
+
+[workspace.dependencies]
+tokio = { version = "1", features = ["full"] }
+
+[workspace.lints.rust]
+unsafe_code = "forbid"
+
+[workspace.lints.clippy]
+unwrap_used = "deny"
+"""
+
+CORE_MANIFEST = """\
+[package]
+name = "demo-core"
+version = "0.1.0"
+edition.workspace = true
+rust-version.workspace = true
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+thiserror = "1"
+
+[features]
+default = ["std"]
+std = []
+"""
+
+API_MANIFEST = """\
+[package]
+name = "demo-api"
+version = "0.1.0"
+edition.workspace = true
+
+[dependencies]
+axum = "0.7"
+tokio.workspace = true
+db = { package = "sqlx", version = "0.7", features = ["postgres", "runtime-tokio"] }
+anyhow = "1"
+"""
+
+
+class TestFromPathRust:
+    """Test from_path on Cargo crates and workspaces."""
+
+    @pytest.fixture
+    def workspace(self, tmp_path):
+        """A virtual workspace with two members and an excluded crate."""
+        (tmp_path / "Cargo.toml").write_text(WORKSPACE_MANIFEST)
+        (tmp_path / "Cargo.lock").touch()
+        (tmp_path / "rust-toolchain.toml").write_text('[toolchain]\nchannel = "1.78.0"\n')
+        for member, manifest in (("core", CORE_MANIFEST), ("api", API_MANIFEST), ("scratch", CORE_MANIFEST)):
+            (tmp_path / "crates" / member / "src").mkdir(parents=True)
+            (tmp_path / "crates" / member / "Cargo.toml").write_text(manifest)
+        return tmp_path
+
+    def test_workspace_environment(self, workspace):
+        """Test edition, MSRV, toolchain and members are detected."""
+        ctx = ProjectContext.from_path(str(workspace))
+        assert ctx.rust_edition == "2021"
+        assert ctx.rust_version == "1.74"
+        assert ctx.rust_toolchain == "1.78.0"
+        assert ctx.workspace_members == ["crates/api", "crates/core"]
+        assert ctx.features == {"demo-core/default": ["std"], "demo-core/std": []}
+
+    def test_workspace_tools_and_conventions(self, workspace):
+        """Test key crates become tools and lints become conventions."""
+        ctx = ProjectContext.from_path(str(workspace))
+        assert ctx.tools["async_runtime"] == "tokio"
+        assert ctx.tools["web_framework"] == "axum"
+        # Renamed dependency, with its database backend
+        assert ctx.tools["database"] == "sqlx (postgres)"
+        assert ctx.tools["errors"] == "thiserror, anyhow"
+        assert ctx.tools["testing"] == "cargo test"
+        assert ctx.conventions["unsafe_code"] == "forbid"
+        assert ctx.conventions["clippy"] == "unwrap_used=deny"
+
+    def test_workspace_files_and_commands(self, workspace):
+        """Test key files and cargo commands cover the whole workspace."""
+        ctx = ProjectContext.from_path(str(workspace))
+        assert ctx.key_files["cargo"] == "Cargo.toml"
+        assert ctx.key_files["cargo_lock"] == "Cargo.lock"
+        assert ctx.key_files["toolchain"] == "rust-toolchain.toml"
+        assert ctx.key_files["crate:demo-api"] == "crates/api"
+        assert ctx.commands["build"] == "cargo build --workspace"
+        assert ctx.commands["test"] == "cargo test --workspace"
+        assert ctx.commands["clippy"] == "cargo clippy --workspace --all-targets -- -D warnings"
+        assert ctx.commands["fmt"] == "cargo fmt --all"
+
+    def test_single_crate(self, tmp_path):
+        """Test a package manifest, legacy toolchain file and Rust tests/ directory."""
+        (tmp_path / "Cargo.toml").write_text(
+            '[package]\nname = "tool"\nversion = "0.1.0"\ndescription = "A CLI"\n\n'
+            '[dependencies]\nclap = "4"\n\n[dev-dependencies]\nproptest = "1"\n'
+        )
+        (tmp_path / "rust-toolchain").write_text("nightly-2024-05-01\n")
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "main.rs").write_text("#![forbid(unsafe_code)]\nfn main() {}\n")
+        (tmp_path / "tests").mkdir()
+        (tmp_path / "tests" / "cli.rs").touch()
+        (tmp_path / ".config").mkdir()
+        (tmp_path / ".config" / "nextest.toml").touch()
+
+        ctx = ProjectContext.from_path(str(tmp_path))
+        assert ctx.name == "tool"
+        assert ctx.description == "A CLI"
+        # No edition key means Cargo's 2015 default
+        assert ctx.rust_edition == "2015"
+        assert ctx.rust_toolchain == "nightly-2024-05-01"
+        assert ctx.workspace_members == []
+        assert ctx.tools["cli"] == "clap"
+        assert ctx.tools["property_testing"] == "proptest"
+        assert ctx.tools["testing"] == "cargo-nextest"
+        assert ctx.commands["test"] == "cargo nextest run"
+        assert ctx.conventions["unsafe_code"] == "forbid"
+        assert ctx.key_files["main"] == "src/main.rs"
+
+    def test_prompt(self, workspace):
+        """Test the prompt carries the Rust environment."""
+        prompt = ProjectContext.from_path(str(workspace)).to_prompt()
+        assert "Environnement Rust:" in prompt
+        assert "MSRV: 1.74" in prompt
+        assert "workspace: crates/api, crates/core" in prompt
+        assert "cargo clippy" in prompt
+        assert "pytest" not in prompt
+
+    def test_roundtrip_and_yaml(self, workspace, tmp_path):
+        """Test Rust fields survive to_dict/from_dict and load from project.yaml."""
+        ctx = ProjectContext.from_path(str(workspace))
+        restored = ProjectContext.from_dict(ctx.to_dict())
+        assert restored.rust_version == "1.74"
+        assert restored.workspace_members == ctx.workspace_members
+        assert restored.features == ctx.features
+
+        (tmp_path / ".gathering").mkdir()
+        (tmp_path / ".gathering" / "project.yaml").write_text(
+            "name: demo\nrust:\n  edition: '2021'\n  msrv: '1.74'\n  toolchain: stable\n"
+        )
+        loaded = load_project_from_yaml(str(tmp_path))
+        assert (loaded.rust_edition, loaded.rust_version, loaded.rust_toolchain) == ("2021", "1.74", "stable")
+
+    def test_load_from_db(self, workspace):
+        """Test the Rust environment survives the JSON rust column."""
+        ctx = ProjectContext.from_path(str(workspace))
+        db = MagicMock()
+        db.fetch_one.return_value = {
+            "id": 3,
+            "name": ctx.name,
+            "local_path": str(workspace),
+            "rust": json.dumps(ctx.rust_config()),
+        }
+        pycopg = MagicMock()
+        pycopg.Database.from_env.return_value = db
+        with patch.dict(sys.modules, {"pycopg": pycopg}):
+            loaded = load_project_from_db(project_id=3)
+        assert loaded.rust_config() == ctx.rust_config()
+        assert loaded.workspace_members == ["crates/api", "crates/core"]
+
+
+class TestSubProjects:
+    """Test sub-project detection in polyglot repositories."""
+
+    @pytest.fixture
+    def monorepo(self, tmp_path):
+        """A Cargo workspace, a Python service, a TS dashboard and a Go tool."""
+        engine = tmp_path / "engine"
+        (engine / "crates" / "core" / "src").mkdir(parents=True)
+        (engine / "Cargo.toml").write_text(
+            '[workspace]\nmembers = ["crates/*"]\n\n'
+            '[workspace.package]\nedition = "2021"\nrust-version = "1.74"\n'
+        )
+        (engine / "crates" / "core" / "Cargo.toml").write_text(CORE_MANIFEST)
+        (engine / "rust-toolchain").write_text("1.78.0\n")
+
+        service = tmp_path / "service"
+        (service / "tests").mkdir(parents=True)
+        (service / "pyproject.toml").write_text(
+            '[project]\nname = "svc"\nrequires-python = ">=3.12"\n'
+            'dependencies = ["fastapi>=0.110"]\n\n[tool.ruff]\nline-length = 100\n'
+        )
+
+        dashboard = tmp_path / "dashboard"
+        (dashboard / "node_modules" / "left-pad").mkdir(parents=True)
+        (dashboard / "node_modules" / "left-pad" / "package.json").write_text('{"name": "left-pad"}')
+        (dashboard / "package.json").write_text(
+            '{"name": "dash", "engines": {"node": ">=20"}, '
+            '"scripts": {"dev": "vite", "build": "vite build", "test": "vitest"}, '
+            '"dependencies": {"react": "^18"}, "devDependencies": {"vite": "^5", "vitest": "^1"}}'
+        )
+        (dashboard / "pnpm-lock.yaml").touch()
+        (dashboard / "tsconfig.json").write_text("{}")
+
+        (tmp_path / "tools").mkdir()
+        (tmp_path / "tools" / "go.mod").write_text("module example.com/tools\n\ngo 1.22\n\ntoolchain go1.22.3\n")
+        (tmp_path / "tools" / "go.sum").touch()
+        return tmp_path
+
+    def test_detects_one_subproject_per_root(self, monorepo):
+        """Test each manifest root is detected; members and dependencies are not."""
+        ctx = ProjectContext.from_path(str(monorepo))
+        assert [(sub.path, sub.language) for sub in ctx.subprojects] == [
+            ("dashboard", "typescript"),
+            ("engine", "rust"),
+            ("service", "python"),
+            ("tools", "go"),
+        ]
+
+    def test_toolchains_commands_and_key_files(self, monorepo):
+        """Test each sub-project gets its own toolchain, tools, commands and files."""
+        subs = {sub.path: sub for sub in ProjectContext.from_path(str(monorepo)).subprojects}
+
+        engine = subs["engine"]
+        assert engine.toolchain == "1.78.0 (edition 2021, MSRV 1.74)"
+        assert engine.commands["test"] == "cargo test --workspace"
+        assert engine.key_files["crate:demo-core"] == "crates/core"
+
+        service = subs["service"]
+        assert (service.name, service.toolchain) == ("svc", "python >=3.12")
+        assert service.tools["web_framework"] == "fastapi"
+        assert service.commands == {"test": "pytest tests/ -v", "lint": "ruff check ."}
+
+        dashboard = subs["dashboard"]
+        assert dashboard.toolchain == "node >=20"
+        assert dashboard.tools["package_manager"] == "pnpm"
+        assert dashboard.tools["ui_framework"] == "react"
+        assert dashboard.commands["build"] == "pnpm run build"
+        assert dashboard.key_files["lockfile"] == "pnpm-lock.yaml"
+
+        tools = subs["tools"]
+        assert (tools.name, tools.toolchain) == ("example.com/tools", "go 1.22.3")
+        assert tools.commands["vet"] == "go vet ./..."
+        assert tools.key_files["go_sum"] == "go.sum"
+
+    def test_single_root_has_no_subprojects(self, tmp_path):
+        """Test a plain project keeps the flat description only."""
+        (tmp_path / "pyproject.toml").write_text('[project]\nname = "solo"\n')
+        assert ProjectContext.from_path(str(tmp_path)).subprojects == []
+
+    def test_subproject_for(self, monorepo):
+        """Test the innermost sub-project holding most working files is chosen."""
+        ctx = ProjectContext(
+            path=str(monorepo),
+            subprojects=[SubProject(path="."), SubProject(path="engine"), SubProject(path="dashboard")],
+        )
+        assert ctx.subproject_for(["engine/crates/core/src/lib.rs"]).path == "engine"
+        assert ctx.subproject_for([str(monorepo / "dashboard" / "src" / "App.tsx")]).path == "dashboard"
+        assert ctx.subproject_for(["README.md"]).path == "."
+        assert ctx.subproject_for(["engine/a.rs", "dashboard/a.ts", "dashboard/b.ts"]).path == "dashboard"
+        assert ctx.subproject_for(["/elsewhere/file.py"]) is None
+        assert ctx.subproject_for([]) is None
+
+    def test_prompt_focuses_on_working_files(self, monorepo):
+        """Test to_prompt details only the sub-project being worked on."""
+        ctx = ProjectContext.from_path(str(monorepo))
+
+        prompt = ctx.to_prompt(["dashboard/src/App.tsx"])
+        assert "Sous-projets:" in prompt
+        assert "  - dashboard: typescript (dash) [actif]" in prompt
+        assert "Sous-projet actif: dashboard (typescript)" in prompt
+        assert "pnpm run build" in prompt
+        assert "go vet" not in prompt
+
+        overview = ctx.to_prompt()
+        assert "  - tools: go (example.com/tools)" in overview
+        assert "Sous-projet actif" not in overview
+
+    def test_roundtrip_and_yaml(self, monorepo, tmp_path):
+        """Test sub-projects survive to_dict/from_dict and load from project.yaml."""
+        ctx = ProjectContext.from_path(str(monorepo))
+        restored = ProjectContext.from_dict(ctx.to_dict())
+        assert restored.subprojects == ctx.subprojects
+
+        (tmp_path / ".gathering").mkdir()
+        (tmp_path / ".gathering" / "project.yaml").write_text(
+            "name: demo\n"
+            "subprojects:\n"
+            "  - path: web\n"
+            "    language: typescript\n"
+            "    toolchain: node 20\n"
+            "    commands:\n"
+            "      build: npm run build\n"
+        )
+        loaded = load_project_from_yaml(str(tmp_path))
+        assert loaded.subprojects == [
+            SubProject(path="web", language="typescript", toolchain="node 20", commands={"build": "npm run build"})
+        ]
+
+    def test_load_from_db(self):
+        """Test the DB loader reads the JSON subprojects column."""
+        db = MagicMock()
+        db.fetch_one.return_value = {
+            "id": 7,
+            "name": "mono",
+            "local_path": "/srv/mono",
+            "subprojects": '[{"path": "tools", "language": "go", "toolchain": "go 1.22"}]',
+        }
+        pycopg = MagicMock()
+        pycopg.Database.from_env.return_value = db
+        with patch.dict(sys.modules, {"pycopg": pycopg}):
+            ctx = load_project_from_db(project_id=7)
+        assert ctx.subprojects == [SubProject(path="tools", language="go", toolchain="go 1.22")]
+
+
 class TestGatheringProject:
     """Test the pre-configured GATHERING_PROJECT constant."""
 
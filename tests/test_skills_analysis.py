@@ -0,0 +1,636 @@
+"""
+Tests for Code Analysis Skill - Rust and multi-language support.
+
+Covers:
+- clippy JSON and rustfmt --check parsing
+- CVSS severity scoring
+- cargo audit output and the advisory-db fallback
+- Cargo.lock duplicate, outdated and yanked crates
+- tree-sitter complexity, metrics, unused imports and token duplicates
+"""
+
+import json
+import shutil
+import subprocess
+from unittest.mock import patch
+
+import pytest
+
+
+REGISTRY = "registry+https://github.com/rust-lang/crates.io-index"
+
+CARGO_LOCK = f"""\
+# This file is automatically @generated by Cargo.
+version = 3
+
+[[package]]
+name = "demo"
+version = "0.1.0"
+dependencies = [
+ "smallvec",
+ "time 0.1.45",
+ "time 0.3.36",
+]
+
+[[package]]
+name = "smallvec"
+version = "1.6.0"
+source = "{REGISTRY}"
+
+[[package]]
+name = "time"
+version = "0.1.45"
+source = "{REGISTRY}"
+
+[[package]]
+name = "time"
+version = "0.3.36"
+source = "{REGISTRY}"
+"""
+
+ADVISORIES = {
+    "smallvec/RUSTSEC-2021-0003.md": """\
+```toml
+[advisory]
+id = "RUSTSEC-2021-0003"
+package = "smallvec"
+date = "2021-01-08"
+cvss = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"
+
+[versions]
+patched = [">= 1.6.1"]
+unaffected = ["< 1.3.0"]
+```
+
+# Buffer overflow in SmallVec::insert_many
+""",
+    "time/RUSTSEC-2020-0071.md": """\
+```toml
+[advisory]
+id = "RUSTSEC-2020-0071"
+package = "time"
+date = "2020-11-18"
+cvss = "CVSS:3.1/AV:L/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:H"
+
+[versions]
+patched = [">= 0.2.23"]
+unaffected = ["= 0.2.0", "= 0.2.1"]
+```
+
+# Potential segfault in the time crate
+""",
+    "time/RUSTSEC-2099-0001.md": """\
+```toml
+[advisory]
+id = "RUSTSEC-2099-0001"
+package = "time"
+date = "2099-01-01"
+withdrawn = "2099-02-01"
+
+[versions]
+patched = []
+```
+
+# Withdrawn advisory
+""",
+}
+
+INDEX = {
+    "sm/al/smallvec": [("1.6.0", True), ("1.6.1", False), ("1.13.2", False)],
+    "ti/me/time": [("0.1.45", False), ("0.1.47", False), ("0.3.36", False)],
+}
+
+CLIPPY_OUTPUT = "\n".join(json.dumps(line) for line in [
+    {"reason": "compiler-artifact", "target": {"kind": ["lib"]}},
+    {
+        "reason": "compiler-message",
+        "target": {"kind": ["lib"]},
+        "message": {
+            "level": "warning",
+            "message": "unneeded `return` statement",
+            "code": {"code": "clippy::needless_return", "explanation": None},
+            "spans": [{"file_name": "src/lib.rs", "line_start": 9, "line_end": 9,
+                       "column_start": 5, "column_end": 17, "is_primary": True}],
+            "children": [{"message": "remove `return`", "spans": [
+                {"file_name": "src/lib.rs", "line_start": 9, "line_end": 9, "column_start": 5,
+                 "column_end": 17, "is_primary": True, "suggested_replacement": "x + 1"}
+            ]}],
+        },
+    },
+    {
+        "reason": "compiler-message",
+        "target": {"kind": ["lib"]},
+        "message": {"level": "warning", "message": "1 warning emitted", "code": None, "spans": [], "children": []},
+    },
+])
+
+RUSTFMT_OUTPUT = """\
+Diff in /work/demo/src/lib.rs:8:
+ }
+-pub fn bad(x: i32) -> i32 { return x+1; }
++pub fn bad(x: i32) -> i32 {
++    return x + 1;
++}
+Diff in /work/demo/src/main.rs:1:
+-fn main() { }
++fn main() {}
+"""
+
+
+@pytest.fixture
+def skill():
+    from gathering.skills.analysis.scanner import CodeAnalysisSkill
+    return CodeAnalysisSkill()
+
+
+@pytest.fixture
+def rust_project(tmp_path):
+    """A locked Rust project with a local advisory-db and crates.io index."""
+    project = tmp_path / "demo"
+    (project / "src").mkdir(parents=True)
+    (project / "Cargo.toml").write_text('[package]\nname = "demo"\nversion = "0.1.0"\nedition = "2021"\n')
+    (project / "src" / "lib.rs").write_text("pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n")
+    (project / "Cargo.lock").write_text(CARGO_LOCK)
+
+    db = tmp_path / "advisory-db"
+    for relative, content in ADVISORIES.items():
+        (db / "crates" / relative).parent.mkdir(parents=True, exist_ok=True)
+        (db / "crates" / relative).write_text(content)
+
+    index = tmp_path / "index"
+    for relative, versions in INDEX.items():
+        (index / relative).parent.mkdir(parents=True, exist_ok=True)
+        (index / relative).write_text("\n".join(
+            json.dumps({"name": relative.rsplit("/", 1)[1], "vers": v, "deps": [], "features": {}, "yanked": y})
+            for v, y in versions
+        ) + "\n")
+
+    return {"path": project, "advisory_db": db, "registry_index": index}
+
+
+def no_cargo_audit(args, cwd, timeout):
+    raise FileNotFoundError("cargo-audit")
+
+
+class TestRustLint:
+    """Test clippy and rustfmt output handling."""
+
+    def test_parse_clippy(self, skill, tmp_path):
+        # Same warning for lib and lib test targets is reported once
+        output = CLIPPY_OUTPUT + "\n" + CLIPPY_OUTPUT
+        issues = skill._parse_clippy_output(output, tmp_path)
+
+        assert issues == [{
+            "file": str(tmp_path / "src" / "lib.rs"),
+            "line": 9,
+            "column": 5,
+            "end_line": 9,
+            "end_column": 17,
+            "code": "clippy::needless_return",
+            "level": "warning",
+            "message": "unneeded `return` statement",
+            "suggestion": "x + 1",
+            "source": "clippy",
+        }]
+
+    def test_parse_rustfmt(self, skill):
+        issues = skill._parse_rustfmt_check(RUSTFMT_OUTPUT)
+
+        assert [(i["file"], i["line"]) for i in issues] == [
+            ("/work/demo/src/lib.rs", 8), ("/work/demo/src/main.rs", 1)
+        ]
+        assert issues[1]["diff"] == "-fn main() { }\n+fn main() {}"
+
+    def test_lint_commands(self, skill, rust_project):
+        path = rust_project["path"]
+        calls = []
+
+        def run(args, cwd, timeout):
+            calls.append(args)
+            stdout = CLIPPY_OUTPUT if args[0] == "clippy" and "--fix" not in args else ""
+            return subprocess.CompletedProcess(args, 0, stdout=stdout, stderr="")
+
+        with patch.object(skill, "_run_cargo", side_effect=run):
+            result = skill.execute("analysis_lint", {"path": str(path), "fix": True})
+
+        assert calls == [
+            ["clippy", "--fix", "--allow-dirty", "--allow-staged", "--allow-no-vcs", "--all-targets"],
+            ["clippy", "--all-targets", "--message-format=json"],
+            ["metadata", "--no-deps", "--format-version", "1"],
+            ["fmt"],
+        ]
+        assert result.data["tools"] == ["clippy", "rustfmt"]
+        assert result.data["by_code"] == {"clippy::needless_return": 1}
+        assert result.data["fixed"]
+
+    def test_lint_workspace_member(self, skill, tmp_path):
+        # clippy reports spans relative to the workspace root, not the member
+        member = tmp_path / "crates" / "foo"
+        (member / "src").mkdir(parents=True)
+        (tmp_path / "Cargo.toml").write_text('[workspace]\nmembers = ["crates/foo"]\n')
+        (member / "Cargo.toml").write_text('[package]\nname = "foo"\nversion = "0.1.0"\n')
+        (member / "src" / "lib.rs").write_text("")
+
+        def run(args, cwd, timeout):
+            if args[0] == "metadata":
+                stdout = json.dumps({"workspace_root": str(tmp_path)})
+            else:
+                stdout = CLIPPY_OUTPUT.replace('"src/lib.rs"', '"crates/foo/src/lib.rs"')
+            return subprocess.CompletedProcess(args, 0, stdout=stdout, stderr="")
+
+        with patch.object(skill, "_run_cargo", side_effect=run):
+            result = skill.execute("analysis_lint", {"path": str(member / "src" / "lib.rs"), "tool": "clippy"})
+
+        assert [(i["file"], i["line"]) for i in result.data["issues"]] == [(str(member / "src" / "lib.rs"), 9)]
+
+    def test_edition_from_workspace(self, skill, tmp_path):
+        (tmp_path / "Cargo.toml").write_text('[workspace]\nmembers = ["core"]\n\n[workspace.package]\nedition = "2024"\n')
+        (tmp_path / "core").mkdir()
+        (tmp_path / "core" / "Cargo.toml").write_text('[package]\nname = "core"\nedition.workspace = true\n')
+
+        assert skill._cargo_edition(tmp_path / "core") == "2024"
+
+    @pytest.mark.skipif(shutil.which("cargo-clippy") is None, reason="clippy not installed")
+    def test_clippy_and_rustfmt(self, skill, rust_project):
+        path = rust_project["path"]
+        (path / "Cargo.lock").unlink()
+        (path / "src" / "lib.rs").write_text("pub fn bad(x: i32) -> i32 { return x+1; }\n")
+
+        result = skill.execute("analysis_lint", {"path": str(path / "src" / "lib.rs")})
+
+        assert not result.success
+        assert result.data["by_code"] == {"clippy::needless_return": 1, "rustfmt": 1}
+        assert result.data["errors"] == []
+
+    @pytest.mark.skipif(shutil.which("cargo-clippy") is None, reason="clippy not installed")
+    def test_clippy_workspace_member(self, skill, tmp_path):
+        member = tmp_path / "crates" / "foo"
+        (member / "src").mkdir(parents=True)
+        (tmp_path / "Cargo.toml").write_text('[workspace]\nmembers = ["crates/foo"]\nresolver = "2"\n')
+        (member / "Cargo.toml").write_text('[package]\nname = "foo"\nversion = "0.1.0"\nedition = "2021"\n')
+        (member / "src" / "lib.rs").write_text("pub fn bad(x: i32) -> i32 {\n    return x + 1;\n}\n")
+
+        result = skill.execute("analysis_lint", {"path": str(member / "src" / "lib.rs"), "tool": "clippy"})
+
+        assert result.data["by_code"] == {"clippy::needless_return": 1}
+        assert result.data["issues"][0]["file"] == str(member / "src" / "lib.rs")
+
+
+class TestRustSecurity:
+    """Test cargo audit and advisory-db matching."""
+
+    def test_cvss_severity(self, skill):
+        assert skill._cvss_severity("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H") == (9.8, "critical")
+        assert skill._cvss_severity("CVSS:3.1/AV:N/AC:L/PR:L/UI:N/S:C/C:L/I:L/A:N") == (6.4, "medium")
+        assert skill._cvss_severity("CVSS:3.1/AV:L/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:H") == (5.1, "medium")
+        assert skill._cvss_severity(None) == (None, "medium")
+
+    def test_advisory_db_fallback(self, skill, rust_project):
+        with patch.object(skill, "_run_cargo", side_effect=no_cargo_audit):
+            result = skill.execute("analysis_security", {
+                "path": str(rust_project["path"]), "advisory_db": str(rust_project["advisory_db"])
+            })
+
+        findings = {f["category"]: f for f in result.data["findings"] if f.get("source") == "advisory-db"}
+        assert not result.success
+        assert result.data["rust_audit"]["tool"] == "advisory-db"
+        # time 0.3.36 is patched and the withdrawn advisory is ignored
+        assert sorted(findings) == ["RUSTSEC-2020-0071", "RUSTSEC-2021-0003"]
+        smallvec = findings["RUSTSEC-2021-0003"]
+        assert smallvec["code"] == "smallvec 1.6.0"
+        assert smallvec["severity"] == "critical"
+        assert smallvec["message"] == "Buffer overflow in SmallVec::insert_many"
+        assert smallvec["line"] == 14
+        assert findings["RUSTSEC-2020-0071"]["code"] == "time 0.1.45"
+
+    def test_severity_filter(self, skill, rust_project):
+        with patch.object(skill, "_run_cargo", side_effect=no_cargo_audit):
+            result = skill.execute("analysis_security", {
+                "path": str(rust_project["path"]), "advisory_db": str(rust_project["advisory_db"]),
+                "severity": "critical"
+            })
+
+        assert [f["category"] for f in result.data["findings"]] == ["RUSTSEC-2021-0003"]
+
+    def test_cargo_audit_report(self, skill, rust_project):
+        report = {
+            "vulnerabilities": {"found": True, "count": 1, "list": [{
+                "advisory": {"id": "RUSTSEC-2021-0003", "package": "smallvec", "title": "Buffer overflow",
+                             "cvss": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"},
+                "versions": {"patched": [">=1.6.1"], "unaffected": []},
+                "package": {"name": "smallvec", "version": "1.6.0"},
+            }]},
+            "warnings": {"unmaintained": [{
+                "kind": "unmaintained",
+                "advisory": {"id": "RUSTSEC-2020-0056", "package": "time", "title": "time is unmaintained",
+                             "informational": "unmaintained"},
+                "versions": {"patched": []},
+                "package": {"name": "time", "version": "0.1.45"},
+            }]},
+        }
+        with patch.object(skill, "_run_cargo", return_value=subprocess.CompletedProcess(
+            [], 1, stdout=json.dumps(report), stderr=""
+        )) as run:
+            audit = skill._cargo_audit(rust_project["path"] / "Cargo.lock", {
+                "advisory_db": str(rust_project["advisory_db"])
+            })
+
+        assert run.call_args.args[0][:4] == ["audit", "--json", "--no-fetch", "--stale"]
+        assert audit["tool"] == "cargo-audit"
+        assert [(f["category"], f["severity"], f["kind"]) for f in audit["findings"]] == [
+            ("RUSTSEC-2021-0003", "critical", "vulnerability"),
+            ("RUSTSEC-2020-0056", "low", "unmaintained"),
+        ]
+
+    def test_missing_advisory_db(self, skill, rust_project, tmp_path):
+        audit = skill._cargo_audit(rust_project["path"] / "Cargo.lock", {"advisory_db": str(tmp_path / "missing")})
+
+        assert audit["findings"] == []
+        assert "advisory-db not found" in audit["error"]
+
+
+class TestCargoDependencies:
+    """Test Cargo.lock dependency analysis."""
+
+    def test_duplicates_outdated_yanked(self, skill, rust_project):
+        with patch.object(skill, "_run_cargo", side_effect=no_cargo_audit):
+            result = skill.execute("analysis_dependencies", {
+                "path": str(rust_project["path"]),
+                "registry_index": str(rust_project["registry_index"]),
+                "advisory_db": str(rust_project["advisory_db"]),
+            })
+
+        data = result.data
+        # The workspace member itself is not a dependency
+        assert [(d["name"], d["version"]) for d in data["dependencies"]] == [
+            ("smallvec", "1.6.0"), ("time", "0.1.45"), ("time", "0.3.36")
+        ]
+        assert data["duplicates"] == [{"name": "time", "versions": ["0.1.45", "0.3.36"]}]
+        assert data["yanked"] == [{"name": "smallvec", "version": "1.6.0", "line": 14}]
+        assert data["outdated"] == [
+            {"name": "smallvec", "version": "1.6.0", "latest_version": "1.13.2", "compatible_version": "1.13.2"},
+            {"name": "time", "version": "0.1.45", "latest_version": "0.3.36", "compatible_version": "0.1.47"},
+        ]
+        assert len(data["vulnerabilities"]) == 2
+        assert not result.success
+
+    def test_without_registry_index(self, skill, rust_project):
+        with patch.object(skill, "_run_cargo", side_effect=no_cargo_audit), \
+                patch.object(skill, "_crate_registry", return_value=None):
+            result = skill.execute("analysis_dependencies", {
+                "path": str(rust_project["path"]), "check_vulnerabilities": False
+            })
+
+        data = result.data
+        assert data["duplicates"] == [{"name": "time", "versions": ["0.1.45", "0.3.36"]}]
+        assert data["outdated"] == [] and data["yanked"] == []
+        assert data["registry_index"] is None
+        assert str(rust_project["path"] / "Cargo.lock") in data["requirement_files"]
+
+
+RUST_SOURCE = """\
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+pub struct Wrapper;
+
+impl Wrapper {
+    // Sorts values into buckets
+    pub fn classify(&self, x: i32, y: i32) -> &'static str {
+        if x > 0 && y > 0 {
+            "both"
+        } else if x > 0 || y > 0 {
+            match x {
+                1 => "one",
+                2 => "two",
+                _ => "some",
+            }
+        } else {
+            "none"
+        }
+    }
+}
+
+fn show(value: &dyn Display) -> String {
+    format!("{}", value)
+}
+"""
+
+JS_SOURCE = """\
+import React, { useState as useLocal, useMemo } from "react";
+
+class Picker {
+  pick(a, b) {
+    return a && b ? a : b ?? 0;
+  }
+}
+
+export const walk = (items) => {
+  for (const item of items) {
+    if (item) {
+      while (useMemo(item)) {}
+    }
+  }
+  switch (items.length) {
+    case 0: return 0;
+    case 1: return 1;
+    default: return 2;
+  }
+};
+"""
+
+GO_SOURCE = """\
+package main
+
+import (
+\t"fmt"
+\t"os"
+\tyaml "gopkg.in/yaml.v3"
+\t"example.com/mod/v2"
+)
+
+type Server struct{}
+
+func (s *Server) Run(a int) {
+\tswitch a {
+\tcase 1:
+\t\tfmt.Println()
+\tcase 2:
+\t}
+\tif a > 1 || a < 0 {
+\t\tmod.Handle()
+\t}
+}
+"""
+
+
+def needs_grammar(language):
+    from gathering.skills.analysis import syntax
+    return pytest.mark.skipif(not syntax.is_available(language), reason=f"tree-sitter {language} grammar not installed")
+
+
+class TestSyntaxMetrics:
+    """Test per-function metrics from the tree-sitter layer."""
+
+    @needs_grammar("rust")
+    def test_rust(self):
+        from gathering.skills.analysis import syntax
+
+        metrics = syntax.analyze_source(RUST_SOURCE, "rust")
+        functions = {f.name: f for f in metrics.functions}
+
+        classify = functions["Wrapper.classify"]
+        # if, &&, else if, ||, and three match arms counted as two branches
+        assert classify.complexity == 7
+        # else-if continues the chain, the match nests inside it
+        assert classify.nesting_depth == 2
+        assert (classify.line, classify.length, classify.loc) == (8, 13, 13)
+        assert functions["show"].complexity == 1
+        assert metrics.classes == 1
+        assert metrics.unused_imports == [("HashMap", 1), ("fmt", 2)]
+
+    @needs_grammar("rust")
+    def test_rust_trait_imports(self):
+        from gathering.skills.analysis import syntax
+
+        source = (
+            "use std::io::Write;\n"
+            "use futures::StreamExt;\n"
+            "use std::path::PathBuf;\n\n"
+            "async fn run(mut out: std::fs::File, rows: Rows) {\n"
+            "    out.write_all(b\"rows\\n\").unwrap();\n"
+            "    let rows = rows.collect::<Vec<_>>().await;\n"
+            "}\n"
+        )
+        # Write and StreamExt only bring write_all and collect into scope
+        assert syntax.analyze_source(source, "rust").unused_imports == [("PathBuf", 3)]
+        # Without method calls the trait is genuinely unused
+        assert syntax.analyze_source("use std::io::Write;\n\nfn main() {}\n", "rust").unused_imports == [("Write", 1)]
+
+    @needs_grammar("javascript")
+    def test_javascript(self):
+        from gathering.skills.analysis import syntax
+
+        metrics = syntax.analyze_source(JS_SOURCE, "javascript")
+        functions = {f.name: f for f in metrics.functions}
+
+        # &&, ternary and ??
+        assert functions["Picker.pick"].complexity == 4
+        # for, if, while and two cases
+        assert functions["walk"].complexity == 6
+        assert functions["walk"].nesting_depth == 3
+        assert metrics.classes == 1
+        assert metrics.unused_imports == [("React", 1), ("useLocal", 1)]
+
+    @needs_grammar("go")
+    def test_go(self):
+        from gathering.skills.analysis import syntax
+
+        metrics = syntax.analyze_source(GO_SOURCE, "go")
+        run = metrics.functions[0]
+
+        assert run.name == "Server.Run"
+        assert run.complexity == 5
+        assert run.nesting_depth == 1
+        assert metrics.unused_imports == [("os", 5), ("yaml", 6)]
+
+    @needs_grammar("rust")
+    def test_skill_complexity(self, skill, tmp_path):
+        (tmp_path / "lib.rs").write_text(RUST_SOURCE)
+        (tmp_path / "util.py").write_text("def f(x):\n    if x:\n        return 1\n    return 0\n")
+
+        result = skill.execute("analysis_complexity", {"path": str(tmp_path), "threshold": 2})
+
+        data = result.data
+        assert [(f["function"], f["complexity"]) for f in data["complex_functions"]] == [
+            ("Wrapper.classify", 7), ("f", 2)
+        ]
+        assert set(data["complex_functions"][0]) == set(data["complex_functions"][1])
+        assert "note" not in data
+
+
+class TestTokenDuplicates:
+    """Test token-window duplicate detection."""
+
+    @staticmethod
+    def stream(kinds, first_line=1, per_line=4):
+        return [(kind, first_line + i // per_line) for i, kind in enumerate(kinds)]
+
+    def test_block_reported_once_at_full_extent(self):
+        from gathering.skills.analysis import syntax
+
+        block = "fn ID ( ID : ID ) -> ID { if ID > LIT { ID } else { LIT } }".split()
+        streams = {
+            "a.rs": self.stream(["use", "ID", ";"] + block),
+            "b.rs": self.stream(block + ["ID"]),
+        }
+
+        duplicates = syntax.find_duplicates(streams, min_tokens=10, min_lines=2)
+
+        assert duplicates == [{
+            "original": {"file": "a.rs", "start_line": 1, "end_line": 7},
+            "duplicate": {"file": "b.rs", "start_line": 1, "end_line": 6},
+            "lines": 6,
+            "tokens": 22,
+        }]
+
+    def test_thresholds(self):
+        from gathering.skills.analysis import syntax
+
+        tokens = ["ID", "(", "LIT", ")", ";", "}"]
+        streams = {"a.go": self.stream(tokens), "b.go": self.stream(tokens)}
+
+        assert syntax.find_duplicates(streams, min_tokens=10, min_lines=1) == []
+        assert syntax.find_duplicates(streams, min_tokens=3, min_lines=3) == []
+        assert [d["tokens"] for d in syntax.find_duplicates(streams, min_tokens=3, min_lines=1)] == [6]
+
+    def test_overlapping_repeats_in_one_file(self):
+        from gathering.skills.analysis import syntax
+
+        streams = {"a.js": self.stream(["ID", ",", "LIT", ","] * 4)}
+
+        assert syntax.find_duplicates(streams, min_tokens=8, min_lines=1) == [{
+            "original": {"file": "a.js", "start_line": 1, "end_line": 2},
+            "duplicate": {"file": "a.js", "start_line": 3, "end_line": 4},
+            "lines": 2,
+            "tokens": 8,
+        }]
+
+
+class TestMixedLanguageAnalysis:
+    """Test how the analyses combine Python and tree-sitter results."""
+
+    def test_python_function_shape(self, skill, tmp_path):
+        (tmp_path / "util.py").write_text(
+            "def f(x):\n"
+            "    # walk\n"
+            "    if x:\n"
+            "        for i in x:\n"
+            "            if i:\n"
+            "                pass\n"
+            "    elif x is None:\n"
+            "        pass\n"
+        )
+
+        result = skill.execute("analysis_complexity", {"path": str(tmp_path), "threshold": 1})
+
+        function = result.data["complex_functions"][0]
+        assert function["complexity"] == 5
+        assert (function["lines"], function["loc"], function["nesting_depth"]) == (8, 7, 3)
+
+    def test_missing_grammar_is_noted(self, skill, tmp_path):
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "lib.rs").write_text(RUST_SOURCE)
+        (tmp_path / "target").mkdir()
+        (tmp_path / "target" / "build.rs").write_text(RUST_SOURCE)
+
+        with patch("gathering.skills.analysis.syntax.analyze_file", return_value=None):
+            complexity = skill.execute("analysis_complexity", {"path": str(tmp_path)})
+            dead_code = skill.execute("analysis_dead_code", {"path": str(tmp_path / "src" / "lib.rs")})
+
+        # Build output under target/ is not analyzed
+        assert complexity.data["note"].startswith("1 Rust/JavaScript/TypeScript/Go files skipped")
+        assert complexity.data["complex_functions"] == []
+        assert dead_code.data["tool"] == "tree-sitter"
+        assert dead_code.data["unused_imports"] == []
+        assert "note" in dead_code.data
@@ -0,0 +1,200 @@
+"""
+Tests for Deploy Skill - Dockerfile generation.
+
+Covers:
+- Cargo [[bin]] discovery (explicit, src/main.rs, src/bin/, workspaces)
+- cargo-chef multi-stage builds with distroless and scratch runtimes
+- Binary validation against the project targets
+- Python and Node templates
+"""
+
+import json
+
+import pytest
+
+
+@pytest.fixture
+def cargo_project(tmp_path):
+    """A package with an explicit [[bin]], src/main.rs and src/bin/ targets."""
+    (tmp_path / "Cargo.toml").write_text(
+        '[package]\nname = "shop"\nversion = "0.1.0"\nedition = "2021"\n\n'
+        '[[bin]]\nname = "shop-admin"\npath = "src/admin.rs"\n'
+    )
+    (tmp_path / "Cargo.lock").write_text("version = 3\n")
+    (tmp_path / "src" / "bin" / "worker").mkdir(parents=True)
+    (tmp_path / "src" / "main.rs").write_text("fn main() {}\n")
+    (tmp_path / "src" / "admin.rs").write_text("fn main() {}\n")
+    (tmp_path / "src" / "bin" / "migrate.rs").write_text("fn main() {}\n")
+    (tmp_path / "src" / "bin" / "worker" / "main.rs").write_text("fn main() {}\n")
+    return tmp_path
+
+
+def generate(tool_input):
+    from gathering.skills.deploy.manager import DeploySkill
+    return DeploySkill().execute("deploy_generate_dockerfile", tool_input)
+
+
+class TestCargoBinaries:
+    """Test [[bin]] target discovery."""
+
+    def test_package_targets(self, cargo_project):
+        from gathering.skills.deploy.manager import DeploySkill
+
+        binaries = {b["name"]: b["path"] for b in DeploySkill()._cargo_binaries(cargo_project)}
+        assert binaries == {
+            "shop-admin": "src/admin.rs",
+            "shop": "src/main.rs",
+            "migrate": "src/bin/migrate.rs",
+            "worker": "src/bin/worker/main.rs",
+        }
+
+    def test_workspace_members(self, tmp_path):
+        from gathering.skills.deploy.manager import DeploySkill
+
+        (tmp_path / "Cargo.toml").write_text('[workspace]\nmembers = ["crates/*"]\nexclude = ["crates/legacy"]\n')
+        for name, main in [("api", True), ("core", False), ("legacy", True)]:
+            (tmp_path / "crates" / name / "src").mkdir(parents=True)
+            (tmp_path / "crates" / name / "Cargo.toml").write_text(f'[package]\nname = "{name}"\nversion = "0.1.0"\n')
+            (tmp_path / "crates" / name / "src" / ("main.rs" if main else "lib.rs")).write_text("")
+
+        binaries = DeploySkill()._cargo_binaries(tmp_path)
+        assert [(b["name"], b["package"], b["path"]) for b in binaries] == [("api", "api", "crates/api/src/main.rs")]
+
+
+class TestRustDockerfile:
+    """Test deploy_generate_dockerfile on Cargo projects."""
+
+    def test_cargo_chef_distroless(self, cargo_project):
+        result = generate({"path": str(cargo_project), "binary": "worker", "port": 8080})
+        assert result.success
+        assert result.needs_confirmation
+        assert result.confirmation_type == "write_file"
+
+        content = result.data["content"]
+        assert result.data["path"] == str(cargo_project / "Dockerfile")
+        assert result.data["language"] == "rust"
+        assert result.data["stages"] == ["chef", "planner", "builder", "runtime"]
+        assert "FROM lukemathwalker/cargo-chef:latest-rust-1 AS chef" in content
+        assert "RUN cargo chef prepare --recipe-path recipe.json" in content
+        # Dependencies are cooked before the sources are copied
+        assert content.index("cargo chef cook --release --locked") < content.index("COPY . .\nRUN cargo build")
+        assert "RUN cargo build --release --locked --bin worker" in content
+        assert "FROM gcr.io/distroless/cc-debian12:nonroot AS runtime" in content
+        assert "COPY --from=builder /app/target/release/worker /usr/local/bin/worker" in content
+        assert content.endswith('EXPOSE 8080\nENTRYPOINT ["/usr/local/bin/worker"]\n')
+        assert "target/" in result.data["dockerignore"]["content"]
+
+    def test_scratch_builds_static_binary(self, cargo_project):
+        (cargo_project / "rust-toolchain.toml").write_text('[toolchain]\nchannel = "1.78.0"\n')
+
+        content = generate({"path": str(cargo_project), "binary": "shop", "runtime": "scratch"}).data["content"]
+        assert "FROM rust:1.78.0-slim-bookworm AS chef" in content
+        assert "RUN cargo install cargo-chef --locked" in content
+        assert "rustup target add x86_64-unknown-linux-musl" in content
+        assert "--target x86_64-unknown-linux-musl --bin shop" in content
+        assert "FROM scratch AS runtime" in content
+        assert "/app/target/x86_64-unknown-linux-musl/release/shop /usr/local/bin/shop" in content
+        assert "ca-certificates.crt" in content
+        assert "USER 65532:65532" in content
+
+    def test_unknown_binary(self, cargo_project):
+        result = generate({"path": str(cargo_project), "binary": "server"})
+        assert not result.success
+        assert result.error == "unknown_binary"
+        assert result.data["binaries"] == ["migrate", "shop", "shop-admin", "worker"]
+
+    def test_ambiguous_without_default_run(self, cargo_project):
+        result = generate({"path": str(cargo_project)})
+        assert result.error == "ambiguous_binary"
+
+        manifest = (cargo_project / "Cargo.toml").read_text()
+        (cargo_project / "Cargo.toml").write_text(manifest.replace('edition = "2021"', 'edition = "2021"\ndefault-run = "shop"'))
+        assert generate({"path": str(cargo_project)}).data["binary"] == "shop"
+
+    def test_library_crate(self, tmp_path):
+        (tmp_path / "Cargo.toml").write_text('[package]\nname = "geo"\nversion = "0.1.0"\n')
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "lib.rs").write_text("")
+
+        result = generate({"path": str(tmp_path)})
+        assert result.error == "no_binary"
+
+    def test_workspace_builds_member_package(self, tmp_path):
+        (tmp_path / "Cargo.toml").write_text('[workspace]\nmembers = ["api"]\n')
+        (tmp_path / "api" / "src").mkdir(parents=True)
+        (tmp_path / "api" / "Cargo.toml").write_text('[package]\nname = "api"\nversion = "0.1.0"\n')
+        (tmp_path / "api" / "src" / "main.rs").write_text("fn main() {}\n")
+
+        content = generate({"path": str(tmp_path)}).data["content"]
+        assert "RUN cargo build --release -p api --bin api" in content
+
+    def test_existing_dockerfile_stale_binaries(self, cargo_project):
+        (cargo_project / "Dockerfile").write_text(
+            "RUN cargo build --release --bin server\nCOPY --from=builder /app/target/release/server /server\n"
+        )
+        (cargo_project / ".dockerignore").write_text("target/\n")
+
+        result = generate({"path": str(cargo_project), "binary": "shop"})
+        assert result.confirmation_message.startswith("Overwrite")
+        assert result.data["existing_unknown_binaries"] == ["server"]
+        assert result.data["dockerignore"] is None
+
+
+class TestPythonNodeDockerfile:
+    """Test deploy_generate_dockerfile on Python and Node projects."""
+
+    def test_python_uv_console_script(self, tmp_path):
+        (tmp_path / "pyproject.toml").write_text(
+            '[project]\nname = "api"\nrequires-python = ">=3.11"\n\n[project.scripts]\napi-server = "api.main:run"\n'
+        )
+        (tmp_path / "uv.lock").write_text("")
+
+        result = generate({"path": str(tmp_path)})
+        content = result.data["content"]
+        assert result.data["language"] == "python"
+        assert result.data["stages"] == ["builder", "runtime"]
+        assert "FROM python:3.11-slim AS builder" in content
+        assert content.index("--no-install-project") < content.index("COPY . .")
+        assert "COPY --from=builder /opt/venv /opt/venv" in content
+        assert content.endswith('USER app\nCMD ["api-server"]\n')
+
+    def test_python_requirements(self, tmp_path):
+        (tmp_path / "requirements.txt").write_text("flask\n")
+        (tmp_path / "app.py").write_text("")
+
+        content = generate({"path": str(tmp_path)}).data["content"]
+        assert "COPY requirements.txt ./\nRUN pip install -r requirements.txt\nCOPY . .\n" in content
+        assert "pip install --no-deps ." not in content
+        assert 'CMD ["python", "app.py"]' in content
+
+        result = generate({"path": str(tmp_path), "binary": "serve"})
+        assert result.error == "unknown_binary"
+
+    def test_node_pnpm_build(self, tmp_path):
+        (tmp_path / "package.json").write_text(json.dumps({
+            "name": "web",
+            "engines": {"node": ">=22"},
+            "scripts": {"build": "tsc", "start": "node dist/index.js"},
+        }))
+        (tmp_path / "pnpm-lock.yaml").write_text("")
+
+        result = generate({"path": str(tmp_path), "port": 3000})
+        content = result.data["content"]
+        assert result.data["stages"] == ["deps", "build", "runtime"]
+        assert "FROM node:22-slim AS deps" in content
+        assert "COPY package.json pnpm-lock.yaml ./\nRUN corepack enable && pnpm install --frozen-lockfile" in content
+        assert "RUN pnpm run build\nRUN pnpm prune --prod" in content
+        assert content.endswith('EXPOSE 3000\nCMD ["pnpm", "run", "start"]\n')
+
+    def test_node_bin_entrypoint(self, tmp_path):
+        (tmp_path / "package.json").write_text(json.dumps({"name": "@acme/cli", "bin": "bin/cli.js"}))
+
+        content = generate({"path": str(tmp_path), "binary": "cli"}).data["content"]
+        assert "COPY package.json ./\nRUN npm install" in content
+        assert 'CMD ["node", "bin/cli.js"]' in content
+
+    def test_language_without_manifest(self, tmp_path):
+        (tmp_path / "package.json").write_text("{}")
+
+        result = generate({"path": str(tmp_path), "language": "rust"})
+        assert result.error == "manifest_not_found"
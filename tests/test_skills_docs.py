@@ -0,0 +1,445 @@
+"""
+Tests for Docs Skill - rustdoc support.
+
+Covers:
+- /// and //! extraction for public items
+- Doc lints (missing docs, # Errors / # Panics, intra-doc links)
+- Rustdoc comment drafts
+- Markdown API reference for a crate
+- Conventional Commits changelog, semver bumps and Cargo version diffs
+"""
+
+import shutil
+import subprocess
+
+import pytest
+
+
+LIB_RS = """\
+//! Geometry primitives.
+//!
+//! Start with [`Circle`] or [`shapes::Square`].
+
+pub mod shapes;
+
+use std::fmt;
+
+/// A circle.
+///
+/// See [`Circle::area`], [`Shape`] and [`Missing`].
+pub struct Circle {
+    /// Radius in metres.
+    pub radius: f64,
+    pub label: String,
+    secret: u8,
+}
+
+impl Circle {
+    /// Creates a circle.
+    ///
+    /// # Errors
+    ///
+    /// Fails on negative radii.
+    pub fn new(radius: f64) -> Result<Self, String> {
+        if radius < 0.0 {
+            return Err("negative".into());
+        }
+        Ok(Circle { radius, label: String::new(), secret: 0 })
+    }
+
+    /// Area, see [`Self::radius`], [`std::f64::consts::PI`] and [crate::nope].
+    pub fn area(&self) -> f64 {
+        assert!(self.radius >= 0.0);
+        std::f64::consts::PI * self.radius * self.radius
+    }
+
+    /// Parses a radius (`v[0]` is ignored).
+    pub fn parse(s: &str) -> Result<Circle, String> {
+        let r: f64 = s.parse().unwrap();
+        Circle::new(r)
+    }
+
+    pub fn undocumented(&self) {}
+
+    fn private(&self) {}
+}
+
+impl fmt::Display for Circle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "circle")
+    }
+}
+
+/// Something with an area; see [serde::Serialize] and [the book](https://doc.rust-lang.org/book/).
+pub trait Shape {
+    /// The area.
+    fn area(&self) -> f64;
+    fn name(&self) -> String;
+}
+
+pub(crate) fn helper() {}
+
+#[cfg(test)]
+mod tests {
+    pub fn check() {}
+}
+"""
+
+SHAPES_RS = """\
+//! Shapes with straight edges.
+
+/// A square.
+pub struct Square;
+
+pub enum Kind {
+    /// Round.
+    Round,
+    Flat,
+}
+"""
+
+
+@pytest.fixture
+def crate(tmp_path):
+    """A library crate with documented, undocumented and private items."""
+    (tmp_path / "Cargo.toml").write_text(
+        '[package]\nname = "geo"\nversion = "0.1.0"\nedition = "2021"\n\n[dependencies]\nserde = "1"\n'
+    )
+    (tmp_path / "src").mkdir()
+    (tmp_path / "src" / "lib.rs").write_text(LIB_RS)
+    (tmp_path / "src" / "shapes.rs").write_text(SHAPES_RS)
+    (tmp_path / "tests").mkdir()
+    (tmp_path / "tests" / "api.rs").write_text("pub fn untested_helper() {}\n")
+    return tmp_path
+
+
+class TestRustDocsExtract:
+    """Test docs_extract on Rust sources."""
+
+    def setup_method(self):
+        from gathering.skills.docs.generator import DocsSkill
+        self.skill = DocsSkill()
+
+    def test_public_items_and_module_docs(self, crate):
+        result = self.skill.execute("docs_extract", {"path": str(crate), "output_format": "json"})
+        assert result.success
+        modules = {doc["module"]: doc for doc in result.data["documentation"]}
+        assert set(modules) == {"crate", "crate::shapes"}
+
+        root = modules["crate"]
+        assert root["module_docstring"].startswith("Geometry primitives.")
+        items = {item["name"]: item for item in root["items"]}
+        assert set(items) == {"shapes", "Circle", "Shape"}
+        assert items["shapes"]["doc"] == "Shapes with straight edges."
+
+        circle = items["Circle"]
+        assert circle["doc"].startswith("A circle.")
+        members = [member["name"] for member in circle["members"]]
+        assert members == ["radius", "label", "new", "area", "parse", "undocumented"]
+        assert circle["traits"] == ["Display"]
+
+    def test_markdown(self, crate):
+        result = self.skill.execute("docs_extract", {"path": str(crate / "src" / "shapes.rs")})
+        output = result.data["documentation"]
+        assert "# Module `crate::shapes`" in output
+        assert "## struct `Square`" in output
+        assert "- `Round` - Round." in output
+
+
+class TestRustDocsLint:
+    """Test docs_lint on Rust sources."""
+
+    def setup_method(self):
+        from gathering.skills.docs.generator import DocsSkill
+        self.skill = DocsSkill()
+
+    def lint(self, path, **options):
+        result = self.skill.execute("docs_lint", {"path": str(path), **options})
+        return result, {(issue["line"], issue["type"], issue["message"]) for issue in result.data["issues"]}
+
+    def test_issues(self, crate):
+        result, issues = self.lint(crate)
+        assert not result.success
+        assert issues == {
+            (12, "broken_intra_doc_link", "Unresolved link to `Missing` in docs of struct `Circle`"),
+            (15, "missing_docs", "Missing documentation for public field `Circle::label`"),
+            (33, "missing_panics_doc", "method `Circle::area` may panic but has no `# Panics` section"),
+            (33, "broken_intra_doc_link", "Unresolved link to `crate::nope` in docs of method `Circle::area`"),
+            (39, "missing_errors_doc", "method `Circle::parse` returns a Result but has no `# Errors` section"),
+            (39, "missing_panics_doc", "method `Circle::parse` may panic but has no `# Panics` section"),
+            (44, "missing_docs", "Missing documentation for public method `Circle::undocumented`"),
+            (59, "missing_docs", "Missing documentation for public method `Shape::name`"),
+            (6, "missing_docs", "Missing documentation for public enum `Kind`"),
+            (9, "missing_docs", "Missing documentation for public variant `Kind::Flat`"),
+        }
+        assert result.data["files_checked"] == 2
+
+    def test_without_links(self, crate):
+        _, issues = self.lint(crate, check_links=False)
+        assert not any(kind == "broken_intra_doc_link" for _, kind, _ in issues)
+
+    def test_missing_crate_docs(self, tmp_path):
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "lib.rs").write_text("/// Adds.\npub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n")
+        result, issues = self.lint(tmp_path / "src" / "lib.rs")
+        assert issues == {(1, "missing_docs", "Missing crate-level documentation (//!)")}
+        assert result.data["files_checked"] == 1
+
+
+class TestRustDocstring:
+    """Test rustdoc drafts from docs_generate_docstring."""
+
+    def setup_method(self):
+        from gathering.skills.docs.generator import DocsSkill
+        self.skill = DocsSkill()
+
+    def test_function(self):
+        code = (
+            "pub fn load<F: Fn(u8) -> u8>(&mut self, path: &Path, mut f: F) -> io::Result<Vec<u8>> {\n"
+            "    f(read(path)?).unwrap()\n"
+            "}\n"
+        )
+        result = self.skill.execute("docs_generate_docstring", {"code": code})
+        assert result.success
+        assert result.data["style"] == "rustdoc"
+        docstring = result.data["docstring"]
+        assert docstring.startswith("/// Brief description.\n///\n/// # Arguments")
+        assert "/// * `path` - Description.\n/// * `f` - Description." in docstring
+        assert "/// # Errors" in docstring
+        assert "/// # Panics" in docstring
+        assert "/// // let result = value.load(...);" in docstring
+
+    def test_unsafe_method_keeps_indentation(self):
+        code = "    pub unsafe fn raw(ptr: *const u8) -> u8 {\n        *ptr\n    }\n"
+        result = self.skill.execute("docs_generate_docstring", {"code": code, "language": "rust"})
+        lines = result.data["docstring"].split("\n")
+        assert all(line.startswith("    ///") for line in lines)
+        assert "    /// # Safety" in lines
+        assert "    /// # Errors" not in lines
+
+    def test_struct(self):
+        result = self.skill.execute("docs_generate_docstring", {"code": "pub struct Point { x: i32 }"})
+        assert result.data["type"] == "struct"
+        assert "/// // let value = Point::new(...);" in result.data["docstring"]
+
+    def test_python_still_detected(self):
+        result = self.skill.execute("docs_generate_docstring", {"code": "def f(x):\n    return x\n"})
+        assert result.data["style"] == "google"
+
+
+class TestRustApiReference:
+    """Test docs_generate_api on a crate."""
+
+    def test_markdown_reference(self, crate):
+        from gathering.skills.docs.generator import DocsSkill
+
+        result = DocsSkill().execute("docs_generate_api", {"path": str(crate)})
+        assert result.success
+        assert result.needs_confirmation
+        assert result.data["path"] == str(crate / "docs" / "api" / "geo.md")
+        content = result.data["content"]
+        assert content.startswith("# `geo` API Reference\n\n## Module `crate`\n\nGeometry primitives.")
+        assert "### struct `Circle`\n\n```rust\npub struct Circle\n```" in content
+        assert "- `pub fn new(radius: f64) -> Result<Self, String>` - Creates a circle." in content
+        assert "**Implements:** `Display`" in content
+        assert "helper" not in content
+        assert "untested_helper" not in content
+        assert "fn private" not in content
+
+    def test_include_private(self, crate):
+        from gathering.skills.docs.generator import DocsSkill
+
+        result = DocsSkill().execute("docs_generate_api", {"path": str(crate), "include_private": True})
+        assert "### function `helper`" in result.data["content"]
+        assert "fn private(&self)" in result.data["content"]
+
+
+requires_git = pytest.mark.skipif(shutil.which("git") is None, reason="git not installed")
+
+
+def commit(repo, message, *extra):
+    """Commit a new file with a message (and optional body paragraphs)."""
+    (repo / f"file{len(list(repo.iterdir()))}").write_text(message)
+    subprocess.run(["git", "add", "-A"], cwd=repo, check=True)
+    args = ["git", "commit", "-q", "-m", message]
+    for paragraph in extra:
+        args += ["-m", paragraph]
+    subprocess.run(args, cwd=repo, check=True)
+
+
+@pytest.fixture
+def workspace_repo(tmp_path):
+    """A git Cargo workspace tagged v0.3.1, followed by a few commits."""
+    (tmp_path / "Cargo.toml").write_text(
+        '[workspace]\nmembers = ["crates/*"]\n\n[workspace.package]\nversion = "0.3.1"\nedition = "2021"\n'
+    )
+    for member in ("core", "cli"):
+        (tmp_path / "crates" / member).mkdir(parents=True)
+    (tmp_path / "crates" / "core" / "Cargo.toml").write_text(
+        '[package]\nname = "demo-core"\nversion.workspace = true\n'
+    )
+    (tmp_path / "crates" / "cli" / "Cargo.toml").write_text(
+        '[package]\nname = "demo-cli"\nversion = "0.3.1"\n\n[dependencies]\n'
+        'core = { package = "demo-core", path = "../core", version = "0.3.1" }\nserde = { version = "1" }\n'
+    )
+    subprocess.run(["git", "init", "-q"], cwd=tmp_path, check=True)
+    subprocess.run(["git", "config", "user.email", "dev@example.com"], cwd=tmp_path, check=True)
+    subprocess.run(["git", "config", "user.name", "Dev"], cwd=tmp_path, check=True)
+    commit(tmp_path, "chore: initial import")
+    subprocess.run(["git", "tag", "v0.3.1"], cwd=tmp_path, check=True)
+    commit(tmp_path, "feat(cli): add --json flag")
+    commit(tmp_path, "fix: handle empty input")
+    commit(tmp_path, "update readme")
+    commit(tmp_path, "docs: fix typo")
+    return tmp_path
+
+
+class TestConventionalCommits:
+    """Test Conventional Commit parsing and semver recommendations."""
+
+    def setup_method(self):
+        from gathering.skills.docs.generator import DocsSkill
+        self.skill = DocsSkill()
+
+    def test_parse(self):
+        commit = self.skill._parse_conventional_commit("abc1234", "feat(parser)!: drop v1 syntax", "")
+        assert commit == {
+            "hash": "abc1234",
+            "type": "feat",
+            "scope": "parser",
+            "description": "drop v1 syntax",
+            "breaking": True,
+            "breaking_notes": [],
+        }
+
+        footer = self.skill._parse_conventional_commit(
+            "def5678", "refactor: rename Parser", "Details.\n\nBREAKING CHANGE: Parser is now Reader\n"
+        )
+        assert footer["breaking"]
+        assert footer["breaking_notes"] == ["Parser is now Reader"]
+        assert footer["scope"] is None
+
+        assert self.skill._parse_conventional_commit("0000000", "Merge branch 'main'", "") is None
+
+    def test_entry(self):
+        commit = self.skill._parse_conventional_commit(
+            "def5678", "refactor(core)!: rename Parser", "BREAKING CHANGE: Parser is now Reader"
+        )
+        assert self.skill._changelog_entry(commit) == (
+            "**BREAKING** **core:** rename Parser - Parser is now Reader (def5678)"
+        )
+
+    def test_semver_bump(self):
+        cases = [
+            ("1.4.2", ["fix"], False, ("patch", "1.4.3")),
+            ("1.4.2", ["fix", "feat"], False, ("minor", "1.5.0")),
+            ("1.4.2", ["fix"], True, ("major", "2.0.0")),
+            ("0.3.1", ["feat"], False, ("patch", "0.3.2")),
+            ("0.3.1", ["fix"], True, ("minor", "0.4.0")),
+            ("2.0.0-rc.1", ["feat"], True, ("major", "2.0.0")),
+            ("1.4.2", ["docs", "chore"], False, (None, None)),
+        ]
+        for current, types, breaking, expected in cases:
+            commits = [{"type": t, "breaking": breaking} for t in types]
+            assert self.skill._semver_bump(current, commits) == expected, current
+
+
+@requires_git
+class TestChangelogFromGit:
+    """Test docs_changelog on git history."""
+
+    def setup_method(self):
+        from gathering.skills.docs.generator import DocsSkill
+        self.skill = DocsSkill()
+
+    def test_entry_since_latest_tag(self, workspace_repo):
+        result = self.skill.execute("docs_changelog", {"path": str(workspace_repo)})
+        assert result.success
+        assert result.needs_confirmation
+        data = result.data
+        assert (data["from_ref"], data["current_version"]) == ("v0.3.1", "0.3.1")
+        assert (data["bump"], data["recommended_version"], data["version"]) == ("patch", "0.3.2", "0.3.2")
+        assert data["skipped_commits"] == ["update readme"]
+
+        entry = data["entry"]
+        assert entry.startswith("## [0.3.2] - ")
+        assert "### Added\n- **cli:** add --json flag (" in entry
+        assert "### Fixed\n- handle empty input (" in entry
+        assert "typo" not in entry
+        assert "Keep a Changelog" in data["content"]
+        assert "manifests" not in data
+
+    def test_breaking_change_and_tag_range(self, workspace_repo):
+        commit(workspace_repo, "refactor(core): rename Parser", "BREAKING CHANGE: Parser is now Reader")
+        subprocess.run(["git", "tag", "v0.4.0"], cwd=workspace_repo, check=True)
+        commit(workspace_repo, "fix: after the release")
+
+        result = self.skill.execute(
+            "docs_changelog", {"path": str(workspace_repo), "from_ref": "v0.3.1", "to_ref": "v0.4.0"}
+        )
+        data = result.data
+        assert (data["bump"], data["recommended_version"]) == ("minor", "0.4.0")
+        assert data["version"] == "0.4.0"
+        assert "### Changed\n- **BREAKING** **core:** rename Parser - Parser is now Reader (" in data["entry"]
+        assert "after the release" not in data["entry"]
+
+    def test_releasing_a_tag(self, workspace_repo):
+        subprocess.run(["git", "tag", "v0.4.0"], cwd=workspace_repo, check=True)
+        commit(workspace_repo, "fix: after the release")
+
+        # Default range: the tag before v0.4.0, not v0.4.0 itself
+        data = self.skill.execute("docs_changelog", {"path": str(workspace_repo), "to_ref": "v0.4.0"}).data
+        assert (data["from_ref"], data["bump"], data["version"]) == ("v0.3.1", "patch", "0.4.0")
+        assert "### Added\n- **cli:** add --json flag (" in data["entry"]
+        assert "after the release" not in data["entry"]
+
+        # A tag with nothing releasable since the previous one gets no empty entry
+        subprocess.run(["git", "tag", "v0.4.1"], cwd=workspace_repo, check=True)
+        commit(workspace_repo, "chore: bump deps")
+        subprocess.run(["git", "tag", "v0.4.2"], cwd=workspace_repo, check=True)
+        result = self.skill.execute("docs_changelog", {"path": str(workspace_repo), "to_ref": "v0.4.2"})
+        assert result.error == "no_changes"
+
+    def test_inserted_below_unreleased(self, workspace_repo):
+        (workspace_repo / "CHANGELOG.md").write_text(
+            "# Changelog\n\n## [Unreleased]\n\n## [0.3.1] - 2026-01-01\n\n### Added\n- First release\n"
+        )
+        content = self.skill.execute("docs_changelog", {"path": str(workspace_repo)}).data["content"]
+        assert content.index("## [Unreleased]") < content.index("## [0.3.2]") < content.index("## [0.3.1]")
+
+    def test_cargo_version_diff(self, workspace_repo):
+        result = self.skill.execute(
+            "docs_changelog", {"path": str(workspace_repo), "version": "0.4.0", "update_cargo": True}
+        )
+        manifests = {m["path"]: m for m in result.data["manifests"]}
+        assert set(manifests) == {
+            str(workspace_repo / "Cargo.toml"),
+            str(workspace_repo / "crates" / "cli" / "Cargo.toml"),
+        }
+        cli = manifests[str(workspace_repo / "crates" / "cli" / "Cargo.toml")]["content"]
+        assert 'version = "0.4.0"\n' in cli
+        assert 'core = { package = "demo-core", path = "../core", version = "0.4.0" }' in cli
+        assert 'serde = { version = "1" }' in cli
+
+        diff = result.data["manifest_diff"]
+        assert "--- a/Cargo.toml\n+++ b/Cargo.toml\n" in diff
+        assert '-version = "0.3.1"\n+version = "0.4.0"\n' in diff
+        # Dry run: nothing is written
+        assert 'version = "0.3.1"' in (workspace_repo / "Cargo.toml").read_text()
+
+    def test_no_releasable_changes(self, workspace_repo):
+        subprocess.run(["git", "tag", "v0.3.2"], cwd=workspace_repo, check=True)
+        commit(workspace_repo, "chore: bump deps")
+        result = self.skill.execute("docs_changelog", {"path": str(workspace_repo)})
+        assert not result.success
+        assert result.error == "no_changes"
+
+    def test_explicit_changes(self, tmp_path):
+        result = self.skill.execute("docs_changelog", {
+            "path": str(tmp_path),
+            "version": "1.0.0",
+            "changes": {"fixed": ["A bug"], "added": ["A feature"]},
+        })
+        entry = result.data["entry"]
+        assert entry.index("### Added") < entry.index("### Fixed")
+        assert "bump" not in result.data
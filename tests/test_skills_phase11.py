@@ -1,5 +1,7 @@
 """Tests for Phase 11 Advanced Skills."""
 
+import shutil
+
 import pytest
 from unittest.mock import Mock, patch, MagicMock
 
@@ -333,6 +335,24 @@ def hello(name
         assert result["success"] is True
         assert "\n" in result["formatted"]
 
+    @pytest.mark.skipif(shutil.which("rustfmt") is None, reason="rustfmt not installed")
+    def test_code_format_rust(self):
+        """Test Rust formatting via rustfmt."""
+        from gathering.skills.code import CodeExecutionSkill
+
+        skill = CodeExecutionSkill()
+
+        result = skill.execute("code_format", {
+            "code": "fn main(){let x=1;}",
+            "language": "rust"
+        })
+        assert result["success"] is True
+        assert result["formatted"] == "fn main() {\n    let x = 1;\n}\n"
+
+        result = skill.execute("code_format", {"code": "fn main( {", "language": "rust"})
+        assert result["success"] is False
+        assert result["error"]
+
     def test_bash_blocked_dangerous(self):
         """Test dangerous bash commands are blocked."""
         from gathering.skills.code import CodeExecutionSkill
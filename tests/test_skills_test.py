@@ -0,0 +1,566 @@
+"""
+Tests for Test Skill - cargo test support.
+
+Covers:
+- Framework detection (pytest vs cargo)
+- cargo test arguments (targets, filters, features, workspace)
+- libtest output parsing and panic analysis
+- Last-failed persistence and reruns
+- Rust coverage (llvm-cov / tarpaulin lcov reports)
+- Rust test scaffolds (unit and integration)
+"""
+
+import json
+import shutil
+import subprocess
+from pathlib import Path
+from unittest.mock import patch
+
+import pytest
+
+
+CARGO_OUTPUT = """\
+    Finished `test` profile [unoptimized + debuginfo] target(s) in 0.01s
+     Running unittests src/lib.rs (target/debug/deps/ct_demo-fff950e7caa2b9c5)
+
+running 4 tests
+test tests::adds ... ok
+test tests::parses ... FAILED
+test tests::slow ... ignored
+test tests::wrong_sum ... FAILED
+
+failures:
+
+---- tests::parses stdout ----
+
+thread 'tests::parses' (19442) panicked at src/lib.rs:26:34:
+called `Result::unwrap()` on an `Err` value: ParseIntError { kind: InvalidDigit }
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- tests::wrong_sum stdout ----
+
+thread 'tests::wrong_sum' (19443) panicked at src/lib.rs:21:9:
+assertion `left == right` failed
+  left: 3
+ right: 4
+
+
+failures:
+    tests::parses
+    tests::wrong_sum
+
+test result: FAILED. 1 passed; 2 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+error: test failed, to rerun pass `--lib`
+     Running tests/api.rs (target/debug/deps/api-98eb7bff9151b41e)
+
+running 1 test
+test api_works ... FAILED
+
+failures:
+
+---- api_works stdout ----
+
+thread 'api_works' panicked at 'math is broken', tests/api.rs:3:5
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    api_works
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+error: test failed, to rerun pass `--test api`
+   Doc-tests ct_demo
+
+running 1 test
+test src/lib.rs - add (line 3) ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.05s
+
+error: 2 targets failed:
+    `--lib`
+    `--test api`
+"""
+
+LIB_RS = """\
+/// Adds numbers.
+///
+/// ```
+/// assert_eq!(ct_demo::add(1, 2), 3);
+/// ```
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds() {
+        assert_eq!(add(1, 2), 3);
+    }
+
+    #[test]
+    fn wrong_sum() {
+        assert_eq!(add(1, 2), 4);
+    }
+
+    #[test]
+    fn parses() {
+        let v: i32 = "x".parse().unwrap();
+        assert_eq!(v, 1);
+    }
+
+    #[test]
+    #[ignore]
+    fn slow() {}
+}
+"""
+
+
+# cargo llvm-cov lcov export: absolute paths, mangled names, a generic
+# function instantiated twice and branch data
+LCOV = """\
+SF:{root}/src/lib.rs
+FN:6,_ZN7ct_demo3add17h0123456789abcdefE
+FN:11,_RNvCs1234_7ct_demo5clamp
+FN:11,_RNvCs5678_7ct_demo5clamp
+FN:19,_ZN7ct_demo6unused17hfedcba9876543210E
+FNDA:3,_ZN7ct_demo3add17h0123456789abcdefE
+FNDA:1,_RNvCs1234_7ct_demo5clamp
+FNDA:0,_RNvCs5678_7ct_demo5clamp
+FNDA:0,_ZN7ct_demo6unused17hfedcba9876543210E
+FNF:3
+FNH:2
+BRDA:12,0,0,1
+BRDA:12,0,1,-
+DA:6,3
+DA:7,3
+DA:8,3
+DA:11,1
+DA:12,1
+DA:13,0
+DA:14,1
+DA:15,1
+DA:19,0
+DA:20,0
+DA:21,0
+BRF:2
+BRH:1
+LF:11
+LH:7
+end_of_record
+SF:{root}/tests/api.rs
+FN:2,_ZN3api9api_works17h0000000000000000E
+FNDA:1,_ZN3api9api_works17h0000000000000000E
+DA:2,1
+DA:3,1
+DA:4,1
+LF:3
+LH:3
+end_of_record
+"""
+
+PARSER_RS = """\
+use std::fmt;
+
+pub fn parse(s: &str) -> Result<i32, String> {
+    s.parse().map_err(|_| s.to_string())
+}
+
+pub async fn fetch(url: &str) -> String {
+    url.to_string()
+}
+
+fn private() {}
+
+pub struct Parser {
+    pub x: i32,
+}
+
+impl Parser {
+    pub fn new(x: i32) -> Self {
+        Parser { x }
+    }
+
+    pub fn value(&self) -> i32 {
+        self.x
+    }
+}
+
+impl fmt::Display for Parser {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.x)
+    }
+}
+"""
+
+
+def completed(stdout: str, returncode: int = 101) -> subprocess.CompletedProcess:
+    return subprocess.CompletedProcess(args=["cargo"], returncode=returncode, stdout=stdout, stderr="")
+
+
+@pytest.fixture
+def crate(tmp_path):
+    """The Rust crate of CARGO_OUTPUT, without its integration test."""
+    (tmp_path / "Cargo.toml").write_text(
+        '[package]\nname = "ct-demo"\nversion = "0.1.0"\nedition = "2021"\n\n[features]\nextra = []\n'
+    )
+    (tmp_path / "src").mkdir()
+    (tmp_path / "src" / "lib.rs").write_text(LIB_RS)
+    return tmp_path
+
+
+class TestFrameworkDetection:
+    """Test pytest vs cargo detection."""
+
+    def setup_method(self):
+        from gathering.skills.test.runner import TestSkill
+        self.skill = TestSkill()
+
+    def test_cargo_crate(self, crate):
+        assert self.skill._detect_framework(crate, {}) == "cargo"
+
+    def test_python_project(self, tmp_path):
+        (tmp_path / "pyproject.toml").write_text("[project]\nname = 'x'\n")
+        assert self.skill._detect_framework(tmp_path, {}) == "pytest"
+
+    def test_mixed_project_with_python_tests(self, crate):
+        (crate / "tests").mkdir()
+        (crate / "tests" / "test_bindings.py").write_text("def test_x(): pass\n")
+        assert self.skill._detect_framework(crate, {}) == "pytest"
+
+    def test_explicit_framework(self, crate):
+        assert self.skill._detect_framework(crate, {"framework": "pytest"}) == "pytest"
+        with pytest.raises(ValueError):
+            self.skill._detect_framework(crate, {"framework": "jest"})
+
+
+class TestCargoArguments:
+    """Test cargo test command construction."""
+
+    def setup_method(self):
+        from gathering.skills.test.runner import TestSkill
+        self.skill = TestSkill()
+
+    def test_targets_and_filters(self):
+        targets, filters = self.skill._cargo_test_targets(
+            ["tests/api.rs", "src/lib.rs", "src/bin/cli.rs", "src/parser.rs", "tests::adds"]
+        )
+        assert targets == ["--test", "api", "--lib", "--bin", "cli"]
+        assert filters == ["parser::", "tests::adds"]
+
+    def test_selection(self):
+        assert self.skill._cargo_selection_args({"workspace": True, "features": ["a", "b"]}) == [
+            "--workspace", "--features", "a,b"
+        ]
+        assert self.skill._cargo_selection_args({"package": "core", "all_features": True}) == [
+            "-p", "core", "--all-features"
+        ]
+
+    def test_run_command(self, crate):
+        with patch.object(self.skill, "_run_cargo", return_value=completed(CARGO_OUTPUT)) as run:
+            response = self.skill.execute("test_run", {
+                "path": str(crate), "tests": ["tests/api.rs", "adds"], "features": ["extra"]
+            })
+
+        assert run.call_args.args[0] == [
+            "test", "--features", "extra", "--test", "api", "--no-fail-fast", "--", "adds"
+        ]
+        assert response.data["framework"] == "cargo"
+
+    def test_rerun_args(self):
+        assert self.skill._cargo_rerun_args(["--lib", "--test api", "--lib"]) == ["--lib", "--test", "api"]
+        assert self.skill._cargo_rerun_args(["-p core --lib", "-p core --doc"]) == ["-p", "core"]
+
+
+class TestCargoOutputParsing:
+    """Test libtest output parsing."""
+
+    def setup_method(self):
+        from gathering.skills.test.runner import TestSkill
+        self.skill = TestSkill()
+
+    def test_summary(self):
+        parsed = self.skill._parse_cargo_test_output(CARGO_OUTPUT)
+
+        assert parsed["passed"] == 2
+        assert parsed["failed"] == 3
+        assert parsed["skipped"] == 1
+        assert parsed["errors"] == 0
+        assert parsed["duration"] == 0.05
+        assert parsed["rerun_targets"] == ["--lib", "--test api"]
+        assert parsed["failures"][1] == {
+            "test": "tests::wrong_sum",
+            "reason": "assertion `left == right` failed (src/lib.rs:21)"
+        }
+
+    def test_compile_errors(self):
+        output = (
+            "error[E0425]: cannot find value `x` in this scope\n"
+            "warning: unused variable: `y`\n"
+            "warning: `demo` (lib test) generated 1 warning\n"
+            "error: could not compile `demo` (lib test) due to 1 previous error\n"
+        )
+        parsed = self.skill._parse_cargo_test_output(output)
+        assert parsed["errors"] == 1
+        assert parsed["warnings"] == 1
+
+    def test_panics(self):
+        panics = self.skill._parse_cargo_panics(CARGO_OUTPUT)
+
+        assert [p["test"] for p in panics] == ["tests::parses", "tests::wrong_sum", "api_works"]
+        assert panics[1]["message"] == "assertion `left == right` failed\n  left: 3\n right: 4"
+        assert (panics[1]["file"], panics[1]["line"], panics[1]["column"]) == ("src/lib.rs", 21, 9)
+        # Pre-1.73 panic format
+        assert panics[2]["message"] == "math is broken"
+        assert panics[2]["file"] == "tests/api.rs"
+
+    def test_analyze_failures(self, crate):
+        response = self.skill.execute("test_analyze_failures", {
+            "path": str(crate), "test_output": CARGO_OUTPUT
+        })
+
+        data = response.data
+        assert data["framework"] == "cargo"
+        assert data["total_failures"] == 3
+        assert data["error_type_summary"] == {"UnwrapErr": 1, "AssertionFailed": 1, "Panic": 1}
+        wrong_sum = data["failures"][1]
+        assert wrong_sum["code_snippet"] == "assert_eq!(add(1, 2), 4);"
+        assert wrong_sum["suggestions"]
+
+
+class TestCargoLastFailed:
+    """Test last-failed persistence and reruns."""
+
+    def setup_method(self):
+        from gathering.skills.test.runner import TestSkill
+        self.skill = TestSkill()
+
+    def test_no_failed_tests(self, crate):
+        response = self.skill.execute("test_last_failed", {"path": str(crate)})
+        assert response.data == {"no_failed_tests": True}
+
+    def test_rerun(self, crate):
+        with patch.object(self.skill, "_run_cargo", return_value=completed(CARGO_OUTPUT)):
+            self.skill.execute("test_run", {"path": str(crate), "workspace": True})
+
+        saved = json.loads((crate / self.skill.CARGO_LAST_FAILED).read_text())
+        assert saved["tests"] == ["tests::parses", "tests::wrong_sum", "api_works"]
+        assert saved["args"] == ["--workspace"]
+
+        with patch.object(self.skill, "_run_cargo", return_value=completed("", returncode=0)) as run:
+            response = self.skill.execute("test_last_failed", {"path": str(crate)})
+
+        assert run.call_args.args[0] == [
+            "test", "--workspace", "--lib", "--test", "api", "--no-fail-fast",
+            "--", "--exact", "tests::parses", "tests::wrong_sum", "api_works"
+        ]
+        assert response.success
+        # Nothing failed this time
+        saved = json.loads((crate / self.skill.CARGO_LAST_FAILED).read_text())
+        assert saved["tests"] == []
+
+
+@pytest.mark.skipif(shutil.which("cargo") is None, reason="cargo not installed")
+class TestCargoIntegration:
+    """Run cargo test on a real crate."""
+
+    def test_run_and_rerun(self, crate):
+        from gathering.skills.test.runner import TestSkill
+        skill = TestSkill()
+
+        response = skill.execute("test_run", {"path": str(crate)})
+        assert not response.success
+        # adds and the doc test pass
+        assert response.data["summary"]["passed"] == 2
+        assert response.data["summary"]["failed"] == 2
+        assert response.data["summary"]["skipped"] == 1
+
+        response = skill.execute("test_last_failed", {"path": str(crate)})
+        assert response.data["summary"]["passed"] == 0
+        assert response.data["summary"]["failed"] == 2
+
+    def test_scaffolds_compile(self, crate):
+        from gathering.skills.test.runner import TestSkill
+        skill = TestSkill()
+        (crate / "src" / "lib.rs").write_text("pub mod parser;\n")
+        (crate / "src" / "parser.rs").write_text(PARSER_RS)
+        (crate / "tests").mkdir()
+
+        for kind in ("unit", "integration"):
+            response = skill.execute("test_create", {"path": str(crate), "module": "src/parser.rs", "kind": kind})
+            Path(response.data["test_path"]).write_text(response.data["content"])
+
+        # Stubs compile and are ignored until implemented
+        response = skill.execute("test_run", {"path": str(crate)})
+        assert response.success
+        assert response.data["summary"]["skipped"] == 10
+
+
+class TestCargoCoverage:
+    """Test Rust coverage with cargo llvm-cov / tarpaulin."""
+
+    def setup_method(self):
+        from gathering.skills.test.runner import TestSkill
+        self.skill = TestSkill()
+
+    def fake_cargo(self, crate, installed=("llvm-cov",)):
+        """Stand-in for _run_cargo: writes the lcov report where asked."""
+        calls = []
+
+        def run(args, cwd=None, timeout=None, merge_output=False):
+            calls.append(args)
+            if args[1:] == ["--version"]:
+                return completed("", returncode=0 if args[0] in installed else 101)
+            lcov = LCOV.format(root=crate.resolve())
+            if "--lcov" in args:
+                Path(args[args.index("--output-path") + 1]).write_text(lcov)
+            elif args[0] == "tarpaulin":
+                (Path(args[args.index("--output-dir") + 1]) / "lcov.info").write_text(lcov)
+            return completed("", returncode=0)
+
+        return run, calls
+
+    def test_parse_lcov(self, crate):
+        files = self.skill._parse_lcov(LCOV.format(root=crate.resolve()), crate)
+
+        lib = files[0]
+        assert lib["file"] == "src/lib.rs"
+        assert (lib["statements"], lib["missing"], lib["coverage"]) == (11, 4, 64)
+        assert lib["missing_lines"] == "13, 19-21"
+        assert (lib["branches"], lib["branch_coverage"]) == (2, 50)
+
+        functions = {f["name"]: f for f in lib["functions"]}
+        assert list(functions) == ["ct_demo::add", "_RNvCs1234_7ct_demo5clamp", "ct_demo::unused"]
+        assert functions["ct_demo::add"]["coverage"] == 100
+        # Both instantiations of clamp are merged
+        clamp = functions["_RNvCs1234_7ct_demo5clamp"]
+        assert (clamp["hits"], clamp["lines"], clamp["coverage"], clamp["branch_coverage"]) == (1, 5, 80, 50)
+        assert functions["ct_demo::unused"]["coverage"] == 0
+        assert functions["ct_demo::unused"]["branch_coverage"] is None
+
+    def test_llvm_cov(self, crate):
+        run, calls = self.fake_cargo(crate)
+        with patch.object(self.skill, "_run_cargo", side_effect=run):
+            response = self.skill.execute("test_coverage", {
+                "path": str(crate), "fail_under": 70, "workspace": True
+            })
+
+        assert calls[1] == ["llvm-cov", "--no-report", "--workspace", "--no-fail-fast"]
+        assert calls[2][:3] == ["llvm-cov", "report", "--lcov"]
+        data = response.data
+        assert data["tool"] == "llvm-cov"
+        assert data["total_coverage"] == 71  # 10 of 14 lines
+        assert data["branch_coverage"] == 50
+        assert data["passed_threshold"] and response.success
+        assert data["uncovered_functions"] == [{"file": "src/lib.rs", "name": "ct_demo::unused", "line": 19}]
+
+    def test_tarpaulin_fallback(self, crate):
+        run, calls = self.fake_cargo(crate, installed=("tarpaulin",))
+        with patch.object(self.skill, "_run_cargo", side_effect=run):
+            response = self.skill.execute("test_coverage", {
+                "path": str(crate), "source": "src", "report_type": "html"
+            })
+
+        assert calls[-1][:2] == ["tarpaulin", "--skip-clean"]
+        assert calls[-1][calls[-1].index("--out"):][:4] == ["--out", "Lcov", "--out", "Html"]
+        data = response.data
+        assert data["tool"] == "tarpaulin"
+        assert [f["file"] for f in data["file_coverage"]] == ["src/lib.rs"]
+        assert data["total_coverage"] == 64
+        assert not data["passed_threshold"]
+
+    def test_no_tool_installed(self, crate):
+        run, _ = self.fake_cargo(crate, installed=())
+        with patch.object(self.skill, "_run_cargo", side_effect=run):
+            response = self.skill.execute("test_coverage", {"path": str(crate)})
+
+        assert not response.success
+        assert response.error == "coverage_tool_not_installed"
+
+
+class TestRustTestCreate:
+    """Test Rust unit and integration test scaffolds."""
+
+    def setup_method(self):
+        from gathering.skills.test.runner import TestSkill
+        self.skill = TestSkill()
+
+    @pytest.fixture
+    def parser_crate(self, crate):
+        (crate / "src" / "lib.rs").write_text("pub mod parser;\n")
+        (crate / "src" / "parser.rs").write_text(PARSER_RS)
+        return crate
+
+    def create(self, crate, **tool_input):
+        return self.skill.execute("test_create", {"path": str(crate), "module": "src/parser.rs", **tool_input})
+
+    def test_unit_module_appended(self, parser_crate):
+        response = self.create(parser_crate)
+
+        data = response.data
+        assert response.needs_confirmation
+        assert data["mode"] == "append"
+        # Public items only; trait impls get one test, private functions none
+        assert data["generated_tests"] == [
+            "test_parse", "test_fetch", "test_parser_new", "test_parser_value", "test_parser_display"
+        ]
+        content = data["content"]
+        assert content.startswith(PARSER_RS.rstrip("\n"))
+        assert "#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    #[ignore" in content
+        assert "        // let instance = Parser::new(...);\n        // let result = instance.value(...);" in content
+        # No tokio dependency: async functions get a plain #[test]
+        assert "    // TODO: async - add tokio to [dev-dependencies] for #[tokio::test]\n    #[test]" in content
+        assert data["async_runtime"] is None
+
+    def test_existing_module_kept(self, parser_crate):
+        existing = "\n#[cfg(test)]\nmod tests {\n    use super::*;\n\n    #[test]\n    fn parse() {\n        assert_eq!(super::parse(\"1\"), Ok(1));\n    }\n}\n"
+        (parser_crate / "src" / "parser.rs").write_text(PARSER_RS + existing)
+
+        data = self.create(parser_crate).data
+
+        assert data["mode"] == "insert"
+        assert data["existing_tests"] == ["parse"]
+        assert "test_parse" not in data["generated_tests"]
+        assert data["content"].count("mod tests {") == 1
+        assert data["content"].rstrip().endswith("todo!(\"test impl Display for Parser\");\n    }\n}")
+
+    def test_tokio_stubs(self, parser_crate):
+        manifest = parser_crate / "Cargo.toml"
+        manifest.write_text(manifest.read_text() + '\n[dev-dependencies]\ntokio = { version = "1", features = ["macros", "rt"] }\n')
+
+        data = self.create(parser_crate).data
+
+        assert data["async_runtime"] == "tokio"
+        assert "    #[tokio::test]\n    #[ignore = \"not implemented\"]\n    async fn test_fetch() {" in data["content"]
+        assert "// let result = fetch(...).await;" in data["content"]
+
+    def test_integration_file(self, parser_crate):
+        data = self.create(parser_crate, kind="integration").data
+
+        assert data["test_path"] == str(parser_crate / "tests" / "parser.rs")
+        assert data["content"].startswith(
+            "//! Integration tests for `ct_demo::parser`.\n\nuse ct_demo::parser::{Parser, fetch, parse};\n"
+        )
+        assert data["mode"] == "append"
+
+        # An existing file keeps its tests
+        (parser_crate / "tests").mkdir()
+        (parser_crate / "tests" / "parser.rs").write_text("use ct_demo::parser::parse;\n\n#[test]\nfn test_parse() {}\n")
+        data = self.create(parser_crate, kind="integration").data
+        assert data["mode"] == "insert"
+        assert data["content"].startswith("use ct_demo::parser::parse;\n\n#[test]\nfn test_parse() {}\n")
+        assert "test_parse" not in data["generated_tests"]
+
+    def test_integration_needs_library(self, crate):
+        (crate / "src" / "main.rs").write_text("pub fn run() {}\nfn main() {}\n")
+        response = self.skill.execute("test_create", {"path": str(crate), "module": "src/main.rs", "kind": "integration"})
+        assert not response.success
+        assert response.error == "not_a_library"
+
+    def test_missing_file(self, crate):
+        response = self.create(crate)
+        assert response.error == "file_not_found"
@@ -188,6 +188,47 @@ class TestConnectionManager:
         assert call_args["data"]["task_id"] == 123
         assert "timestamp" in call_args
 
+    @pytest.mark.asyncio
+    async def test_broadcast_to_subscribers(self):
+        """Test messages only reach clients subscribed to the project or file."""
+        manager = ConnectionManager()
+        project_ws, file_ws, other_file_ws, other_project_ws = [AsyncMock() for _ in range(4)]
+        for websocket in (project_ws, file_ws, other_file_ws, other_project_ws):
+            await manager.connect(websocket)
+
+        manager.subscribe(project_ws, 1)
+        manager.subscribe(file_ws, 1, "src/lib.rs")
+        manager.subscribe(other_file_ws, 1, "src/main.rs")
+        manager.subscribe(other_project_ws, 2)
+
+        count = await manager.broadcast_to_subscribers(1, "src/lib.rs", {"type": "lsp.diagnostics"})
+
+        assert count == 2
+        project_ws.send_json.assert_called_once()
+        file_ws.send_json.assert_called_once()
+        other_file_ws.send_json.assert_not_called()
+        other_project_ws.send_json.assert_not_called()
+        assert "timestamp" in file_ws.send_json.call_args[0][0]
+
+    @pytest.mark.asyncio
+    async def test_unsubscribe(self):
+        """Test removing subscriptions by project and file."""
+        manager = ConnectionManager()
+        websocket = AsyncMock()
+        await manager.connect(websocket)
+
+        manager.subscribe(websocket, 1, "src/lib.rs")
+        manager.subscribe(websocket, 1, "src/main.rs")
+        manager.subscribe(websocket, 2)
+
+        assert manager.unsubscribe(websocket, 1, "src/lib.rs") == 1
+        assert manager.get_subscribers(1, "src/lib.rs") == []
+        assert manager.get_subscribers(1, "src/main.rs") == [websocket]
+
+        assert manager.unsubscribe(websocket) == 2
+        assert manager.get_stats()["clients"][0]["subscriptions"] == []
+        assert manager.subscribe(AsyncMock(), 1) is False  # not connected
+
     def test_get_stats(self):
         """Test getting connection statistics."""
         manager = ConnectionManager()
@@ -277,6 +318,32 @@ class TestWebSocketIntegration:
         # Should have subscribed to events
         # (Event bus will have subscribers)
 
+    @pytest.mark.asyncio
+    async def test_diagnostics_sent_to_subscribers(self):
+        """Test LSP diagnostics events go to the file's subscribers only."""
+        from gathering.events import Event, EventType
+        from gathering.websocket.integration import _send_diagnostics_to_subscribers
+
+        manager = ConnectionManager()
+        subscriber, bystander = AsyncMock(), AsyncMock()
+        await manager.connect(subscriber)
+        await manager.connect(bystander)
+        manager.subscribe(subscriber, 7, "src/lib.rs")
+
+        event = Event(
+            type=EventType.LSP_DIAGNOSTICS,
+            data={"file_path": "src/lib.rs", "language": "rust", "diagnostics": [], "count": 0},
+            project_id=7,
+        )
+        with patch("gathering.websocket.integration.get_connection_manager", return_value=manager):
+            await _send_diagnostics_to_subscribers(event)
+
+        message = subscriber.send_json.call_args[0][0]
+        assert message["type"] == "lsp.diagnostics"
+        assert message["data"]["project_id"] == 7
+        assert message["data"]["file_path"] == "src/lib.rs"
+        bystander.send_json.assert_not_called()
+
 
 class TestGracefulDegradation:
     """Test graceful degradation without FastAPI."""